@@ -0,0 +1,332 @@
+//! Folder ACL lock
+//! Adds deny entries for Everyone to the Apps folder, the kept version's binaries and ProductInfo.xml, tracked so unprotect removes exactly those
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::os::windows::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use windows_sys::Win32::Foundation::{LocalFree, ERROR_SUCCESS};
+use windows_sys::Win32::Security::Authorization::{
+    GetNamedSecurityInfoW, SetEntriesInAclW, SetNamedSecurityInfoW, DENY_ACCESS, EXPLICIT_ACCESS_W,
+    NO_MULTIPLE_TRUSTEE, SE_FILE_OBJECT, TRUSTEE_IS_SID, TRUSTEE_IS_WELL_KNOWN_GROUP, TRUSTEE_W,
+};
+use windows_sys::Win32::Security::{
+    AclSizeInformation, CreateWellKnownSid, DeleteAce, EqualSid, GetAce, GetAclInformation,
+    WinWorldSid, ACCESS_DENIED_ACE, ACL, ACL_SIZE_INFORMATION, DACL_SECURITY_INFORMATION,
+    INHERITED_ACE, NO_INHERITANCE, PSECURITY_DESCRIPTOR, SECURITY_MAX_SID_SIZE,
+};
+use windows_sys::Win32::Storage::FileSystem::{
+    DELETE, FILE_ADD_SUBDIRECTORY, FILE_APPEND_DATA, FILE_DELETE_CHILD, FILE_WRITE_ATTRIBUTES,
+    FILE_WRITE_DATA, FILE_WRITE_EA,
+};
+use windows_sys::Win32::System::SystemServices::ACCESS_DENIED_ACE_TYPE;
+
+use super::log_entry::LogEntry;
+use super::{hardening, installs, paths};
+
+/// What a lock denies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockKind {
+    /// Apps folder: no new version folders and no deleting or renaming it; configure.ini stays writable
+    Apps,
+    /// Kept version folder itself: no deleting or renaming; CapCut still downloads effects, fonts and LUTs into it
+    Version,
+    /// Single file: no writes, attribute changes or deletes
+    File,
+}
+
+impl LockKind {
+    fn mask(self) -> u32 {
+        match self {
+            LockKind::Apps => FILE_ADD_SUBDIRECTORY | FILE_DELETE_CHILD | DELETE,
+            LockKind::Version => DELETE,
+            LockKind::File => {
+                FILE_WRITE_DATA | FILE_APPEND_DATA | FILE_WRITE_EA | FILE_WRITE_ATTRIBUTES | DELETE
+            }
+        }
+    }
+}
+
+/// A path this app added a deny entry to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AclLock {
+    path: String,
+    kind: LockKind,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AclLocks {
+    locks: Vec<AclLock>,
+}
+
+/// Get the lock list path
+fn get_locks_path() -> Option<PathBuf> {
+    installs::get_install_data_dir().map(|p| p.join("acl_locks.json"))
+}
+
+fn load_locks() -> AclLocks {
+    get_locks_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_locks(locks: &AclLocks) -> Result<(), String> {
+    let path = get_locks_path().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(locks).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save ACL lock list: {}", e))
+}
+
+fn to_wide(path: &Path) -> Vec<u16> {
+    path.as_os_str().encode_wide().chain(Some(0)).collect()
+}
+
+fn win32_error(code: u32) -> String {
+    io::Error::from_raw_os_error(code as i32).to_string()
+}
+
+/// SID of the Everyone group, which covers the updater whatever account it runs as
+fn everyone_sid() -> Result<[u8; SECURITY_MAX_SID_SIZE as usize], String> {
+    let mut sid = [0u8; SECURITY_MAX_SID_SIZE as usize];
+    let mut size = SECURITY_MAX_SID_SIZE;
+    // SAFETY: `sid` is a writable buffer of `size` bytes
+    let ok = unsafe {
+        CreateWellKnownSid(
+            WinWorldSid,
+            std::ptr::null_mut(),
+            sid.as_mut_ptr().cast(),
+            &mut size,
+        )
+    };
+    if ok == 0 {
+        return Err(io::Error::last_os_error().to_string());
+    }
+    Ok(sid)
+}
+
+/// Read the DACL of a path; the security descriptor owning it must be freed with `LocalFree`
+fn read_dacl(wide: &[u16]) -> Result<(*mut ACL, PSECURITY_DESCRIPTOR), String> {
+    let mut dacl: *mut ACL = std::ptr::null_mut();
+    let mut descriptor: PSECURITY_DESCRIPTOR = std::ptr::null_mut();
+    // SAFETY: `wide` is NUL-terminated; the out pointers are valid for the call
+    let code = unsafe {
+        GetNamedSecurityInfoW(
+            wide.as_ptr(),
+            SE_FILE_OBJECT,
+            DACL_SECURITY_INFORMATION,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut dacl,
+            std::ptr::null_mut(),
+            &mut descriptor,
+        )
+    };
+    if code != ERROR_SUCCESS {
+        return Err(win32_error(code));
+    }
+    Ok((dacl, descriptor))
+}
+
+fn write_dacl(wide: &[u16], dacl: *const ACL) -> Result<(), String> {
+    // SAFETY: `wide` is NUL-terminated and `dacl` points to a valid ACL
+    let code = unsafe {
+        SetNamedSecurityInfoW(
+            wide.as_ptr(),
+            SE_FILE_OBJECT,
+            DACL_SECURITY_INFORMATION,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            dacl,
+            std::ptr::null(),
+        )
+    };
+    if code != ERROR_SUCCESS {
+        return Err(win32_error(code));
+    }
+    Ok(())
+}
+
+/// Add a deny entry for Everyone to a path
+fn add_deny(path: &Path, kind: LockKind) -> Result<(), String> {
+    let wide = to_wide(path);
+    let mut sid = everyone_sid()?;
+    let (dacl, descriptor) = read_dacl(&wide)?;
+    let access = EXPLICIT_ACCESS_W {
+        grfAccessPermissions: kind.mask(),
+        grfAccessMode: DENY_ACCESS,
+        // Never inherited, so asset folders below the kept version stay writable
+        grfInheritance: NO_INHERITANCE,
+        Trustee: TRUSTEE_W {
+            pMultipleTrustee: std::ptr::null_mut(),
+            MultipleTrusteeOperation: NO_MULTIPLE_TRUSTEE,
+            TrusteeForm: TRUSTEE_IS_SID,
+            TrusteeType: TRUSTEE_IS_WELL_KNOWN_GROUP,
+            ptstrName: sid.as_mut_ptr().cast(),
+        },
+    };
+    let mut new_dacl: *mut ACL = std::ptr::null_mut();
+    // SAFETY: `dacl` lives in `descriptor`, which is freed only after the new ACL is written
+    unsafe {
+        let code = SetEntriesInAclW(1, &access, dacl, &mut new_dacl);
+        let result = if code == ERROR_SUCCESS {
+            write_dacl(&wide, new_dacl)
+        } else {
+            Err(win32_error(code))
+        };
+        if !new_dacl.is_null() {
+            LocalFree(new_dacl.cast());
+        }
+        LocalFree(descriptor);
+        result
+    }
+}
+
+/// Remove the explicit deny entries for Everyone with exactly this lock's rights
+fn remove_deny(path: &Path, kind: LockKind) -> Result<(), String> {
+    let wide = to_wide(path);
+    let mut sid = everyone_sid()?;
+    let (dacl, descriptor) = read_dacl(&wide)?;
+    if dacl.is_null() {
+        // SAFETY: `descriptor` came from GetNamedSecurityInfoW
+        unsafe { LocalFree(descriptor) };
+        return Ok(());
+    }
+    // SAFETY: `dacl` lives in `descriptor`, which is freed last; ACE pointers come from GetAce
+    unsafe {
+        let mut info = ACL_SIZE_INFORMATION::default();
+        let mut removed = false;
+        if GetAclInformation(
+            dacl,
+            (&mut info as *mut ACL_SIZE_INFORMATION).cast(),
+            std::mem::size_of::<ACL_SIZE_INFORMATION>() as u32,
+            AclSizeInformation,
+        ) != 0
+        {
+            // Backwards, so deleting an entry keeps the remaining indices valid
+            for index in (0..info.AceCount).rev() {
+                let mut ace: *mut core::ffi::c_void = std::ptr::null_mut();
+                if GetAce(dacl, index, &mut ace) == 0 {
+                    continue;
+                }
+                let denied = &*(ace as *const ACCESS_DENIED_ACE);
+                let ours = denied.Header.AceType as u32 == ACCESS_DENIED_ACE_TYPE
+                    && denied.Header.AceFlags as u32 & INHERITED_ACE == 0
+                    && denied.Mask == kind.mask()
+                    && EqualSid(
+                        (&denied.SidStart as *const u32).cast_mut().cast(),
+                        sid.as_mut_ptr().cast(),
+                    ) != 0;
+                if ours && DeleteAce(dacl, index) != 0 {
+                    removed = true;
+                }
+            }
+        }
+        let result = if removed {
+            write_dacl(&wide, dacl)
+        } else {
+            Ok(())
+        };
+        LocalFree(descriptor);
+        result
+    }
+}
+
+/// Paths the lock covers, with what each denies
+pub fn paths_to_lock(apps: &Path, kept: &Path) -> Vec<(PathBuf, LockKind)> {
+    let mut targets = vec![
+        (apps.to_path_buf(), LockKind::Apps),
+        (kept.to_path_buf(), LockKind::Version),
+    ];
+    targets.extend(
+        hardening::critical_files(kept)
+            .into_iter()
+            .map(|file| (file, LockKind::File)),
+    );
+    let product_info = apps.join("ProductInfo.xml");
+    if product_info.is_file() {
+        targets.push((product_info, LockKind::File));
+    }
+    targets
+}
+
+/// Deny writes and deletes on the Apps folder, the kept version's binaries and ProductInfo.xml
+pub fn lock_install(apps: &Path, kept: &Path, logs: &mut Vec<LogEntry>) -> Result<(), String> {
+    let mut locks = load_locks();
+    let mut count = 0;
+
+    for (path, kind) in paths_to_lock(apps, kept) {
+        let transport = paths::path_to_transport(&path);
+        if locks
+            .locks
+            .iter()
+            .any(|l| l.path == transport && l.kind == kind)
+        {
+            count += 1;
+            continue;
+        }
+        if let Err(e) = add_deny(&path, kind) {
+            logs.push(
+                LogEntry::warn(format!("Could not lock permissions: {}", e)).with_path(&path),
+            );
+            continue;
+        }
+        locks.locks.push(AclLock {
+            path: transport,
+            kind,
+        });
+        count += 1;
+    }
+
+    save_locks(&locks)?;
+    logs.push(LogEntry::ok(format!(
+        "Write and delete denied on {} path(s)",
+        count
+    )));
+    Ok(())
+}
+
+/// Number of paths still recorded as locked
+pub fn locked_count() -> usize {
+    load_locks().locks.len()
+}
+
+/// Remove every deny entry this app added; entries that could not be removed stay recorded
+pub fn unlock_all(logs: &mut Vec<LogEntry>) {
+    let locks = load_locks();
+    if locks.locks.is_empty() {
+        return;
+    }
+
+    let mut remaining = AclLocks::default();
+    let mut released = 0;
+    for lock in locks.locks {
+        let path = paths::path_from_transport(&lock.path);
+        if !path.exists() {
+            // Deleted along with its deny entry
+            continue;
+        }
+        match remove_deny(&path, lock.kind) {
+            Ok(()) => released += 1,
+            Err(e) => {
+                logs.push(
+                    LogEntry::warn(format!("Could not remove permission lock: {}", e))
+                        .with_path(&path),
+                );
+                remaining.locks.push(lock);
+            }
+        }
+    }
+
+    match save_locks(&remaining) {
+        Ok(()) => logs.push(LogEntry::ok(format!(
+            "Permission lock removed from {} path(s)",
+            released
+        ))),
+        Err(e) => logs.push(LogEntry::warn(e)),
+    }
+}
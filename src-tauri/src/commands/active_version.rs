@@ -0,0 +1,86 @@
+//! Active version detection
+//! Works out which version folder CapCut is configured to launch from configure.ini and ProductInfo.xml
+
+use serde::Serialize;
+use std::fs;
+
+use super::paths::CapCutPaths;
+use super::version::{self, Version};
+use super::{channel, config_diff, reprotect};
+
+/// Where the active version was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActiveSource {
+    ConfigureIni,
+    ProductInfo,
+    /// configure.ini is locked; the version kept by the last protection run
+    Protection,
+}
+
+/// Version folder CapCut launches
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveVersion {
+    pub name: String,
+    pub source: ActiveSource,
+}
+
+/// Folder named by a recorded version: the same name, else the same version once the build is ignored
+fn match_folder<'a>(value: &str, folders: &'a [String]) -> Option<&'a String> {
+    if let Some(exact) = folders.iter().find(|f| f.as_str() == value) {
+        return Some(exact);
+    }
+    let recorded = Version::parse(value)?;
+    folders
+        .iter()
+        .filter(|f| Version::parse(f).is_some_and(|v| v.cmp_common(&recorded).is_eq()))
+        .max_by(|a, b| version::cmp_names(a, b))
+}
+
+/// Newest folder named by any of `values`
+fn newest_match(values: impl Iterator<Item = String>, folders: &[String]) -> Option<String> {
+    values
+        .filter_map(|value| match_folder(&value, folders))
+        .max_by(|a, b| version::cmp_names(a, b))
+        .cloned()
+}
+
+/// Version folder recorded in configure.ini's version keys (none while they are pinned to 1.0.0.0)
+fn from_config(install: &CapCutPaths, folders: &[String]) -> Option<String> {
+    let content = fs::read(install.apps.join("configure.ini")).ok()?;
+    let profile = channel::guard_profile(channel::detect_channel(&install.root, &install.apps));
+    let values = config_diff::parse_ini(&content)
+        .into_iter()
+        .filter(|(key, _)| profile.version_keys.contains(&key.as_str()))
+        .map(|(_, value)| value);
+    newest_match(values, folders)
+}
+
+/// Version folder named anywhere in ProductInfo.xml (empty while it is a blocker)
+fn from_product_info(install: &CapCutPaths, folders: &[String]) -> Option<String> {
+    let content = fs::read(install.apps.join("ProductInfo.xml")).ok()?;
+    let text = String::from_utf8_lossy(&content);
+    let values = text
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .filter(|token| token.contains('.'))
+        .map(str::to_string);
+    newest_match(values, folders)
+}
+
+/// Detect the active version among the install's version folder names
+pub fn detect(install: &CapCutPaths, folders: &[String]) -> Option<ActiveVersion> {
+    let found = |name: Option<String>, source| name.map(|name| ActiveVersion { name, source });
+    found(from_config(install, folders), ActiveSource::ConfigureIni)
+        .or_else(|| {
+            found(
+                from_product_info(install, folders),
+                ActiveSource::ProductInfo,
+            )
+        })
+        .or_else(|| {
+            found(
+                reprotect::kept_version().filter(|kept| folders.contains(kept)),
+                ActiveSource::Protection,
+            )
+        })
+}
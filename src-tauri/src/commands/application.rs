@@ -0,0 +1,145 @@
+//! Supported applications
+//! CapCut and the Chinese JianYing Pro (剪映专业版) share a layout but differ in folder, process, updater and registry names
+
+use serde::{Deserialize, Serialize};
+
+use super::paths::{self, CapCutPaths};
+use super::{installs, settings};
+
+/// Application the wizard and guards work on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Application {
+    #[default]
+    Capcut,
+    Jianying,
+}
+
+/// Names that differ between the applications
+#[derive(Debug)]
+pub struct AppProfile {
+    pub display_name: &'static str,
+    /// Install folder names under LOCALAPPDATA, tried in order
+    pub install_folders: &'static [&'static str],
+    /// Main executable inside each version folder
+    pub main_executable: &'static str,
+    /// Process names of the running application
+    pub process_names: &'static [&'static str],
+    /// Updater executables placed under User Data\Download
+    pub updater_names: &'static [&'static str],
+    /// configure.ini keys that record the installed version
+    pub version_keys: &'static [&'static str],
+    /// HKCU keys the application and its installer create
+    pub registry_keys: &'static [&'static str],
+}
+
+const CAPCUT_PROFILE: AppProfile = AppProfile {
+    display_name: "CapCut",
+    install_folders: &["CapCut"],
+    main_executable: "CapCut.exe",
+    process_names: &["CapCut.exe", "CapCut"],
+    updater_names: &["update.exe"],
+    version_keys: &["last_version"],
+    registry_keys: &[
+        r"Software\Bytedance\CapCut",
+        r"Software\CapCut",
+        r"Software\Microsoft\Windows\CurrentVersion\Uninstall\CapCut",
+        r"Software\Microsoft\Windows\CurrentVersion\App Paths\CapCut.exe",
+    ],
+};
+
+/// JianYing has a single updater for all channels and also records the installer's version
+const JIANYING_PROFILE: AppProfile = AppProfile {
+    display_name: "JianYing Pro",
+    install_folders: &["JianyingPro"],
+    main_executable: "JianyingPro.exe",
+    process_names: &["JianyingPro.exe", "JianyingPro"],
+    updater_names: &["update.exe", "JianyingProUpdate.exe"],
+    version_keys: &["last_version", "installer_version"],
+    registry_keys: &[
+        r"Software\Bytedance\JianyingPro",
+        r"Software\Microsoft\Windows\CurrentVersion\Uninstall\JianyingPro",
+        r"Software\Microsoft\Windows\CurrentVersion\App Paths\JianyingPro.exe",
+    ],
+};
+
+/// Every supported application
+pub const ALL: &[Application] = &[Application::Capcut, Application::Jianying];
+
+impl Application {
+    pub fn profile(self) -> &'static AppProfile {
+        match self {
+            Application::Capcut => &CAPCUT_PROFILE,
+            Application::Jianying => &JIANYING_PROFILE,
+        }
+    }
+}
+
+/// Application of an install, from its folder name or the executables of its versions
+pub fn of_install(install: &CapCutPaths) -> Application {
+    let jianying = JIANYING_PROFILE.install_folders;
+    let named = jianying
+        .iter()
+        .any(|name| install.variant.eq_ignore_ascii_case(name));
+    let has_exe = std::fs::read_dir(&install.apps)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .any(|e| e.path().join(JIANYING_PROFILE.main_executable).is_file());
+    if named || has_exe {
+        Application::Jianying
+    } else {
+        Application::Capcut
+    }
+}
+
+/// Application the calling thread works on: that of its install, else the one chosen on the Welcome screen
+pub fn current() -> Application {
+    match paths::resolve_capcut_paths() {
+        Some(install) => of_install(&install),
+        None => settings::load_settings().application,
+    }
+}
+
+/// Application choice shown on the Welcome screen
+#[derive(Debug, Clone, Serialize)]
+pub struct ApplicationStatus {
+    pub application: Application,
+    pub display_name: String,
+    /// Whether an install of the application was found
+    pub found: bool,
+    pub root: Option<String>,
+}
+
+fn status() -> ApplicationStatus {
+    let install = installs::active_install();
+    let application = match &install {
+        Some(i) => of_install(i),
+        None => settings::load_settings().application,
+    };
+    ApplicationStatus {
+        application,
+        display_name: application.profile().display_name.to_string(),
+        found: install.as_ref().is_some_and(|i| i.apps.is_dir()),
+        root: install.map(|i| paths::path_to_transport(&i.root)),
+    }
+}
+
+/// Get the application the wizard works on
+#[tauri::command]
+pub fn get_application() -> ApplicationStatus {
+    status()
+}
+
+/// Choose the application; switches to a detected install of it, or back to auto-detection
+#[tauri::command]
+pub fn set_application(application: Application) -> Result<ApplicationStatus, String> {
+    let mut app_settings = settings::load_settings();
+    app_settings.application = application;
+    app_settings.capcut_path = installs::detect_installs()
+        .into_iter()
+        .find(|i| of_install(i) == application)
+        .map(|i| i.root.to_string_lossy().to_string());
+    settings::save_user_settings(&app_settings)?;
+    Ok(status())
+}
@@ -0,0 +1,140 @@
+//! Apps folder guard
+//! Watches the Apps folder of the protected install and re-runs the saved protection when CapCut stages a new version folder
+
+use notify::event::{EventKind, ModifyKind};
+use notify::{RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use super::paths::{self, CapCutPaths};
+use super::{power, process, reprotect, settings};
+
+/// Quiet time after the last new folder before acting (updaters copy version folders in bursts)
+const SETTLE: Duration = Duration::from_secs(5);
+
+/// Ticks (500 ms) between re-reading the setting and the protection profile while watching
+const RELOAD_TICKS: u32 = 10;
+
+/// What the guard is armed for; a change re-arms it
+#[derive(PartialEq)]
+struct Target {
+    apps: PathBuf,
+    kept_version: String,
+}
+
+/// The active install's Apps folder while automatic re-apply is on and protection was run
+fn current_target() -> Option<(CapCutPaths, Target)> {
+    if !settings::load_settings().auto_reapply_protection {
+        return None;
+    }
+    let kept_version = reprotect::kept_version()?;
+    let install = paths::resolve_capcut_paths().filter(|p| p.apps.is_dir())?;
+    let target = Target {
+        apps: install.apps.clone(),
+        kept_version,
+    };
+    Some((install, target))
+}
+
+/// Names of the version folders in the install's Apps folder
+fn version_folders(install: &CapCutPaths) -> BTreeSet<String> {
+    fs::read_dir(&install.apps)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| install.is_version_dir(p))
+        .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .collect()
+}
+
+/// Watch the Apps folder and re-apply protection when new version folders appear, until stopped
+pub fn watch(app: AppHandle, stop: Arc<AtomicBool>, low_impact: bool) {
+    let settle = if low_impact {
+        SETTLE.max(Duration::from_millis(power::LOW_IMPACT_SETTLE_MS))
+    } else {
+        SETTLE
+    };
+
+    while !stop.load(Ordering::Relaxed) {
+        let (install, target) = match current_target() {
+            Some(t) => t,
+            None => {
+                thread::sleep(Duration::from_secs(2));
+                continue;
+            }
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(w) => w,
+            Err(e) => {
+                tracing::error!("Apps guard failed to start: {}", e);
+                return;
+            }
+        };
+        if watcher
+            .watch(&target.apps, RecursiveMode::NonRecursive)
+            .is_err()
+        {
+            thread::sleep(Duration::from_secs(2));
+            continue;
+        }
+
+        let mut known = version_folders(&install);
+        let mut pending_since: Option<Instant> = None;
+        let mut ticks = 0;
+        while !stop.load(Ordering::Relaxed) {
+            match rx.recv_timeout(Duration::from_millis(500)) {
+                Ok(Ok(event)) => {
+                    if matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_))
+                    ) {
+                        pending_since = Some(Instant::now());
+                    }
+                }
+                // Drive unplugged: re-arm quietly once it is back
+                Ok(Err(_)) if !target.apps.is_dir() => break,
+                Ok(Err(e)) => tracing::error!("Apps guard error: {}", e),
+                Err(RecvTimeoutError::Timeout) => {
+                    ticks += 1;
+                    // Setting turned off, protection reverted or another install made active
+                    if ticks >= RELOAD_TICKS {
+                        ticks = 0;
+                        if current_target().map(|(_, t)| t).as_ref() != Some(&target) {
+                            break;
+                        }
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            // CapCut (or its updater) still running: the deletion would fail, so wait
+            if pending_since.is_none_or(|t| t.elapsed() < settle) || process::is_capcut_running() {
+                continue;
+            }
+            pending_since = None;
+            let current = version_folders(&install);
+            let new_versions: Vec<String> = current
+                .difference(&known)
+                .filter(|name| **name != target.kept_version)
+                .cloned()
+                .collect();
+            if !new_versions.is_empty() {
+                reprotect::reapply_after_update(&app, &new_versions);
+            }
+            // Our own deletions fire events too; they only shrink the set
+            while rx.try_recv().is_ok() {}
+            known = version_folders(&install);
+        }
+    }
+}
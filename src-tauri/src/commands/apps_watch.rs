@@ -0,0 +1,107 @@
+//! Apps folder watcher
+//! Tells the version-select screen when version folders appear or disappear (e.g., a staged update)
+
+use notify::event::{EventKind, ModifyKind};
+use notify::{RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+
+use super::paths;
+
+/// Quiet time after the last change before the list is re-read (installers copy in bursts)
+const SETTLE: Duration = Duration::from_millis(1500);
+
+/// Managed watcher state (one watcher per app)
+#[derive(Default)]
+pub struct AppsWatchState {
+    stop_flag: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+/// Names of the version folders under `apps`
+fn version_folders(apps: &Path) -> BTreeSet<String> {
+    fs::read_dir(apps)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect()
+}
+
+/// Emit `apps-changed` whenever the set of version folders changes, until stopped
+fn watch(app: AppHandle, apps: &Path, stop: Arc<AtomicBool>) {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(w) => w,
+        Err(e) => {
+            tracing::error!("Apps watcher failed to start: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = watcher.watch(apps, RecursiveMode::NonRecursive) {
+        tracing::error!("Apps watcher failed to start: {}", e);
+        return;
+    }
+
+    let mut known = version_folders(apps);
+    let mut pending_since: Option<Instant> = None;
+    while !stop.load(Ordering::Relaxed) {
+        match rx.recv_timeout(Duration::from_millis(500)) {
+            Ok(Ok(event)) => {
+                // configure.ini rewrites show up as plain modifications
+                if matches!(
+                    event.kind,
+                    EventKind::Create(_)
+                        | EventKind::Remove(_)
+                        | EventKind::Modify(ModifyKind::Name(_))
+                ) {
+                    pending_since = Some(Instant::now());
+                }
+            }
+            Ok(Err(e)) => tracing::error!("Apps watcher error: {}", e),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        if pending_since.is_some_and(|t| t.elapsed() >= SETTLE) {
+            pending_since = None;
+            let current = version_folders(apps);
+            if current != known {
+                known = current;
+                let _ = app.emit("apps-changed", ());
+            }
+        }
+    }
+}
+
+/// Start watching the Apps folder (replaces a running watcher)
+#[tauri::command]
+pub fn start_apps_watch(app: AppHandle, state: State<'_, AppsWatchState>) -> Result<(), String> {
+    let apps = paths::get_capcut_apps_path()
+        .filter(|p| p.is_dir())
+        .ok_or("CapCut Apps folder not found")?;
+    let mut slot = state.stop_flag.lock().map_err(|e| e.to_string())?;
+    if let Some(previous) = slot.take() {
+        previous.store(true, Ordering::Relaxed);
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    *slot = Some(stop.clone());
+    std::thread::spawn(move || watch(app, &apps, stop));
+    Ok(())
+}
+
+/// Stop watching the Apps folder
+#[tauri::command]
+pub fn stop_apps_watch(state: State<'_, AppsWatchState>) {
+    if let Some(stop) = state.stop_flag.lock().ok().and_then(|mut f| f.take()) {
+        stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,254 @@
+//! Archive install
+//! Runs a downloaded archive installer silently, then keeps the version it installed and protects it
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, State};
+
+use super::cleaner::CacheCategory;
+use super::integrity::{self, SignatureStatus};
+use super::log_entry::LogEntry;
+use super::protector::{self, ProtectionParams, ProtectionResult};
+use super::{downloader, paths, process, reprotect, revert, scanner, version};
+
+/// Switch of the CapCut installer for an unattended install without its window
+const SILENT_ARGS: &[&str] = &["--silent_install=1"];
+
+/// The installer is killed and the run aborted after this long
+const INSTALL_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Time the installer gets to launch CapCut after it exits, so it can be closed before protecting
+const LAUNCH_GRACE: Duration = Duration::from_secs(5);
+
+/// Only one archive install runs at a time
+#[derive(Default)]
+pub struct ArchiveInstallState {
+    running: AtomicBool,
+}
+
+/// Protection options of the pipeline (the wizard's options screen)
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstallOptions {
+    #[serde(default)]
+    pub backup_drafts_to: Option<String>,
+    #[serde(default)]
+    pub draft_projects: Vec<String>,
+    pub clean_cache: bool,
+    #[serde(default)]
+    pub cache_categories: Vec<CacheCategory>,
+    pub lock_config: bool,
+    pub create_blockers: bool,
+    #[serde(default)]
+    pub harden_binaries: bool,
+    #[serde(default)]
+    pub stub_updaters: bool,
+    #[serde(default)]
+    pub lock_acls: bool,
+    #[serde(default)]
+    pub block_domains: bool,
+    #[serde(default)]
+    pub firewall_rules: bool,
+    #[serde(default)]
+    pub disable_autoruns: bool,
+}
+
+/// Step of the pipeline, emitted as `archive-install-progress`
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallStage {
+    Unprotecting,
+    Installing,
+    ClosingCapcut,
+    Scanning,
+    Protecting,
+}
+
+/// Outcome of installing and protecting an archive version
+#[derive(Serialize)]
+pub struct ArchiveInstallResult {
+    /// Version folder the installer created (the one kept)
+    pub installed_version: String,
+    /// Steps before protection (unprotect, install, scan)
+    pub logs: Vec<LogEntry>,
+    pub protection: ProtectionResult,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Installer from the app's Downloads folder; anything else is refused
+fn downloaded_installer(path: &str) -> Result<PathBuf, String> {
+    let dir = downloader::get_download_dir()
+        .and_then(|d| fs::canonicalize(d).ok())
+        .ok_or("No archive version has been downloaded yet")?;
+    let installer = fs::canonicalize(paths::path_from_transport(path))
+        .map_err(|_| "The downloaded installer was not found; download it again".to_string())?;
+    let is_exe = installer
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("exe"));
+    if !installer.starts_with(&dir) || !is_exe {
+        return Err("Only installers downloaded by Version Guard can be installed".to_string());
+    }
+    Ok(installer)
+}
+
+/// Run the installer silently and wait for it to exit
+fn run_installer(installer: &Path) -> Result<(), String> {
+    let mut child = Command::new(installer)
+        .args(SILENT_ARGS)
+        .spawn()
+        .map_err(|e| format!("Could not start the installer: {}", e))?;
+
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => {
+                return Err(format!(
+                    "The installer failed (exit code {})",
+                    status.code().unwrap_or(-1)
+                ))
+            }
+            Ok(None) if started.elapsed() >= INSTALL_TIMEOUT => {
+                let _ = child.kill();
+                return Err("The installer did not finish within 10 minutes".to_string());
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(500)),
+            Err(e) => return Err(format!("Could not wait for the installer: {}", e)),
+        }
+    }
+}
+
+fn install_and_protect(
+    app: &AppHandle,
+    installer: &str,
+    options: &InstallOptions,
+) -> Result<ArchiveInstallResult, String> {
+    let stage = |s: InstallStage| {
+        let _ = app.emit("archive-install-progress", s);
+    };
+    let installer = downloaded_installer(installer)?;
+    if process::is_capcut_running() {
+        return Err("CapCut is still running. Please close it.".to_string());
+    }
+    // Checked again in case the file was swapped since the download; an unchecked installer never runs
+    let (signature, signer) = integrity::check_signature(&installer);
+    if signature != SignatureStatus::Valid && !downloader::matches_published_hash(&installer) {
+        return Err(format!(
+            "The installer has no valid Bytedance signature ({}) and does not match a published hash, so it was not run",
+            signer.as_deref().unwrap_or(match signature {
+                SignatureStatus::Unsigned => "unsigned",
+                SignatureStatus::Unknown => "could not be checked",
+                _ => "invalid",
+            })
+        ));
+    }
+
+    let mut logs: Vec<LogEntry> = Vec::new();
+
+    // Locks and blockers would make the installer fail, and the watchdog would delete what it installs
+    if reprotect::kept_version().is_some() || protector::check_protection_status().is_protected {
+        stage(InstallStage::Unprotecting);
+        logs.push(LogEntry::step("Removing current protection"));
+        let reverted = revert::revert()?;
+        logs.extend(reverted.logs);
+        for check in reverted.checks.iter().filter(|c| !c.removed) {
+            logs.push(LogEntry::warn(format!(
+                "{}: {}",
+                check.name,
+                check.detail.as_deref().unwrap_or("still in place")
+            )));
+        }
+    }
+
+    stage(InstallStage::Installing);
+    let before: HashSet<String> = scanner::scan_installed_versions()
+        .into_iter()
+        .map(|v| v.name)
+        .collect();
+    let started = now_secs();
+    logs.push(LogEntry::step("Running installer").with_path(&installer));
+    run_installer(&installer)?;
+    logs.push(LogEntry::ok("Installer finished"));
+
+    // The installer usually launches CapCut when it finishes
+    thread::sleep(LAUNCH_GRACE);
+    if process::is_capcut_running() {
+        stage(InstallStage::ClosingCapcut);
+        let closed = process::close_capcut()?;
+        logs.push(LogEntry::ok(format!(
+            "Closed CapCut after install ({})",
+            closed.join(", ")
+        )));
+    }
+
+    stage(InstallStage::Scanning);
+    let versions = scanner::scan_installed_versions();
+    // A reinstall of a version already present rewrites its folder instead of adding one
+    let installed = versions
+        .iter()
+        .filter(|v| !before.contains(&v.name) || v.modified.is_some_and(|m| m >= started))
+        .max_by(|a, b| version::cmp_names(&a.name, &b.name))
+        .ok_or("The installer finished but no CapCut version folder was added")?;
+    logs.push(LogEntry::ok(format!(
+        "Installed version {}",
+        installed.name
+    )));
+
+    stage(InstallStage::Protecting);
+    let protection = protector::run_full_protection(ProtectionParams {
+        versions_to_delete: versions
+            .iter()
+            .filter(|v| v.path != installed.path)
+            .map(|v| v.path.clone())
+            .collect(),
+        keep_version: Some(installed.path.clone()),
+        backup_drafts_to: options.backup_drafts_to.clone(),
+        draft_projects: options.draft_projects.clone(),
+        clean_cache: options.clean_cache,
+        cache_categories: options.cache_categories.clone(),
+        lock_config: options.lock_config,
+        create_blockers: options.create_blockers,
+        harden_binaries: options.harden_binaries,
+        stub_updaters: options.stub_updaters,
+        lock_acls: options.lock_acls,
+        block_domains: options.block_domains,
+        firewall_rules: options.firewall_rules,
+        disable_autoruns: options.disable_autoruns,
+    });
+
+    Ok(ArchiveInstallResult {
+        installed_version: installed.name.clone(),
+        logs,
+        protection,
+    })
+}
+
+/// Install a downloaded archive version silently and protect it, deleting every other version
+#[tauri::command]
+pub async fn install_archive_version(
+    app: AppHandle,
+    state: State<'_, ArchiveInstallState>,
+    path: String,
+    options: InstallOptions,
+) -> Result<ArchiveInstallResult, String> {
+    if state.running.swap(true, Ordering::SeqCst) {
+        return Err("An archive version is already being installed".to_string());
+    }
+    let result =
+        tauri::async_runtime::spawn_blocking(move || install_and_protect(&app, &path, &options))
+            .await
+            .map_err(|e| e.to_string());
+    state.running.store(false, Ordering::SeqCst);
+    result?
+}
@@ -0,0 +1,121 @@
+//! Downloaded asset migration
+//! Moves user-downloaded effects, fonts and LUTs from versions about to be deleted into the kept version
+
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+use super::log_entry::LogEntry;
+
+/// Asset folders (relative to a version folder) that CapCut downloads into
+const ASSET_DIRS: &[(&str, &str)] = &[
+    ("effects", "Resources\\effect_download"),
+    ("effects", "Resources\\downloaded_effects"),
+    ("fonts", "Resources\\fonts"),
+    ("LUTs", "Resources\\lut"),
+    ("LUTs", "Resources\\luts"),
+];
+
+/// Move one file, falling back to copy + delete across volumes
+fn move_file(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to).map_err(|e| e.to_string())?;
+    fs::remove_file(from).ok();
+    Ok(())
+}
+
+/// Downloaded assets `migrate_assets` would move, as (file count, bytes)
+pub fn pending_assets(from: &Path, kept: &Path) -> (usize, u64) {
+    let mut files = 0;
+    let mut bytes = 0;
+    for (_, rel) in ASSET_DIRS {
+        let source_dir = from.join(rel);
+        if !source_dir.is_dir() {
+            continue;
+        }
+        let target_dir = kept.join(rel);
+        for entry in WalkDir::new(&source_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let missing = entry
+                .path()
+                .strip_prefix(&source_dir)
+                .is_ok_and(|r| !target_dir.join(r).exists());
+            if missing {
+                files += 1;
+                bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            }
+        }
+    }
+    (files, bytes)
+}
+
+/// Move downloaded assets missing from `kept` out of `from`; returns the number of files moved
+///
+/// Files the kept version already has are left alone so its bundled copies are never replaced.
+pub fn migrate_assets(from: &Path, kept: &Path, logs: &mut Vec<LogEntry>) -> usize {
+    let name = from.file_name().unwrap_or_default().to_string_lossy();
+    let mut moved = 0;
+    let mut failed = 0;
+
+    for (kind, rel) in ASSET_DIRS {
+        let source_dir = from.join(rel);
+        if !source_dir.is_dir() {
+            continue;
+        }
+        let target_dir = kept.join(rel);
+
+        let mut moved_here = 0;
+        for entry in WalkDir::new(&source_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let rel_file = match entry.path().strip_prefix(&source_dir) {
+                Ok(r) => r,
+                Err(_) => continue,
+            };
+            let target = target_dir.join(rel_file);
+            if target.exists() {
+                continue;
+            }
+            match move_file(entry.path(), &target) {
+                Ok(()) => moved_here += 1,
+                Err(e) => {
+                    logs.push(
+                        LogEntry::warn(format!(
+                            "Could not move {} from {}: {}",
+                            rel_file.display(),
+                            name,
+                            e
+                        ))
+                        .with_path(entry.path()),
+                    );
+                    failed += 1;
+                }
+            }
+        }
+        if moved_here > 0 {
+            logs.push(LogEntry::ok(format!(
+                "Moved {} downloaded {} file(s) from {}",
+                moved_here, kind, name
+            )));
+        }
+        moved += moved_here;
+    }
+
+    if failed > 0 {
+        logs.push(LogEntry::warn(format!(
+            "{} asset(s) from {} stay in its backup",
+            failed, name
+        )));
+    }
+    moved
+}
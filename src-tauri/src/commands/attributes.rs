@@ -0,0 +1,87 @@
+//! Read-only attribute clearing
+//! Parallel walk of large trees (cache folders can hold hundreds of thousands of files)
+
+use rayon::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::{io_priority, paths};
+
+/// Attribute changes are applied in batches of this many files per task
+const BATCH_SIZE: usize = 256;
+
+/// Read-only entries under `root`, walking each top-level subtree on its own thread
+fn find_readonly(root: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    // Never walk into a junction's target; only the link itself is in scope
+    let children: Vec<PathBuf> = match fs::read_dir(root) {
+        Ok(entries) if !paths::is_link(root) => {
+            entries.filter_map(|e| e.ok()).map(|e| e.path()).collect()
+        }
+        // A single file, link or unreadable folder is checked on its own
+        _ => Vec::new(),
+    };
+    if is_readonly(root) {
+        found.push(root.to_path_buf());
+    }
+
+    found.par_extend(children.par_iter().flat_map_iter(|child| {
+        // Directory metadata comes with the listing on Windows, so no extra call per file
+        WalkDir::new(child)
+            .follow_root_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.metadata().is_ok_and(|m| m.permissions().readonly()))
+            .map(|e| e.into_path())
+    }));
+    found
+}
+
+fn is_readonly(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|m| m.permissions().readonly())
+}
+
+/// Clear read-only from `path` and everything under it
+///
+/// Trees without read-only entries are only walked, never written.
+pub fn clear_readonly_recursive(path: &Path) -> Result<(), String> {
+    // Stays on low-priority threads when deleting in background mode
+    io_priority::install(|| clear_readonly(path))
+}
+
+#[allow(clippy::permissions_set_readonly_false)]
+fn clear_readonly(path: &Path) -> Result<(), String> {
+    let readonly = find_readonly(path);
+    if readonly.is_empty() {
+        return Ok(());
+    }
+
+    let failed: usize = readonly
+        .par_chunks(BATCH_SIZE)
+        .map(|batch| {
+            batch
+                .iter()
+                .filter(|p| {
+                    let mut perms = match fs::symlink_metadata(p) {
+                        Ok(m) => m.permissions(),
+                        // Removed meanwhile
+                        Err(_) => return false,
+                    };
+                    perms.set_readonly(false);
+                    fs::set_permissions(p, perms).is_err()
+                })
+                .count()
+        })
+        .sum();
+
+    match failed {
+        0 => Ok(()),
+        n => Err(format!(
+            "Could not clear read-only on {} of {} file(s) under {}",
+            n,
+            readonly.len(),
+            path.display()
+        )),
+    }
+}
@@ -0,0 +1,385 @@
+//! CapCut scheduled tasks and autorun entries
+//! Finds Task Scheduler tasks and Run-key entries that start CapCut or its updater, and disables or deletes them
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::os::windows::process::CommandExt;
+use std::path::PathBuf;
+use std::process::Command;
+use winreg::enums::*;
+use winreg::RegKey;
+
+use super::log_entry::LogEntry;
+use super::{paths, policy};
+
+/// Prevents a console window flashing up for PowerShell
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Entries whose name or command contains this are CapCut's (case-insensitive)
+const MATCH: &str = "capcut";
+
+/// Our own startup entry (see `autostart.rs`), never touched
+const OWN_ENTRY: &str = "CCVersionGuard";
+
+/// Run keys checked, with the StartupApproved key Task Manager uses to disable their entries
+const RUN_KEYS: &[(&str, &str)] = &[
+    (
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\Run",
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\Run",
+    ),
+    (
+        r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Run",
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\Run32",
+    ),
+    (r"SOFTWARE\Microsoft\Windows\CurrentVersion\RunOnce", ""),
+];
+
+/// StartupApproved value marking an entry disabled (first byte 3; 2 = enabled)
+const APPROVED_DISABLED: [u8; 12] = [3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+/// Lists CapCut tasks as "path|name|state|command" lines
+const TASKS_SCRIPT: &str = r#"
+[Console]::OutputEncoding = [Text.Encoding]::UTF8
+Get-ScheduledTask | ForEach-Object {
+  $cmd = ($_.Actions | ForEach-Object { "$($_.Execute) $($_.Arguments)".Trim() }) -join '; '
+  if ("$($_.TaskName) $cmd" -match 'capcut') {
+    $_.TaskPath + '|' + $_.TaskName + '|' + $_.State + '|' + $cmd
+  }
+}
+"#;
+
+/// Where an autorun entry lives
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutorunKind {
+    ScheduledTask,
+    RunKey,
+}
+
+/// A scheduled task or Run-key entry that starts CapCut or its updater
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutorunEntry {
+    /// "task:<path><name>" or "run:<HKCU|HKLM>\<key>|<value>"
+    pub id: String,
+    pub kind: AutorunKind,
+    pub name: String,
+    /// Task folder or registry key
+    pub location: String,
+    pub command: String,
+    pub enabled: bool,
+}
+
+/// Entries disabled by protection runs, re-enabled on unprotect
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DisabledAutoruns {
+    ids: Vec<String>,
+}
+
+fn get_disabled_path() -> Option<PathBuf> {
+    paths::get_app_data_dir().map(|p| p.join("disabled_autoruns.json"))
+}
+
+fn load_disabled() -> DisabledAutoruns {
+    get_disabled_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_disabled(disabled: &DisabledAutoruns) -> Result<(), String> {
+    let path = get_disabled_path().ok_or("Could not determine data directory")?;
+    let content = serde_json::to_string_pretty(disabled).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save disabled autoruns: {}", e))
+}
+
+fn hive(name: &str) -> Option<RegKey> {
+    match name {
+        "HKCU" => Some(RegKey::predef(HKEY_CURRENT_USER)),
+        "HKLM" => Some(RegKey::predef(HKEY_LOCAL_MACHINE)),
+        _ => None,
+    }
+}
+
+fn registry_error(e: std::io::Error) -> String {
+    match e.kind() {
+        std::io::ErrorKind::PermissionDenied => {
+            "Changing machine-wide entries requires running Version Guard as administrator"
+                .to_string()
+        }
+        _ => e.to_string(),
+    }
+}
+
+/// CapCut entries of the Run keys in both hives
+fn run_key_entries() -> Vec<AutorunEntry> {
+    let mut entries = Vec::new();
+    for hive_name in ["HKCU", "HKLM"] {
+        let root = match hive(hive_name) {
+            Some(r) => r,
+            None => continue,
+        };
+        for (run_key, approved_key) in RUN_KEYS {
+            let key = match root.open_subkey(run_key) {
+                Ok(k) => k,
+                Err(_) => continue,
+            };
+            let approved = root.open_subkey(approved_key).ok();
+            for (name, value) in key.enum_values().filter_map(|v| v.ok()) {
+                let command = value.to_string();
+                let matches =
+                    name.to_lowercase().contains(MATCH) || command.to_lowercase().contains(MATCH);
+                if !matches || name.eq_ignore_ascii_case(OWN_ENTRY) {
+                    continue;
+                }
+                let enabled = approved
+                    .as_ref()
+                    .and_then(|k| k.get_raw_value(&name).ok())
+                    .is_none_or(|v| v.bytes.first().is_none_or(|b| b % 2 == 0));
+                entries.push(AutorunEntry {
+                    id: format!("run:{}\\{}|{}", hive_name, run_key, name),
+                    kind: AutorunKind::RunKey,
+                    location: format!("{}\\{}", hive_name, run_key),
+                    name,
+                    command: command.trim_matches('"').to_string(),
+                    enabled,
+                });
+            }
+        }
+    }
+    entries
+}
+
+/// CapCut tasks in Task Scheduler
+fn scheduled_task_entries() -> Vec<AutorunEntry> {
+    let output = match Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", TASKS_SCRIPT])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+    {
+        Ok(o) => o,
+        Err(_) => return Vec::new(),
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().splitn(4, '|');
+            let (path, name, state) = (fields.next()?, fields.next()?, fields.next()?);
+            Some(AutorunEntry {
+                id: format!("task:{}{}", path, name),
+                kind: AutorunKind::ScheduledTask,
+                name: name.to_string(),
+                location: path.to_string(),
+                command: fields.next().unwrap_or_default().to_string(),
+                enabled: !state.eq_ignore_ascii_case("Disabled"),
+            })
+        })
+        .collect()
+}
+
+/// Every CapCut scheduled task and Run-key entry
+pub fn find_autoruns() -> Vec<AutorunEntry> {
+    let mut entries = scheduled_task_entries();
+    entries.extend(run_key_entries());
+    entries
+}
+
+/// Run a ScheduledTasks cmdlet on one task; the path and name are passed through the environment
+fn task_cmdlet(entry: &AutorunEntry, cmdlet: &str) -> Result<(), String> {
+    let script = format!(
+        "$ErrorActionPreference = 'Stop'; try {{ {} -TaskPath $env:CCVG_TASK_PATH -TaskName $env:CCVG_TASK_NAME | Out-Null }} catch {{ $_.Exception.Message; exit 1 }}",
+        cmdlet
+    );
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .env("CCVG_TASK_PATH", &entry.location)
+        .env("CCVG_TASK_NAME", &entry.name)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("Could not run PowerShell: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Could not change task {}: {}",
+            entry.name,
+            String::from_utf8_lossy(&output.stdout).trim()
+        ))
+    }
+}
+
+/// Hive, Run key and StartupApproved key of a Run-key entry
+fn run_key_parts(entry: &AutorunEntry) -> Result<(RegKey, &'static str, &'static str), String> {
+    let (hive_name, key) = entry
+        .location
+        .split_once('\\')
+        .ok_or("Unknown registry location")?;
+    let root = hive(hive_name).ok_or("Unknown registry hive")?;
+    RUN_KEYS
+        .iter()
+        .find(|(run, _)| *run == key)
+        .map(|(run, approved)| (root, *run, *approved))
+        .ok_or_else(|| "Unknown Run key".to_string())
+}
+
+fn set_enabled(entry: &AutorunEntry, enabled: bool) -> Result<(), String> {
+    match entry.kind {
+        AutorunKind::ScheduledTask => task_cmdlet(
+            entry,
+            if enabled {
+                "Enable-ScheduledTask"
+            } else {
+                "Disable-ScheduledTask"
+            },
+        ),
+        AutorunKind::RunKey => {
+            let (root, _, approved_key) = run_key_parts(entry)?;
+            if approved_key.is_empty() {
+                return Err("RunOnce entries can only be deleted".to_string());
+            }
+            let (approved, _) = root.create_subkey(approved_key).map_err(registry_error)?;
+            if enabled {
+                match approved.delete_value(&entry.name) {
+                    Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(registry_error(e)),
+                    _ => Ok(()),
+                }
+            } else {
+                approved
+                    .set_raw_value(
+                        &entry.name,
+                        &winreg::RegValue {
+                            bytes: APPROVED_DISABLED.to_vec(),
+                            vtype: REG_BINARY,
+                        },
+                    )
+                    .map_err(registry_error)
+            }
+        }
+    }
+}
+
+fn delete(entry: &AutorunEntry) -> Result<(), String> {
+    match entry.kind {
+        AutorunKind::ScheduledTask => {
+            task_cmdlet(entry, "Unregister-ScheduledTask -Confirm:$false")
+        }
+        AutorunKind::RunKey => {
+            let (root, run_key, approved_key) = run_key_parts(entry)?;
+            root.open_subkey_with_flags(run_key, KEY_WRITE)
+                .and_then(|k| k.delete_value(&entry.name))
+                .map_err(registry_error)?;
+            if let Ok(approved) = root.open_subkey_with_flags(approved_key, KEY_WRITE) {
+                let _ = approved.delete_value(&entry.name);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn find_entry(id: &str) -> Result<AutorunEntry, String> {
+    find_autoruns()
+        .into_iter()
+        .find(|e| e.id == id)
+        .ok_or_else(|| "That autorun entry no longer exists".to_string())
+}
+
+/// Disable every enabled CapCut autorun entry, remembering them for unprotect
+pub fn disable_all(logs: &mut Vec<LogEntry>) -> Result<(), String> {
+    let mut disabled = load_disabled();
+    let mut failed = 0;
+    for entry in find_autoruns().into_iter().filter(|e| e.enabled) {
+        // RunOnce entries have no disabled state and run at most once more
+        if entry.kind == AutorunKind::RunKey && entry.location.ends_with("RunOnce") {
+            continue;
+        }
+        match set_enabled(&entry, false) {
+            Ok(()) => {
+                logs.push(LogEntry::ok(format!("Autorun disabled: {}", entry.name)));
+                if !disabled.ids.contains(&entry.id) {
+                    disabled.ids.push(entry.id);
+                }
+            }
+            Err(e) => {
+                logs.push(LogEntry::warn(e));
+                failed += 1;
+            }
+        }
+    }
+    save_disabled(&disabled)?;
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} autorun entry(s) could not be disabled", n)),
+    }
+}
+
+/// Re-enable the entries protection runs disabled that still exist
+pub fn restore_disabled(logs: &mut Vec<LogEntry>) {
+    let mut disabled = load_disabled();
+    if disabled.ids.is_empty() {
+        return;
+    }
+    let entries = find_autoruns();
+    disabled.ids.retain(|id| {
+        let entry = match entries.iter().find(|e| e.id == *id) {
+            Some(e) => e,
+            None => return false,
+        };
+        match set_enabled(entry, true) {
+            Ok(()) => {
+                logs.push(LogEntry::ok(format!("Autorun re-enabled: {}", entry.name)));
+                false
+            }
+            Err(e) => {
+                logs.push(LogEntry::warn(e));
+                true
+            }
+        }
+    });
+    if let Err(e) = save_disabled(&disabled) {
+        logs.push(LogEntry::warn(e));
+    }
+}
+
+/// Number of entries disabled by protection runs that unprotect still has to re-enable
+pub fn pending_restore_count() -> usize {
+    load_disabled().ids.len()
+}
+
+/// List CapCut scheduled tasks and Run-key entries
+#[tauri::command]
+pub async fn scan_autoruns() -> Result<Vec<AutorunEntry>, String> {
+    tauri::async_runtime::spawn_blocking(find_autoruns)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Disable or re-enable one CapCut autorun entry
+#[tauri::command]
+pub async fn set_autorun_enabled(id: String, enabled: bool) -> Result<(), String> {
+    if enabled && policy::load_policy().disallow_unprotect {
+        return Err(policy::POLICY_BLOCKED.to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let entry = find_entry(&id)?;
+        set_enabled(&entry, enabled)?;
+        // A manual change takes the entry out of unprotect's hands
+        let mut disabled = load_disabled();
+        disabled.ids.retain(|d| *d != id);
+        save_disabled(&disabled)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Delete one CapCut autorun entry
+#[tauri::command]
+pub async fn delete_autorun(id: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let entry = find_entry(&id)?;
+        delete(&entry)?;
+        let mut disabled = load_disabled();
+        disabled.ids.retain(|d| *d != id);
+        save_disabled(&disabled)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
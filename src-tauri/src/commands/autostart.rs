@@ -14,8 +14,8 @@ pub fn get_autostart_enabled() -> bool {
     let hkcu = RegKey::predef(HKEY_CURRENT_USER);
 
     if let Ok(key) = hkcu.open_subkey(STARTUP_KEY) {
-        let result: Result<String, _> = key.get_value(APP_NAME);
-        return result.is_ok();
+        // Raw read: the stored exe path may not be valid Unicode
+        return key.get_raw_value(APP_NAME).is_ok();
     }
 
     false
@@ -24,6 +24,10 @@ pub fn get_autostart_enabled() -> bool {
 /// Enable or disable auto-start
 #[tauri::command]
 pub fn set_autostart_enabled(enabled: bool) -> Result<(), String> {
+    if !enabled && super::policy::load_policy().force_autostart {
+        return Err(super::policy::POLICY_BLOCKED.to_string());
+    }
+
     let hkcu = RegKey::predef(HKEY_CURRENT_USER);
 
     if enabled {
@@ -31,13 +35,11 @@ pub fn set_autostart_enabled(enabled: bool) -> Result<(), String> {
         let exe_path =
             env::current_exe().map_err(|e| format!("Failed to get executable path: {}", e))?;
 
-        let exe_str = exe_path.to_string_lossy().to_string();
-
         let key = hkcu
             .open_subkey_with_flags(STARTUP_KEY, winreg::enums::KEY_WRITE)
             .map_err(|e| format!("Failed to open registry key: {}", e))?;
 
-        key.set_value(APP_NAME, &exe_str)
+        key.set_value(APP_NAME, &exe_path.as_os_str())
             .map_err(|e| format!("Failed to set registry value: {}", e))?;
     } else {
         let key = hkcu
@@ -38,14 +38,12 @@ pub struct RestoreResult {
 }
 
 /// Get the backup directory path
-fn get_backup_dir() -> Option<PathBuf> {
-    std::env::var("LOCALAPPDATA")
-        .ok()
-        .map(|p| PathBuf::from(p).join("CCVersionGuard").join("Backups"))
+pub fn get_backup_dir() -> Option<PathBuf> {
+    super::paths::get_app_data_dir().map(|p| p.join("Backups"))
 }
 
 /// Calculate directory size
-fn calc_dir_size(path: &Path) -> u64 {
+pub fn calc_dir_size(path: &Path) -> u64 {
     WalkDir::new(path)
         .into_iter()
         .filter_map(|e| e.ok())
@@ -105,7 +103,7 @@ pub fn create_backup(version_path: &Path, reason: &str) -> BackupResult {
     let size_bytes = calc_dir_size(&backup_path);
     let metadata = BackupMetadata {
         version_name,
-        original_path: version_path.to_string_lossy().to_string(),
+        original_path: super::paths::path_to_transport(version_path),
         created_at: timestamp,
         size_bytes,
         reason: reason.to_string(),
@@ -118,7 +116,7 @@ pub fn create_backup(version_path: &Path, reason: &str) -> BackupResult {
         serde_json::to_string_pretty(&metadata).unwrap_or_default(),
     ) {
         // Non-fatal error, backup still exists
-        eprintln!("Warning: Could not save metadata: {}", e);
+        tracing::warn!("Could not save metadata: {}", e);
     }
 
     BackupResult {
@@ -129,7 +127,7 @@ pub fn create_backup(version_path: &Path, reason: &str) -> BackupResult {
 }
 
 /// Copy directory recursively
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
+pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
     if !src.is_dir() {
         return Err("Source is not a directory".to_string());
     }
@@ -198,7 +196,7 @@ pub fn restore_backup(backup_id: &str) -> RestoreResult {
         }
     };
 
-    let original_path = PathBuf::from(&metadata.original_path);
+    let original_path = super::paths::path_from_transport(&metadata.original_path);
 
     // Ensure parent directory exists
     if let Some(parent) = original_path.parent() {
@@ -0,0 +1,56 @@
+//! User-added blocker files
+//! Extra updater staging paths (relative to the CapCut folder) locked alongside the built-in blockers
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use super::settings;
+
+/// Validate a blocker path from settings: relative to the CapCut folder, never leaving it
+pub fn parse_blocker_path(value: &str) -> Result<PathBuf, String> {
+    let trimmed = value.trim().trim_matches(['\\', '/']);
+    if trimmed.is_empty() {
+        return Err("Enter a path relative to the CapCut folder".to_string());
+    }
+    let path = PathBuf::from(trimmed.replace('/', "\\"));
+    if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(format!(
+            "{} must be relative to the CapCut folder (no drive, root or ..)",
+            value.trim()
+        ));
+    }
+    Ok(path)
+}
+
+/// Absolute paths of the valid user-added blockers for an install
+pub fn extra_blocker_paths(capcut_root: &Path) -> Vec<PathBuf> {
+    settings::load_settings()
+        .extra_blockers
+        .iter()
+        .filter_map(|v| parse_blocker_path(v).ok())
+        .map(|rel| capcut_root.join(rel))
+        .collect()
+}
+
+/// Whether `path` is an empty read-only file, as written by protection
+pub fn is_blocker(path: &Path) -> bool {
+    fs::metadata(path)
+        .map(|meta| meta.is_file() && meta.len() == 0 && meta.permissions().readonly())
+        .unwrap_or(false)
+}
+
+/// Add a blocker path to settings; returns the updated list
+#[tauri::command]
+pub fn add_extra_blocker(path: String) -> Result<Vec<String>, String> {
+    let normalized = parse_blocker_path(&path)?.to_string_lossy().to_string();
+    let mut app_settings = settings::load_settings();
+    if !app_settings
+        .extra_blockers
+        .iter()
+        .any(|p| p.eq_ignore_ascii_case(&normalized))
+    {
+        app_settings.extra_blockers.push(normalized);
+        settings::save_user_settings(&app_settings)?;
+    }
+    Ok(app_settings.extra_blockers)
+}
@@ -0,0 +1,104 @@
+//! CapCut release channel detection
+//! Beta builds use a different updater and extra config keys, so guards adapt per channel
+
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use super::application::{self, Application};
+
+/// CapCut release channel
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Channel {
+    Stable,
+    Beta,
+}
+
+/// Files and keys the guards must cover for a channel
+#[derive(Debug, Clone, Copy)]
+pub struct GuardProfile {
+    /// Updater executables placed under User Data\Download
+    pub updater_names: &'static [&'static str],
+    /// configure.ini keys that record the installed version
+    pub version_keys: &'static [&'static str],
+}
+
+const STABLE_PROFILE: GuardProfile = GuardProfile {
+    updater_names: &["update.exe"],
+    version_keys: &["last_version"],
+};
+
+const BETA_PROFILE: GuardProfile = GuardProfile {
+    updater_names: &["update.exe", "update_beta.exe"],
+    version_keys: &["last_version", "beta_last_version"],
+};
+
+/// configure.ini keys that name the update channel
+const CHANNEL_KEYS: &[&str] = &["channel", "update_channel"];
+
+/// Check configure.ini for a beta channel entry
+fn config_declares_beta(apps_path: &Path) -> bool {
+    let content = match fs::read_to_string(apps_path.join("configure.ini")) {
+        Ok(c) => c,
+        Err(_) => return false,
+    };
+
+    content.lines().any(|line| {
+        let mut parts = line.splitn(2, '=');
+        let key = parts.next().unwrap_or_default().trim().to_lowercase();
+        let value = parts.next().unwrap_or_default().trim().to_lowercase();
+        CHANNEL_KEYS.contains(&key.as_str()) && value.contains("beta")
+    })
+}
+
+/// Detect the channel from config entries, beta-only keys, or beta version folders
+pub fn detect_channel(capcut_root: &Path, apps_path: &Path) -> Channel {
+    if config_declares_beta(apps_path) {
+        return Channel::Beta;
+    }
+
+    let beta_updater = capcut_root
+        .join("User Data")
+        .join("Download")
+        .join("update_beta.exe");
+    if beta_updater.exists() {
+        return Channel::Beta;
+    }
+
+    let has_beta_folder = fs::read_dir(apps_path)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .any(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .to_lowercase()
+                .contains("beta")
+        });
+
+    if has_beta_folder {
+        Channel::Beta
+    } else {
+        Channel::Stable
+    }
+}
+
+/// Guard profile for a channel of the application the calling thread works on
+///
+/// JianYing has one updater for all channels, so its profile comes from `application.rs`.
+pub fn guard_profile(channel: Channel) -> GuardProfile {
+    match (application::current(), channel) {
+        (Application::Jianying, _) => {
+            let profile = Application::Jianying.profile();
+            GuardProfile {
+                updater_names: profile.updater_names,
+                version_keys: profile.version_keys,
+            }
+        }
+        (Application::Capcut, Channel::Stable) => STABLE_PROFILE,
+        (Application::Capcut, Channel::Beta) => BETA_PROFILE,
+    }
+}
@@ -1,18 +1,93 @@
 //! Cache cleaning functionality
-//! Migrated from original eframe/egui main.rs
+//! Sorts CapCut's disposable User Data folders into categories that are sized and purged individually
 
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
-/// Get cache directories for CapCut
-fn get_cache_dirs(capcut_root: &Path) -> Vec<PathBuf> {
+use super::log_entry::LogEntry;
+use super::{attributes, deletion_log, exclusions, format, io_priority, paths};
+
+/// Kind of disposable data under User Data
+///
+/// `User Data\Download` holds the update blockers and is never part of a category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheCategory {
+    Cache,
+    DraftCache,
+    EffectCache,
+    Logs,
+    CrashDumps,
+    TempDownloads,
+}
+
+/// Every category, in display order
+pub const ALL_CATEGORIES: &[CacheCategory] = &[
+    CacheCategory::Cache,
+    CacheCategory::DraftCache,
+    CacheCategory::EffectCache,
+    CacheCategory::Logs,
+    CacheCategory::CrashDumps,
+    CacheCategory::TempDownloads,
+];
+
+impl CacheCategory {
+    pub fn label(self) -> &'static str {
+        match self {
+            CacheCategory::Cache => "Media cache",
+            CacheCategory::DraftCache => "Draft cache",
+            CacheCategory::EffectCache => "Effect cache",
+            CacheCategory::Logs => "Logs",
+            CacheCategory::CrashDumps => "Crash dumps",
+            CacheCategory::TempDownloads => "Temporary downloads",
+        }
+    }
+
+    /// Folders under User Data holding this category
+    fn folders(self) -> &'static [&'static str] {
+        match self {
+            CacheCategory::Cache => &["Cache", "Shadow_Cache", "Smart_Crop"],
+            CacheCategory::DraftCache => &["DraftCache", "Thumbnails"],
+            CacheCategory::EffectCache => &["EffectCache", "Effect_Cache"],
+            CacheCategory::Logs => &["Log", "Logs", "xlog"],
+            CacheCategory::CrashDumps => &["Crashpad", "Dump", "CrashDumps"],
+            CacheCategory::TempDownloads => &["Temp", "TempDownload"],
+        }
+    }
+
+    /// Cleaned unless the user picks categories; logs and crash dumps are kept for troubleshooting
+    fn is_default(self) -> bool {
+        !matches!(self, CacheCategory::Logs | CacheCategory::CrashDumps)
+    }
+}
+
+/// Categories to clean: the chosen ones, or the defaults when none are chosen
+pub fn resolve_categories(chosen: &[CacheCategory]) -> Vec<CacheCategory> {
+    if chosen.is_empty() {
+        ALL_CATEGORIES
+            .iter()
+            .copied()
+            .filter(|c| c.is_default())
+            .collect()
+    } else {
+        chosen.to_vec()
+    }
+}
+
+/// Existing cache directories of the given categories
+fn get_cache_dirs(
+    capcut_root: &Path,
+    categories: &[CacheCategory],
+) -> Vec<(CacheCategory, PathBuf)> {
     let user_data = capcut_root.join("User Data");
-    vec![
-        user_data.join("Cache"),
-        user_data.join("Shadow_Cache"),
-        user_data.join("Smart_Crop"),
-    ]
+    categories
+        .iter()
+        .flat_map(|&category| category.folders().iter().map(move |name| (category, name)))
+        .map(|(category, name)| (category, user_data.join(name)))
+        .filter(|(_, dir)| dir.is_dir())
+        .collect()
 }
 
 /// Calculate directory size
@@ -26,89 +101,201 @@ fn calculate_dir_size(path: &Path) -> u64 {
         .sum()
 }
 
-/// Unset readonly attribute recursively
-fn unset_readonly_recursive(path: &Path) -> Result<(), String> {
-    for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
-        let p = entry.path();
-        if let Ok(meta) = fs::metadata(p) {
-            let mut perms = meta.permissions();
-            if perms.readonly() {
-                perms.set_readonly(false);
-                fs::set_permissions(p, perms).ok();
-            }
-        }
-    }
-    Ok(())
+/// Size in bytes of each existing cache directory, across all categories
+pub fn cache_dir_sizes(capcut_root: &Path) -> Vec<(PathBuf, u64)> {
+    get_cache_dirs(capcut_root, ALL_CATEGORIES)
+        .into_iter()
+        .map(|(_, d)| {
+            let size = calculate_dir_size(&d);
+            (d, size)
+        })
+        .collect()
 }
 
-/// Calculate total cache size in MB
+/// Calculate the size in MB of the chosen categories (the defaults when none are given)
 #[tauri::command]
-pub fn calculate_cache_size() -> f64 {
-    let capcut_root = match std::env::var("LOCALAPPDATA") {
-        Ok(p) => PathBuf::from(p).join("CapCut"),
-        Err(_) => return 0.0,
+pub fn calculate_cache_size(categories: Option<Vec<CacheCategory>>) -> f64 {
+    let capcut_root = match paths::get_capcut_root_path() {
+        Some(p) => p,
+        None => return 0.0,
     };
 
-    let dirs = get_cache_dirs(&capcut_root);
-    let total_bytes: u64 = dirs
+    let categories = resolve_categories(&categories.unwrap_or_default());
+    let total_bytes: u64 = get_cache_dirs(&capcut_root, &categories)
         .iter()
-        .filter(|d| d.exists())
-        .map(|d| calculate_dir_size(d))
+        .map(|(_, d)| calculate_dir_size(d))
         .sum();
 
     total_bytes as f64 / (1024.0 * 1024.0)
 }
 
+/// What cleaning one category would remove
+#[derive(Serialize)]
+pub struct CacheCategoryPreview {
+    pub category: CacheCategory,
+    pub name: String,
+    pub paths: Vec<String>,
+    pub files: u64,
+    pub bytes: u64,
+    /// Cleaned when no categories are chosen
+    pub default: bool,
+}
+
+/// Dry run of the cache cleaner
+#[derive(Serialize)]
+pub struct CachePreview {
+    pub categories: Vec<CacheCategoryPreview>,
+    pub total_bytes: u64,
+}
+
+/// Walk the directories of the given categories and report what cleaning would remove, without deleting
+///
+/// Categories with no folder on disk are left out.
+pub fn preview_cache_at(capcut_root: &Path, categories: &[CacheCategory]) -> CachePreview {
+    let dirs = get_cache_dirs(capcut_root, categories);
+    let categories: Vec<CacheCategoryPreview> = categories
+        .iter()
+        .filter_map(|&category| {
+            let folders: Vec<&PathBuf> = dirs
+                .iter()
+                .filter(|(c, _)| *c == category)
+                .map(|(_, d)| d)
+                .collect();
+            if folders.is_empty() {
+                return None;
+            }
+            let (files, bytes) = folders
+                .iter()
+                .flat_map(|d| WalkDir::new(d).into_iter().filter_map(|e| e.ok()))
+                .filter(|e| e.file_type().is_file())
+                .filter_map(|e| e.metadata().ok())
+                .fold((0, 0), |(files, bytes), m| (files + 1, bytes + m.len()));
+            Some(CacheCategoryPreview {
+                category,
+                name: category.label().to_string(),
+                paths: folders
+                    .iter()
+                    .map(|d| d.to_string_lossy().to_string())
+                    .collect(),
+                files,
+                bytes,
+                default: category.is_default(),
+            })
+        })
+        .collect();
+
+    CachePreview {
+        total_bytes: categories.iter().map(|c| c.bytes).sum(),
+        categories,
+    }
+}
+
+/// Preview cache cleaning (dry run) of every category
+#[tauri::command]
+pub async fn preview_cache_clean() -> CachePreview {
+    let capcut_root = match paths::get_capcut_root_path() {
+        Some(p) => p,
+        None => {
+            return CachePreview {
+                categories: vec![],
+                total_bytes: 0,
+            }
+        }
+    };
+
+    tauri::async_runtime::spawn_blocking(move || preview_cache_at(&capcut_root, ALL_CATEGORIES))
+        .await
+        .unwrap_or(CachePreview {
+            categories: vec![],
+            total_bytes: 0,
+        })
+}
+
 /// Cache cleaning result
-#[derive(serde::Serialize)]
+#[derive(Serialize)]
 pub struct CacheCleanResult {
     pub success: bool,
     pub cleaned_mb: f64,
-    pub logs: Vec<String>,
+    pub logs: Vec<LogEntry>,
 }
 
-/// Clean cache directories
+/// Clean the chosen categories (the defaults when none are given)
 #[tauri::command]
-pub fn clean_cache() -> CacheCleanResult {
-    let capcut_root = match std::env::var("LOCALAPPDATA") {
-        Ok(p) => PathBuf::from(p).join("CapCut"),
-        Err(_) => {
+pub fn clean_cache(categories: Option<Vec<CacheCategory>>) -> CacheCleanResult {
+    let capcut_root = match paths::get_capcut_root_path() {
+        Some(p) => p,
+        None => {
             return CacheCleanResult {
                 success: false,
                 cleaned_mb: 0.0,
-                logs: vec!["Failed to get LOCALAPPDATA".to_string()],
+                logs: vec![LogEntry::warn("Could not find CapCut installation")],
             }
         }
     };
 
-    let dirs = get_cache_dirs(&capcut_root);
+    clean_cache_at(
+        &capcut_root,
+        &resolve_categories(&categories.unwrap_or_default()),
+    )
+}
+
+/// Clean the given categories of a specific CapCut installation
+pub fn clean_cache_at(capcut_root: &Path, categories: &[CacheCategory]) -> CacheCleanResult {
+    let _background = io_priority::enter();
+    let dirs = get_cache_dirs(capcut_root, categories);
     let mut total_cleaned: u64 = 0;
-    let mut logs: Vec<String> = Vec::new();
-
-    for dir in dirs {
-        if dir.exists() {
-            let name = dir.file_name().unwrap_or_default().to_string_lossy();
-            let size = calculate_dir_size(&dir);
-            logs.push(format!(
-                "Cleaning: {} ({:.1} MB)",
+    let mut logs: Vec<LogEntry> = Vec::new();
+    let excluded = exclusions::excluded_paths();
+
+    for (category, dir) in dirs {
+        let name = dir.file_name().unwrap_or_default().to_string_lossy();
+        if exclusions::is_excluded(&dir, &excluded) {
+            logs.push(
+                LogEntry::warn(format!("Skipped {} (on the never-touch list)", name))
+                    .with_path(&dir),
+            );
+            continue;
+        }
+        let size = calculate_dir_size(&dir);
+        logs.push(
+            LogEntry::info(format!(
+                "Cleaning {}: {} ({})",
+                category.label(),
                 name,
-                size as f64 / (1024.0 * 1024.0)
-            ));
+                format::format_size(size)
+            ))
+            .with_path(&dir),
+        );
 
-            if let Err(e) = unset_readonly_recursive(&dir) {
-                logs.push(format!("[!] Warning: {}", e));
-            }
+        if let Err(e) = attributes::clear_readonly_recursive(&dir) {
+            logs.push(LogEntry::warn(format!("Warning: {}", e)).with_path(&dir));
+        }
 
-            if let Err(e) = fs::remove_dir_all(&dir) {
-                logs.push(format!("[!] Failed to clean {}: {}", name, e));
+        let removed = deletion_log::remove_logged(&dir, "cache", |d| {
+            if exclusions::touches_excluded(d, &excluded) {
+                exclusions::remove_dir_except(d, &excluded)
             } else {
-                total_cleaned += size;
+                fs::remove_dir_all(d).map_err(|e| e.to_string())
             }
+        });
+        if let Err(e) = removed {
+            logs.push(LogEntry::warn(format!("Failed to clean {}: {}", name, e)).with_path(&dir));
+        } else {
+            // Excluded entries stay behind
+            let kept = if dir.exists() {
+                calculate_dir_size(&dir)
+            } else {
+                0
+            };
+            total_cleaned += size.saturating_sub(kept);
         }
     }
 
     let cleaned_mb = total_cleaned as f64 / (1024.0 * 1024.0);
-    logs.push(format!("[OK] Cleaned {:.1} MB of cache", cleaned_mb));
+    logs.push(LogEntry::ok(format!(
+        "Cleaned {} of cache",
+        format::format_size(total_cleaned)
+    )));
 
     CacheCleanResult {
         success: true,
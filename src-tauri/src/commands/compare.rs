@@ -0,0 +1,133 @@
+//! Version folder comparison
+//! Diffs two installed versions so users can see what an update changed before deleting it
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::{integrity, paths};
+
+/// A file present in one or both folders
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChange {
+    /// Path relative to the version folder
+    pub path: String,
+    pub left_size: Option<u64>,
+    pub right_size: Option<u64>,
+}
+
+/// Differences between two version folders
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionDiff {
+    /// Only in the right folder
+    pub added: Vec<FileChange>,
+    /// Only in the left folder
+    pub removed: Vec<FileChange>,
+    /// In both folders with different content
+    pub changed: Vec<FileChange>,
+    pub unchanged: usize,
+    pub left_size: u64,
+    pub right_size: u64,
+    /// Right minus left, in bytes
+    pub size_delta: i64,
+}
+
+/// Map relative file paths to sizes (kept as paths so non-Unicode names still resolve)
+fn list_files(root: &Path) -> BTreeMap<PathBuf, u64> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let rel = e.path().strip_prefix(root).ok()?.to_path_buf();
+            let size = e.metadata().ok()?.len();
+            Some((rel, size))
+        })
+        .collect()
+}
+
+/// Same-size files are compared by hash; different sizes are always changed
+fn contents_differ(left: &Path, right: &Path, left_size: u64, right_size: u64) -> bool {
+    if left_size != right_size {
+        return true;
+    }
+    match (integrity::hash_file(left), integrity::hash_file(right)) {
+        (Some(a), Some(b)) => a != b,
+        _ => true,
+    }
+}
+
+/// Diff two version folders (left = older, right = newer)
+pub fn diff_version_dirs(left: &Path, right: &Path) -> Result<VersionDiff, String> {
+    for dir in [left, right] {
+        if !dir.is_dir() {
+            return Err(format!("Not a version folder: {}", dir.display()));
+        }
+    }
+
+    let left_files = list_files(left);
+    let right_files = list_files(right);
+
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut changed = Vec::new();
+    let mut unchanged = 0;
+
+    for (path, &left_size) in &left_files {
+        match right_files.get(path) {
+            None => removed.push(FileChange {
+                path: path.to_string_lossy().to_string(),
+                left_size: Some(left_size),
+                right_size: None,
+            }),
+            Some(&right_size) => {
+                if contents_differ(&left.join(path), &right.join(path), left_size, right_size) {
+                    changed.push(FileChange {
+                        path: path.to_string_lossy().to_string(),
+                        left_size: Some(left_size),
+                        right_size: Some(right_size),
+                    });
+                } else {
+                    unchanged += 1;
+                }
+            }
+        }
+    }
+
+    for (path, &right_size) in &right_files {
+        if !left_files.contains_key(path) {
+            added.push(FileChange {
+                path: path.to_string_lossy().to_string(),
+                left_size: None,
+                right_size: Some(right_size),
+            });
+        }
+    }
+
+    let left_size: u64 = left_files.values().sum();
+    let right_size: u64 = right_files.values().sum();
+
+    Ok(VersionDiff {
+        added,
+        removed,
+        changed,
+        unchanged,
+        left_size,
+        right_size,
+        size_delta: right_size as i64 - left_size as i64,
+    })
+}
+
+/// Compare two installed version folders
+#[tauri::command]
+pub async fn compare_versions(left: String, right: String) -> Result<VersionDiff, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        diff_version_dirs(
+            &paths::path_from_transport(&left),
+            &paths::path_from_transport(&right),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
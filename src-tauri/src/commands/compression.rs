@@ -0,0 +1,170 @@
+//! NTFS compression for kept version folders
+//! Applies FSCTL_SET_COMPRESSION to every file so switcher-mode installs take less disk space
+
+use serde::Serialize;
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+use walkdir::WalkDir;
+use windows_sys::Win32::Foundation::{
+    CloseHandle, GENERIC_READ, GENERIC_WRITE, INVALID_HANDLE_VALUE,
+};
+use windows_sys::Win32::Storage::FileSystem::{
+    CreateFileW, GetCompressedFileSizeW, COMPRESSION_FORMAT_DEFAULT, FILE_FLAG_BACKUP_SEMANTICS,
+    FILE_SHARE_READ, FILE_SHARE_WRITE, INVALID_FILE_SIZE, OPEN_EXISTING,
+};
+use windows_sys::Win32::System::Ioctl::FSCTL_SET_COMPRESSION;
+use windows_sys::Win32::System::IO::DeviceIoControl;
+
+use super::log_entry::LogEntry;
+use super::{format, paths, process};
+
+/// Compression result
+#[derive(Serialize)]
+pub struct CompressionResult {
+    pub success: bool,
+    pub error: Option<String>,
+    pub logs: Vec<LogEntry>,
+    /// Size on disk before and after (MB)
+    pub before_mb: f64,
+    pub after_mb: f64,
+}
+
+fn to_wide(path: &Path) -> Vec<u16> {
+    path.as_os_str().encode_wide().chain(Some(0)).collect()
+}
+
+/// Allocated size of a file (reflects compression)
+fn size_on_disk(path: &Path) -> u64 {
+    let wide = to_wide(path);
+    let mut high: u32 = 0;
+    // SAFETY: `wide` is a NUL-terminated path that outlives the call
+    let low = unsafe { GetCompressedFileSizeW(wide.as_ptr(), &mut high) };
+    if low == INVALID_FILE_SIZE && std::io::Error::last_os_error().raw_os_error() != Some(0) {
+        return std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    }
+    (u64::from(high) << 32) | u64::from(low)
+}
+
+/// Set the NTFS compression flag on a file or directory
+fn set_compression(path: &Path) -> Result<(), String> {
+    let wide = to_wide(path);
+    // SAFETY: `wide` is NUL-terminated; the handle is closed before returning
+    unsafe {
+        let handle = CreateFileW(
+            wide.as_ptr(),
+            GENERIC_READ | GENERIC_WRITE,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            std::ptr::null(),
+            OPEN_EXISTING,
+            // Required to open directory handles
+            FILE_FLAG_BACKUP_SEMANTICS,
+            std::ptr::null_mut(),
+        );
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+
+        let format: u16 = COMPRESSION_FORMAT_DEFAULT;
+        let mut returned: u32 = 0;
+        let ok = DeviceIoControl(
+            handle,
+            FSCTL_SET_COMPRESSION,
+            &format as *const u16 as *const _,
+            std::mem::size_of::<u16>() as u32,
+            std::ptr::null_mut(),
+            0,
+            &mut returned,
+            std::ptr::null_mut(),
+        );
+        let err = std::io::Error::last_os_error();
+        CloseHandle(handle);
+
+        if ok == 0 {
+            return Err(err.to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Compress a folder in place (directories too, so new files inherit compression)
+pub fn compress_dir(dir: &Path) -> CompressionResult {
+    let mut logs: Vec<LogEntry> = Vec::new();
+    let mut before: u64 = 0;
+    let mut after: u64 = 0;
+    let mut failed = 0;
+
+    logs.push(LogEntry::info("Compressing...").with_path(dir));
+
+    for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let is_file = entry.file_type().is_file();
+        if is_file {
+            before += size_on_disk(path);
+        }
+        if set_compression(path).is_err() {
+            failed += 1;
+        }
+        if is_file {
+            after += size_on_disk(path);
+        }
+    }
+
+    let before_mb = before as f64 / (1024.0 * 1024.0);
+    let after_mb = after as f64 / (1024.0 * 1024.0);
+    if failed > 0 {
+        logs.push(
+            LogEntry::warn(format!("{} item(s) could not be compressed", failed)).with_path(dir),
+        );
+    }
+    logs.push(LogEntry::ok(format!(
+        "{} -> {} ({} saved)",
+        format::format_size(before),
+        format::format_size(after),
+        format::format_size(before.saturating_sub(after))
+    )));
+
+    CompressionResult {
+        success: true,
+        error: None,
+        logs,
+        before_mb,
+        after_mb,
+    }
+}
+
+/// Apply NTFS compression to a kept version folder
+#[tauri::command]
+pub async fn compress_version(path: String) -> CompressionResult {
+    if process::is_capcut_running() {
+        return CompressionResult {
+            success: false,
+            error: Some("Close CapCut before compressing its files".to_string()),
+            logs: vec![],
+            before_mb: 0.0,
+            after_mb: 0.0,
+        };
+    }
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let dir = paths::path_from_transport(&path);
+        if !dir.is_dir() {
+            return CompressionResult {
+                success: false,
+                error: Some(format!("Version folder not found: {}", path)),
+                logs: vec![],
+                before_mb: 0.0,
+                after_mb: 0.0,
+            };
+        }
+        compress_dir(&dir)
+    })
+    .await;
+
+    result.unwrap_or_else(|e| CompressionResult {
+        success: false,
+        error: Some(e.to_string()),
+        logs: vec![],
+        before_mb: 0.0,
+        after_mb: 0.0,
+    })
+}
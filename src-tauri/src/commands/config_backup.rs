@@ -0,0 +1,212 @@
+//! Configuration backups
+//! Copies configure.ini and ProductInfo.xml before each protection run so a bad run can be rolled back
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::backup::RestoreResult;
+use super::{acl_lock, attributes, config_guard, installs, paths, policy};
+
+/// Files protection overwrites in the Apps folder
+const BACKED_UP_FILES: &[&str] = &["configure.ini", "ProductInfo.xml"];
+
+/// Older backups beyond this count are deleted
+const MAX_CONFIG_BACKUPS: usize = 20;
+
+/// Appended to a file's name while its restored copy is staged next to it
+const STAGING_SUFFIX: &str = ".restoring";
+
+/// Metadata stored alongside each backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigBackup {
+    /// Folder name, `<unix time>` (with a counter when taken within the same second)
+    pub id: String,
+    /// Apps folder the files were copied from
+    pub apps_path: String,
+    pub created_at: u64,
+    /// Names of the files in the backup
+    pub files: Vec<String>,
+}
+
+/// Get the configuration backup directory of the install the thread works on
+pub fn get_config_backup_dir() -> Option<PathBuf> {
+    installs::get_install_data_dir().map(|p| p.join("ConfigBackups"))
+}
+
+fn load_metadata(backup_path: &Path) -> Option<ConfigBackup> {
+    fs::read_to_string(backup_path.join("_backup_metadata.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+}
+
+/// Copy configure.ini and ProductInfo.xml into a new timestamped backup; returns its ID
+///
+/// Nothing is written when neither file exists yet.
+pub fn create_config_backup(apps_path: &Path) -> Result<Option<String>, String> {
+    let files: Vec<&str> = BACKED_UP_FILES
+        .iter()
+        .copied()
+        .filter(|name| apps_path.join(name).is_file())
+        .collect();
+    if files.is_empty() {
+        return Ok(None);
+    }
+
+    let backup_dir = get_config_backup_dir().ok_or("Could not determine backup directory")?;
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let mut id = created_at.to_string();
+    let mut counter = 1;
+    while backup_dir.join(&id).exists() {
+        counter += 1;
+        id = format!("{}_{}", created_at, counter);
+    }
+    let backup_path = backup_dir.join(&id);
+    fs::create_dir_all(&backup_path)
+        .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+
+    for name in &files {
+        fs::copy(apps_path.join(name), backup_path.join(name))
+            .map_err(|e| format!("Failed to back up {}: {}", name, e))?;
+    }
+    let metadata = ConfigBackup {
+        id: id.clone(),
+        apps_path: paths::path_to_transport(apps_path),
+        created_at,
+        files: files.iter().map(|n| n.to_string()).collect(),
+    };
+    fs::write(
+        backup_path.join("_backup_metadata.json"),
+        serde_json::to_string_pretty(&metadata).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| format!("Failed to save backup metadata: {}", e))?;
+
+    prune(&backup_dir);
+    Ok(Some(id))
+}
+
+/// Delete the oldest backups beyond `MAX_CONFIG_BACKUPS`
+fn prune(backup_dir: &Path) {
+    let backups = list_config_backups();
+    for old in backups.iter().skip(MAX_CONFIG_BACKUPS) {
+        if let Err(e) = fs::remove_dir_all(backup_dir.join(&old.id)) {
+            tracing::warn!("Could not delete old config backup: {}", e);
+        }
+    }
+}
+
+/// List configuration backups of the active install, newest first
+#[tauri::command]
+pub fn list_config_backups() -> Vec<ConfigBackup> {
+    let backup_dir = match get_config_backup_dir() {
+        Some(d) if d.exists() => d,
+        _ => return Vec::new(),
+    };
+
+    let mut backups: Vec<ConfigBackup> = fs::read_dir(&backup_dir)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| load_metadata(&e.path()))
+        .collect();
+    backups.sort_by_key(|b| std::cmp::Reverse((b.created_at, b.id.clone())));
+    backups
+}
+
+/// Whether a backup ID names a single folder (no separators, drive or parent references)
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && !id.contains(['/', '\\', ':']) && !id.contains("..")
+}
+
+fn staging_path(target: &Path) -> PathBuf {
+    let mut name = target.file_name().unwrap_or_default().to_os_string();
+    name.push(STAGING_SUFFIX);
+    target.with_file_name(name)
+}
+
+/// Put configure.ini and ProductInfo.xml back as they were before a protection run
+///
+/// The copies are staged first; the configure.ini pin is released only once all of them are in
+/// place next to their targets, otherwise the watchdog would lock the file again right away.
+#[tauri::command]
+pub fn restore_config_backup(backup_id: String) -> RestoreResult {
+    let failed = |error: String| RestoreResult {
+        success: false,
+        restored_path: None,
+        error: Some(error),
+    };
+
+    if policy::load_policy().disallow_unprotect {
+        return failed(policy::POLICY_BLOCKED.to_string());
+    }
+    if !is_valid_id(&backup_id) {
+        return failed(format!("Invalid backup ID: {}", backup_id));
+    }
+    // Deny entries on ProductInfo.xml would fail the copy halfway through
+    if acl_lock::locked_count() > 0 {
+        return failed(
+            "Folder permissions are locked; Unprotect before restoring a configuration backup"
+                .to_string(),
+        );
+    }
+
+    let backup_path = match get_config_backup_dir() {
+        Some(d) => d.join(&backup_id),
+        None => return failed("Could not determine backup directory".to_string()),
+    };
+    let metadata = match load_metadata(&backup_path) {
+        Some(m) => m,
+        None => return failed(format!("Backup not found: {}", backup_id)),
+    };
+    let apps_path = paths::path_from_transport(&metadata.apps_path);
+    if !apps_path.is_dir() {
+        return failed(format!(
+            "The Apps folder of this backup no longer exists: {}",
+            apps_path.display()
+        ));
+    }
+
+    let mut staged: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for name in &metadata.files {
+        let target = apps_path.join(name);
+        let staging = staging_path(&target);
+        if let Err(e) = fs::copy(backup_path.join(name), &staging) {
+            for (staging, _) in &staged {
+                fs::remove_file(staging).ok();
+            }
+            fs::remove_file(&staging).ok();
+            return failed(format!("Failed to restore {}: {}", name, e));
+        }
+        staged.push((staging, target));
+    }
+
+    if let Err(e) = config_guard::clear_pin() {
+        for (staging, _) in &staged {
+            fs::remove_file(staging).ok();
+        }
+        return failed(format!("Could not release configure.ini pin: {}", e));
+    }
+    for (staging, target) in &staged {
+        // ProductInfo.xml is a read-only blocker while protected
+        if target.exists() {
+            if let Err(e) = attributes::clear_readonly_recursive(target) {
+                return failed(e);
+            }
+        }
+        if let Err(e) = fs::rename(staging, target) {
+            return failed(format!("Failed to restore {}: {}", target.display(), e));
+        }
+    }
+
+    RestoreResult {
+        success: true,
+        restored_path: Some(metadata.apps_path),
+        error: None,
+    }
+}
@@ -0,0 +1,152 @@
+//! configure.ini diff
+//! Compares the live configure.ini with the copy taken when protection locked it
+
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use super::{config_guard, paths};
+
+/// How a key differs from the locked copy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffStatus {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One key CapCut changed since protection
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigDiffEntry {
+    /// Key name, prefixed with "[section] " outside the top level
+    pub key: String,
+    pub locked: Option<String>,
+    pub live: Option<String>,
+    pub status: DiffStatus,
+}
+
+/// Live configure.ini compared with the locked copy
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigDiff {
+    /// False when there is no locked copy to compare with (not protected yet)
+    pub available: bool,
+    pub config_path: Option<String>,
+    pub changes: Vec<ConfigDiffEntry>,
+    /// Keys identical in both files
+    pub unchanged: usize,
+}
+
+/// Key/value pairs in file order; values keep CapCut's ANSI paths readable via lossy decoding
+pub fn parse_ini(content: &[u8]) -> Vec<(String, String)> {
+    let mut section = String::new();
+    let mut entries = Vec::new();
+    for line in String::from_utf8_lossy(content).lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let key = match section.as_str() {
+                "" => key.trim().to_string(),
+                s => format!("[{}] {}", s, key.trim()),
+            };
+            entries.push((key, value.trim().to_string()));
+        }
+    }
+    entries
+}
+
+fn diff_entries(
+    locked: &[(String, String)],
+    live: &[(String, String)],
+) -> (Vec<ConfigDiffEntry>, usize) {
+    let find = |entries: &[(String, String)], key: &str| {
+        entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+    };
+    let mut changes = Vec::new();
+    let mut unchanged = 0;
+
+    for (key, value) in locked {
+        match find(live, key) {
+            Some(v) if &v == value => unchanged += 1,
+            Some(v) => changes.push(ConfigDiffEntry {
+                key: key.clone(),
+                locked: Some(value.clone()),
+                live: Some(v),
+                status: DiffStatus::Changed,
+            }),
+            None => changes.push(ConfigDiffEntry {
+                key: key.clone(),
+                locked: Some(value.clone()),
+                live: None,
+                status: DiffStatus::Removed,
+            }),
+        }
+    }
+    for (key, value) in live {
+        if find(locked, key).is_none() {
+            changes.push(ConfigDiffEntry {
+                key: key.clone(),
+                locked: None,
+                live: Some(value.clone()),
+                status: DiffStatus::Added,
+            });
+        }
+    }
+    (changes, unchanged)
+}
+
+/// Diff configure.ini in `apps_path` against the locked copy
+fn diff_with_snapshot(apps_path: &Path) -> ConfigDiff {
+    let config_path = apps_path.join("configure.ini");
+    let locked = match config_guard::get_snapshot_path().and_then(|p| fs::read(p).ok()) {
+        Some(c) => parse_ini(&c),
+        None => {
+            return ConfigDiff {
+                available: false,
+                config_path: Some(config_path.to_string_lossy().to_string()),
+                changes: Vec::new(),
+                unchanged: 0,
+            }
+        }
+    };
+    // A deleted configure.ini shows every locked key as removed
+    let live = fs::read(&config_path)
+        .map(|c| parse_ini(&c))
+        .unwrap_or_default();
+    let (changes, unchanged) = diff_entries(&locked, &live);
+
+    ConfigDiff {
+        available: true,
+        config_path: Some(config_path.to_string_lossy().to_string()),
+        changes,
+        unchanged,
+    }
+}
+
+/// Diff the live configure.ini against the locked copy
+pub fn current_diff() -> ConfigDiff {
+    match paths::get_capcut_apps_path() {
+        Some(apps_path) => diff_with_snapshot(&apps_path),
+        None => ConfigDiff {
+            available: false,
+            config_path: None,
+            changes: Vec::new(),
+            unchanged: 0,
+        },
+    }
+}
+
+/// Keys CapCut changed in configure.ini since protection
+#[tauri::command]
+pub fn get_config_diff() -> ConfigDiff {
+    current_diff()
+}
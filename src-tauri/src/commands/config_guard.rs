@@ -0,0 +1,177 @@
+//! configure.ini guard
+//! Watches the locked configure.ini and restores the pinned version keys as soon as CapCut rewrites them
+
+use notify::{RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use super::notifier::{self, AlertKind};
+use super::{installs, paths, power, protector};
+
+/// Wait after a change so CapCut finishes writing before we read the file
+const SETTLE_MS: u64 = 250;
+
+/// Ticks (500 ms) between re-reading the pin while watching
+const PIN_RELOAD_TICKS: u32 = 10;
+
+/// The configure.ini lock applied by protection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ConfigPin {
+    /// Apps folder holding configure.ini
+    apps_path: String,
+}
+
+/// Get the config pin file path
+fn get_pin_path() -> Option<PathBuf> {
+    installs::get_install_data_dir().map(|p| p.join("config_pin.json"))
+}
+
+/// Copy of configure.ini taken right after it was locked, for the config diff
+pub fn get_snapshot_path() -> Option<PathBuf> {
+    installs::get_install_data_dir().map(|p| p.join("configure.locked.ini"))
+}
+
+fn load_pin() -> Option<ConfigPin> {
+    get_pin_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+}
+
+/// Remember that configure.ini in this Apps folder must stay locked
+pub fn pin_config(apps_path: &Path) -> Result<(), String> {
+    let path = get_pin_path().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    if let Some(snapshot) = get_snapshot_path() {
+        fs::copy(apps_path.join("configure.ini"), snapshot)
+            .map_err(|e| format!("Failed to snapshot configure.ini: {}", e))?;
+    }
+    let pin = ConfigPin {
+        apps_path: paths::path_to_transport(apps_path),
+    };
+    let content = serde_json::to_string_pretty(&pin).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save config pin: {}", e))
+}
+
+/// Stop guarding configure.ini (protection removed)
+pub fn clear_pin() -> Result<(), String> {
+    if let Some(snapshot) = get_snapshot_path().filter(|p| p.exists()) {
+        fs::remove_file(snapshot).map_err(|e| e.to_string())?;
+    }
+    match get_pin_path() {
+        Some(p) if p.exists() => fs::remove_file(p).map_err(|e| e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Whether configure.ini is pinned for the watchdog
+pub fn is_pinned() -> bool {
+    load_pin().is_some()
+}
+
+/// Re-lock the pinned configure.ini if CapCut unlocked it; returns the restored file
+pub fn restore_if_unlocked() -> Option<PathBuf> {
+    let apps_path = paths::path_from_transport(&load_pin()?.apps_path);
+    match protector::relock_configuration(&apps_path) {
+        Ok(true) => Some(apps_path.join("configure.ini")),
+        Ok(false) => None,
+        Err(e) => {
+            tracing::warn!("Could not restore configure.ini: {}", e);
+            None
+        }
+    }
+}
+
+/// Tell the frontend and record an alert for a restored configure.ini
+pub fn report_restore(app: &AppHandle, config_path: &Path) {
+    let _ = app.emit(
+        "watchdog-config-restored",
+        config_path.to_string_lossy().to_string(),
+    );
+    let details = format!(
+        "CapCut rewrote its version keys in configure.ini; the locked values were restored.\n\nPath: {}",
+        config_path.display()
+    );
+    if let Err(e) = notifier::send_alert(AlertKind::UpdateBlocked, &details) {
+        tracing::warn!("Config guard email alert failed: {}", e);
+    }
+}
+
+/// Watch the pinned Apps folder and restore configure.ini on every rewrite (once per burst in low-impact mode) until stopped
+pub fn watch(app: AppHandle, stop: Arc<AtomicBool>, low_impact: bool) {
+    while !stop.load(Ordering::Relaxed) {
+        let pin = match load_pin() {
+            Some(p) => p,
+            None => {
+                thread::sleep(Duration::from_secs(2));
+                continue;
+            }
+        };
+        let apps_path = paths::path_from_transport(&pin.apps_path);
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(w) => w,
+            Err(e) => {
+                tracing::error!("Config watcher failed to start: {}", e);
+                return;
+            }
+        };
+        if watcher
+            .watch(&apps_path, RecursiveMode::NonRecursive)
+            .is_err()
+        {
+            thread::sleep(Duration::from_secs(2));
+            continue;
+        }
+
+        let mut ticks = 0;
+        while !stop.load(Ordering::Relaxed) {
+            match rx.recv_timeout(Duration::from_millis(500)) {
+                Ok(Ok(event)) => {
+                    let touches_config = event.paths.iter().any(|p| {
+                        p.file_name()
+                            .is_some_and(|n| n.eq_ignore_ascii_case("configure.ini"))
+                    });
+                    if !touches_config {
+                        continue;
+                    }
+                    if low_impact {
+                        // Act once per burst: wait until events stop arriving
+                        let quiet = Duration::from_millis(power::LOW_IMPACT_SETTLE_MS);
+                        while rx.recv_timeout(quiet).is_ok() {}
+                    } else {
+                        thread::sleep(Duration::from_millis(SETTLE_MS));
+                    }
+                    // Our own rewrite also fires events; the restore is a no-op for those
+                    while rx.try_recv().is_ok() {}
+                    if let Some(config_path) = restore_if_unlocked() {
+                        report_restore(&app, &config_path);
+                    }
+                }
+                // Drive unplugged: re-arm quietly once it is back
+                Ok(Err(_)) if !apps_path.is_dir() => break,
+                Ok(Err(e)) => tracing::error!("Config watcher error: {}", e),
+                Err(RecvTimeoutError::Timeout) => {
+                    ticks += 1;
+                    // Protection removed or moved to another install
+                    if ticks >= PIN_RELOAD_TICKS {
+                        ticks = 0;
+                        if load_pin().as_ref() != Some(&pin) {
+                            break;
+                        }
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    }
+}
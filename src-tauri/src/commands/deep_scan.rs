@@ -0,0 +1,133 @@
+//! Deep scan for relocated CapCut installs
+//! Searches every fixed drive for the Apps\<version>\CapCut.exe layout (moved installs, junctions)
+
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use windows_sys::Win32::Storage::FileSystem::{GetDriveTypeW, GetLogicalDrives};
+
+use super::{paths, version};
+
+/// GetDriveTypeW result for local disks (WindowsProgramming isn't an enabled feature)
+const DRIVE_FIXED: u32 = 3;
+
+/// Deep enough for %LOCALAPPDATA%\CapCut\Apps (C:\Users\<name>\AppData\Local\CapCut\Apps)
+const MAX_DEPTH: usize = 7;
+
+/// Folders that never hold a CapCut install and are slow or protected to walk
+const SKIPPED_DIRS: &[&str] = &[
+    "windows",
+    "$recycle.bin",
+    "system volume information",
+    "$windows.~bt",
+    "$windows.~ws",
+    "windows.old",
+    "recovery",
+    "node_modules",
+    ".git",
+];
+
+/// A CapCut install found by the deep scan
+#[derive(Debug, Clone, Serialize)]
+pub struct DeepScanHit {
+    /// CapCut folder (parent of Apps), ready for `validate_custom_path`
+    pub root: String,
+    /// Version folders containing CapCut.exe
+    pub versions: Vec<String>,
+}
+
+/// Root paths of all fixed drives (e.g., "C:\")
+pub fn fixed_drives() -> Vec<PathBuf> {
+    // SAFETY: no arguments; returns a bitmask of present drive letters
+    let mask = unsafe { GetLogicalDrives() };
+    (0..26u8)
+        .filter(|i| mask & (1 << i) != 0)
+        .map(|i| format!("{}:\\", (b'A' + i) as char))
+        .filter(|root| {
+            let wide: Vec<u16> = root.encode_utf16().chain(Some(0)).collect();
+            // SAFETY: `wide` is a NUL-terminated root path that outlives the call
+            unsafe { GetDriveTypeW(wide.as_ptr()) == DRIVE_FIXED }
+        })
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Version folders under `apps` that contain CapCut.exe (or JianyingPro.exe)
+fn versions_with_exe(apps: &Path) -> Vec<String> {
+    let mut versions: Vec<String> = fs::read_dir(apps)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| paths::has_main_executable(p))
+        .map(|p| {
+            p.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect();
+    version::sort_names(&mut versions);
+    versions
+}
+
+/// Walk the given drive roots for CapCut installs
+fn scan_roots(roots: &[PathBuf]) -> Vec<DeepScanHit> {
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    let mut hits = Vec::new();
+
+    for root in roots {
+        let mut walker = WalkDir::new(root)
+            .max_depth(MAX_DEPTH)
+            .follow_links(true)
+            .into_iter();
+        while let Some(entry) = walker.next() {
+            let entry = match entry {
+                Ok(e) => e,
+                Err(_) => continue,
+            };
+            if !entry.file_type().is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+            if SKIPPED_DIRS.contains(&name.as_str()) {
+                walker.skip_current_dir();
+                continue;
+            }
+            if name != "apps" {
+                continue;
+            }
+
+            let versions = versions_with_exe(entry.path());
+            if versions.is_empty() {
+                continue;
+            }
+            // Nothing below an install's Apps folder is another install
+            walker.skip_current_dir();
+
+            // A junction exposes the same install twice
+            let real = fs::canonicalize(entry.path()).unwrap_or_else(|_| entry.path().into());
+            if !seen.insert(real) {
+                continue;
+            }
+            if let Some(capcut_root) = entry.path().parent() {
+                hits.push(DeepScanHit {
+                    root: capcut_root.to_string_lossy().to_string(),
+                    versions,
+                });
+            }
+        }
+    }
+    hits
+}
+
+/// Search all fixed drives for CapCut installs
+#[tauri::command]
+pub async fn deep_scan_installs() -> Vec<DeepScanHit> {
+    tauri::async_runtime::spawn_blocking(|| scan_roots(&fixed_drives()))
+        .await
+        .unwrap_or_default()
+}
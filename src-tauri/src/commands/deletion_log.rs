@@ -0,0 +1,109 @@
+//! Per-file deletion audit log
+//! Optionally records every file removed by version deletion and cache cleaning to a CSV manifest
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use walkdir::WalkDir;
+
+use super::{paths, policy, report, settings};
+
+/// CSV header of the manifest
+const HEADER: &str = "timestamp,category,size_bytes,path\n";
+
+/// Serializes appends to the manifest across threads
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Get the deletion manifest path
+fn get_log_path() -> Option<PathBuf> {
+    paths::get_app_data_dir().map(|p| p.join("deletion_log.csv"))
+}
+
+/// Append files that no longer exist to the manifest
+fn append(category: &str, files: &[(PathBuf, u64)]) -> Result<(), String> {
+    let _guard = LOG_LOCK.lock();
+    let path = get_log_path().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| e.to_string())?;
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let mut out = String::new();
+    if is_new {
+        out.push_str(HEADER);
+    }
+    for (file_path, size) in files.iter().filter(|(p, _)| !p.exists()) {
+        out.push_str(&format!(
+            "{},{},{},{}\n",
+            timestamp,
+            report::csv_field(category),
+            size,
+            report::csv_field(&file_path.to_string_lossy())
+        ));
+    }
+    file.write_all(out.as_bytes()).map_err(|e| e.to_string())
+}
+
+/// Run `remove` on `dir`, logging every file it actually deleted when the audit log is enabled
+pub fn remove_logged<F>(dir: &Path, category: &str, remove: F) -> Result<(), String>
+where
+    F: FnOnce(&Path) -> Result<(), String>,
+{
+    if !settings::load_settings().log_deleted_files {
+        return remove(dir);
+    }
+
+    let files: Vec<(PathBuf, u64)> = WalkDir::new(dir)
+        .follow_root_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| {
+            let size = e.metadata().map_or(0, |m| m.len());
+            (e.into_path(), size)
+        })
+        .collect();
+
+    // Log after removing so a partial failure only lists what is really gone
+    let result = remove(dir);
+    if let Err(e) = append(category, &files) {
+        tracing::warn!("Could not write deletion log: {}", e);
+    }
+    result
+}
+
+/// Save a copy of the deletion manifest
+#[tauri::command]
+pub fn export_deletion_log(path: String) -> Result<(), String> {
+    let source = get_log_path().ok_or("Could not determine data directory")?;
+    if !source.exists() {
+        return Err("No deletions have been logged yet".to_string());
+    }
+    let _guard = LOG_LOCK.lock();
+    fs::copy(&source, &path)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to write export: {}", e))
+}
+
+/// Delete the deletion manifest
+#[tauri::command]
+pub fn clear_deletion_log() -> Result<(), String> {
+    if policy::load_policy().lock_settings {
+        return Err(policy::POLICY_BLOCKED.to_string());
+    }
+    let _guard = LOG_LOCK.lock();
+    match get_log_path() {
+        Some(p) if p.exists() => fs::remove_file(p).map_err(|e| e.to_string()),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,172 @@
+//! Diagnostics bundle
+//! Zips scan results, CapCut's config files, protection status, system info and log files for bug reports, with user and machine names masked
+
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::{doctor, environment, logging, paths, protector, report};
+
+/// Names shorter than this are left alone; masking them would mangle unrelated text
+const MIN_MASKED_LEN: usize = 3;
+
+/// What went into a diagnostics bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticsInfo {
+    pub files: Vec<String>,
+}
+
+/// Replace every ASCII-case-insensitive occurrence of `needle`
+fn replace_ignore_case(text: &str, needle: &str, with: &str) -> String {
+    if needle.is_empty() {
+        return text.to_string();
+    }
+    // ASCII lowercasing keeps byte offsets, so matches in `lower` index into `text`
+    let lower = text.to_ascii_lowercase();
+    let needle = needle.to_ascii_lowercase();
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (pos, _) in lower.match_indices(&needle) {
+        out.push_str(&text[last..pos]);
+        out.push_str(with);
+        last = pos + needle.len();
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+/// Mask the user name in profile paths (plain and JSON-escaped) and the computer name
+fn anonymize(text: &str) -> String {
+    let mut masked = text.to_string();
+    if let Ok(user) = std::env::var("USERNAME") {
+        for sep in ["\\", "\\\\", "/"] {
+            masked = replace_ignore_case(
+                &masked,
+                &format!("Users{}{}", sep, user),
+                &format!("Users{}<user>", sep),
+            );
+        }
+    }
+    if let Ok(computer) = std::env::var("COMPUTERNAME") {
+        if computer.len() >= MIN_MASKED_LEN {
+            masked = replace_ignore_case(&masked, &computer, "<computer>");
+        }
+    }
+    masked
+}
+
+/// Plain-text system summary
+fn system_info(app_version: &str) -> String {
+    let env = environment::check_environment();
+    let mut lines = vec![
+        format!("CC Version Guard {}", app_version),
+        report::windows_version(),
+        format!("Architecture: {}", std::env::consts::ARCH),
+        format!("Supported: {}", env.supported),
+    ];
+    lines.extend(env.problems.iter().map(|p| format!("Problem: {}", p)));
+    lines.extend(env.notes.iter().map(|n| format!("Note: {}", n)));
+    lines.push(String::new());
+    lines.push("Doctor:".to_string());
+    lines.extend(
+        doctor::run_checks()
+            .iter()
+            .map(|c| format!("[{}] {}: {}", c.status.label(), c.name, c.detail)),
+    );
+    lines.join("\r\n")
+}
+
+fn write_entry(
+    zip: &mut ZipWriter<File>,
+    files: &mut Vec<String>,
+    name: &str,
+    content: &str,
+) -> Result<(), String> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file(name, options).map_err(|e| e.to_string())?;
+    zip.write_all(anonymize(content).as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    files.push(name.to_string());
+    Ok(())
+}
+
+fn to_json(value: &impl Serialize) -> Result<String, String> {
+    serde_json::to_string_pretty(value).map_err(|e| e.to_string())
+}
+
+fn write_bundle(app_version: &str, dest: &Path) -> Result<DiagnosticsInfo, String> {
+    let file = File::create(dest).map_err(|e| format!("Failed to create bundle: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let mut files = Vec::new();
+
+    write_entry(
+        &mut zip,
+        &mut files,
+        "system.txt",
+        &system_info(app_version),
+    )?;
+    write_entry(
+        &mut zip,
+        &mut files,
+        "scan.json",
+        &to_json(&report::build_report(app_version.to_string()))?,
+    )?;
+    write_entry(
+        &mut zip,
+        &mut files,
+        "protection_status.json",
+        &to_json(&protector::check_protection_status())?,
+    )?;
+
+    // CapCut may write configure.ini in the ANSI codepage; lossy text is enough to read it
+    if let Some(apps) = paths::get_capcut_apps_path() {
+        for name in ["configure.ini", "ProductInfo.xml"] {
+            if let Ok(bytes) = fs::read(apps.join(name)) {
+                write_entry(&mut zip, &mut files, name, &String::from_utf8_lossy(&bytes))?;
+            }
+        }
+    }
+
+    let logs = logging::get_log_dir()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file());
+    for log in logs {
+        if let Ok(bytes) = fs::read(log.path()) {
+            let name = format!("logs/{}", log.file_name().to_string_lossy());
+            write_entry(
+                &mut zip,
+                &mut files,
+                &name,
+                &String::from_utf8_lossy(&bytes),
+            )?;
+        }
+    }
+
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(DiagnosticsInfo { files })
+}
+
+/// Save a diagnostics zip for bug reports (paths and names anonymized)
+#[tauri::command]
+pub async fn export_diagnostics(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<DiagnosticsInfo, String> {
+    let app_version = app.package_info().version.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let dest = Path::new(&path);
+        let result = write_bundle(&app_version, dest);
+        if result.is_err() {
+            fs::remove_file(dest).ok();
+        }
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,244 @@
+//! Environment doctor
+//! Runs every environment check and prints a pass/warn/fail report (`capcut_guard_tauri.exe doctor`)
+
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+use super::{environment, folder_access, format, paths, process, protector};
+
+/// Below this, backups and golden copy imports are likely to fail
+const LOW_DISK_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Below this, even a protection run (asset migration, logs) may fail
+const CRITICAL_DISK_BYTES: u64 = 500 * 1024 * 1024;
+
+/// Outcome of one doctor check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+        }
+    }
+}
+
+/// One doctor check result
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+fn check(name: &str, status: CheckStatus, detail: impl Into<String>) -> DoctorCheck {
+    DoctorCheck {
+        name: name.to_string(),
+        status,
+        detail: detail.into(),
+    }
+}
+
+/// Bytes free to the current user on the volume holding `path`
+fn free_disk_bytes(path: &Path) -> Option<u64> {
+    let wide: Vec<u16> = path
+        .to_string_lossy()
+        .encode_utf16()
+        .chain(Some(0))
+        .collect();
+    let mut free = 0u64;
+    // SAFETY: `wide` is NUL-terminated and outlives the call; unused outputs may be null
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            wide.as_ptr(),
+            &mut free,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    (ok != 0).then_some(free)
+}
+
+/// Run every environment check
+pub fn run_checks() -> Vec<DoctorCheck> {
+    let mut checks = Vec::new();
+
+    let env = environment::check_environment();
+    checks.push(match env.build {
+        Some(_) if env.supported => check("Windows", CheckStatus::Pass, env.os.clone()),
+        _ if !env.supported => check("Windows", CheckStatus::Fail, env.problems.join("; ")),
+        _ => check(
+            "Windows",
+            CheckStatus::Warn,
+            "Could not read the Windows build number",
+        ),
+    });
+
+    checks.push(if env.elevated {
+        check("Elevation", CheckStatus::Pass, "Running as administrator")
+    } else {
+        check(
+            "Elevation",
+            CheckStatus::Warn,
+            "Not elevated; the update domain block and fleet mode need administrator rights",
+        )
+    });
+
+    let capcut = match paths::resolve_capcut_paths() {
+        Some(p) if p.apps.is_dir() => p,
+        Some(p) => {
+            checks.push(check(
+                "CapCut install",
+                CheckStatus::Fail,
+                format!("{} has no Apps folder", p.root.display()),
+            ));
+            return checks;
+        }
+        None => {
+            checks.push(check(
+                "CapCut install",
+                CheckStatus::Fail,
+                "CapCut was not found; choose the folder in the app or run a deep scan",
+            ));
+            return checks;
+        }
+    };
+    let versions = fs::read_dir(&capcut.apps)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| paths::has_main_executable(&e.path()))
+                .count()
+        })
+        .unwrap_or(0);
+    checks.push(if versions == 0 {
+        check(
+            "CapCut install",
+            CheckStatus::Fail,
+            format!("No version with CapCut.exe in {}", capcut.apps.display()),
+        )
+    } else {
+        check(
+            "CapCut install",
+            CheckStatus::Pass,
+            format!(
+                "{} ({} version(s), {} folder)",
+                capcut.apps.display(),
+                versions,
+                capcut.variant
+            ),
+        )
+    });
+
+    checks.push(match folder_access::can_write(&capcut.apps) {
+        Ok(()) => check("Permissions", CheckStatus::Pass, "Apps folder is writable"),
+        Err(e) => check(
+            "Permissions",
+            CheckStatus::Fail,
+            format!("Cannot write to the Apps folder: {}", e),
+        ),
+    });
+
+    // Security software and sync clients that undo or block our changes
+    let mut hints = Vec::new();
+    if folder_access::check_blocked(&capcut.apps).is_some() {
+        hints.push("Controlled Folder Access blocks writes to the Apps folder".to_string());
+    } else if folder_access::is_enabled() {
+        hints.push("Controlled Folder Access is on (not blocking right now)".to_string());
+    }
+    if let Some(root) = paths::cloud_sync_root(&capcut.root) {
+        hints.push(format!(
+            "CapCut folder is synced by OneDrive ({}); sync can restore deleted versions",
+            root.display()
+        ));
+    }
+    checks.push(if hints.is_empty() {
+        check("AV / sync interference", CheckStatus::Pass, "None detected")
+    } else {
+        check(
+            "AV / sync interference",
+            CheckStatus::Warn,
+            hints.join("; "),
+        )
+    });
+
+    let status = protector::check_protection_status();
+    checks.push(if status.is_protected {
+        check(
+            "Guards",
+            CheckStatus::Pass,
+            "configure.ini locked, blocker files in place",
+        )
+    } else {
+        let mut missing = Vec::new();
+        if !status.config_locked {
+            missing.push("configure.ini not locked");
+        }
+        if !status.blockers_exist {
+            missing.push("blocker files missing");
+        }
+        check("Guards", CheckStatus::Warn, missing.join(", "))
+    });
+
+    checks.push(if process::is_capcut_running() {
+        check(
+            "CapCut process",
+            CheckStatus::Warn,
+            "CapCut is running; close it before protecting",
+        )
+    } else {
+        check("CapCut process", CheckStatus::Pass, "Not running")
+    });
+
+    checks.push(match free_disk_bytes(&capcut.root) {
+        Some(free) if free < CRITICAL_DISK_BYTES => check(
+            "Disk space",
+            CheckStatus::Fail,
+            format!("{} free", format::format_size(free)),
+        ),
+        Some(free) if free < LOW_DISK_BYTES => check(
+            "Disk space",
+            CheckStatus::Warn,
+            format!("{} free; backups may not fit", format::format_size(free)),
+        ),
+        Some(free) => check(
+            "Disk space",
+            CheckStatus::Pass,
+            format!("{} free", format::format_size(free)),
+        ),
+        None => check("Disk space", CheckStatus::Warn, "Could not read free space"),
+    });
+
+    checks
+}
+
+/// Print the doctor report; returns the process exit code (1 if any check failed)
+pub fn run_doctor_cli() -> i32 {
+    let checks = run_checks();
+    println!("CC Version Guard doctor");
+    println!();
+    for c in &checks {
+        println!("[{}] {:<24} {}", c.status.label(), c.name, c.detail);
+    }
+
+    let count = |s: CheckStatus| checks.iter().filter(|c| c.status == s).count();
+    let failed = count(CheckStatus::Fail);
+    println!();
+    println!(
+        "{} passed, {} warning(s), {} failed",
+        count(CheckStatus::Pass),
+        count(CheckStatus::Warn),
+        failed
+    );
+    i32::from(failed > 0)
+}
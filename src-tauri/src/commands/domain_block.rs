@@ -0,0 +1,85 @@
+//! Update domain blocking
+//! Points CapCut's update-check domains to 0.0.0.0 in the hosts file as an optional protection layer
+
+use serde::Serialize;
+
+use super::{hosts, policy};
+
+/// Name of our block in the hosts file
+const HOSTS_BLOCK: &str = "update domains";
+
+/// Hosts block left by the loopback update check redirect of earlier releases
+const LEGACY_REDIRECT_BLOCK: &str = "update responder";
+
+/// Unroutable address; connections fail at once instead of timing out
+const BLACKHOLE: &str = "0.0.0.0";
+
+/// Verified update-check hosts only; shared telemetry and settings hosts also serve effects and assets
+pub const UPDATE_DOMAINS: &[&str] = &["update.capcut.com", "upgrade.capcut.com"];
+
+/// Update domain block state shown in the protection options
+#[derive(Debug, Clone, Serialize)]
+pub struct DomainBlockStatus {
+    pub blocked: bool,
+    pub domains: Vec<String>,
+    /// The old loopback redirect block also lists the update domains; the first hosts entry wins, so the outcome depends on file order
+    pub conflict: bool,
+}
+
+fn domains() -> Vec<String> {
+    UPDATE_DOMAINS.iter().map(|d| d.to_string()).collect()
+}
+
+/// Whether the hosts file holds our update domain block
+pub fn is_blocked() -> bool {
+    hosts::block_present(HOSTS_BLOCK)
+}
+
+/// Add (or refresh) the update domain block in the hosts file, taking the domains over from an old loopback redirect block
+pub fn apply() -> Result<(), String> {
+    remove_legacy_redirect()?;
+    hosts::write_block(HOSTS_BLOCK, BLACKHOLE, &domains())
+}
+
+/// Remove the update domain block from the hosts file, if present
+pub fn remove() -> Result<(), String> {
+    hosts::remove_block(HOSTS_BLOCK)
+}
+
+/// Whether the hosts file still holds the old loopback redirect block
+pub fn legacy_redirect_present() -> bool {
+    hosts::block_present(LEGACY_REDIRECT_BLOCK)
+}
+
+/// Remove the old loopback redirect block from the hosts file, if present
+pub fn remove_legacy_redirect() -> Result<(), String> {
+    hosts::remove_block(LEGACY_REDIRECT_BLOCK)
+}
+
+fn status() -> DomainBlockStatus {
+    DomainBlockStatus {
+        blocked: is_blocked(),
+        domains: domains(),
+        conflict: legacy_redirect_present(),
+    }
+}
+
+/// Block or unblock CapCut's update domains in the hosts file
+#[tauri::command]
+pub fn block_update_domains(enabled: bool) -> Result<DomainBlockStatus, String> {
+    if !enabled && policy::load_policy().disallow_unprotect {
+        return Err(policy::POLICY_BLOCKED.to_string());
+    }
+    if enabled {
+        apply()?;
+    } else {
+        remove()?;
+    }
+    Ok(status())
+}
+
+/// Get the update domain block state
+#[tauri::command]
+pub fn update_domains_status() -> DomainBlockStatus {
+    status()
+}
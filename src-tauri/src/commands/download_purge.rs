@@ -0,0 +1,277 @@
+//! Continuous purge of the updater download folder
+//! Watches `User Data\Download` and deletes installer payloads as soon as they finish writing
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::os::windows::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+use walkdir::WalkDir;
+
+use super::notifier::{self, AlertKind};
+use super::{exclusions, format, paths, policy, settings, stats, updater_stub};
+
+/// How long a file's size must stay unchanged before it counts as fully written
+const SETTLE_MS: u64 = 1500;
+
+/// Minimum gap between two purge alerts; payloads deleted in between go into the next one
+const ALERT_INTERVAL_SECS: u64 = 60;
+
+/// Download purge status reported to the frontend
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PurgeStatus {
+    pub running: bool,
+    /// Folder being watched
+    pub watched_dir: Option<String>,
+    /// Payloads deleted since the watcher started
+    pub purged: u64,
+}
+
+/// Managed purge state (one watcher per app)
+#[derive(Default)]
+pub struct PurgeState {
+    status: Arc<Mutex<PurgeStatus>>,
+    stop_flag: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+/// A file seen in the download folder that may still be downloading
+struct Pending {
+    size: u64,
+    since: Instant,
+}
+
+impl PurgeState {
+    /// Current status snapshot
+    pub fn status(&self) -> PurgeStatus {
+        self.status.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Start watching the download folder (no-op if already running)
+    pub fn start(&self, app: AppHandle) -> Result<PurgeStatus, String> {
+        let mut flag_slot = self.stop_flag.lock().map_err(|e| e.to_string())?;
+        if flag_slot.is_some() {
+            return Ok(self.status());
+        }
+
+        let download_dir = paths::get_capcut_root_path()
+            .ok_or("CapCut installation not found")?
+            .join("User Data")
+            .join("Download");
+        fs::create_dir_all(&download_dir)
+            .map_err(|e| format!("Could not create download folder: {}", e))?;
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)
+            .map_err(|e| format!("Could not start folder watcher: {}", e))?;
+        watcher
+            .watch(&download_dir, RecursiveMode::Recursive)
+            .map_err(|e| format!("Could not watch {}: {}", download_dir.display(), e))?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        *flag_slot = Some(stop.clone());
+        if let Ok(mut s) = self.status.lock() {
+            *s = PurgeStatus {
+                running: true,
+                watched_dir: Some(download_dir.to_string_lossy().to_string()),
+                purged: 0,
+            };
+        }
+
+        let status = self.status.clone();
+        thread::spawn(move || run_loop(app, watcher, rx, download_dir, status, stop));
+        Ok(self.status())
+    }
+
+    /// Signal the watcher to stop
+    pub fn stop(&self) -> PurgeStatus {
+        if let Some(stop) = self.stop_flag.lock().ok().and_then(|mut f| f.take()) {
+            stop.store(true, Ordering::Relaxed);
+        }
+        if let Ok(mut s) = self.status.lock() {
+            s.running = false;
+        }
+        self.status()
+    }
+}
+
+/// Size of a file that may be a payload; read-only files are blockers and are left alone
+fn payload_size(path: &Path) -> Option<u64> {
+    fs::metadata(path)
+        .ok()
+        .filter(|m| m.is_file() && !m.permissions().readonly())
+        .map(|m| m.len())
+}
+
+/// Delete a payload once no other process has it open; returns its size
+fn try_purge(path: &Path) -> Option<u64> {
+    let size = payload_size(path)?;
+    // Exclusive open fails while the updater is still writing the file
+    fs::OpenOptions::new()
+        .read(true)
+        .share_mode(0)
+        .open(path)
+        .ok()?;
+    fs::remove_file(path).ok()?;
+    Some(size)
+}
+
+/// Send one alert for a batch of deleted payloads
+fn send_purge_alert(batch: &[(PathBuf, u64)]) {
+    let total: u64 = batch.iter().map(|(_, bytes)| bytes).sum();
+    let paths: Vec<String> = batch
+        .iter()
+        .map(|(path, bytes)| format!("{} ({})", path.display(), format::format_size(*bytes)))
+        .collect();
+    let details = format!(
+        "{} downloaded CapCut update payload(s) were deleted ({}).\n\n{}",
+        batch.len(),
+        format::format_size(total),
+        paths.join("\n")
+    );
+    if let Err(e) = notifier::send_alert(AlertKind::UpdateBlocked, &details) {
+        tracing::warn!("Download purge email alert failed: {}", e);
+    }
+}
+
+/// Watch loop: queue new files, delete each once its size has settled
+fn run_loop(
+    app: AppHandle,
+    // Kept alive here; dropping it ends the watch
+    _watcher: RecommendedWatcher,
+    rx: mpsc::Receiver<notify::Result<notify::Event>>,
+    download_dir: PathBuf,
+    status: Arc<Mutex<PurgeStatus>>,
+    stop: Arc<AtomicBool>,
+) {
+    let settle = Duration::from_millis(SETTLE_MS);
+    let alert_interval = Duration::from_secs(ALERT_INTERVAL_SECS);
+    let mut pending: HashMap<PathBuf, Pending> = HashMap::new();
+    // Deleted payloads not alerted yet, and when the last alert went out
+    let mut unalerted: Vec<(PathBuf, u64)> = Vec::new();
+    let mut last_alert: Option<Instant> = None;
+    let excluded = exclusions::excluded_paths();
+
+    // Payloads that landed while the watcher was off
+    for entry in WalkDir::new(&download_dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        pending.insert(
+            entry.into_path(),
+            Pending {
+                size: 0,
+                since: Instant::now(),
+            },
+        );
+    }
+
+    while !stop.load(Ordering::Relaxed) {
+        match rx.recv_timeout(Duration::from_millis(500)) {
+            Ok(Ok(event)) => {
+                for path in event.paths {
+                    pending.entry(path).or_insert(Pending {
+                        size: 0,
+                        since: Instant::now(),
+                    });
+                }
+            }
+            Ok(Err(e)) => tracing::error!("Download watcher error: {}", e),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        let mut purged: Vec<(PathBuf, u64)> = Vec::new();
+        // Re-read each pass: a protection run may stub updaters while the watcher runs
+        let stubbed = if pending.is_empty() {
+            Vec::new()
+        } else {
+            updater_stub::stubbed_files()
+        };
+        pending.retain(|path, seen| {
+            let is_stubbed = stubbed
+                .iter()
+                .any(|s| s.as_os_str().eq_ignore_ascii_case(path.as_os_str()));
+            if exclusions::is_excluded(path, &excluded) || is_stubbed {
+                return false;
+            }
+            let size = match payload_size(path) {
+                Some(s) if s > 0 => s,
+                // Gone, a blocker, a folder, or not written yet
+                Some(_) => return true,
+                None => return false,
+            };
+            if size != seen.size {
+                seen.size = size;
+                seen.since = Instant::now();
+                return true;
+            }
+            if seen.since.elapsed() < settle {
+                return true;
+            }
+            match try_purge(path) {
+                Some(bytes) => {
+                    purged.push((path.clone(), bytes));
+                    false
+                }
+                None => true,
+            }
+        });
+
+        for (path, bytes) in purged {
+            stats::record_purge(bytes);
+            if let Ok(mut s) = status.lock() {
+                s.purged += 1;
+            }
+            let _ = app.emit("download-purged", path.to_string_lossy().to_string());
+            unalerted.push((path, bytes));
+        }
+
+        // One alert per batch, and none more often than the interval
+        if !unalerted.is_empty() && last_alert.is_none_or(|t| t.elapsed() >= alert_interval) {
+            send_purge_alert(&unalerted);
+            unalerted.clear();
+            last_alert = Some(Instant::now());
+        }
+    }
+
+    if !unalerted.is_empty() {
+        send_purge_alert(&unalerted);
+    }
+}
+
+/// Turn the continuous download purge on or off and remember the choice
+#[tauri::command]
+pub fn set_download_purge(
+    app: AppHandle,
+    state: State<'_, PurgeState>,
+    enabled: bool,
+) -> Result<PurgeStatus, String> {
+    if policy::load_policy().lock_settings {
+        return Err(policy::POLICY_BLOCKED.to_string());
+    }
+
+    let status = if enabled {
+        state.start(app)?
+    } else {
+        state.stop()
+    };
+
+    let mut app_settings = settings::load_settings();
+    app_settings.purge_downloads = enabled;
+    settings::save_settings(&app_settings)?;
+    Ok(status)
+}
+
+/// Get download purge status
+#[tauri::command]
+pub fn download_purge_status(state: State<'_, PurgeState>) -> PurgeStatus {
+    state.status()
+}
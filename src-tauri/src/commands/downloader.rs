@@ -0,0 +1,312 @@
+//! Archive downloads
+//! Downloads archive installers in-app with progress events, resume of partial files and a published-hash or signature check
+
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+
+use super::integrity::{self, SignatureStatus};
+use super::{paths, scanner};
+
+/// Prevents a console window flashing up for the download
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Suffix of an unfinished download; it is resumed on the next attempt
+const PART_SUFFIX: &str = ".part";
+
+/// Progress events are emitted at most this often
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Streams the response into the .part file, appending when the server honors the range request.
+/// Output lines: "start|<offset>|<total or -1>", "progress|<bytes>", "done|<bytes>", "error|<message>".
+const DOWNLOAD_SCRIPT: &str = r#"
+$ErrorActionPreference = 'Stop'
+$part = $env:CCVG_TARGET
+$offset = 0
+if (Test-Path -LiteralPath $part) { $offset = (Get-Item -LiteralPath $part).Length }
+try {
+  $req = [Net.HttpWebRequest]::Create($env:CCVG_URL)
+  $req.UserAgent = 'CC-Version-Guard'
+  $req.Timeout = 30000
+  $req.ReadWriteTimeout = 30000
+  if ($offset -gt 0) { $req.AddRange([long]$offset) }
+  try { $resp = $req.GetResponse() } catch [Net.WebException] {
+    $r = $_.Exception.Response
+    if ($r -and [int]$r.StatusCode -eq 416) { 'done|' + $offset; exit 0 }
+    throw
+  }
+  if ([int]$resp.StatusCode -ne 206) { $offset = 0 }
+  $total = -1
+  if ($resp.ContentLength -ge 0) { $total = $offset + $resp.ContentLength }
+  'start|' + $offset + '|' + $total
+  $mode = if ($offset -gt 0) { [IO.FileMode]::Append } else { [IO.FileMode]::Create }
+  $out = [IO.File]::Open($part, $mode, [IO.FileAccess]::Write)
+  $in = $resp.GetResponseStream()
+  $buf = New-Object byte[] 262144
+  $done = $offset
+  $last = $offset
+  while (($n = $in.Read($buf, 0, $buf.Length)) -gt 0) {
+    $out.Write($buf, 0, $n)
+    $done += $n
+    if ($done - $last -ge 524288) { 'progress|' + $done; $last = $done }
+  }
+  $out.Close(); $in.Close(); $resp.Close()
+  'done|' + $done
+} catch {
+  'error|' + $_.Exception.Message
+  exit 1
+}
+"#;
+
+/// Downloads in flight, by URL
+#[derive(Default)]
+pub struct DownloadState {
+    active: Mutex<HashSet<String>>,
+}
+
+/// Progress of a download, emitted as `archive-download-progress`
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadProgress {
+    pub url: String,
+    pub downloaded: u64,
+    /// None when the server doesn't send a length
+    pub total: Option<u64>,
+    /// Continued from an earlier partial download
+    pub resumed: bool,
+}
+
+/// A downloaded and verified installer
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadedInstaller {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+    /// Matched the archive entry's published hash (otherwise only the signature vouches for it)
+    pub hash_verified: bool,
+    pub signature: SignatureStatus,
+    pub signer: Option<String>,
+    pub resumed: bool,
+}
+
+/// Outcome of checking a finished download
+struct Verified {
+    sha256: String,
+    hash_verified: bool,
+    signature: SignatureStatus,
+    signer: Option<String>,
+}
+
+/// Folder downloaded installers are kept in
+pub fn get_download_dir() -> Option<PathBuf> {
+    paths::get_app_data_dir().map(|p| p.join("Downloads"))
+}
+
+/// Installer file name from the last URL segment
+fn file_name(url: &str) -> Result<String, String> {
+    url.split(['?', '#'])
+        .next()
+        .and_then(|u| u.rsplit('/').next())
+        .filter(|n| n.to_lowercase().ends_with(".exe") && !n.contains(['\\', ':']))
+        .map(str::to_string)
+        .ok_or_else(|| "The download link does not point to an installer".to_string())
+}
+
+/// Published hash of an archive entry; only URLs from the archive lists may be downloaded
+fn published_hash(url: &str) -> Result<Option<String>, String> {
+    scanner::curated_archive_versions()
+        .into_iter()
+        .chain(scanner::all_archive_versions())
+        .find(|v| v.download_url == url)
+        .map(|v| v.sha256)
+        .ok_or_else(|| "Only versions from the archive list can be downloaded".to_string())
+}
+
+/// Whether a downloaded installer matches the published hash of the archive entry it came from
+pub fn matches_published_hash(installer: &Path) -> bool {
+    let name = installer.file_name().unwrap_or_default().to_string_lossy();
+    let published = scanner::curated_archive_versions()
+        .into_iter()
+        .chain(scanner::all_archive_versions())
+        .filter(|v| file_name(&v.download_url).is_ok_and(|n| n.eq_ignore_ascii_case(&name)))
+        .find_map(|v| v.sha256);
+    match published {
+        Some(expected) => integrity::hash_file(installer).is_some_and(|h| h == expected),
+        None => false,
+    }
+}
+
+fn parse_u64(field: Option<&str>) -> Option<u64> {
+    field.and_then(|f| f.trim().parse().ok())
+}
+
+/// Run the download script, emitting progress; returns whether a partial file was continued
+fn fetch(app: &AppHandle, url: &str, part: &Path) -> Result<bool, String> {
+    let mut child = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", DOWNLOAD_SCRIPT])
+        .env("CCVG_URL", url)
+        .env("CCVG_TARGET", part)
+        .stdout(Stdio::piped())
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()
+        .map_err(|e| format!("Could not run PowerShell: {}", e))?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or("Could not read download output")?;
+    let mut progress = DownloadProgress {
+        url: url.to_string(),
+        downloaded: 0,
+        total: None,
+        resumed: false,
+    };
+    let mut last_emit: Option<Instant> = None;
+    let mut error: Option<String> = None;
+    let mut finished = false;
+
+    for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+        let mut fields = line.trim().splitn(3, '|');
+        match fields.next() {
+            Some("start") => {
+                progress.downloaded = parse_u64(fields.next()).unwrap_or(0);
+                progress.resumed = progress.downloaded > 0;
+                progress.total = parse_u64(fields.next());
+            }
+            Some("progress") => {
+                progress.downloaded = parse_u64(fields.next()).unwrap_or(progress.downloaded);
+                if last_emit.is_some_and(|t| t.elapsed() < PROGRESS_INTERVAL) {
+                    continue;
+                }
+            }
+            Some("done") => {
+                progress.downloaded = parse_u64(fields.next()).unwrap_or(progress.downloaded);
+                progress.total = Some(progress.downloaded);
+                finished = true;
+            }
+            Some("error") => {
+                error = fields.next().map(str::to_string);
+                continue;
+            }
+            _ => continue,
+        }
+        let _ = app.emit("archive-download-progress", &progress);
+        last_emit = Some(Instant::now());
+    }
+
+    let status = child.wait().map_err(|e| e.to_string())?;
+    if !status.success() || !finished {
+        return Err(format!(
+            "Download failed: {}",
+            error.unwrap_or_else(|| "connection closed".to_string())
+        ));
+    }
+    Ok(progress.resumed)
+}
+
+/// Check the finished file against the published hash and its signature; without a published hash only a valid Bytedance signature passes
+fn verify(url: &str, part: &Path) -> Result<Verified, String> {
+    let sha256 = integrity::hash_file(part).ok_or("Could not hash the downloaded installer")?;
+    let published = published_hash(url)?;
+    if let Some(expected) = &published {
+        if *expected != sha256 {
+            return Err(format!(
+                "SHA-256 mismatch: expected {}, got {}. The download was deleted",
+                expected, sha256
+            ));
+        }
+    }
+    let (signature, signer) = integrity::check_signature(part);
+    if matches!(
+        signature,
+        SignatureStatus::Invalid | SignatureStatus::UnexpectedSigner
+    ) {
+        return Err(format!(
+            "The installer's signature is not a valid Bytedance signature ({}). The download was deleted",
+            signer.as_deref().unwrap_or("invalid")
+        ));
+    }
+    // Nothing else vouches for an installer the archive list publishes no hash for
+    if published.is_none() && signature != SignatureStatus::Valid {
+        return Err(format!(
+            "The installer has no published hash and its Bytedance signature could not be confirmed ({}). The download was deleted",
+            match signature {
+                SignatureStatus::Unsigned => "unsigned",
+                _ => "signature check unavailable",
+            }
+        ));
+    }
+    Ok(Verified {
+        sha256,
+        hash_verified: published.is_some(),
+        signature,
+        signer,
+    })
+}
+
+fn download(app: &AppHandle, url: &str) -> Result<DownloadedInstaller, String> {
+    let name = file_name(url)?;
+    published_hash(url)?;
+    let dir = get_download_dir().ok_or("Could not determine download directory")?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create download folder: {}", e))?;
+    let target = dir.join(&name);
+    let part = dir.join(format!("{}{}", name, PART_SUFFIX));
+
+    // A finished download is verified again instead of fetched twice
+    let resumed = if target.is_file() {
+        fs::rename(&target, &part).map_err(|e| e.to_string())?;
+        false
+    } else {
+        fetch(app, url, &part)?
+    };
+
+    let verified = match verify(url, &part) {
+        Ok(v) => v,
+        Err(e) => {
+            let _ = fs::remove_file(&part);
+            return Err(e);
+        }
+    };
+    fs::rename(&part, &target).map_err(|e| format!("Failed to finish download: {}", e))?;
+
+    Ok(DownloadedInstaller {
+        path: paths::path_to_transport(&target),
+        size: fs::metadata(&target).map(|m| m.len()).unwrap_or(0),
+        sha256: verified.sha256,
+        hash_verified: verified.hash_verified,
+        signature: verified.signature,
+        signer: verified.signer,
+        resumed,
+    })
+}
+
+/// Download an archive installer into the app's Downloads folder, continuing a partial download
+#[tauri::command]
+pub async fn download_archive_version(
+    app: AppHandle,
+    state: State<'_, DownloadState>,
+    url: String,
+) -> Result<DownloadedInstaller, String> {
+    {
+        let mut active = state.active.lock().map_err(|e| e.to_string())?;
+        if !active.insert(url.clone()) {
+            return Err("This version is already downloading".to_string());
+        }
+    }
+
+    let task_url = url.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || download(&app, &task_url))
+        .await
+        .map_err(|e| e.to_string());
+
+    if let Ok(mut active) = state.active.lock() {
+        active.remove(&url);
+    }
+    result?
+}
@@ -0,0 +1,152 @@
+//! Draft compatibility check
+//! Reads the app version and schema each project was last saved with and flags projects
+//! that a kept older version may refuse to open
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use super::version::Version;
+use super::{drafts, installs, paths};
+
+/// Project files holding the draft, newest layout first
+const CONTENT_FILES: &[&str] = &["draft_info.json", "draft_content.json"];
+
+/// Project file holding the name shown in CapCut
+const META_FILE: &str = "draft_meta_info.json";
+
+/// Whether a project should open in the kept version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DraftCompatStatus {
+    Compatible,
+    /// Last saved by a newer CapCut than the kept version
+    Newer,
+    /// No readable version (missing or encrypted draft file)
+    Unknown,
+}
+
+/// Compatibility of one project with the kept version
+#[derive(Debug, Clone, Serialize)]
+pub struct DraftCompat {
+    /// Project folder name
+    pub folder: String,
+    /// Name shown in CapCut
+    pub name: String,
+    /// CapCut version that last saved the project
+    pub saved_with: Option<String>,
+    /// Draft schema number (`version` in the draft file)
+    pub schema: Option<u64>,
+    pub status: DraftCompatStatus,
+}
+
+/// Compatibility of every project with the kept version
+#[derive(Debug, Clone, Serialize)]
+pub struct DraftCompatReport {
+    pub kept_version: String,
+    pub projects: Vec<DraftCompat>,
+    /// Projects that may not open in the kept version
+    pub newer: usize,
+}
+
+/// Whether `saved_with` is newer than `kept`, comparing only the parts both have
+fn is_newer(saved_with: &str, kept: &str) -> Option<bool> {
+    let saved = Version::parse(saved_with)?;
+    let kept = Version::parse(kept)?;
+    Some(saved.cmp_common(&kept).is_gt())
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+}
+
+/// App version that last saved a draft, falling back to the one that created it
+fn saved_with(content: &Value) -> Option<String> {
+    ["last_modified_platform", "platform"]
+        .iter()
+        .find_map(|key| content.get(key)?.get("app_version")?.as_str())
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Check one project folder against the kept version
+pub fn check_project(project: &Path, kept_version: &str) -> DraftCompat {
+    let folder = project
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let name = read_json(&project.join(META_FILE))
+        .and_then(|meta| meta.get("draft_name")?.as_str().map(str::to_string))
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| folder.clone());
+    let content = CONTENT_FILES
+        .iter()
+        .find_map(|file| read_json(&project.join(file)));
+
+    let saved_with = content.as_ref().and_then(saved_with);
+    let schema = content.as_ref().and_then(|c| c.get("version")?.as_u64());
+    let status = match saved_with
+        .as_deref()
+        .and_then(|v| is_newer(v, kept_version))
+    {
+        Some(true) => DraftCompatStatus::Newer,
+        Some(false) => DraftCompatStatus::Compatible,
+        None => DraftCompatStatus::Unknown,
+    };
+
+    DraftCompat {
+        folder,
+        name,
+        saved_with,
+        schema,
+        status,
+    }
+}
+
+/// Check every project in the drafts folder against the kept version folder name
+pub fn check_drafts(kept_version: &str) -> DraftCompatReport {
+    let projects: Vec<DraftCompat> = drafts::get_drafts_dir()
+        .map(|dir| drafts::list_projects(&dir))
+        .unwrap_or_default()
+        .iter()
+        .map(|project| check_project(project, kept_version))
+        .collect();
+
+    DraftCompatReport {
+        kept_version: kept_version.to_string(),
+        newer: projects
+            .iter()
+            .filter(|p| p.status == DraftCompatStatus::Newer)
+            .count(),
+        projects,
+    }
+}
+
+/// Check which projects may not open in the version to keep; `install` picks an install by key
+#[tauri::command]
+pub async fn check_draft_compatibility(
+    keep_version: String,
+    install: Option<String>,
+) -> Result<DraftCompatReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let scoped = match install {
+            Some(key) => Some(
+                installs::detect_installs()
+                    .into_iter()
+                    .find(|i| installs::install_key(i) == key)
+                    .ok_or("A selected CapCut installation was not found anymore")?,
+            ),
+            None => None,
+        };
+        let _scope = scoped.as_ref().map(installs::scope);
+        let kept = paths::path_from_transport(&keep_version);
+        let name = kept.file_name().unwrap_or_default().to_string_lossy();
+        Ok(check_drafts(&name))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
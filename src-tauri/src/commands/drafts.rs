@@ -0,0 +1,494 @@
+//! CapCut draft (project) backups
+//! Copies the drafts folder into timestamped snapshots, syncs only changed projects
+//! into a mirror on an external drive or network folder, or zips chosen projects
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::log_entry::LogEntry;
+use super::settings::ScheduleSettings;
+use super::{backup, format, paths, process, settings};
+
+/// Snapshot folder name prefix (followed by Unix seconds)
+const SNAPSHOT_PREFIX: &str = "drafts_";
+
+/// Folder holding the incremental mirror
+const MIRROR_DIR: &str = "drafts_mirror";
+
+/// Per-project fingerprints from the last incremental sync
+const MANIFEST_FILE: &str = ".ccvg_manifest.json";
+
+/// Draft backup result
+#[derive(Debug, Clone, Serialize)]
+pub struct DraftBackupResult {
+    pub success: bool,
+    pub error: Option<String>,
+    pub logs: Vec<LogEntry>,
+    /// Folder the drafts were copied to
+    pub snapshot: Option<String>,
+    /// Projects copied this run
+    pub copied: usize,
+    /// Projects skipped because they had not changed (incremental only)
+    pub unchanged: usize,
+}
+
+impl DraftBackupResult {
+    fn failed(error: String, logs: Vec<LogEntry>) -> Self {
+        Self {
+            success: false,
+            error: Some(error),
+            logs,
+            snapshot: None,
+            copied: 0,
+            unchanged: 0,
+        }
+    }
+}
+
+/// Cheap change detector for one project folder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ProjectFingerprint {
+    files: u64,
+    bytes: u64,
+    /// Newest file modification (Unix seconds)
+    newest: u64,
+}
+
+/// Key in `User Data\Config\globalSetting` holding a drafts folder chosen in CapCut's settings
+const CUSTOM_DRAFT_KEY: &str = "currentCustomDraftPath=";
+
+/// Drafts folder set in CapCut's settings, when it still exists
+fn custom_drafts_dir(root: &Path) -> Option<PathBuf> {
+    let setting =
+        fs::read_to_string(root.join("User Data").join("Config").join("globalSetting")).ok()?;
+    setting
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(CUSTOM_DRAFT_KEY))
+        .map(|value| PathBuf::from(value.trim().trim_matches('"')))
+        .filter(|dir| dir.is_dir())
+}
+
+/// CapCut's drafts folder: the one chosen in its settings, else the default
+pub fn get_drafts_dir() -> Option<PathBuf> {
+    paths::get_capcut_root_path().map(|root| {
+        custom_drafts_dir(&root).unwrap_or_else(|| {
+            root.join("User Data")
+                .join("Projects")
+                .join("com.lveditor.draft")
+        })
+    })
+}
+
+/// Backup destination from the schedule settings (app data folder when unset)
+pub fn destination(schedules: &ScheduleSettings) -> Option<PathBuf> {
+    match schedules.draft_backup_destination.trim() {
+        "" => paths::get_app_data_dir().map(|p| p.join("DraftBackups")),
+        custom => Some(PathBuf::from(custom)),
+    }
+}
+
+/// Snapshot folders in a destination, newest first
+fn list_snapshots(dest: &Path) -> Vec<(u64, PathBuf)> {
+    let mut snapshots: Vec<(u64, PathBuf)> = fs::read_dir(dest)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let created = name.strip_prefix(SNAPSHOT_PREFIX)?.parse().ok()?;
+            Some((created, e.path()))
+        })
+        .collect();
+    snapshots.sort_by_key(|(created, _)| std::cmp::Reverse(*created));
+    snapshots
+}
+
+/// Delete snapshots beyond the retention count (0 keeps all)
+fn prune_snapshots(dest: &Path, retention: u32, logs: &mut Vec<LogEntry>) {
+    if retention == 0 {
+        return;
+    }
+    for (_, old) in list_snapshots(dest).into_iter().skip(retention as usize) {
+        match fs::remove_dir_all(&old) {
+            Ok(()) => logs.push(LogEntry::info("Removed old snapshot").with_path(&old)),
+            Err(e) => logs.push(
+                LogEntry::warn(format!("Could not remove old snapshot: {}", e)).with_path(&old),
+            ),
+        }
+    }
+}
+
+/// Whether the drive or network share holding `dest` is reachable
+fn destination_available(dest: &Path) -> bool {
+    dest.ancestors()
+        .filter(|a| !a.as_os_str().is_empty())
+        .last()
+        .is_some_and(|root| root.exists())
+}
+
+/// Project folders inside the drafts folder
+pub fn list_projects(drafts: &Path) -> Vec<PathBuf> {
+    fs::read_dir(drafts)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect()
+}
+
+/// File count, total size and newest modification of a project
+fn fingerprint(project: &Path) -> ProjectFingerprint {
+    let mut print = ProjectFingerprint {
+        files: 0,
+        bytes: 0,
+        newest: 0,
+    };
+    for meta in WalkDir::new(project)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+    {
+        print.files += 1;
+        print.bytes += meta.len();
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs());
+        print.newest = print.newest.max(modified);
+    }
+    print
+}
+
+/// Sync only projects that changed since the last run into `dest\drafts_mirror`
+///
+/// Projects deleted in CapCut are kept in the mirror so a backup never loses work.
+pub fn sync_drafts(dest: &Path) -> DraftBackupResult {
+    let mut logs: Vec<LogEntry> = Vec::new();
+
+    let drafts = match get_drafts_dir() {
+        Some(d) if d.is_dir() => d,
+        _ => return DraftBackupResult::failed("CapCut drafts folder not found".to_string(), logs),
+    };
+    if !destination_available(dest) {
+        return DraftBackupResult::failed(
+            format!(
+                "Backup destination {} is not available (is the drive connected?)",
+                dest.display()
+            ),
+            logs,
+        );
+    }
+
+    let mirror = dest.join(MIRROR_DIR);
+    if let Err(e) = fs::create_dir_all(&mirror) {
+        return DraftBackupResult::failed(
+            format!("Failed to create {}: {}", mirror.display(), e),
+            logs,
+        );
+    }
+    let manifest_path = mirror.join(MANIFEST_FILE);
+    let mut manifest: BTreeMap<String, ProjectFingerprint> = fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+
+    logs.push(LogEntry::info("Syncing drafts...").with_path(&mirror));
+    let mut copied = 0;
+    let mut unchanged = 0;
+    let mut failed = 0;
+    for project in list_projects(&drafts) {
+        let name = project
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let print = fingerprint(&project);
+        let target = mirror.join(&name);
+        if target.is_dir() && manifest.get(&name) == Some(&print) {
+            unchanged += 1;
+            continue;
+        }
+
+        // Replace the whole project so files removed from it don't linger
+        fs::remove_dir_all(&target).ok();
+        match backup::copy_dir_recursive(&project, &target) {
+            Ok(()) => {
+                manifest.insert(name, print);
+                copied += 1;
+            }
+            Err(e) => {
+                // Forget the fingerprint so the next run retries this project
+                manifest.remove(&name);
+                logs.push(
+                    LogEntry::warn(format!("Could not copy {}: {}", name, e)).with_path(&project),
+                );
+                failed += 1;
+            }
+        }
+    }
+
+    if let Ok(content) = serde_json::to_string_pretty(&manifest) {
+        fs::write(&manifest_path, content).ok();
+    }
+    logs.push(LogEntry::ok(format!(
+        "{} changed project(s) copied, {} unchanged",
+        copied, unchanged
+    )));
+
+    DraftBackupResult {
+        success: failed == 0,
+        error: (failed > 0).then(|| format!("{} project(s) could not be copied", failed)),
+        logs,
+        snapshot: Some(mirror.to_string_lossy().to_string()),
+        copied,
+        unchanged,
+    }
+}
+
+/// Copy the drafts folder into a new snapshot under `dest`
+pub fn snapshot_drafts(dest: &Path, retention: u32) -> DraftBackupResult {
+    let mut logs: Vec<LogEntry> = Vec::new();
+
+    let drafts = match get_drafts_dir() {
+        Some(d) if d.is_dir() => d,
+        _ => return DraftBackupResult::failed("CapCut drafts folder not found".to_string(), logs),
+    };
+    if !destination_available(dest) {
+        return DraftBackupResult::failed(
+            format!(
+                "Backup destination {} is not available (is the drive connected?)",
+                dest.display()
+            ),
+            logs,
+        );
+    }
+
+    let created = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let snapshot = dest.join(format!("{}{}", SNAPSHOT_PREFIX, created));
+
+    logs.push(LogEntry::info("Backing up drafts...").with_path(&snapshot));
+    if let Err(e) = backup::copy_dir_recursive(&drafts, &snapshot) {
+        // Don't leave a partial snapshot that retention would count
+        fs::remove_dir_all(&snapshot).ok();
+        return DraftBackupResult::failed(format!("Failed to copy drafts: {}", e), logs);
+    }
+    logs.push(
+        LogEntry::ok(format!(
+            "Drafts backed up ({})",
+            format::format_size(backup::calc_dir_size(&snapshot))
+        ))
+        .with_path(&snapshot),
+    );
+
+    prune_snapshots(dest, retention, &mut logs);
+
+    DraftBackupResult {
+        success: true,
+        error: None,
+        logs,
+        snapshot: Some(snapshot.to_string_lossy().to_string()),
+        copied: list_projects(&drafts).len(),
+        unchanged: 0,
+    }
+}
+
+/// Back up drafts with the saved settings (incremental sync or a new snapshot)
+pub fn run_configured_backup(schedules: &ScheduleSettings) -> DraftBackupResult {
+    match destination(schedules) {
+        Some(dest) if schedules.draft_backup_incremental => sync_drafts(&dest),
+        Some(dest) => snapshot_drafts(&dest, schedules.draft_backup_retention),
+        None => {
+            DraftBackupResult::failed("Could not determine backup destination".to_string(), vec![])
+        }
+    }
+}
+
+/// Back up drafts now with the saved destination and retention
+#[tauri::command]
+pub async fn backup_drafts_now() -> DraftBackupResult {
+    if process::is_capcut_running() {
+        return DraftBackupResult::failed(
+            "Close CapCut so open projects are saved before backing up".to_string(),
+            vec![],
+        );
+    }
+
+    tauri::async_runtime::spawn_blocking(|| {
+        run_configured_backup(&settings::load_settings().schedules)
+    })
+    .await
+    .unwrap_or_else(|e| DraftBackupResult::failed(e.to_string(), vec![]))
+}
+
+/// A project in the drafts folder
+#[derive(Debug, Clone, Serialize)]
+pub struct DraftProject {
+    /// Project folder name
+    pub name: String,
+    pub bytes: u64,
+    /// Newest file modification (Unix seconds)
+    pub modified: u64,
+}
+
+/// List the projects in the drafts folder, most recently edited first
+#[tauri::command]
+pub async fn list_draft_projects() -> Result<Vec<DraftProject>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let drafts = match get_drafts_dir() {
+            Some(d) if d.is_dir() => d,
+            _ => return Err("CapCut drafts folder not found".to_string()),
+        };
+        let mut projects: Vec<DraftProject> = list_projects(&drafts)
+            .iter()
+            .map(|project| {
+                let print = fingerprint(project);
+                DraftProject {
+                    name: project
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string(),
+                    bytes: print.bytes,
+                    modified: print.newest,
+                }
+            })
+            .collect();
+        projects.sort_by_key(|p| std::cmp::Reverse(p.modified));
+        Ok(projects)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// A zip of draft projects
+#[derive(Debug, Clone, Serialize)]
+pub struct DraftArchiveInfo {
+    pub path: String,
+    pub projects: usize,
+    pub files: usize,
+    pub total_bytes: u64,
+}
+
+/// Project folders to archive: the named ones, or all when none are named
+pub fn projects_to_archive(names: &[String]) -> Vec<PathBuf> {
+    let drafts = match get_drafts_dir() {
+        Some(d) => d,
+        None => return Vec::new(),
+    };
+    list_projects(&drafts)
+        .into_iter()
+        .filter(|p| {
+            names.is_empty()
+                || names
+                    .iter()
+                    .any(|n| p.file_name().is_some_and(|f| f.to_string_lossy() == *n))
+        })
+        .collect()
+}
+
+/// Name of the zip written for the current install, unique per install and second
+pub fn archive_name() -> String {
+    let variant = paths::resolve_capcut_paths()
+        .map(|p| p.variant)
+        .unwrap_or_else(|| "CapCut".to_string());
+    let created = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    format!("{}_{}{}.zip", variant, SNAPSHOT_PREFIX, created)
+}
+
+fn write_archive(projects: &[PathBuf], dest: &Path) -> Result<DraftArchiveInfo, String> {
+    let file = File::create(dest).map_err(|e| format!("Failed to create archive: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let mut files = 0;
+    let mut total_bytes = 0;
+
+    for project in projects {
+        let base = project.parent().unwrap_or(project);
+        for entry in WalkDir::new(project)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let rel = match entry.path().strip_prefix(base) {
+                Ok(r) => r.to_string_lossy().replace('\\', "/"),
+                Err(_) => continue,
+            };
+            let size = entry.metadata().map_or(0, |m| m.len());
+            let options = SimpleFileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .large_file(size >= u64::from(u32::MAX));
+            zip.start_file(rel.as_str(), options)
+                .map_err(|e| e.to_string())?;
+            let mut source = File::open(entry.path())
+                .map_err(|e| format!("Failed to read {}: {}", entry.path().display(), e))?;
+            io::copy(&mut source, &mut zip)
+                .map_err(|e| format!("Failed to archive {}: {}", rel, e))?;
+            files += 1;
+            total_bytes += size;
+        }
+    }
+
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(DraftArchiveInfo {
+        path: dest.to_string_lossy().to_string(),
+        projects: projects.len(),
+        files,
+        total_bytes,
+    })
+}
+
+/// Zip the named projects (all when none are named) into a new archive in `dest_dir`
+pub fn archive_drafts(names: &[String], dest_dir: &Path) -> Result<DraftArchiveInfo, String> {
+    let projects = projects_to_archive(names);
+    if projects.is_empty() {
+        return Err("No CapCut projects found to back up".to_string());
+    }
+    if !destination_available(dest_dir) {
+        return Err(format!(
+            "Backup destination {} is not available (is the drive connected?)",
+            dest_dir.display()
+        ));
+    }
+    fs::create_dir_all(dest_dir)
+        .map_err(|e| format!("Failed to create {}: {}", dest_dir.display(), e))?;
+
+    let dest = dest_dir.join(archive_name());
+    let result = write_archive(&projects, &dest);
+    if result.is_err() {
+        // Don't leave a truncated zip that looks like a good backup
+        fs::remove_file(&dest).ok();
+    }
+    result
+}
+
+/// Zip chosen projects into a folder picked by the user
+#[tauri::command]
+pub async fn backup_drafts(
+    projects: Vec<String>,
+    dest: String,
+) -> Result<DraftArchiveInfo, String> {
+    if process::is_capcut_running() {
+        return Err("Close CapCut so open projects are saved before backing up".to_string());
+    }
+
+    tauri::async_runtime::spawn_blocking(move || archive_drafts(&projects, Path::new(&dest)))
+        .await
+        .map_err(|e| e.to_string())?
+}
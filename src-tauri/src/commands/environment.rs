@@ -0,0 +1,98 @@
+//! Startup environment check
+//! Explains unsupported Windows builds or a missing LOCALAPPDATA instead of reporting "CapCut not found"
+
+use serde::Serialize;
+use std::path::Path;
+use std::process::Command;
+use winreg::enums::HKEY_LOCAL_MACHINE;
+use winreg::RegKey;
+
+use super::report;
+
+/// First Windows 10 build; CapCut's desktop app needs Windows 10 or later
+const MIN_WINDOWS_BUILD: u32 = 10240;
+
+const WINDOWS_VERSION_KEY: &str = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion";
+
+/// Result of the startup environment check
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentCheck {
+    /// False when Version Guard cannot work here (see `problems`)
+    pub supported: bool,
+    /// Windows product name and build
+    pub os: String,
+    pub build: Option<u32>,
+    pub local_app_data: Option<String>,
+    pub elevated: bool,
+    /// Reasons the app cannot work
+    pub problems: Vec<String>,
+    /// Limitations that only affect some features
+    pub notes: Vec<String>,
+}
+
+/// Check if the current process runs with administrator rights
+pub fn is_elevated() -> bool {
+    // "net session" only succeeds from an elevated prompt
+    Command::new("net")
+        .arg("session")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Windows build number (e.g., 19045)
+fn windows_build() -> Option<u32> {
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(WINDOWS_VERSION_KEY)
+        .and_then(|k| k.get_value::<String, _>("CurrentBuild"))
+        .ok()
+        .and_then(|b| b.trim().parse().ok())
+}
+
+/// Check the OS build, LOCALAPPDATA and privileges
+#[tauri::command]
+pub fn check_environment() -> EnvironmentCheck {
+    let mut problems = Vec::new();
+    let mut notes = Vec::new();
+
+    let build = windows_build();
+    match build {
+        Some(b) if b < MIN_WINDOWS_BUILD => problems.push(format!(
+            "Windows build {} is not supported. CapCut and Version Guard need Windows 10 or later.",
+            b
+        )),
+        Some(_) => {}
+        None => notes.push("Could not read the Windows build number".to_string()),
+    }
+
+    let local_app_data = std::env::var("LOCALAPPDATA").ok();
+    match &local_app_data {
+        Some(dir) if Path::new(dir).is_dir() => {}
+        Some(dir) => problems.push(format!(
+            "LOCALAPPDATA points to {}, which does not exist. CapCut installs there, so it can't be found.",
+            dir
+        )),
+        None => problems.push(
+            "LOCALAPPDATA is not set, so the CapCut folder can't be located. This happens in some service accounts and stripped-down environments."
+                .to_string(),
+        ),
+    }
+
+    let elevated = is_elevated();
+    if !elevated {
+        notes.push(
+            "Running without administrator rights: the update domain block and fleet mode need Version Guard started as administrator"
+                .to_string(),
+        );
+    }
+
+    EnvironmentCheck {
+        supported: problems.is_empty(),
+        os: report::windows_version(),
+        build,
+        local_app_data,
+        elevated,
+        problems,
+        notes,
+    }
+}
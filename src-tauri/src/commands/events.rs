@@ -0,0 +1,105 @@
+//! Guard event history for the in-app notification center
+//! Persists blocked updates, broken guards and repairs with read/unread state
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use super::notifier::AlertKind;
+use super::paths;
+
+/// Oldest events are dropped beyond this count
+const MAX_EVENTS: usize = 200;
+
+/// Serializes read-modify-write of the events file across threads
+static EVENTS_LOCK: Mutex<()> = Mutex::new(());
+
+/// A recorded guard event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuardEvent {
+    pub id: u64,
+    pub kind: AlertKind,
+    pub message: String,
+    /// Unix seconds
+    pub timestamp: u64,
+    pub read: bool,
+}
+
+/// Get the events file path
+fn get_events_path() -> Option<PathBuf> {
+    paths::get_app_data_dir().map(|p| p.join("events.json"))
+}
+
+fn load_events() -> Vec<GuardEvent> {
+    get_events_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_events(events: &[GuardEvent]) -> Result<(), String> {
+    let path = get_events_path().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(events).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save events: {}", e))
+}
+
+/// Record a guard event (newest first)
+pub fn record_event(kind: AlertKind, message: &str) {
+    tracing::info!(event = ?kind, "{}", message);
+    let _guard = EVENTS_LOCK.lock();
+    let mut events = load_events();
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let id = events.iter().map(|e| e.id).max().unwrap_or(0) + 1;
+
+    events.insert(
+        0,
+        GuardEvent {
+            id,
+            kind,
+            message: message.to_string(),
+            timestamp,
+            read: false,
+        },
+    );
+    events.truncate(MAX_EVENTS);
+
+    if let Err(e) = save_events(&events) {
+        tracing::warn!("Could not record guard event: {}", e);
+    }
+}
+
+/// List recorded guard events (newest first)
+#[tauri::command]
+pub fn list_events() -> Vec<GuardEvent> {
+    load_events()
+}
+
+/// Count unread guard events
+#[tauri::command]
+pub fn unread_event_count() -> usize {
+    load_events().iter().filter(|e| !e.read).count()
+}
+
+/// Mark all guard events as read
+#[tauri::command]
+pub fn mark_events_read() -> Result<(), String> {
+    let _guard = EVENTS_LOCK.lock();
+    let mut events = load_events();
+    events.iter_mut().for_each(|e| e.read = true);
+    save_events(&events)
+}
+
+/// Delete all guard events
+#[tauri::command]
+pub fn clear_events() -> Result<(), String> {
+    let _guard = EVENTS_LOCK.lock();
+    save_events(&[])
+}
@@ -0,0 +1,77 @@
+//! Never-touch path list
+//! Version folders, drafts or plugin dirs the deleter, cleaner and download purge must always skip
+
+use std::fs;
+use std::os::windows::fs::FileTypeExt;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::settings;
+
+/// Lowercased, separator-normalized form for comparing Windows paths
+fn normalized(path: &Path) -> String {
+    let s = path.to_string_lossy().replace('/', "\\").to_lowercase();
+    s.trim_end_matches('\\').to_string()
+}
+
+/// Whether `path` is `parent` or lies inside it
+pub fn is_within(path: &Path, parent: &Path) -> bool {
+    let (path, parent) = (normalized(path), normalized(parent));
+    path == parent || path.starts_with(&format!("{}\\", parent))
+}
+
+/// Excluded paths from settings
+pub fn excluded_paths() -> Vec<PathBuf> {
+    settings::load_settings()
+        .excluded_paths
+        .iter()
+        .filter(|p| !p.trim().is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Whether `path` is on, or inside an entry of, the exclusion list
+pub fn is_excluded(path: &Path, excluded: &[PathBuf]) -> bool {
+    excluded.iter().any(|e| is_within(path, e))
+}
+
+/// Excluded paths that lie inside `dir`
+pub fn excluded_within(dir: &Path, excluded: &[PathBuf]) -> Vec<PathBuf> {
+    excluded
+        .iter()
+        .filter(|e| is_within(e, dir) && !is_within(dir, e))
+        .cloned()
+        .collect()
+}
+
+/// Whether removing `path` as a whole would delete something on the exclusion list
+pub fn touches_excluded(path: &Path, excluded: &[PathBuf]) -> bool {
+    is_excluded(path, excluded) || !excluded_within(path, excluded).is_empty()
+}
+
+/// Remove `dir` except the excluded entries inside it (and the folders leading to them)
+pub fn remove_dir_except(dir: &Path, excluded: &[PathBuf]) -> Result<(), String> {
+    // Children before parents, so emptied folders can be removed on the way up
+    for entry in WalkDir::new(dir)
+        .follow_root_links(false)
+        .contents_first(true)
+        .into_iter()
+        .filter_entry(|e| !is_excluded(e.path(), excluded))
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if entry.file_type().is_dir() {
+            // Still holds an excluded entry
+            if !excluded_within(path, excluded).is_empty() {
+                continue;
+            }
+            fs::remove_dir(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        } else if entry.file_type().is_symlink_dir() {
+            // Directory junction or symlink: the link goes, its target stays
+            fs::remove_dir(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        } else {
+            fs::remove_file(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! Starred archive versions
+//! Floats favorites to the top of the archive lists and reports when their download changes
+
+use super::notifier::{self, AlertKind};
+use super::scanner::{self, ArchiveVersion};
+use super::settings::{self, FavoriteArchive};
+
+/// Flag starred entries and move them to the top (order otherwise kept)
+pub fn mark_favorites(mut versions: Vec<ArchiveVersion>) -> Vec<ArchiveVersion> {
+    let favorites = settings::load_settings().favorite_archives;
+    for v in versions.iter_mut() {
+        v.favorite = favorites.iter().any(|f| f.label == v.persona);
+    }
+    versions.sort_by_key(|v| !v.favorite);
+    versions
+}
+
+/// Compare starred entries against the bundled archive lists, alert on changes
+pub fn check_favorite_changes() {
+    let mut app_settings = settings::load_settings();
+    if app_settings.favorite_archives.is_empty() {
+        return;
+    }
+
+    let known: Vec<ArchiveVersion> = scanner::curated_archive_versions()
+        .into_iter()
+        .chain(scanner::all_archive_versions())
+        .collect();
+
+    let mut changes: Vec<String> = Vec::new();
+    for favorite in app_settings.favorite_archives.iter_mut() {
+        match known.iter().find(|v| v.persona == favorite.label) {
+            None => changes.push(format!("{} is no longer listed", favorite.label)),
+            Some(v) if v.download_url != favorite.url => {
+                changes.push(format!("{} has a new download link", favorite.label));
+                favorite.url = v.download_url.clone();
+            }
+            Some(_) => {}
+        }
+    }
+    if changes.is_empty() {
+        return;
+    }
+
+    // A locked policy keeps the old links; the change alert below still goes out
+    if let Err(e) = settings::save_user_settings(&app_settings) {
+        tracing::warn!("Could not update favorites: {}", e);
+    }
+    if app_settings.notifications.on_favorite_changed {
+        if let Err(e) = notifier::send_alert(AlertKind::FavoriteChanged, &changes.join("\n")) {
+            tracing::warn!("Favorite change email failed: {}", e);
+        }
+    }
+}
+
+/// Star or unstar an archive version; returns the new starred state
+#[tauri::command]
+pub fn toggle_favorite_archive(label: String, url: String) -> Result<bool, String> {
+    let mut app_settings = settings::load_settings();
+    let before = app_settings.favorite_archives.len();
+    app_settings.favorite_archives.retain(|f| f.label != label);
+
+    let starred = app_settings.favorite_archives.len() == before;
+    if starred {
+        app_settings
+            .favorite_archives
+            .push(FavoriteArchive { label, url });
+    }
+
+    settings::save_user_settings(&app_settings)?;
+    Ok(starred)
+}
@@ -0,0 +1,258 @@
+//! Updater firewall rules
+//! Creates outbound Windows Firewall rules that cut CapCut's updater executables off the network, tracked so they can be listed and removed
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+
+use super::log_entry::LogEntry;
+use super::{channel, installs, paths, policy};
+
+/// Prevents a console window flashing up for netsh
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Every rule name starts with this, so rules are recognizable in the firewall console
+const RULE_PREFIX: &str = "CC Version Guard";
+
+/// Apps subfolders searched for updater copies (version folders and their direct subfolders)
+const APPS_SEARCH_DEPTH: usize = 3;
+
+/// A firewall rule this app created
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirewallRule {
+    pub name: String,
+    /// Blocked executable (the rule applies even while the file doesn't exist)
+    pub program: String,
+    /// Still present in the firewall (filled in when listing)
+    #[serde(default)]
+    pub present: bool,
+}
+
+/// Rules created for the install the calling thread works on
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RuleList {
+    rules: Vec<FirewallRule>,
+}
+
+fn get_rules_path() -> Option<PathBuf> {
+    installs::get_install_data_dir().map(|p| p.join("firewall_rules.json"))
+}
+
+fn load_rules() -> RuleList {
+    get_rules_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_rules(list: &RuleList) -> Result<(), String> {
+    let path = get_rules_path().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(list).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save firewall rule list: {}", e))
+}
+
+/// Rule name for a program: the file name plus a short hash of its full path
+fn rule_name(program: &Path) -> String {
+    let hash: String = Sha256::digest(program.to_string_lossy().to_lowercase().as_bytes())
+        .iter()
+        .take(4)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!(
+        "{} - {} ({})",
+        RULE_PREFIX,
+        program.file_name().unwrap_or_default().to_string_lossy(),
+        hash
+    )
+}
+
+/// Run netsh with a pre-quoted argument string; Err carries netsh's output
+fn netsh(args: &str) -> Result<(), String> {
+    let output = Command::new("netsh")
+        .raw_arg(args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("Could not run netsh: {}", e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let message = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Err(if message.is_empty() {
+        "netsh failed".to_string()
+    } else {
+        message
+    })
+}
+
+fn rule_exists(name: &str) -> bool {
+    netsh(&format!("advfirewall firewall show rule name=\"{}\"", name)).is_ok()
+}
+
+fn delete_rule(name: &str) -> Result<(), String> {
+    netsh(&format!(
+        "advfirewall firewall delete rule name=\"{}\"",
+        name
+    ))
+}
+
+/// Updater executables of the install: the ones under User Data\Download and copies inside Apps
+fn updater_programs() -> Vec<PathBuf> {
+    let install = match paths::resolve_capcut_paths() {
+        Some(p) => p,
+        None => return Vec::new(),
+    };
+    let names =
+        channel::guard_profile(channel::detect_channel(&install.root, &install.apps)).updater_names;
+    let download_dir = install.root.join("User Data").join("Download");
+
+    let mut programs: Vec<PathBuf> = names.iter().map(|n| download_dir.join(n)).collect();
+    programs.extend(
+        WalkDir::new(&install.apps)
+            .max_depth(APPS_SEARCH_DEPTH)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| {
+                let name = e.file_name().to_string_lossy();
+                names.iter().any(|n| name.eq_ignore_ascii_case(n))
+            })
+            .map(|e| e.into_path()),
+    );
+    programs
+}
+
+/// Rules `block_updaters` would add (updaters without a rule yet)
+pub fn missing_rules() -> Vec<FirewallRule> {
+    updater_programs()
+        .into_iter()
+        .map(|program| FirewallRule {
+            name: rule_name(&program),
+            program: program.to_string_lossy().to_string(),
+            present: false,
+        })
+        .filter(|r| !rule_exists(&r.name))
+        .collect()
+}
+
+/// Create a blocking outbound rule for each updater executable, adding them to the rule list
+pub fn block_updaters(logs: &mut Vec<LogEntry>) -> Result<(), String> {
+    let mut list = load_rules();
+    let mut failed: Option<String> = None;
+    for program in updater_programs() {
+        let name = rule_name(&program);
+        if rule_exists(&name) {
+            continue;
+        }
+        let created = netsh(&format!(
+            "advfirewall firewall add rule name=\"{}\" dir=out action=block program=\"{}\" enable=yes description=\"Blocks the CapCut updater (created by CC Version Guard)\"",
+            name,
+            program.display()
+        ));
+        match created {
+            Ok(()) => {
+                logs.push(
+                    LogEntry::ok(format!("Firewall rule added: {}", name)).with_path(&program),
+                );
+                list.rules.retain(|r| r.name != name);
+                list.rules.push(FirewallRule {
+                    name,
+                    program: program.to_string_lossy().to_string(),
+                    present: true,
+                });
+            }
+            Err(e) => failed = Some(e),
+        }
+    }
+    save_rules(&list)?;
+    match failed {
+        Some(e) => Err(format!(
+            "Could not add every firewall rule (run Version Guard as administrator): {}",
+            e
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Rules created for the install, with whether each is still in the firewall
+pub fn created_rules() -> Vec<FirewallRule> {
+    load_rules()
+        .rules
+        .into_iter()
+        .map(|r| FirewallRule {
+            present: rule_exists(&r.name),
+            ..r
+        })
+        .collect()
+}
+
+/// Number of created rules still in the firewall
+pub fn present_count() -> usize {
+    created_rules().iter().filter(|r| r.present).count()
+}
+
+/// Delete every rule created for the install; rules that are already gone are dropped from the list
+pub fn remove_all(logs: &mut Vec<LogEntry>) -> Result<(), String> {
+    let mut list = load_rules();
+    if list.rules.is_empty() {
+        return Ok(());
+    }
+    let mut kept: Vec<FirewallRule> = Vec::new();
+    for rule in list.rules.drain(..) {
+        if !rule_exists(&rule.name) {
+            continue;
+        }
+        match delete_rule(&rule.name) {
+            Ok(()) => logs.push(LogEntry::ok(format!(
+                "Firewall rule removed: {}",
+                rule.name
+            ))),
+            Err(e) => {
+                logs.push(LogEntry::warn(format!(
+                    "Could not remove firewall rule {}: {}",
+                    rule.name, e
+                )));
+                kept.push(rule);
+            }
+        }
+    }
+    list.rules = kept;
+    save_rules(&list)?;
+    if list.rules.is_empty() {
+        Ok(())
+    } else {
+        Err(
+            "Some firewall rules could not be removed (run Version Guard as administrator)"
+                .to_string(),
+        )
+    }
+}
+
+/// List the updater firewall rules this app created for the active install
+#[tauri::command]
+pub async fn list_firewall_rules() -> Result<Vec<FirewallRule>, String> {
+    tauri::async_runtime::spawn_blocking(created_rules)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Remove the updater firewall rules this app created for the active install
+#[tauri::command]
+pub async fn remove_firewall_rules() -> Result<Vec<LogEntry>, String> {
+    if policy::load_policy().disallow_unprotect {
+        return Err(policy::POLICY_BLOCKED.to_string());
+    }
+    tauri::async_runtime::spawn_blocking(|| {
+        let mut logs = Vec::new();
+        remove_all(&mut logs)?;
+        Ok(logs)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,358 @@
+//! Fleet mode: unattended protection of every user profile on a machine
+//! Run elevated with `--fleet <config.json>`; writes a per-profile JSON report
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use winreg::enums::*;
+use winreg::RegKey;
+
+use super::cleaner::CacheCategory;
+use super::log_entry::LogEntry;
+use super::notifier::{self, AlertKind};
+use super::paths::{CapCutPaths, InstallLayout, PathDetectionSource};
+use super::{backup, cleaner, deletion_log, environment, exclusions, installs, process, protector};
+
+/// Registry key listing every local user profile
+const PROFILE_LIST_KEY: &str = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\ProfileList";
+
+fn default_true() -> bool {
+    true
+}
+
+/// Deployment configuration supplied by the administrator
+#[derive(Debug, Clone, Deserialize)]
+pub struct FleetConfig {
+    /// Version to keep (e.g., "2.9.0" matches "2.9.0.1457")
+    pub target_version: String,
+    #[serde(default = "default_true")]
+    pub clean_cache: bool,
+    /// Cache categories to purge; empty means the cleaner's defaults
+    #[serde(default)]
+    pub cache_categories: Vec<CacheCategory>,
+    #[serde(default = "default_true")]
+    pub lock_config: bool,
+    #[serde(default = "default_true")]
+    pub create_blockers: bool,
+    /// Back up removed versions before deleting them
+    #[serde(default = "default_true")]
+    pub backup_versions: bool,
+    /// Where to write the report (defaults to next to the config file)
+    #[serde(default)]
+    pub report_path: Option<String>,
+}
+
+/// Outcome for a single user profile
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileStatus {
+    Protected,
+    Skipped,
+    Failed,
+}
+
+/// Per-profile result entry
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileReport {
+    pub profile_path: String,
+    pub status: ProfileStatus,
+    pub kept_version: Option<String>,
+    pub deleted_versions: Vec<String>,
+    pub error: Option<String>,
+    pub logs: Vec<LogEntry>,
+}
+
+/// Full fleet run report
+#[derive(Debug, Clone, Serialize)]
+pub struct FleetReport {
+    pub machine: String,
+    pub started_at: u64,
+    pub target_version: String,
+    pub elevated: bool,
+    pub profiles: Vec<ProfileReport>,
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Enumerate local user profile directories from the registry
+fn list_user_profiles() -> Vec<PathBuf> {
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let profile_list = match hklm.open_subkey(PROFILE_LIST_KEY) {
+        Ok(k) => k,
+        Err(_) => return Vec::new(),
+    };
+
+    profile_list
+        .enum_keys()
+        .filter_map(|k| k.ok())
+        // Real user accounts only (S-1-5-21-*); skips SYSTEM, LocalService, etc.
+        .filter(|sid| sid.starts_with("S-1-5-21-"))
+        .filter_map(|sid| profile_list.open_subkey(&sid).ok())
+        .filter_map(|k| k.get_value::<String, _>("ProfileImagePath").ok())
+        .map(PathBuf::from)
+        .filter(|p| p.exists())
+        .collect()
+}
+
+/// Check if a version folder name matches the requested target version
+fn matches_target(folder_name: &str, target: &str) -> bool {
+    folder_name == target || folder_name.starts_with(&format!("{}.", target))
+}
+
+/// Protect a single user profile
+fn protect_profile(profile: &Path, config: &FleetConfig) -> ProfileReport {
+    let mut report = ProfileReport {
+        profile_path: profile.to_string_lossy().to_string(),
+        status: ProfileStatus::Skipped,
+        kept_version: None,
+        deleted_versions: Vec::new(),
+        error: None,
+        logs: Vec::new(),
+    };
+
+    let capcut_root = profile.join("AppData").join("Local").join("CapCut");
+    let install = CapCutPaths {
+        apps: capcut_root.join("Apps"),
+        root: capcut_root,
+        source: PathDetectionSource::UserProfile(
+            profile
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+        ),
+        variant: "CapCut".to_string(),
+        layout: InstallLayout::Standard,
+        linked_from: None,
+    }
+    .follow_apps_link();
+    if !install.apps.exists() {
+        report
+            .logs
+            .push(LogEntry::info("CapCut not installed for this profile"));
+        return report;
+    }
+    // Per-install state (pin, originals, history) goes to this profile's install, not the admin's
+    let _scope = installs::scope(&install);
+
+    let version_dirs: Vec<PathBuf> = fs::read_dir(&install.apps)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| install.is_version_dir(p))
+        .collect();
+
+    let folder_name = |p: &PathBuf| {
+        p.file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    };
+
+    let matches: Vec<&PathBuf> = version_dirs
+        .iter()
+        .filter(|p| matches_target(&folder_name(p), &config.target_version))
+        .collect();
+    // Never delete anything unless the target names exactly one installed version
+    let keep = match matches.as_slice() {
+        [p] => (*p).clone(),
+        [] => {
+            report.status = ProfileStatus::Failed;
+            report.error = Some(format!(
+                "Target version {} is not installed",
+                config.target_version
+            ));
+            return report;
+        }
+        several => {
+            report.status = ProfileStatus::Failed;
+            report.error = Some(format!(
+                "Target version {} matches several installed versions ({}); use the full folder name",
+                config.target_version,
+                several.iter().map(|p| folder_name(p)).collect::<Vec<_>>().join(", ")
+            ));
+            return report;
+        }
+    };
+    report.kept_version = Some(folder_name(&keep));
+    report
+        .logs
+        .push(LogEntry::ok(format!("Keeping {}", folder_name(&keep))).with_path(&keep));
+
+    let excluded = exclusions::excluded_paths();
+    for dir in version_dirs.iter().filter(|p| **p != keep) {
+        let name = folder_name(dir);
+
+        if exclusions::touches_excluded(dir, &excluded) {
+            report.logs.push(
+                LogEntry::warn(format!("Skipped {} (on the never-touch list)", name))
+                    .with_path(dir),
+            );
+            continue;
+        }
+
+        if config.backup_versions {
+            let backup_result = backup::create_backup(dir, "Version deleted by fleet deployment");
+            if !backup_result.success {
+                report.logs.push(
+                    LogEntry::warn(format!(
+                        "Backup failed for {}: {}",
+                        name,
+                        backup_result.error.unwrap_or_default()
+                    ))
+                    .with_path(dir),
+                );
+            }
+        }
+
+        if let Err(e) = deletion_log::remove_logged(dir, "version", protector::delete_version_dir) {
+            report.status = ProfileStatus::Failed;
+            report.error = Some(format!("Failed to delete {}: {}", name, e));
+            return report;
+        }
+        report
+            .logs
+            .push(LogEntry::ok(format!("Deleted {}", name)).with_path(dir));
+        report.deleted_versions.push(name);
+    }
+
+    if config.clean_cache {
+        let cache_result = cleaner::clean_cache_at(
+            &install.root,
+            &cleaner::resolve_categories(&config.cache_categories),
+        );
+        report.logs.extend(cache_result.logs);
+    }
+
+    if config.lock_config || config.create_blockers {
+        let protect_result = protector::apply_protection_at(
+            &install.root,
+            &install.apps,
+            config.lock_config,
+            config.create_blockers,
+        );
+        report.logs.extend(protect_result.logs);
+        if !protect_result.success {
+            report.status = ProfileStatus::Failed;
+            report.error = protect_result.error;
+            return report;
+        }
+    }
+
+    report.status = ProfileStatus::Protected;
+    report
+}
+
+/// Run a fleet deployment and return the report
+pub fn run_fleet(config: &FleetConfig) -> Result<FleetReport, String> {
+    if process::is_capcut_running() {
+        return Err("CapCut is running on this machine. Close it for all users first.".to_string());
+    }
+
+    let mut report = FleetReport {
+        machine: std::env::var("COMPUTERNAME").unwrap_or_default(),
+        started_at: now_secs(),
+        target_version: config.target_version.clone(),
+        elevated: environment::is_elevated(),
+        profiles: Vec::new(),
+    };
+
+    for profile in list_user_profiles() {
+        report.profiles.push(protect_profile(&profile, config));
+    }
+
+    Ok(report)
+}
+
+/// Entry point for `--fleet <config.json>`; returns the process exit code
+pub fn run_fleet_from_file(config_path: &str) -> i32 {
+    let config: FleetConfig = match fs::read_to_string(config_path)
+        .map_err(|e| e.to_string())
+        .and_then(|c| serde_json::from_str(&c).map_err(|e| e.to_string()))
+    {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Invalid fleet config {}: {}", config_path, e);
+            return 2;
+        }
+    };
+
+    let report = match run_fleet(&config) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Fleet deployment aborted: {}", e);
+            return 1;
+        }
+    };
+
+    if !report.elevated {
+        eprintln!("Warning: not running elevated; other profiles may fail");
+    }
+
+    let report_path = config
+        .report_path
+        .clone()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            Path::new(config_path)
+                .with_file_name(format!("ccguard-fleet-report-{}.json", report.started_at))
+        });
+
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => {
+            if let Err(e) = fs::write(&report_path, json) {
+                eprintln!("Failed to write report {}: {}", report_path.display(), e);
+            } else {
+                println!("Report written to {}", report_path.display());
+            }
+        }
+        Err(e) => eprintln!("Failed to serialize report: {}", e),
+    }
+
+    let failed = report
+        .profiles
+        .iter()
+        .filter(|p| matches!(p.status, ProfileStatus::Failed))
+        .count();
+    println!(
+        "Processed {} profile(s), {} failed",
+        report.profiles.len(),
+        failed
+    );
+
+    if failed > 0 {
+        let details: Vec<String> = report
+            .profiles
+            .iter()
+            .filter(|p| matches!(p.status, ProfileStatus::Failed))
+            .map(|p| {
+                format!(
+                    "{}: {}",
+                    p.profile_path,
+                    p.error.clone().unwrap_or_default()
+                )
+            })
+            .collect();
+        let body = format!(
+            "Fleet deployment could not protect {} profile(s):\n\n{}",
+            failed,
+            details.join("\n")
+        );
+        if let Err(e) = notifier::send_alert(AlertKind::ProtectionBroken, &body) {
+            eprintln!("Email alert failed: {}", e);
+        }
+    }
+
+    if failed > 0 {
+        1
+    } else {
+        0
+    }
+}
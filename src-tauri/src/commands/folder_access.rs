@@ -0,0 +1,131 @@
+//! Controlled Folder Access detection
+//! Tells apart Windows ransomware protection blocking our writes from ordinary permission errors
+
+use serde::Serialize;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use winreg::enums::*;
+use winreg::RegKey;
+
+use super::exclusions;
+
+/// Defender setting (1 = block, 2 = audit only)
+const CFA_KEY: &str = "SOFTWARE\\Microsoft\\Windows Defender\\Windows Defender Exploit Guard\\Controlled Folder Access";
+
+/// Same setting when pushed by Group Policy
+const CFA_POLICY_KEY: &str =
+    "SOFTWARE\\Policies\\Microsoft\\Windows Defender\\Windows Defender Exploit Guard\\Controlled Folder Access";
+
+/// Folders added to the protected list besides the Windows defaults (value names are the paths)
+const PROTECTED_FOLDERS_SUBKEY: &str = "ProtectedFolders";
+
+/// Shell Folders values of the user folders Windows protects by default
+const DEFAULT_PROTECTED_FOLDERS: &[&str] = &[
+    "Personal",
+    "My Pictures",
+    "My Video",
+    "My Music",
+    "Desktop",
+    "Favorites",
+];
+
+/// Same folders under the Public profile
+const PUBLIC_PROTECTED_FOLDERS: &[&str] = &["Documents", "Pictures", "Videos", "Music", "Desktop"];
+
+/// Windows Security page with the Controlled Folder Access settings
+const CFA_SETTINGS_URL: &str = "windowsdefender://ransomwareprotection/";
+
+/// Controlled Folder Access is blocking writes to the CapCut folder
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderAccessBlock {
+    /// Folder we could not write to
+    pub blocked_path: String,
+    /// Executable to add to the allow list
+    pub app_path: String,
+}
+
+/// Whether Controlled Folder Access is set to block (audit mode never blocks)
+pub fn is_enabled() -> bool {
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    [CFA_POLICY_KEY, CFA_KEY]
+        .iter()
+        .filter_map(|key| hklm.open_subkey(key).ok())
+        .filter_map(|k| k.get_value::<u32, _>("EnableControlledFolderAccess").ok())
+        .next()
+        == Some(1)
+}
+
+/// Folders Controlled Folder Access guards: the user's and Public documents, media and desktop folders, plus added ones
+fn protected_folders() -> Vec<PathBuf> {
+    let mut folders: Vec<PathBuf> = Vec::new();
+
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    if let Ok(shell) =
+        hkcu.open_subkey("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Shell Folders")
+    {
+        folders.extend(
+            DEFAULT_PROTECTED_FOLDERS
+                .iter()
+                .filter_map(|name| shell.get_value::<String, _>(name).ok())
+                .map(PathBuf::from),
+        );
+    }
+    if let Some(public) = std::env::var_os("PUBLIC").map(PathBuf::from) {
+        folders.extend(
+            PUBLIC_PROTECTED_FOLDERS
+                .iter()
+                .map(|name| public.join(name)),
+        );
+    }
+
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    for key in [CFA_POLICY_KEY, CFA_KEY] {
+        if let Ok(added) = hklm.open_subkey(format!("{}\\{}", key, PROTECTED_FOLDERS_SUBKEY)) {
+            folders.extend(
+                added
+                    .enum_values()
+                    .filter_map(|v| v.ok())
+                    .map(|(name, _)| PathBuf::from(name)),
+            );
+        }
+    }
+    folders
+}
+
+/// Whether `dir` lies in a folder Controlled Folder Access guards
+pub fn is_protected_folder(dir: &Path) -> bool {
+    protected_folders()
+        .iter()
+        .any(|folder| exclusions::is_within(dir, folder))
+}
+
+/// Try creating and removing a file in `dir`
+pub fn can_write(dir: &Path) -> Result<(), std::io::Error> {
+    let probe = dir.join(".ccvg_write_probe");
+    fs::write(&probe, b"")?;
+    fs::remove_file(&probe)
+}
+
+/// Detect Controlled Folder Access blocking writes to `dir`
+pub fn check_blocked(dir: &Path) -> Option<FolderAccessBlock> {
+    // Outside a protected folder a denied write is a plain permission problem (e.g., our own ACL lock)
+    if !dir.is_dir() || !is_enabled() || !is_protected_folder(dir) {
+        return None;
+    }
+    match can_write(dir) {
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => Some(FolderAccessBlock {
+            blocked_path: dir.to_string_lossy().to_string(),
+            app_path: std::env::current_exe()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        }),
+        _ => None,
+    }
+}
+
+/// Open Windows Security at the Controlled Folder Access settings
+#[tauri::command]
+pub fn open_folder_access_settings() -> Result<(), String> {
+    tauri_plugin_opener::open_url(CFA_SETTINGS_URL, None::<&str>).map_err(|e| e.to_string())
+}
@@ -0,0 +1,76 @@
+//! Locale-aware number and size formatting
+//! Sizes in the largest fitting unit with the user's Windows decimal and grouping separators
+
+use std::sync::OnceLock;
+use windows_sys::Win32::Globalization::{GetLocaleInfoEx, LOCALE_SDECIMAL, LOCALE_STHOUSAND};
+
+const SIZE_UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+
+/// Decimal and grouping separators of the user's locale
+struct Separators {
+    decimal: String,
+    group: String,
+}
+
+/// One locale string of the user's default locale
+fn locale_info(lctype: u32) -> Option<String> {
+    let mut buf = [0u16; 8];
+    // SAFETY: a null locale name means the user default; `buf` is sized as passed
+    let len =
+        unsafe { GetLocaleInfoEx(std::ptr::null(), lctype, buf.as_mut_ptr(), buf.len() as i32) };
+    // `len` counts the terminating NUL
+    (len > 1).then(|| String::from_utf16_lossy(&buf[..len as usize - 1]))
+}
+
+fn separators() -> &'static Separators {
+    static SEPARATORS: OnceLock<Separators> = OnceLock::new();
+    SEPARATORS.get_or_init(|| Separators {
+        decimal: locale_info(LOCALE_SDECIMAL).unwrap_or_else(|| ".".to_string()),
+        // Some locales (e.g., Swiss) group with an apostrophe or no-break space
+        group: locale_info(LOCALE_STHOUSAND).unwrap_or_else(|| ",".to_string()),
+    })
+}
+
+/// Format a number with a fixed number of decimals, grouped by thousands
+pub fn format_number(value: f64, decimals: usize) -> String {
+    let seps = separators();
+    let fixed = format!("{:.*}", decimals, value.abs());
+    let (int_part, frac_part) = match fixed.split_once('.') {
+        Some((i, f)) => (i, Some(f)),
+        None => (fixed.as_str(), None),
+    };
+
+    let mut grouped = String::new();
+    for (i, digit) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            grouped.push_str(&seps.group);
+        }
+        grouped.push(digit);
+    }
+    if let Some(frac) = frac_part {
+        grouped.push_str(&seps.decimal);
+        grouped.push_str(frac);
+    }
+    // "-0.0" reads as a glitch; only show the sign for visible amounts
+    if value < 0.0 && grouped.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        grouped.insert(0, '-');
+    }
+    grouped
+}
+
+/// Format a byte count in the largest fitting unit (e.g., "4.0 GB", not "4096.0 MB")
+pub fn format_size(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < SIZE_UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    let decimals = if unit == 0 { 0 } else { 1 };
+    format!("{} {}", format_number(value, decimals), SIZE_UNITS[unit])
+}
+
+/// Format a size given in megabytes
+pub fn format_size_mb(mb: f64) -> String {
+    format_size((mb.max(0.0) * 1024.0 * 1024.0) as u64)
+}
@@ -0,0 +1,198 @@
+//! GitHub API access
+//! Optional personal access token and rate-limit aware retries for requests to api.github.com
+
+use serde::de::DeserializeOwned;
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::settings;
+
+/// Prevents a console window flashing up for the request
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Token is handed to PowerShell through this variable so it never appears on a command line
+const TOKEN_ENV: &str = "CCVG_GITHUB_TOKEN";
+
+/// Token used when none is set in Settings (e.g., provisioned by an administrator)
+const FALLBACK_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+/// Requests are tried at most this many times
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Rate-limit waits longer than this are reported instead of waited out
+const MAX_WAIT_SECS: u64 = 60;
+
+/// First retry delay after a network or server error (doubles each attempt)
+const BACKOFF_SECS: u64 = 2;
+
+/// Status line written by the request script ahead of the body
+struct ResponseHead {
+    /// HTTP status (0 = no response, e.g., DNS or TLS failure)
+    status: u16,
+    remaining: Option<u64>,
+    /// Unix time the rate-limit window resets
+    reset: Option<u64>,
+    retry_after: Option<u64>,
+}
+
+/// Outcome of one request
+enum Attempt {
+    Done(Vec<u8>),
+    /// Worth retrying after the given delay
+    Retry(Duration, String),
+    Failed(String),
+}
+
+/// Token from Settings, else from the GITHUB_TOKEN environment variable
+fn token() -> Option<String> {
+    let configured = settings::load_settings().github_token.trim().to_string();
+    if !configured.is_empty() {
+        return Some(configured);
+    }
+    std::env::var(FALLBACK_TOKEN_ENV)
+        .ok()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// First line of the script output: "status|remaining|reset|retry-after"
+fn parse_head(line: &str) -> ResponseHead {
+    let mut parts = line.trim().split('|').map(|p| p.trim().parse::<u64>().ok());
+    let mut next = || parts.next().flatten();
+    ResponseHead {
+        status: next().unwrap_or(0) as u16,
+        remaining: next(),
+        reset: next(),
+        retry_after: next(),
+    }
+}
+
+/// Human-readable wait, e.g. "12 min"
+fn describe_wait(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{} s", secs.max(1)),
+        60..=3599 => format!("{} min", secs.div_ceil(60)),
+        _ => format!("{} h {} min", secs / 3600, (secs % 3600) / 60),
+    }
+}
+
+/// Run one GET request through PowerShell
+fn request(url: &str, token: Option<&str>) -> Attempt {
+    let script = format!(
+        "[Console]::OutputEncoding = [Text.Encoding]::UTF8; \
+         $h = @{{ 'User-Agent' = 'CC-Version-Guard'; 'Accept' = 'application/vnd.github+json' }}; \
+         if ($env:{token}) {{ $h['Authorization'] = 'Bearer ' + $env:{token} }}; \
+         try {{ \
+           $r = Invoke-WebRequest -Uri '{url}' -UseBasicParsing -TimeoutSec 20 -Headers $h; \
+           '200|' + $r.Headers['X-RateLimit-Remaining'] + '|' + $r.Headers['X-RateLimit-Reset'] + '|'; \
+           $r.Content \
+         }} catch {{ \
+           $resp = $_.Exception.Response; \
+           if ($resp) {{ \
+             '' + [int]$resp.StatusCode + '|' + $resp.Headers['X-RateLimit-Remaining'] + '|' + \
+               $resp.Headers['X-RateLimit-Reset'] + '|' + $resp.Headers['Retry-After'] \
+           }} else {{ '0|||'; $_.Exception.Message }} \
+         }}",
+        token = TOKEN_ENV,
+        url = url
+    );
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .env_remove(TOKEN_ENV);
+    if let Some(token) = token {
+        command.env(TOKEN_ENV, token);
+    }
+    let output = match command.output() {
+        Ok(o) => o,
+        Err(e) => return Attempt::Failed(format!("Could not run PowerShell: {}", e)),
+    };
+
+    let stdout = output.stdout;
+    let split = stdout
+        .iter()
+        .position(|&b| b == b'\n')
+        .unwrap_or(stdout.len());
+    let head = parse_head(&String::from_utf8_lossy(&stdout[..split]));
+    let body = stdout.get(split + 1..).unwrap_or_default().to_vec();
+
+    match head.status {
+        200 => Attempt::Done(body),
+        0 => {
+            let reason = String::from_utf8_lossy(&body).trim().to_string();
+            Attempt::Retry(
+                Duration::ZERO,
+                format!(
+                    "Could not reach GitHub: {}",
+                    if reason.is_empty() {
+                        "request failed"
+                    } else {
+                        &reason
+                    }
+                ),
+            )
+        }
+        401 => Attempt::Failed(
+            "GitHub rejected the access token; check it in Settings → GitHub Access".to_string(),
+        ),
+        // Primary limit (remaining 0) or secondary limit (Retry-After)
+        403 | 429 if head.remaining == Some(0) || head.retry_after.is_some() => {
+            let wait = head
+                .retry_after
+                .or_else(|| head.reset.map(|r| r.saturating_sub(now_secs())))
+                .unwrap_or(MAX_WAIT_SECS);
+            let message = format!(
+                "GitHub API rate limit reached; try again in {}{}",
+                describe_wait(wait),
+                if token.is_some() {
+                    ""
+                } else {
+                    ". Add a GitHub token in Settings → GitHub Access to raise the limit"
+                }
+            );
+            if wait <= MAX_WAIT_SECS {
+                Attempt::Retry(Duration::from_secs(wait), message)
+            } else {
+                Attempt::Failed(message)
+            }
+        }
+        status if status >= 500 => Attempt::Retry(
+            Duration::ZERO,
+            format!("GitHub is unavailable (HTTP {})", status),
+        ),
+        status => Attempt::Failed(format!("GitHub request failed (HTTP {})", status)),
+    }
+}
+
+/// GET a GitHub API URL and parse the JSON response, retrying rate limits and transient errors
+pub fn get_json<T: DeserializeOwned>(url: &str) -> Result<T, String> {
+    let token = token();
+    let mut backoff = Duration::from_secs(BACKOFF_SECS);
+    let mut attempt = 1;
+    loop {
+        match request(url, token.as_deref()) {
+            Attempt::Done(body) => {
+                return serde_json::from_slice(&body)
+                    .map_err(|e| format!("Unexpected response from GitHub: {}", e))
+            }
+            Attempt::Failed(message) => return Err(message),
+            Attempt::Retry(_, message) if attempt >= MAX_ATTEMPTS => return Err(message),
+            Attempt::Retry(wait, _) => {
+                // Rate limits say how long to wait; other errors back off exponentially
+                thread::sleep(if wait.is_zero() { backoff } else { wait });
+                backoff *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! Golden copy export/import
+//! Packs a known-good version folder into a checksummed zip and installs it on other machines
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::log_entry::LogEntry;
+use super::{config_guard, paths, process, protector};
+
+/// Manifest entry name inside the archive
+const MANIFEST_NAME: &str = "golden.json";
+
+/// Prefix of version files inside the archive
+const FILES_PREFIX: &str = "files/";
+
+const FORMAT_VERSION: u32 = 1;
+
+/// One file of the golden copy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GoldenFile {
+    /// Path relative to the version folder, '/'-separated
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+/// Archive manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GoldenManifest {
+    format_version: u32,
+    /// Version folder name (e.g., "2.9.0.966")
+    version: String,
+    created_at: u64,
+    files: Vec<GoldenFile>,
+}
+
+/// Summary of an exported or imported golden copy
+#[derive(Debug, Clone, Serialize)]
+pub struct GoldenCopyInfo {
+    pub version: String,
+    pub files: usize,
+    pub total_bytes: u64,
+}
+
+/// Golden copy import result
+#[derive(Debug, Clone, Serialize)]
+pub struct GoldenImportResult {
+    pub success: bool,
+    pub error: Option<String>,
+    pub logs: Vec<LogEntry>,
+    pub info: Option<GoldenCopyInfo>,
+}
+
+/// Writer that hashes everything passing through
+struct HashingWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reject manifest paths that could escape the version folder
+fn safe_relative(path: &str) -> Option<PathBuf> {
+    let rel = PathBuf::from(path);
+    rel.components()
+        .all(|c| matches!(c, Component::Normal(_)))
+        .then_some(rel)
+}
+
+fn write_archive(version_dir: &Path, dest: &Path) -> Result<GoldenCopyInfo, String> {
+    let version = version_dir
+        .file_name()
+        .ok_or("Invalid version folder")?
+        .to_string_lossy()
+        .to_string();
+    if !paths::has_main_executable(version_dir) {
+        return Err(format!("{} does not contain CapCut.exe", version));
+    }
+
+    let file = File::create(dest).map_err(|e| format!("Failed to create archive: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let mut files = Vec::new();
+
+    for entry in WalkDir::new(version_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let rel = match entry.path().strip_prefix(version_dir) {
+            Ok(r) => r.to_string_lossy().replace('\\', "/"),
+            Err(_) => continue,
+        };
+        let size = entry.metadata().map_or(0, |m| m.len());
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .large_file(size >= u64::from(u32::MAX));
+        zip.start_file(format!("{}{}", FILES_PREFIX, rel), options)
+            .map_err(|e| e.to_string())?;
+
+        let mut source = File::open(entry.path())
+            .map_err(|e| format!("Failed to read {}: {}", entry.path().display(), e))?;
+        let mut writer = HashingWriter {
+            inner: &mut zip,
+            hasher: Sha256::new(),
+        };
+        io::copy(&mut source, &mut writer)
+            .map_err(|e| format!("Failed to archive {}: {}", rel, e))?;
+        files.push(GoldenFile {
+            path: rel,
+            size,
+            sha256: format!("{:x}", writer.hasher.finalize()),
+        });
+    }
+
+    let manifest = GoldenManifest {
+        format_version: FORMAT_VERSION,
+        version: version.clone(),
+        created_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        files,
+    };
+    zip.start_file(MANIFEST_NAME, SimpleFileOptions::default())
+        .map_err(|e| e.to_string())?;
+    let json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    zip.write_all(&json).map_err(|e| e.to_string())?;
+    zip.finish().map_err(|e| e.to_string())?;
+
+    Ok(GoldenCopyInfo {
+        version,
+        files: manifest.files.len(),
+        total_bytes: manifest.files.iter().map(|f| f.size).sum(),
+    })
+}
+
+/// Extract and verify every manifest file into `target`
+fn extract_verified(
+    archive: &mut ZipArchive<File>,
+    manifest: &GoldenManifest,
+    target: &Path,
+) -> Result<(), String> {
+    for file in &manifest.files {
+        let rel = safe_relative(&file.path)
+            .ok_or_else(|| format!("Unsafe path in manifest: {}", file.path))?;
+        let out_path = target.join(rel);
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+
+        let mut entry = archive
+            .by_name(&format!("{}{}", FILES_PREFIX, file.path))
+            .map_err(|_| format!("Archive is missing {}", file.path))?;
+        let out = File::create(&out_path).map_err(|e| e.to_string())?;
+        let mut writer = HashingWriter {
+            inner: out,
+            hasher: Sha256::new(),
+        };
+        io::copy(&mut entry, &mut writer)
+            .map_err(|e| format!("Failed to extract {}: {}", file.path, e))?;
+        if format!("{:x}", writer.hasher.finalize()) != file.sha256 {
+            return Err(format!("Checksum mismatch for {}", file.path));
+        }
+    }
+    Ok(())
+}
+
+fn install_archive(
+    archive_path: &Path,
+    logs: &mut Vec<LogEntry>,
+) -> Result<GoldenCopyInfo, String> {
+    if process::is_capcut_running() {
+        return Err("Close CapCut before importing a golden copy".to_string());
+    }
+    let apps_path = paths::get_capcut_apps_path().ok_or("CapCut installation not found")?;
+
+    let file = File::open(archive_path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Not a golden copy: {}", e))?;
+    let manifest: GoldenManifest = {
+        let mut entry = archive
+            .by_name(MANIFEST_NAME)
+            .map_err(|_| "Not a golden copy (manifest missing)")?;
+        let mut json = String::new();
+        entry.read_to_string(&mut json).map_err(|e| e.to_string())?;
+        serde_json::from_str(&json).map_err(|e| format!("Invalid manifest: {}", e))?
+    };
+    if manifest.format_version > FORMAT_VERSION {
+        return Err("Golden copy was made by a newer Version Guard".to_string());
+    }
+    let version = safe_relative(&manifest.version)
+        .filter(|p| p.components().count() == 1)
+        .ok_or("Invalid version name in manifest")?;
+
+    let final_dir = apps_path.join(&version);
+    if final_dir.exists() {
+        return Err(format!("Version {} is already installed", manifest.version));
+    }
+    logs.push(LogEntry::step(format!(
+        "Importing CapCut {} ({} files)",
+        manifest.version,
+        manifest.files.len()
+    )));
+
+    // Extract next to the target so a failed check never leaves a half-installed version
+    let staging = apps_path.join(format!(".{}.importing", manifest.version));
+    if staging.exists() {
+        fs::remove_dir_all(&staging).map_err(|e| e.to_string())?;
+    }
+    if let Err(e) = extract_verified(&mut archive, &manifest, &staging) {
+        fs::remove_dir_all(&staging).ok();
+        return Err(e);
+    }
+    logs.push(LogEntry::ok("All checksums verified"));
+    fs::rename(&staging, &final_dir).map_err(|e| format!("Failed to install: {}", e))?;
+    logs.push(LogEntry::ok("Installed").with_path(&final_dir));
+
+    // Same configure.ini pin as a protection run, so CapCut won't update it
+    let config = apps_path.join("configure.ini");
+    match protector::relock_configuration(&apps_path) {
+        Ok(_) => logs.push(LogEntry::ok("configure.ini version pinned").with_path(&config)),
+        Err(e) => logs
+            .push(LogEntry::warn(format!("Could not pin configure.ini: {}", e)).with_path(&config)),
+    }
+    if let Err(e) = config_guard::pin_config(&apps_path) {
+        logs.push(LogEntry::warn(format!("Could not pin configure.ini: {}", e)).with_path(&config));
+    }
+
+    Ok(GoldenCopyInfo {
+        version: manifest.version.clone(),
+        files: manifest.files.len(),
+        total_bytes: manifest.files.iter().map(|f| f.size).sum(),
+    })
+}
+
+/// Export a version folder as a checksummed golden copy archive
+#[tauri::command]
+pub async fn export_golden_copy(path: String, dest: String) -> Result<GoldenCopyInfo, String> {
+    let version_dir = paths::path_from_transport(&path);
+    let dest = PathBuf::from(dest);
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = write_archive(&version_dir, &dest);
+        if result.is_err() {
+            fs::remove_file(&dest).ok();
+        }
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Verify a golden copy and install it into CapCut\Apps with the version pinned
+#[tauri::command]
+pub async fn import_golden_copy(path: String) -> GoldenImportResult {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut logs = Vec::new();
+        match install_archive(Path::new(&path), &mut logs) {
+            Ok(info) => GoldenImportResult {
+                success: true,
+                error: None,
+                logs,
+                info: Some(info),
+            },
+            Err(e) => GoldenImportResult {
+                success: false,
+                error: Some(e),
+                logs,
+                info: None,
+            },
+        }
+    })
+    .await
+    .unwrap_or_else(|e| GoldenImportResult {
+        success: false,
+        error: Some(e.to_string()),
+        logs: vec![],
+        info: None,
+    })
+}
@@ -0,0 +1,137 @@
+//! Kept version hardening
+//! Marks the kept version's executables and manifests read-only, tracked so unprotect can undo it
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::log_entry::LogEntry;
+use super::{installs, paths};
+
+/// File types an updater would patch in place
+const CRITICAL_EXTENSIONS: &[&str] = &["exe", "dll", "manifest"];
+
+/// Files this app made read-only (already read-only files are never listed)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HardenedFiles {
+    files: Vec<String>,
+}
+
+/// Get the hardened file list path
+fn get_hardened_path() -> Option<PathBuf> {
+    installs::get_install_data_dir().map(|p| p.join("hardened_files.json"))
+}
+
+fn load_hardened() -> HardenedFiles {
+    get_hardened_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_hardened(hardened: &HardenedFiles) -> Result<(), String> {
+    let path = get_hardened_path().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(hardened).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save hardened file list: {}", e))
+}
+
+/// Top-level executables and manifests of a version folder
+pub fn critical_files(version_dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(version_dir)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            p.extension().is_some_and(|ext| {
+                CRITICAL_EXTENSIONS
+                    .iter()
+                    .any(|c| ext.eq_ignore_ascii_case(c))
+            })
+        })
+        .collect()
+}
+
+/// Critical files of a version folder that hardening would make read-only
+pub fn files_to_harden(version_dir: &Path) -> Vec<PathBuf> {
+    critical_files(version_dir)
+        .into_iter()
+        .filter(|f| fs::metadata(f).is_ok_and(|m| !m.permissions().readonly()))
+        .collect()
+}
+
+/// Make the kept version's critical binaries read-only
+pub fn harden_version(version_dir: &Path, logs: &mut Vec<LogEntry>) -> Result<(), String> {
+    let mut hardened = load_hardened();
+    let mut count = 0;
+
+    for file in critical_files(version_dir) {
+        let mut perms = match fs::metadata(&file) {
+            Ok(m) => m.permissions(),
+            Err(_) => continue,
+        };
+        if perms.readonly() {
+            continue;
+        }
+        perms.set_readonly(true);
+        if let Err(e) = fs::set_permissions(&file, perms) {
+            logs.push(LogEntry::warn(format!("Could not make read-only: {}", e)).with_path(&file));
+            continue;
+        }
+        let transport = paths::path_to_transport(&file);
+        if !hardened.files.contains(&transport) {
+            hardened.files.push(transport);
+        }
+        count += 1;
+    }
+
+    save_hardened(&hardened)?;
+    logs.push(LogEntry::ok(format!(
+        "{} executable(s) and manifest(s) made read-only",
+        count
+    )));
+    Ok(())
+}
+
+/// Number of files still recorded as hardened
+pub fn hardened_count() -> usize {
+    load_hardened().files.len()
+}
+
+/// Clear read-only from every file this app hardened
+#[allow(clippy::permissions_set_readonly_false)]
+pub fn unharden_all(logs: &mut Vec<LogEntry>) {
+    let hardened = load_hardened();
+    if hardened.files.is_empty() {
+        return;
+    }
+
+    let mut restored = 0;
+    for transport in &hardened.files {
+        let file = paths::path_from_transport(transport);
+        let mut perms = match fs::metadata(&file) {
+            Ok(m) => m.permissions(),
+            // Deleted with its version
+            Err(_) => continue,
+        };
+        perms.set_readonly(false);
+        match fs::set_permissions(&file, perms) {
+            Ok(()) => restored += 1,
+            Err(e) => logs
+                .push(LogEntry::warn(format!("Could not clear read-only: {}", e)).with_path(&file)),
+        }
+    }
+
+    match save_hardened(&HardenedFiles::default()) {
+        Ok(()) => logs.push(LogEntry::ok(format!(
+            "Read-only removed from {} hardened file(s)",
+            restored
+        ))),
+        Err(e) => logs.push(LogEntry::warn(e)),
+    }
+}
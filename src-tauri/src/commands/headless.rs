@@ -0,0 +1,234 @@
+//! Headless command line
+//! `scan`, `protect` and `status` run the wizard's scanner and protector without opening the window
+
+use super::installs::{self, InstallScope};
+use super::protector::{self, ProtectionParams};
+use super::scanner::{self, VersionInfo};
+use super::{format, integrity::Integrity, paths, reprotect};
+
+/// Exit code when no CapCut install was found or the arguments don't match it
+const EXIT_USAGE: i32 = 2;
+
+/// Options of `protect`, as given on the command line
+pub struct ProtectOptions {
+    /// Version folder name or CapCut.exe build to keep
+    pub keep: String,
+    /// Folder to zip the drafts into before deleting anything
+    pub backup_drafts_to: Option<String>,
+    pub clean_cache: bool,
+    pub lock_config: bool,
+    pub create_blockers: bool,
+    pub harden_binaries: bool,
+    pub stub_updaters: bool,
+    pub lock_acls: bool,
+    pub block_domains: bool,
+    pub firewall_rules: bool,
+    pub disable_autoruns: bool,
+}
+
+/// Scope the rest of the command to `--install` (key or folder), or keep the active install
+fn select_install(install: Option<&str>) -> Result<Option<InstallScope>, String> {
+    let given = match install {
+        Some(g) => g,
+        None => return Ok(None),
+    };
+    let wanted = given.trim().trim_end_matches('\\').to_lowercase();
+    installs::detect_installs()
+        .iter()
+        .find(|i| {
+            installs::install_key(i) == wanted
+                || i.root.to_string_lossy().to_lowercase() == wanted
+                || i.apps.to_string_lossy().to_lowercase() == wanted
+        })
+        .map(|i| Some(installs::scope(i)))
+        .ok_or_else(|| format!("No CapCut installation matches {}", given))
+}
+
+fn print_json<T: serde::Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("Failed to serialize output: {}", e),
+    }
+}
+
+fn integrity_label(integrity: &Integrity) -> &'static str {
+    match integrity {
+        Integrity::Complete => "ok",
+        Integrity::MissingBinaries => "missing files",
+        Integrity::HashMismatch => "modified",
+    }
+}
+
+/// Entry point for `scan`; lists installed versions, returns the process exit code
+pub fn run_scan(install: Option<&str>, json: bool) -> i32 {
+    let _scope = match select_install(install) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{}", e);
+            return EXIT_USAGE;
+        }
+    };
+    let apps = match paths::get_capcut_apps_path().filter(|p| p.is_dir()) {
+        Some(p) => p,
+        None => {
+            eprintln!("CapCut installation not found");
+            return EXIT_USAGE;
+        }
+    };
+
+    let versions = scanner::scan_installed_versions();
+    if json {
+        print_json(&versions);
+        return 0;
+    }
+
+    println!("{}", apps.display());
+    if versions.is_empty() {
+        println!("No versions installed");
+        return 0;
+    }
+    let kept = reprotect::kept_version();
+    for v in &versions {
+        println!(
+            "{} {:<16} {:<14} {:>10}  {}",
+            if kept.as_deref() == Some(v.name.as_str()) {
+                "*"
+            } else {
+                " "
+            },
+            v.name,
+            v.build.as_deref().unwrap_or("-"),
+            format::format_size_mb(v.size_mb),
+            integrity_label(&v.integrity)
+        );
+    }
+    0
+}
+
+/// The scanned version `keep` names, by folder name or CapCut.exe build
+fn find_version<'a>(versions: &'a [VersionInfo], keep: &str) -> Option<&'a VersionInfo> {
+    let keep = keep.trim().trim_start_matches(['v', 'V']);
+    versions
+        .iter()
+        .find(|v| v.name == keep)
+        .or_else(|| versions.iter().find(|v| v.build.as_deref() == Some(keep)))
+}
+
+/// Entry point for `protect`; keeps one version and deletes the rest, returns the process exit code
+pub fn run_protect(install: Option<&str>, options: &ProtectOptions) -> i32 {
+    let _scope = match select_install(install) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{}", e);
+            return EXIT_USAGE;
+        }
+    };
+    let versions = scanner::scan_installed_versions();
+    if versions.is_empty() {
+        eprintln!("No CapCut versions found");
+        return EXIT_USAGE;
+    }
+    let kept = match find_version(&versions, &options.keep) {
+        Some(v) => v,
+        None => {
+            let names: Vec<&str> = versions.iter().map(|v| v.name.as_str()).collect();
+            eprintln!(
+                "Version {} is not installed (installed: {})",
+                options.keep,
+                names.join(", ")
+            );
+            return EXIT_USAGE;
+        }
+    };
+
+    let result = protector::run_full_protection(ProtectionParams {
+        versions_to_delete: versions
+            .iter()
+            .filter(|v| v.path != kept.path)
+            .map(|v| v.path.clone())
+            .collect(),
+        keep_version: Some(kept.path.clone()),
+        backup_drafts_to: options.backup_drafts_to.clone(),
+        draft_projects: Vec::new(),
+        clean_cache: options.clean_cache,
+        cache_categories: Vec::new(),
+        lock_config: options.lock_config,
+        create_blockers: options.create_blockers,
+        harden_binaries: options.harden_binaries,
+        stub_updaters: options.stub_updaters,
+        lock_acls: options.lock_acls,
+        block_domains: options.block_domains,
+        firewall_rules: options.firewall_rules,
+        disable_autoruns: options.disable_autoruns,
+    });
+    for entry in &result.logs {
+        println!("{}", entry);
+    }
+    for failed in &result.failed_deletions {
+        eprintln!("Could not delete {}: {}", failed.name, failed.error);
+    }
+
+    if !result.success {
+        eprintln!(
+            "Protection failed: {}",
+            result.error.unwrap_or_else(|| "unknown error".to_string())
+        );
+        return 1;
+    }
+    println!("Version {} is protected", kept.name);
+    i32::from(!result.failed_deletions.is_empty())
+}
+
+/// Status as printed by `status --json`
+#[derive(serde::Serialize)]
+struct StatusReport {
+    install: String,
+    kept_version: Option<String>,
+    is_protected: bool,
+    config_locked: bool,
+    blockers_exist: bool,
+}
+
+/// Entry point for `status`; exits 0 when protected, 1 when not
+pub fn run_status(install: Option<&str>, json: bool) -> i32 {
+    let _scope = match select_install(install) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{}", e);
+            return EXIT_USAGE;
+        }
+    };
+    let root = match paths::get_capcut_root_path() {
+        Some(p) => p,
+        None => {
+            eprintln!("CapCut installation not found");
+            return EXIT_USAGE;
+        }
+    };
+
+    let status = protector::check_protection_status();
+    let report = StatusReport {
+        install: root.to_string_lossy().to_string(),
+        kept_version: reprotect::kept_version(),
+        is_protected: status.is_protected,
+        config_locked: status.config_locked,
+        blockers_exist: status.blockers_exist,
+    };
+    if json {
+        print_json(&report);
+    } else {
+        let yes_no = |b: bool| if b { "yes" } else { "no" };
+        println!("Install:        {}", report.install);
+        println!(
+            "Protected:      {}",
+            if report.is_protected { "yes" } else { "NO" }
+        );
+        println!(
+            "Kept version:   {}",
+            report.kept_version.as_deref().unwrap_or("-")
+        );
+        println!("Config locked:  {}", yes_no(report.config_locked));
+        println!("Update blocked: {}", yes_no(report.blockers_exist));
+    }
+    i32::from(!report.is_protected)
+}
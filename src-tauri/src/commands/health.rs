@@ -0,0 +1,122 @@
+//! Protection health check
+//! Verifies each guard one by one (blockers, configure.ini pin, single version) for the health screen
+
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use super::{blockers, channel, config_guard, paths, protector, reprotect, version};
+
+/// One guard and whether it is still in place
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthCheck {
+    pub name: String,
+    pub intact: bool,
+    /// What is wrong, when broken
+    pub detail: Option<String>,
+}
+
+impl HealthCheck {
+    fn new(name: impl Into<String>, intact: bool, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            intact,
+            detail: (!intact).then(|| detail.into()),
+        }
+    }
+}
+
+/// Result of the health check
+#[derive(Debug, Clone, Serialize)]
+pub struct ProtectionHealth {
+    /// Every check passed
+    pub intact: bool,
+    /// Version kept by the last protection run
+    pub kept_version: Option<String>,
+    pub checks: Vec<HealthCheck>,
+}
+
+/// Check an empty read-only blocker file and say what is wrong with it
+fn blocker_check(name: String, path: &Path) -> HealthCheck {
+    let detail = match fs::metadata(path) {
+        Err(_) => "Missing".to_string(),
+        Ok(m) if !m.is_file() => "A folder is in its place".to_string(),
+        Ok(m) if m.len() > 0 => format!(
+            "Not empty ({} bytes); CapCut may have written to it",
+            m.len()
+        ),
+        Ok(_) => "Not read-only".to_string(),
+    };
+    HealthCheck::new(name, blockers::is_blocker(path), detail)
+}
+
+/// Verify every protection of the active install
+#[tauri::command]
+pub fn check_protection_health() -> Result<ProtectionHealth, String> {
+    let capcut_paths = paths::resolve_capcut_paths().ok_or("Could not find CapCut installation")?;
+    let apps_path = &capcut_paths.apps;
+    let capcut_root = &capcut_paths.root;
+    let profile = channel::guard_profile(channel::detect_channel(capcut_root, apps_path));
+    let kept_version = reprotect::kept_version();
+
+    let mut checks = vec![blocker_check(
+        "ProductInfo.xml blocker".to_string(),
+        &apps_path.join("ProductInfo.xml"),
+    )];
+    let download_dir = capcut_root.join("User Data").join("Download");
+    checks.extend(
+        profile
+            .updater_names
+            .iter()
+            .map(|name| blocker_check(format!("{} blocker", name), &download_dir.join(name))),
+    );
+    checks.extend(
+        blockers::extra_blocker_paths(capcut_root)
+            .iter()
+            .map(|path| blocker_check(format!("Extra blocker {}", path.display()), path)),
+    );
+
+    checks.push(HealthCheck::new(
+        "configure.ini version keys",
+        protector::check_protection_status().config_locked,
+        format!(
+            "Not all of {} are set to 1.0.0.0",
+            profile.version_keys.join(", ")
+        ),
+    ));
+    checks.push(HealthCheck::new(
+        "configure.ini pinned for the watchdog",
+        config_guard::is_pinned(),
+        "No pin recorded; rewrites are not restored automatically",
+    ));
+
+    let mut versions: Vec<String> = fs::read_dir(apps_path)
+        .map_err(|e| format!("Could not read the Apps folder: {}", e))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| capcut_paths.is_version_dir(p))
+        .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .collect();
+    version::sort_names(&mut versions);
+    let single = match (&kept_version, versions.as_slice()) {
+        (Some(kept), [only]) => only == kept,
+        (None, [_]) => true,
+        _ => false,
+    };
+    let detail = match (&kept_version, versions.len()) {
+        (_, 0) => "No version folder found".to_string(),
+        (Some(kept), 1) => format!("{} is installed instead of {}", versions[0], kept),
+        (_, n) => format!("{} version folders: {}", n, versions.join(", ")),
+    };
+    checks.push(HealthCheck::new(
+        "Only one version installed",
+        single,
+        detail,
+    ));
+
+    Ok(ProtectionHealth {
+        intact: checks.iter().all(|c| c.intact),
+        kept_version,
+        checks,
+    })
+}
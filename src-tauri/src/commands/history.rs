@@ -0,0 +1,107 @@
+//! Protection run history
+//! Keeps recent full protection runs with per-step durations to spot pathologically slow steps
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use super::installs;
+use super::snapshot::StateChange;
+
+/// Oldest runs are dropped beyond this count
+const MAX_RUNS: usize = 50;
+
+/// Serializes read-modify-write of the history file across threads
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+/// Duration of one protection step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepTiming {
+    pub step: String,
+    pub millis: u64,
+}
+
+impl StepTiming {
+    /// Time elapsed since a step started
+    pub fn since(step: &str, started: Instant) -> Self {
+        Self {
+            step: step.to_string(),
+            millis: started.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+/// A recorded full protection run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtectionRun {
+    /// Unix seconds
+    pub timestamp: u64,
+    /// Folder name of the kept version
+    pub kept_version: Option<String>,
+    pub success: bool,
+    pub error: Option<String>,
+    pub steps: Vec<StepTiming>,
+    /// What the run changed (empty for runs recorded before snapshots existed)
+    #[serde(default)]
+    pub changes: Vec<StateChange>,
+}
+
+/// Get the history file path
+fn get_history_path() -> Option<PathBuf> {
+    installs::get_install_data_dir().map(|p| p.join("protection_history.json"))
+}
+
+fn load_runs() -> Vec<ProtectionRun> {
+    get_history_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_runs(runs: &[ProtectionRun]) -> Result<(), String> {
+    let path = get_history_path().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(runs).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save history: {}", e))
+}
+
+/// Record a protection run (newest first)
+pub fn record_run(
+    kept_version: Option<String>,
+    success: bool,
+    error: Option<String>,
+    steps: &[StepTiming],
+    changes: Vec<StateChange>,
+) {
+    let _guard = HISTORY_LOCK.lock();
+    let mut runs = load_runs();
+    runs.insert(
+        0,
+        ProtectionRun {
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            kept_version,
+            success,
+            error,
+            steps: steps.to_vec(),
+            changes,
+        },
+    );
+    runs.truncate(MAX_RUNS);
+
+    if let Err(e) = save_runs(&runs) {
+        tracing::warn!("Could not record protection run: {}", e);
+    }
+}
+
+/// List recent protection runs (newest first)
+#[tauri::command]
+pub fn list_protection_runs() -> Vec<ProtectionRun> {
+    load_runs()
+}
@@ -0,0 +1,102 @@
+//! Windows hosts file blocks
+//! Adds and removes our entries between marker lines so each feature's block can be cleanly taken out again
+
+use std::fs;
+use std::os::windows::process::CommandExt;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Hide the console window of helper processes
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Marker lines are "# >>> CC Version Guard <name>" and "# <<< CC Version Guard <name>"
+const MARKER_PREFIX: &str = "CC Version Guard";
+
+/// Get the Windows hosts file path
+pub fn hosts_path() -> PathBuf {
+    let system_root = std::env::var_os("SystemRoot").unwrap_or_else(|| "C:\\Windows".into());
+    PathBuf::from(system_root)
+        .join("System32")
+        .join("drivers")
+        .join("etc")
+        .join("hosts")
+}
+
+fn markers(name: &str) -> (String, String) {
+    (
+        format!("# >>> {} {}", MARKER_PREFIX, name),
+        format!("# <<< {} {}", MARKER_PREFIX, name),
+    )
+}
+
+/// Replace the block `name` in the hosts file, pointing `hosts` at `address` (an empty list removes it)
+pub fn write_block(name: &str, address: &str, hosts: &[String]) -> Result<(), String> {
+    let (begin, end) = markers(name);
+    let path = hosts_path();
+    // The hosts file may be in any ANSI code page; only our ASCII lines are touched
+    // Only a missing file counts as empty; writing after any other read error would wipe it
+    let content = match fs::read(&path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(format!("Failed to read hosts file: {}", e)),
+    };
+    let mut lines: Vec<&[u8]> = Vec::new();
+    let mut in_block = false;
+    for line in content.split(|b| *b == b'\n') {
+        let trimmed = line.trim_ascii();
+        if trimmed == begin.as_bytes() {
+            in_block = true;
+        } else if trimmed == end.as_bytes() {
+            in_block = false;
+        } else if !in_block {
+            lines.push(line.strip_suffix(b"\r").unwrap_or(line));
+        }
+    }
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+
+    let mut out: Vec<u8> = lines.join(&b"\r\n"[..]);
+    if !hosts.is_empty() {
+        out.extend_from_slice(b"\r\n\r\n");
+        out.extend_from_slice(begin.as_bytes());
+        for host in hosts {
+            out.extend_from_slice(format!("\r\n{} {}", address, host).as_bytes());
+        }
+        out.extend_from_slice(b"\r\n");
+        out.extend_from_slice(end.as_bytes());
+    }
+    out.extend_from_slice(b"\r\n");
+
+    fs::write(&path, out).map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => {
+            "Editing the hosts file requires running Version Guard as administrator".to_string()
+        }
+        _ => format!("Failed to update hosts file: {}", e),
+    })?;
+
+    // Cached lookups would keep reaching the real servers
+    let _ = Command::new("ipconfig")
+        .arg("/flushdns")
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
+    Ok(())
+}
+
+/// Whether the hosts file still holds the block `name`
+pub fn block_present(name: &str) -> bool {
+    let (begin, _) = markers(name);
+    fs::read(hosts_path()).is_ok_and(|content| {
+        content
+            .split(|b| *b == b'\n')
+            .any(|line| line.trim_ascii() == begin.as_bytes())
+    })
+}
+
+/// Remove the block `name` from the hosts file, if present
+pub fn remove_block(name: &str) -> Result<(), String> {
+    match block_present(name) {
+        true => write_block(name, "", &[]),
+        false => Ok(()),
+    }
+}
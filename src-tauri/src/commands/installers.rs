@@ -0,0 +1,110 @@
+//! Downloaded CapCut installer check
+//! Finds fresh CapCut installers in the Downloads folder, which would reinstall the latest version
+//! over a protected install, and quarantines them on request
+
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use winreg::enums::*;
+use winreg::RegKey;
+
+use super::paths;
+
+/// Installers older than this are assumed to be known to the user
+const FRESH_DAYS: u64 = 14;
+
+/// Downloads known folder ID in the shell folders key
+const DOWNLOADS_FOLDER_ID: &str = "{374DE290-123F-4565-9164-39C4925E467B}";
+
+/// Appended to quarantined installers so they can't be double-clicked
+const QUARANTINE_SUFFIX: &str = ".quarantined";
+
+/// A CapCut installer found in Downloads
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallerInfo {
+    pub path: String,
+    pub name: String,
+    pub size_bytes: u64,
+    /// Last-modified time (Unix seconds)
+    pub modified: u64,
+}
+
+/// The user's Downloads folder (follows a relocated folder)
+fn downloads_dir() -> Option<PathBuf> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    hkcu.open_subkey("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Shell Folders")
+        .and_then(|k| k.get_value::<String, _>(DOWNLOADS_FOLDER_ID))
+        .ok()
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("USERPROFILE").map(|p| PathBuf::from(p).join("Downloads")))
+        .filter(|p| p.is_dir())
+}
+
+/// CapCut installers in Downloads modified within the last two weeks
+pub fn find_fresh_installers() -> Vec<InstallerInfo> {
+    let dir = match downloads_dir() {
+        Some(d) => d,
+        None => return Vec::new(),
+    };
+    let cutoff = SystemTime::now() - Duration::from_secs(FRESH_DAYS * 24 * 60 * 60);
+
+    let mut found: Vec<InstallerInfo> = fs::read_dir(dir)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let lower = name.to_ascii_lowercase();
+            if !lower.contains("capcut") || !lower.ends_with(".exe") {
+                return None;
+            }
+            let meta = e.metadata().ok().filter(|m| m.is_file())?;
+            let modified = meta.modified().ok().filter(|t| *t >= cutoff)?;
+            Some(InstallerInfo {
+                path: paths::path_to_transport(&e.path()),
+                name,
+                size_bytes: meta.len(),
+                modified: modified
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs()),
+            })
+        })
+        .collect();
+    found.sort_by_key(|i| std::cmp::Reverse(i.modified));
+    found
+}
+
+/// List fresh CapCut installers in Downloads
+#[tauri::command]
+pub fn check_downloaded_installers() -> Vec<InstallerInfo> {
+    find_fresh_installers()
+}
+
+/// Move an installer into the app's quarantine folder, renamed so it can't be run
+#[tauri::command]
+pub fn quarantine_installer(path: String) -> Result<String, String> {
+    let source = paths::path_from_transport(&path);
+    let name = source
+        .file_name()
+        .ok_or("Not an installer file")?
+        .to_string_lossy()
+        .to_string();
+    if !name.to_ascii_lowercase().ends_with(".exe") {
+        return Err("Not an installer file".to_string());
+    }
+
+    let quarantine = paths::get_app_data_dir()
+        .ok_or("Could not determine data directory")?
+        .join("Quarantine");
+    fs::create_dir_all(&quarantine).map_err(|e| e.to_string())?;
+
+    let target = quarantine.join(format!("{}{}", name, QUARANTINE_SUFFIX));
+    if fs::rename(&source, &target).is_err() {
+        // Downloads may be on another drive
+        fs::copy(&source, &target).map_err(|e| format!("Failed to quarantine: {}", e))?;
+        fs::remove_file(&source).map_err(|e| format!("Failed to quarantine: {}", e))?;
+    }
+    Ok(target.to_string_lossy().to_string())
+}
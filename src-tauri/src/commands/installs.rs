@@ -0,0 +1,378 @@
+//! Multiple CapCut installations
+//! Finds every install on the machine and scopes work and per-install state (pin, history, guards) to one of them
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+use super::paths::{self, CapCutPaths, InstallLayout, PathDetectionSource};
+use super::protector::{ProtectionParams, ProtectionResult};
+use super::scanner::{self, VersionInfo};
+use super::{protector, reprotect, settings, version};
+
+/// Per-install state lives in a subfolder of the data directory named by `install_key`
+const INSTALLS_DIR: &str = "Installs";
+
+/// State files kept per install (moved from the data directory root on first use)
+const INSTALL_FILES: &[&str] = &[
+    "protection_profile.json",
+    "protection_history.json",
+    "config_pin.json",
+    "configure.locked.ini",
+    "hardened_files.json",
+    "integrity.json",
+    "observation_timeline.json",
+];
+
+/// A detected install as shown in Settings → Installations
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallSummary {
+    /// Stable identifier (see `install_key`)
+    pub key: String,
+    pub root: String,
+    pub apps: String,
+    pub variant: String,
+    pub layout: InstallLayout,
+    pub source: PathDetectionSource,
+    /// The install every screen works on
+    pub active: bool,
+    /// Version folders with CapCut.exe
+    pub versions: Vec<String>,
+    /// Version kept by the last protection run of this install
+    pub kept_version: Option<String>,
+    pub protected: bool,
+}
+
+/// An install with its versions, for picking one version per install in the wizard
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallVersions {
+    pub key: String,
+    pub root: String,
+    pub variant: String,
+    pub source: PathDetectionSource,
+    pub active: bool,
+    pub versions: Vec<VersionInfo>,
+}
+
+/// One install's part of a protection run over several installs
+#[derive(Deserialize)]
+pub struct InstallProtectionRun {
+    pub key: String,
+    pub params: ProtectionParams,
+}
+
+/// Outcome of protecting one of several installs
+#[derive(Serialize)]
+pub struct InstallProtectionResult {
+    pub key: String,
+    pub root: String,
+    pub variant: String,
+    pub result: ProtectionResult,
+}
+
+/// Leaves an install scope when dropped
+pub struct InstallScope {
+    previous: Option<CapCutPaths>,
+}
+
+impl Drop for InstallScope {
+    fn drop(&mut self) {
+        paths::set_scoped_install(self.previous.take());
+    }
+}
+
+/// Make the calling thread resolve CapCut paths and per-install state to `install`
+pub fn scope(install: &CapCutPaths) -> InstallScope {
+    InstallScope {
+        previous: paths::set_scoped_install(Some(install.clone())),
+    }
+}
+
+/// The install chosen in Settings (or auto-detected), ignoring any scope of this thread
+pub fn active_install() -> Option<CapCutPaths> {
+    let previous = paths::set_scoped_install(None);
+    let active = paths::resolve_capcut_paths();
+    paths::set_scoped_install(previous);
+    active
+}
+
+/// Stable identifier of an install: hash of its lowercased root folder
+pub fn install_key(install: &CapCutPaths) -> String {
+    let root = install.root.to_string_lossy().to_lowercase();
+    Sha256::digest(root.trim_end_matches('\\').as_bytes())
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn same_install(a: &CapCutPaths, b: &CapCutPaths) -> bool {
+    a.apps.to_string_lossy().to_lowercase() == b.apps.to_string_lossy().to_lowercase()
+}
+
+/// Every CapCut install found: saved folders, registry entries, LOCALAPPDATA variants, Program Files
+/// and portable folders
+pub fn detect_installs() -> Vec<CapCutPaths> {
+    let app_settings = settings::load_settings();
+    let mut candidates: Vec<CapCutPaths> = Vec::new();
+
+    candidates.extend(
+        app_settings
+            .capcut_path
+            .iter()
+            .chain(&app_settings.known_installs)
+            .filter_map(|p| paths::parse_custom_path(p)),
+    );
+    candidates.extend(
+        paths::registry_install_roots()
+            .into_iter()
+            .filter_map(paths::registry_install),
+    );
+    if let Some(local) = std::env::var_os("LOCALAPPDATA").map(PathBuf::from) {
+        candidates.extend(paths::known_install_folder_names().iter().map(|name| {
+            let root = local.join(name);
+            CapCutPaths {
+                variant: name.clone(),
+                apps: root.join("Apps"),
+                root,
+                source: PathDetectionSource::DefaultLocation,
+                layout: InstallLayout::Standard,
+                linked_from: None,
+            }
+        }));
+    }
+    candidates.extend(paths::program_files_installs());
+    candidates.extend(paths::portable_installs());
+    candidates.extend(other_profile_installs());
+
+    let mut installs: Vec<CapCutPaths> = Vec::new();
+    for install in candidates.into_iter().map(CapCutPaths::follow_apps_link) {
+        if install.apps.is_dir() && !installs.iter().any(|i| same_install(i, &install)) {
+            installs.push(install);
+        }
+    }
+    installs
+}
+
+/// LOCALAPPDATA installs of the other user profiles (their folders are only readable as administrator)
+fn other_profile_installs() -> Vec<CapCutPaths> {
+    let own = match std::env::var_os("USERPROFILE").map(PathBuf::from) {
+        Some(p) => p,
+        None => return Vec::new(),
+    };
+    let users = match own.parent() {
+        Some(u) => u,
+        None => return Vec::new(),
+    };
+    let names = paths::known_install_folder_names();
+    let mut installs = Vec::new();
+    for profile in fs::read_dir(users)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+    {
+        let profile = profile.path();
+        if profile == own || !profile.is_dir() {
+            continue;
+        }
+        let user = profile
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let local = profile.join("AppData").join("Local");
+        installs.extend(names.iter().map(|name| {
+            let root = local.join(name);
+            CapCutPaths {
+                variant: name.clone(),
+                apps: root.join("Apps"),
+                root,
+                source: PathDetectionSource::UserProfile(user.clone()),
+                layout: InstallLayout::Standard,
+                linked_from: None,
+            }
+        }));
+    }
+    installs
+}
+
+/// Installs the watchdog covers: every install with a protection profile, plus the active one
+pub fn watched_installs() -> Vec<CapCutPaths> {
+    let active = active_install();
+    let mut watched: Vec<CapCutPaths> = active.iter().cloned().collect();
+    for install in detect_installs() {
+        if watched.iter().any(|w| same_install(w, &install)) {
+            continue;
+        }
+        let _scope = scope(&install);
+        if reprotect::kept_version().is_some() {
+            watched.push(install.clone());
+        }
+    }
+    watched
+}
+
+/// Move state files written before installs had their own folders to the install active now
+fn migrate_legacy_state(base: &Path) {
+    if base.join(INSTALLS_DIR).exists() {
+        return;
+    }
+    let legacy: Vec<&str> = INSTALL_FILES
+        .iter()
+        .copied()
+        .filter(|name| base.join(name).exists())
+        .collect();
+    let install = match active_install() {
+        Some(i) if !legacy.is_empty() => i,
+        _ => return,
+    };
+    let target = base.join(INSTALLS_DIR).join(install_key(&install));
+    if let Err(e) = fs::create_dir_all(&target) {
+        tracing::warn!("Could not create install data folder: {}", e);
+        return;
+    }
+    for name in legacy {
+        if let Err(e) = fs::rename(base.join(name), target.join(name)) {
+            tracing::warn!("Could not move {} to install data: {}", name, e);
+        }
+    }
+}
+
+/// Data folder for state of the install the calling thread works on
+///
+/// Falls back to the data directory itself while no install is found.
+pub fn get_install_data_dir() -> Option<PathBuf> {
+    static MIGRATED: Once = Once::new();
+
+    let base = paths::get_app_data_dir()?;
+    MIGRATED.call_once(|| migrate_legacy_state(&base));
+    Some(match paths::resolve_capcut_paths() {
+        Some(install) => base.join(INSTALLS_DIR).join(install_key(&install)),
+        None => base,
+    })
+}
+
+fn summarize(install: &CapCutPaths, active: Option<&CapCutPaths>) -> InstallSummary {
+    let _scope = scope(install);
+    let mut versions: Vec<String> = fs::read_dir(&install.apps)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| paths::has_main_executable(p))
+        .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .collect();
+    version::sort_names(&mut versions);
+
+    InstallSummary {
+        key: install_key(install),
+        root: paths::path_to_transport(&install.root),
+        apps: paths::path_to_transport(&install.apps),
+        variant: install.variant.clone(),
+        layout: install.layout,
+        source: install.source.clone(),
+        active: active.is_some_and(|a| same_install(a, install)),
+        versions,
+        kept_version: reprotect::kept_version(),
+        protected: protector::check_protection_status().is_protected,
+    }
+}
+
+/// List every detected CapCut install with its own protection state
+#[tauri::command]
+pub async fn list_installs() -> Result<Vec<InstallSummary>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let active = active_install();
+        detect_installs()
+            .iter()
+            .map(|install| summarize(install, active.as_ref()))
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Scan the versions of every detected install
+#[tauri::command]
+pub async fn scan_all_installs() -> Result<Vec<InstallVersions>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let active = active_install();
+        detect_installs()
+            .iter()
+            .map(|install| {
+                let _scope = scope(install);
+                InstallVersions {
+                    key: install_key(install),
+                    root: paths::path_to_transport(&install.root),
+                    variant: install.variant.clone(),
+                    source: install.source.clone(),
+                    active: active.as_ref().is_some_and(|a| same_install(a, install)),
+                    versions: scanner::scan_installed_versions(),
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Protect several installs one after another, each with its own kept version
+///
+/// An install that fails or has disappeared gets a failed result; the others still run.
+#[tauri::command]
+pub async fn protect_installs(
+    runs: Vec<InstallProtectionRun>,
+) -> Result<Vec<InstallProtectionResult>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let installs = detect_installs();
+        let mut results = Vec::new();
+        for run in runs {
+            let install = match installs.iter().find(|i| install_key(i) == run.key) {
+                Some(i) => i,
+                None => {
+                    results.push(InstallProtectionResult {
+                        root: run.key.clone(),
+                        key: run.key,
+                        variant: String::new(),
+                        result: ProtectionResult {
+                            success: false,
+                            error: Some(
+                                "This CapCut installation was not found anymore".to_string(),
+                            ),
+                            logs: Vec::new(),
+                            failed_deletions: Vec::new(),
+                            step_timings: Vec::new(),
+                        },
+                    });
+                    continue;
+                }
+            };
+            let _scope = scope(install);
+            results.push(InstallProtectionResult {
+                key: run.key,
+                root: paths::path_to_transport(&install.root),
+                variant: install.variant.clone(),
+                result: protector::run_full_protection(run.params),
+            });
+        }
+        Ok(results)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Make a detected install the one every screen works on
+#[tauri::command]
+pub fn set_active_install(key: String) -> Result<(), String> {
+    let install = detect_installs()
+        .into_iter()
+        .find(|i| install_key(i) == key)
+        .ok_or("That CapCut installation was not found anymore")?;
+
+    let mut app_settings = settings::load_settings();
+    app_settings.capcut_path = Some(install.root.to_string_lossy().to_string());
+    settings::save_user_settings(&app_settings)
+}
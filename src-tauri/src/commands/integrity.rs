@@ -0,0 +1,273 @@
+//! Version folder integrity checks
+//! Reads the exe build number, compares binaries against a recorded baseline hash,
+//! and flags repacked builds (bad signature, injected loader DLLs)
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::c_void;
+use std::fs;
+use std::os::windows::ffi::OsStrExt;
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use windows_sys::Win32::Storage::FileSystem::{
+    GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW, VS_FFI_SIGNATURE,
+    VS_FIXEDFILEINFO,
+};
+
+use super::{installs, paths};
+
+/// CapCut.exe hashes by path with the size and modification time they were taken at
+static HASH_CACHE: Mutex<BTreeMap<PathBuf, (u64, SystemTime, String)>> =
+    Mutex::new(BTreeMap::new());
+
+/// Publisher expected in the Authenticode signer of official builds
+const EXPECTED_SIGNER: &str = "Bytedance";
+
+/// Proxy DLLs that mod loaders drop next to the exe; CapCut doesn't ship these
+const SUSPICIOUS_BINARIES: &[&str] = &[
+    "version.dll",
+    "winmm.dll",
+    "winhttp.dll",
+    "dinput8.dll",
+    "dbghelp.dll",
+];
+
+/// Hide the console window of helper processes
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Integrity state of an installed version
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Integrity {
+    Complete,
+    MissingBinaries,
+    HashMismatch,
+}
+
+/// Get the baseline hash store path
+fn get_baseline_path() -> Option<PathBuf> {
+    installs::get_install_data_dir().map(|p| p.join("integrity.json"))
+}
+
+/// Load recorded baseline hashes (version name -> SHA-256 of CapCut.exe)
+fn load_baselines() -> HashMap<String, String> {
+    get_baseline_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// Save baseline hashes
+fn save_baselines(baselines: &HashMap<String, String>) {
+    let path = match get_baseline_path() {
+        Some(p) => p,
+        None => return,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).ok();
+    }
+    if let Ok(content) = serde_json::to_string_pretty(baselines) {
+        fs::write(path, content).ok();
+    }
+}
+
+/// SHA-256 of a file as lowercase hex
+pub fn hash_file(path: &Path) -> Option<String> {
+    let mut file = fs::File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).ok()?;
+    Some(format!("{:x}", hasher.finalize()))
+}
+
+/// SHA-256 of CapCut.exe, hashed again only when its size or modification time changed
+fn exe_hash(exe: &Path) -> Option<String> {
+    let meta = fs::metadata(exe).ok()?;
+    let stamp = (meta.len(), meta.modified().ok()?);
+    if let Some((size, modified, hash)) = HASH_CACHE.lock().ok()?.get(exe) {
+        if (*size, *modified) == stamp {
+            return Some(hash.clone());
+        }
+    }
+    let hash = hash_file(exe)?;
+    if let Ok(mut cache) = HASH_CACHE.lock() {
+        cache.insert(exe.to_path_buf(), (stamp.0, stamp.1, hash.clone()));
+    }
+    Some(hash)
+}
+
+/// Read the file version (e.g., "2.9.0.1457") from an exe's version resource
+pub fn read_exe_build(exe: &Path) -> Option<String> {
+    let wide: Vec<u16> = exe.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut handle: u32 = 0;
+    // SAFETY: `wide` is a NUL-terminated path that outlives the call
+    let size = unsafe { GetFileVersionInfoSizeW(wide.as_ptr(), &mut handle) };
+    if size == 0 {
+        return None;
+    }
+    let mut data = vec![0u8; size as usize];
+    // SAFETY: `data` is a writable buffer of `size` bytes
+    if unsafe { GetFileVersionInfoW(wide.as_ptr(), 0, size, data.as_mut_ptr().cast()) } == 0 {
+        return None;
+    }
+
+    // The root block (a single backslash) is the VS_FIXEDFILEINFO
+    let root: [u16; 2] = [u16::from(b'\\'), 0];
+    let mut info: *mut c_void = std::ptr::null_mut();
+    let mut len: u32 = 0;
+    // SAFETY: `data` holds the version resource read above; on success `info` points into it
+    let found = unsafe { VerQueryValueW(data.as_ptr().cast(), root.as_ptr(), &mut info, &mut len) };
+    if found == 0 || info.is_null() || (len as usize) < std::mem::size_of::<VS_FIXEDFILEINFO>() {
+        return None;
+    }
+    // SAFETY: non-null and at least one VS_FIXEDFILEINFO long inside `data`; the byte buffer may be unaligned
+    let fixed = unsafe { std::ptr::read_unaligned(info.cast::<VS_FIXEDFILEINFO>()) };
+    if fixed.dwSignature != VS_FFI_SIGNATURE as u32 {
+        return None;
+    }
+
+    let (ms, ls) = (fixed.dwFileVersionMS, fixed.dwFileVersionLS);
+    Some(format!(
+        "{}.{}.{}.{}",
+        ms >> 16,
+        ms & 0xFFFF,
+        ls >> 16,
+        ls & 0xFFFF
+    ))
+}
+
+/// Check a version folder against the required binaries and its baseline hash
+///
+/// The first check of a version records its baseline; later checks compare against it.
+pub fn check_version(version_dir: &Path) -> Integrity {
+    if !paths::has_main_executable(version_dir) {
+        return Integrity::MissingBinaries;
+    }
+
+    let name = version_dir
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let hash = match exe_hash(&paths::main_executable(version_dir)) {
+        Some(h) => h,
+        None => return Integrity::MissingBinaries,
+    };
+
+    let mut baselines = load_baselines();
+    match baselines.get(&name) {
+        Some(expected) if *expected != hash => Integrity::HashMismatch,
+        Some(_) => Integrity::Complete,
+        None => {
+            baselines.insert(name, hash);
+            save_baselines(&baselines);
+            Integrity::Complete
+        }
+    }
+}
+
+/// Authenticode signature state of CapCut.exe or a downloaded installer
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    Valid,
+    Unsigned,
+    Invalid,
+    UnexpectedSigner,
+    /// Signature could not be checked (e.g., PowerShell unavailable)
+    Unknown,
+}
+
+/// Result of checking a version folder for repacking/modding
+#[derive(Debug, Clone, Serialize)]
+pub struct TamperReport {
+    pub modified: bool,
+    pub signature: SignatureStatus,
+    pub signer: Option<String>,
+    /// Loader DLLs found next to CapCut.exe
+    pub suspicious_files: Vec<String>,
+}
+
+/// Check the Authenticode signature of an exe (CapCut.exe or an installer) via PowerShell
+pub fn check_signature(exe: &Path) -> (SignatureStatus, Option<String>) {
+    // The path goes through the environment so non-Unicode names survive intact
+    let script = "$s = Get-AuthenticodeSignature -LiteralPath $env:CCVG_TARGET; \
+                  Write-Output $s.Status.ToString(); \
+                  Write-Output $s.SignerCertificate.Subject";
+    let output = match Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .env("CCVG_TARGET", exe)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+    {
+        Ok(o) if o.status.success() => o,
+        _ => return (SignatureStatus::Unknown, None),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines().map(str::trim);
+    let status = lines.next().unwrap_or_default().to_string();
+    let signer = lines.next().filter(|l| !l.is_empty()).map(str::to_string);
+
+    let signature = match status.as_str() {
+        "Valid" => match &signer {
+            Some(s) if s.contains(EXPECTED_SIGNER) => SignatureStatus::Valid,
+            _ => SignatureStatus::UnexpectedSigner,
+        },
+        "NotSigned" => SignatureStatus::Unsigned,
+        "" => SignatureStatus::Unknown,
+        _ => SignatureStatus::Invalid,
+    };
+    (signature, signer)
+}
+
+/// Check a version folder for signs of a modified or repacked build
+pub fn check_tampering(version_dir: &Path) -> TamperReport {
+    let (signature, signer) = check_signature(&paths::main_executable(version_dir));
+
+    let suspicious_files: Vec<String> = fs::read_dir(version_dir)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| {
+            SUSPICIOUS_BINARIES
+                .iter()
+                .any(|s| name.eq_ignore_ascii_case(s))
+        })
+        .collect();
+
+    let modified = !suspicious_files.is_empty()
+        || matches!(
+            signature,
+            SignatureStatus::Unsigned
+                | SignatureStatus::Invalid
+                | SignatureStatus::UnexpectedSigner
+        );
+
+    TamperReport {
+        modified,
+        signature,
+        signer,
+        suspicious_files,
+    }
+}
+
+/// Check a version folder for a modified/"unlocked" build before protecting it
+#[tauri::command]
+pub async fn check_build_authenticity(path: String) -> TamperReport {
+    tauri::async_runtime::spawn_blocking(move || {
+        check_tampering(&paths::path_from_transport(&path))
+    })
+    .await
+    .unwrap_or(TamperReport {
+        modified: false,
+        signature: SignatureStatus::Unknown,
+        signer: None,
+        suspicious_files: Vec::new(),
+    })
+}
@@ -0,0 +1,72 @@
+//! Background I/O priority for deletions
+//! Lets version deletion and cache cleaning yield the disk to foreground apps (Settings → Low Priority Deletion)
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::cell::Cell;
+use std::sync::OnceLock;
+use windows_sys::Win32::System::Threading::{
+    GetCurrentThread, SetThreadPriority, THREAD_MODE_BACKGROUND_BEGIN, THREAD_MODE_BACKGROUND_END,
+};
+
+use super::settings;
+
+/// Worker threads for parallel work started in background mode (also paces it)
+const BACKGROUND_THREADS: usize = 2;
+
+thread_local! {
+    /// Whether this thread is currently in background mode
+    static BACKGROUND: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Leaves background mode when dropped
+pub struct BackgroundGuard {
+    active: bool,
+}
+
+impl Drop for BackgroundGuard {
+    fn drop(&mut self) {
+        if self.active {
+            // SAFETY: the pseudo-handle always refers to the calling thread
+            unsafe { SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_END) };
+            BACKGROUND.set(false);
+        }
+    }
+}
+
+/// Put the calling thread in background mode (low I/O and CPU priority) if the setting is on
+pub fn enter() -> BackgroundGuard {
+    if BACKGROUND.get() || !settings::load_settings().low_priority_io {
+        return BackgroundGuard { active: false };
+    }
+    // SAFETY: the pseudo-handle always refers to the calling thread
+    let active =
+        unsafe { SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN) } != 0;
+    if active {
+        BACKGROUND.set(true);
+    }
+    BackgroundGuard { active }
+}
+
+fn background_pool() -> Option<&'static ThreadPool> {
+    static POOL: OnceLock<Option<ThreadPool>> = OnceLock::new();
+    POOL.get_or_init(|| {
+        ThreadPoolBuilder::new()
+            .num_threads(BACKGROUND_THREADS)
+            .thread_name(|i| format!("background-io-{}", i))
+            // SAFETY: the pseudo-handle always refers to the calling thread
+            .start_handler(|_| unsafe {
+                SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN);
+            })
+            .build()
+            .ok()
+    })
+    .as_ref()
+}
+
+/// Run rayon work on low-priority threads when the caller is in background mode
+pub fn install<R: Send>(op: impl FnOnce() -> R + Send) -> R {
+    match background_pool().filter(|_| BACKGROUND.get()) {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
@@ -0,0 +1,277 @@
+//! Uninstall leftover cleanup
+//! Finds data an uninstalled CapCut or JianYing left behind (AppData, Temp, registry keys, Start Menu shortcuts, prefetch) and removes the items picked on the review screen
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use winreg::enums::*;
+use winreg::RegKey;
+
+use super::application::{self, Application};
+use super::log_entry::LogEntry;
+use super::{attributes, format, installs, paths, process, scanner};
+
+/// Registry items are listed under this prefix
+const HKCU_PREFIX: &str = "HKCU\\";
+
+/// Start Menu folders relative to APPDATA and ProgramData
+const START_MENU: &str = r"Microsoft\Windows\Start Menu\Programs";
+
+/// Where a leftover lives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeftoverKind {
+    AppData,
+    Temp,
+    Registry,
+    Shortcut,
+    Prefetch,
+}
+
+/// Something an uninstall left behind
+#[derive(Debug, Clone, Serialize)]
+pub struct LeftoverItem {
+    /// Path, or `HKCU\<key>` for registry keys
+    pub id: String,
+    pub kind: LeftoverKind,
+    /// Application it belongs to, as shown to the user
+    pub application: String,
+    pub bytes: u64,
+    /// Holds CapCut projects; left unticked on the review screen
+    pub contains_drafts: bool,
+}
+
+/// Applications with at least one version folder holding their executable
+fn installed_applications() -> Vec<Application> {
+    installs::detect_installs()
+        .iter()
+        .filter(|install| {
+            fs::read_dir(&install.apps)
+                .into_iter()
+                .flatten()
+                .filter_map(|e| e.ok())
+                .any(|e| paths::has_main_executable(&e.path()))
+        })
+        .map(application::of_install)
+        .collect()
+}
+
+/// Lowercase name fragments that mark a file or folder as the application's
+fn name_hints(app: Application) -> Vec<String> {
+    let profile = app.profile();
+    let exe_stem = profile.main_executable.trim_end_matches(".exe");
+    profile
+        .install_folders
+        .iter()
+        .copied()
+        .chain(std::iter::once(exe_stem))
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn matches_hint(name: &str, hints: &[String]) -> bool {
+    let lower = name.to_lowercase();
+    hints.iter().any(|h| lower.contains(h.as_str()))
+}
+
+fn env_dir(var: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|p| p.is_dir())
+}
+
+fn path_item(path: &Path, kind: LeftoverKind, app: Application) -> LeftoverItem {
+    let (bytes, contains_drafts) = if path.is_dir() {
+        scanner::folder_summary(path)
+    } else {
+        (fs::metadata(path).map_or(0, |m| m.len()), false)
+    };
+    LeftoverItem {
+        id: paths::path_to_transport(path),
+        kind,
+        application: app.profile().display_name.to_string(),
+        bytes,
+        contains_drafts,
+    }
+}
+
+/// Install folders under LOCALAPPDATA and same-named folders under APPDATA
+fn app_data_items(app: Application) -> Vec<LeftoverItem> {
+    let own = paths::get_app_data_dir();
+    ["LOCALAPPDATA", "APPDATA"]
+        .iter()
+        .filter_map(|var| env_dir(var))
+        .flat_map(|base| {
+            app.profile()
+                .install_folders
+                .iter()
+                .map(move |name| base.join(name))
+        })
+        .filter(|p| p.is_dir() && own.as_deref() != Some(p.as_path()))
+        .map(|p| path_item(&p, LeftoverKind::AppData, app))
+        .collect()
+}
+
+/// Files and folders in Temp named after the application
+fn temp_items(app: Application) -> Vec<LeftoverItem> {
+    let hints = name_hints(app);
+    fs::read_dir(std::env::temp_dir())
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| matches_hint(&e.file_name().to_string_lossy(), &hints))
+        .map(|e| path_item(&e.path(), LeftoverKind::Temp, app))
+        .collect()
+}
+
+/// HKCU keys of the application that still exist
+fn registry_items(app: Application) -> Vec<LeftoverItem> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    app.profile()
+        .registry_keys
+        .iter()
+        .filter(|key| hkcu.open_subkey(key).is_ok())
+        .map(|key| LeftoverItem {
+            id: format!("{}{}", HKCU_PREFIX, key),
+            kind: LeftoverKind::Registry,
+            application: app.profile().display_name.to_string(),
+            bytes: 0,
+            contains_drafts: false,
+        })
+        .collect()
+}
+
+/// Start Menu shortcuts and folders named after the application, for the user and all users
+fn shortcut_items(app: Application) -> Vec<LeftoverItem> {
+    let hints = name_hints(app);
+    let mut items = Vec::new();
+    for base in ["APPDATA", "ProgramData"]
+        .iter()
+        .filter_map(|var| env_dir(var))
+    {
+        let mut walk = WalkDir::new(base.join(START_MENU))
+            .min_depth(1)
+            .max_depth(2)
+            .into_iter();
+        while let Some(entry) = walk.next() {
+            let entry = match entry {
+                Ok(e) => e,
+                Err(_) => continue,
+            };
+            let name = entry.file_name().to_string_lossy();
+            if !matches_hint(&name, &hints) {
+                continue;
+            }
+            if entry.file_type().is_dir() {
+                // The whole folder is listed; its shortcuts go with it
+                walk.skip_current_dir();
+            } else if !name.to_lowercase().ends_with(".lnk") {
+                continue;
+            }
+            items.push(path_item(entry.path(), LeftoverKind::Shortcut, app));
+        }
+    }
+    items
+}
+
+/// Prefetch files of the main executable (`CAPCUT.EXE-<hash>.pf`); listing them needs administrator rights
+fn prefetch_items(app: Application) -> Vec<LeftoverItem> {
+    let prefix = format!("{}-", app.profile().main_executable.to_uppercase());
+    let dir = match env_dir("SystemRoot") {
+        Some(d) => d.join("Prefetch"),
+        None => return Vec::new(),
+    };
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().to_uppercase();
+            name.starts_with(&prefix) && name.ends_with(".PF")
+        })
+        .map(|e| path_item(&e.path(), LeftoverKind::Prefetch, app))
+        .collect()
+}
+
+/// Leftovers of every supported application that is no longer installed
+pub fn find_leftovers() -> Vec<LeftoverItem> {
+    let installed = installed_applications();
+    application::ALL
+        .iter()
+        .copied()
+        .filter(|app| !installed.contains(app))
+        .flat_map(|app| {
+            let mut items = app_data_items(app);
+            items.extend(temp_items(app));
+            items.extend(registry_items(app));
+            items.extend(shortcut_items(app));
+            items.extend(prefetch_items(app));
+            items
+        })
+        .collect()
+}
+
+fn remove_item(item: &LeftoverItem) -> Result<(), String> {
+    if item.kind == LeftoverKind::Registry {
+        let key = item.id.trim_start_matches(HKCU_PREFIX);
+        return RegKey::predef(HKEY_CURRENT_USER)
+            .delete_subkey_all(key)
+            .map_err(|e| e.to_string());
+    }
+    let path = paths::path_from_transport(&item.id);
+    attributes::clear_readonly_recursive(&path)?;
+    if path.is_dir() {
+        fs::remove_dir_all(&path).map_err(|e| e.to_string())
+    } else {
+        fs::remove_file(&path).map_err(|e| e.to_string())
+    }
+}
+
+/// Remove the chosen leftovers; only ids found by a fresh scan are touched
+pub fn remove_leftovers(ids: &[String]) -> Vec<LogEntry> {
+    let mut logs = Vec::new();
+    let mut removed = 0;
+    let mut freed = 0;
+    for item in find_leftovers()
+        .iter()
+        .filter(|item| ids.contains(&item.id))
+    {
+        match remove_item(item) {
+            Ok(()) => {
+                removed += 1;
+                freed += item.bytes;
+                logs.push(LogEntry::ok(format!("Removed {}", item.id)));
+            }
+            Err(e) => logs.push(LogEntry::warn(format!(
+                "Could not remove {}: {}",
+                item.id, e
+            ))),
+        }
+    }
+    logs.push(LogEntry::ok(format!(
+        "{} leftover(s) removed, {} freed",
+        removed,
+        format::format_size(freed)
+    )));
+    logs
+}
+
+/// Find leftovers of uninstalled CapCut and JianYing installs
+#[tauri::command]
+pub async fn scan_leftovers() -> Result<Vec<LeftoverItem>, String> {
+    tauri::async_runtime::spawn_blocking(find_leftovers)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Remove the leftovers picked on the review screen
+#[tauri::command]
+pub async fn clean_leftovers(ids: Vec<String>) -> Result<Vec<LogEntry>, String> {
+    if process::is_capcut_running() {
+        return Err("CapCut is still running. Please close it.".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || remove_leftovers(&ids))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,157 @@
+//! Local broken-link reports for archive entries
+//! Records failed downloads on this machine only and exports them for a GitHub issue
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::os::windows::process::CommandExt;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+
+use super::paths;
+
+/// Prevents a console window flashing up for the probe
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Serializes read-modify-write of the reports file
+static REPORTS_LOCK: Mutex<()> = Mutex::new(());
+
+/// A download link the user flagged as broken
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkReport {
+    /// Archive label (e.g., "5.3.0 (Latest)")
+    pub label: String,
+    pub url: String,
+    /// Unix seconds
+    pub reported_at: u64,
+    /// HTTP status seen when re-checking the link, if the server answered
+    pub http_status: Option<u16>,
+    /// Connection error when no status was returned
+    pub error: Option<String>,
+}
+
+/// Get the link reports file path
+fn get_reports_path() -> Option<PathBuf> {
+    paths::get_app_data_dir().map(|p| p.join("link_reports.json"))
+}
+
+fn load_reports() -> Vec<LinkReport> {
+    get_reports_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_reports(reports: &[LinkReport]) -> Result<(), String> {
+    let path = get_reports_path().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(reports).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save link reports: {}", e))
+}
+
+/// Send a HEAD request and return the HTTP status, or the connection error
+fn probe_url(url: &str) -> (Option<u16>, Option<String>) {
+    let script = format!(
+        "try {{ \
+           (Invoke-WebRequest -Uri '{}' -Method Head -UseBasicParsing -MaximumRedirection 5 -TimeoutSec 20).StatusCode \
+         }} catch {{ \
+           if ($_.Exception.Response) {{ [int]$_.Exception.Response.StatusCode }} \
+           else {{ Write-Output ('ERR ' + $_.Exception.Message) }} \
+         }}",
+        url.replace('\'', "''")
+    );
+    let output = match Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+    {
+        Ok(o) => o,
+        Err(e) => return (None, Some(format!("Could not run PowerShell: {}", e))),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout.lines().map(str::trim).find(|l| !l.is_empty());
+    match line {
+        Some(l) => match l.parse::<u16>() {
+            Ok(status) => (Some(status), None),
+            Err(_) => (None, Some(l.trim_start_matches("ERR ").to_string())),
+        },
+        None => (None, Some("No response".to_string())),
+    }
+}
+
+/// Render reports as Markdown for pasting into a GitHub issue
+fn to_markdown(reports: &[LinkReport], app_version: &str) -> String {
+    let mut out = format!(
+        "### Broken archive links\n\nReported from CC Version Guard v{}.\n\n\
+         | Version | Status | Reported (UTC, Unix) | URL |\n|---|---|---|---|\n",
+        app_version
+    );
+    for r in reports {
+        let status = match (&r.http_status, &r.error) {
+            (Some(code), _) => format!("HTTP {}", code),
+            (None, Some(err)) => err.replace('|', "/"),
+            (None, None) => "unknown".to_string(),
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            r.label, status, r.reported_at, r.url
+        ));
+    }
+    out
+}
+
+/// Flag an archive link as broken; the link is re-checked to capture its HTTP status
+#[tauri::command]
+pub async fn report_broken_link(label: String, url: String) -> Result<LinkReport, String> {
+    let probe_target = url.clone();
+    let (http_status, error) =
+        tauri::async_runtime::spawn_blocking(move || probe_url(&probe_target))
+            .await
+            .map_err(|e| e.to_string())?;
+
+    let report = LinkReport {
+        label,
+        url,
+        reported_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        http_status,
+        error,
+    };
+
+    let _guard = REPORTS_LOCK.lock();
+    let mut reports = load_reports();
+    // Keep only the latest report per link
+    reports.retain(|r| r.url != report.url);
+    reports.insert(0, report.clone());
+    save_reports(&reports)?;
+    Ok(report)
+}
+
+/// List locally recorded broken-link reports (newest first)
+#[tauri::command]
+pub fn list_link_reports() -> Vec<LinkReport> {
+    load_reports()
+}
+
+/// Delete all broken-link reports
+#[tauri::command]
+pub fn clear_link_reports() -> Result<(), String> {
+    let _guard = REPORTS_LOCK.lock();
+    save_reports(&[])
+}
+
+/// Export broken-link reports as Markdown
+#[tauri::command]
+pub fn export_link_reports(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    let reports = load_reports();
+    if reports.is_empty() {
+        return Err("No broken links have been reported".to_string());
+    }
+    let content = to_markdown(&reports, &app.package_info().version.to_string());
+    fs::write(&path, content).map_err(|e| format!("Failed to write report: {}", e))
+}
@@ -0,0 +1,94 @@
+//! Structured log entries
+//! Typed log lines for protection runs so frontends render and filter by level instead of text prefixes
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// Severity of a log entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Info,
+    Ok,
+    Warn,
+    Step,
+}
+
+impl LogLevel {
+    /// Lowercase name, matching the serialized form
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Info => "info",
+            LogLevel::Ok => "ok",
+            LogLevel::Warn => "warn",
+            LogLevel::Step => "step",
+        }
+    }
+}
+
+/// One line of a protection run log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub level: LogLevel,
+    /// Protection step that produced the entry (e.g., "Version cleanup")
+    pub step: Option<String>,
+    pub message: String,
+    /// File or folder the entry is about
+    pub path: Option<String>,
+}
+
+impl LogEntry {
+    fn new(level: LogLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            step: None,
+            message: message.into(),
+            path: None,
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(LogLevel::Info, message)
+    }
+
+    pub fn ok(message: impl Into<String>) -> Self {
+        Self::new(LogLevel::Ok, message)
+    }
+
+    pub fn warn(message: impl Into<String>) -> Self {
+        Self::new(LogLevel::Warn, message)
+    }
+
+    pub fn step(message: impl Into<String>) -> Self {
+        Self::new(LogLevel::Step, message)
+    }
+
+    /// Attach the file or folder the entry is about
+    pub fn with_path(mut self, path: &Path) -> Self {
+        self.path = Some(path.to_string_lossy().to_string());
+        self
+    }
+}
+
+/// Plain-text form used in emails and text logs
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self.level {
+            LogLevel::Info => "",
+            LogLevel::Ok => "[OK] ",
+            LogLevel::Warn => "[!] ",
+            LogLevel::Step => ">> ",
+        };
+        write!(f, "{}{}", prefix, self.message)
+    }
+}
+
+/// Label every entry from `start` on with the step that produced it
+pub fn tag_step(logs: &mut [LogEntry], start: usize, step: &str) {
+    for entry in logs.iter_mut().skip(start) {
+        if entry.step.is_none() {
+            entry.step = Some(step.to_string());
+        }
+    }
+}
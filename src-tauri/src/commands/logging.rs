@@ -0,0 +1,70 @@
+//! Log file
+//! Timestamped, leveled entries written through `tracing` to a daily rotating file in the app data folder
+
+use std::path::PathBuf;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt;
+use tracing_subscriber::prelude::*;
+
+use super::log_entry::{LogEntry, LogLevel};
+use super::paths;
+
+/// Log files are named `version-guard.<date>.log`
+const LOG_PREFIX: &str = "version-guard";
+
+/// Daily files kept before the oldest is deleted
+const MAX_LOG_FILES: usize = 14;
+
+/// Folder holding the log files
+pub fn get_log_dir() -> Option<PathBuf> {
+    paths::get_app_data_dir().map(|p| p.join("Logs"))
+}
+
+/// Install the global subscriber: everything from info up goes to the log file, warnings also to stderr
+///
+/// Without a usable log folder only the stderr output is set up.
+pub fn init() {
+    let file = get_log_dir().and_then(|dir| {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(LOG_PREFIX)
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(dir)
+            .ok()
+    });
+    let file_layer = file.map(|appender| {
+        fmt::layer()
+            .with_writer(appender)
+            .with_ansi(false)
+            .with_filter(LevelFilter::INFO)
+    });
+    let stderr_layer = fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_filter(LevelFilter::WARN);
+    let _ = tracing_subscriber::registry()
+        .with(file_layer)
+        .with(stderr_layer)
+        .try_init();
+}
+
+/// Write the entries of a run (protection, revert, uninstall) to the log file
+pub fn record(run: &str, logs: &[LogEntry]) {
+    for entry in logs {
+        let step = entry.step.as_deref().unwrap_or("");
+        let path = entry.path.as_deref().unwrap_or("");
+        match entry.level {
+            LogLevel::Warn => tracing::warn!(run, step, path, "{}", entry.message),
+            level => tracing::info!(run, step, path, kind = level.as_str(), "{}", entry.message),
+        }
+    }
+}
+
+/// Open the log folder in Explorer
+#[tauri::command]
+pub fn open_log_folder() -> Result<(), String> {
+    let dir = get_log_dir().ok_or("Could not determine data directory")?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    tauri_plugin_opener::open_path(&dir, None::<&str>).map_err(|e| e.to_string())
+}
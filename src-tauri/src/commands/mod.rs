@@ -1,8 +1,79 @@
+pub mod acl_lock;
+pub mod active_version;
+pub mod application;
+pub mod apps_guard;
+pub mod apps_watch;
+pub mod archive_install;
+pub mod asset_migration;
+pub mod attributes;
+pub mod autoruns;
 pub mod autostart;
 pub mod backup;
+pub mod blockers;
+pub mod channel;
 pub mod cleaner;
+pub mod compare;
+pub mod compression;
+pub mod config_backup;
+pub mod config_diff;
+pub mod config_guard;
+pub mod deep_scan;
+pub mod deletion_log;
+pub mod diagnostics;
+pub mod doctor;
+pub mod domain_block;
+pub mod download_purge;
+pub mod downloader;
+pub mod draft_compat;
+pub mod drafts;
+pub mod environment;
+pub mod events;
+pub mod exclusions;
+pub mod favorites;
+pub mod firewall;
+pub mod fleet;
+pub mod folder_access;
+pub mod format;
+pub mod github;
+pub mod golden;
+pub mod hardening;
+pub mod headless;
+pub mod health;
+pub mod history;
+pub mod hosts;
+pub mod installers;
+pub mod installs;
+pub mod integrity;
+pub mod io_priority;
+pub mod leftovers;
+pub mod link_reports;
+pub mod log_entry;
+pub mod logging;
+pub mod monitor;
+pub mod notifier;
+pub mod observer;
 pub mod paths;
+pub mod policy;
+pub mod power;
+pub mod preview;
 pub mod process;
 pub mod protector;
+pub mod registry_guard;
+pub mod report;
+pub mod reprotect;
+pub mod revert;
 pub mod scanner;
+pub mod scheduler;
+pub mod self_update;
+pub mod settings;
+pub mod shadow_recovery;
+pub mod slimming;
+pub mod snapshot;
+pub mod stats;
 pub mod switcher;
+pub mod tray;
+pub mod uninstall;
+pub mod updater_stub;
+pub mod version;
+pub mod watchdog;
+pub mod wizard;
@@ -0,0 +1,65 @@
+//! CapCut resource usage monitor
+//! Live CPU/RAM of CapCut and its helper processes, to confirm updaters stay dead after protection
+
+use serde::Serialize;
+use std::sync::Mutex;
+use sysinfo::System;
+use tauri::State;
+
+use super::paths;
+
+/// A running CapCut-related process
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessUsage {
+    pub pid: u32,
+    pub name: String,
+    /// Share of total CPU across all cores (0-100)
+    pub cpu_percent: f32,
+    pub memory_mb: f64,
+}
+
+/// Snapshot of every CapCut-related process
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceUsage {
+    pub processes: Vec<ProcessUsage>,
+    pub total_cpu_percent: f32,
+    pub total_memory_mb: f64,
+}
+
+/// Managed process table; CPU usage is measured between successive refreshes
+#[derive(Default)]
+pub struct MonitorState {
+    system: Mutex<System>,
+}
+
+/// Sample CPU and memory of processes named like CapCut or running from its folder
+#[tauri::command]
+pub fn capcut_resource_usage(state: State<'_, MonitorState>) -> Result<ResourceUsage, String> {
+    let mut system = state.system.lock().map_err(|e| e.to_string())?;
+    system.refresh_processes();
+
+    let root = paths::get_capcut_root_path();
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get()) as f32;
+
+    let mut processes: Vec<ProcessUsage> = system
+        .processes()
+        .iter()
+        .filter(|(_, p)| {
+            p.name().to_ascii_lowercase().contains("capcut")
+                || matches!((p.exe(), &root), (Some(exe), Some(root)) if exe.starts_with(root))
+        })
+        .map(|(pid, p)| ProcessUsage {
+            pid: pid.as_u32(),
+            name: p.name().to_string(),
+            cpu_percent: p.cpu_usage() / cores,
+            memory_mb: p.memory() as f64 / (1024.0 * 1024.0),
+        })
+        .collect();
+    processes.sort_by(|a, b| b.memory_mb.total_cmp(&a.memory_mb));
+
+    Ok(ResourceUsage {
+        total_cpu_percent: processes.iter().map(|p| p.cpu_percent).sum(),
+        total_memory_mb: processes.iter().map(|p| p.memory_mb).sum(),
+        processes,
+    })
+}
@@ -0,0 +1,111 @@
+//! Guard alerts: notification center entries and admin email over SMTP
+//! Used by background protection monitoring to report blocked updates and broken guards
+
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use serde::{Deserialize, Serialize};
+
+use super::events;
+use super::settings::{self, EmailSettings};
+
+/// Kind of event an alert is sent for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    UpdateBlocked,
+    ProtectionBroken,
+    FavoriteChanged,
+}
+
+/// Build an SMTP transport from settings
+fn build_transport(email: &EmailSettings) -> Result<SmtpTransport, String> {
+    if email.smtp_host.trim().is_empty() {
+        return Err("SMTP host is not configured".to_string());
+    }
+
+    let builder = if email.starttls {
+        SmtpTransport::starttls_relay(&email.smtp_host)
+    } else {
+        SmtpTransport::relay(&email.smtp_host)
+    }
+    .map_err(|e| format!("Invalid SMTP host: {}", e))?
+    .port(email.smtp_port);
+
+    let builder = if email.username.is_empty() {
+        builder
+    } else {
+        builder.credentials(Credentials::new(
+            email.username.clone(),
+            email.password.clone(),
+        ))
+    };
+
+    Ok(builder.build())
+}
+
+/// Send an email with the configured SMTP settings
+fn send_email(email: &EmailSettings, subject: &str, body: &str) -> Result<(), String> {
+    let machine = std::env::var("COMPUTERNAME").unwrap_or_else(|_| "unknown".to_string());
+
+    let message = Message::builder()
+        .from(
+            email
+                .from
+                .parse()
+                .map_err(|e| format!("Invalid sender address: {}", e))?,
+        )
+        .to(email
+            .to
+            .parse()
+            .map_err(|e| format!("Invalid recipient address: {}", e))?)
+        .subject(format!("[CC Version Guard] {} ({})", subject, machine))
+        .header(ContentType::TEXT_PLAIN)
+        .body(format!("Machine: {}\n\n{}", machine, body))
+        .map_err(|e| format!("Failed to build email: {}", e))?;
+
+    build_transport(email)?
+        .send(&message)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to send email: {}", e))
+}
+
+/// Record a guard event and email the admin, honoring notification preferences
+pub fn send_alert(kind: AlertKind, details: &str) -> Result<(), String> {
+    events::record_event(kind, details);
+
+    let settings = settings::load_settings();
+    let prefs = &settings.notifications;
+
+    let wanted = prefs.enabled
+        && match kind {
+            AlertKind::UpdateBlocked => prefs.on_update_blocked,
+            AlertKind::ProtectionBroken => prefs.on_protection_broken,
+            AlertKind::FavoriteChanged => prefs.on_favorite_changed,
+        };
+    if !settings.email.enabled || !wanted {
+        return Ok(());
+    }
+
+    let subject = match kind {
+        AlertKind::UpdateBlocked => "Update attempt blocked",
+        AlertKind::ProtectionBroken => "Protection broken",
+        AlertKind::FavoriteChanged => "Favorite version changed",
+    };
+    send_email(&settings.email, subject, details)
+}
+
+/// Send a test email using the saved SMTP settings
+#[tauri::command]
+pub async fn send_test_email() -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let settings = settings::load_settings();
+        send_email(
+            &settings.email,
+            "Test email",
+            "Email alerts are configured correctly.",
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
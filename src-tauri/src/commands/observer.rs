@@ -0,0 +1,174 @@
+//! Monitor-only observation
+//! Records every create/modify/delete in the CapCut folder without intervening, as a reviewable timeline
+
+use notify::event::{EventKind, ModifyKind};
+use notify::{RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use super::{installs, paths};
+
+/// Oldest changes are dropped beyond this count
+const MAX_CHANGES: usize = 2000;
+
+/// Buffered changes are written at most this often
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Serializes read-modify-write of the timeline file across threads
+static TIMELINE_LOCK: Mutex<()> = Mutex::new(());
+
+/// File system change kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Create,
+    Modify,
+    Rename,
+    Delete,
+}
+
+/// One observed change in the CapCut folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObservedChange {
+    pub kind: ChangeKind,
+    pub path: String,
+    /// Unix seconds
+    pub timestamp: u64,
+}
+
+/// Get the timeline file path
+fn get_timeline_path() -> Option<PathBuf> {
+    installs::get_install_data_dir().map(|p| p.join("observation_timeline.json"))
+}
+
+fn load_timeline() -> Vec<ObservedChange> {
+    get_timeline_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_timeline(changes: &[ObservedChange]) -> Result<(), String> {
+    let path = get_timeline_path().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string(changes).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save timeline: {}", e))
+}
+
+/// Prepend buffered changes (oldest first) to the timeline (newest first)
+fn append_changes(buffered: &mut Vec<ObservedChange>) {
+    if buffered.is_empty() {
+        return;
+    }
+    let _guard = TIMELINE_LOCK.lock();
+    let mut timeline: Vec<ObservedChange> = buffered.drain(..).rev().collect();
+    timeline.extend(load_timeline());
+    timeline.truncate(MAX_CHANGES);
+    if let Err(e) = save_timeline(&timeline) {
+        tracing::warn!("Could not record observed changes: {}", e);
+    }
+}
+
+fn change_kind(kind: &EventKind) -> Option<ChangeKind> {
+    match kind {
+        EventKind::Create(_) => Some(ChangeKind::Create),
+        EventKind::Modify(ModifyKind::Name(_)) => Some(ChangeKind::Rename),
+        EventKind::Modify(_) => Some(ChangeKind::Modify),
+        EventKind::Remove(_) => Some(ChangeKind::Delete),
+        _ => None,
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Record changes under the CapCut folder until `stop` is set
+pub fn watch(app: AppHandle, stop: Arc<AtomicBool>) {
+    let root = loop {
+        if stop.load(Ordering::Relaxed) {
+            return;
+        }
+        match paths::get_capcut_root_path() {
+            Some(p) if p.is_dir() => break p,
+            _ => thread::sleep(Duration::from_secs(2)),
+        }
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(w) => w,
+        Err(e) => {
+            tracing::error!("Observation watcher failed to start: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = watcher.watch(&root, RecursiveMode::Recursive) {
+        tracing::error!("Observation watcher failed to start: {}", e);
+        return;
+    }
+
+    let mut buffered: Vec<ObservedChange> = Vec::new();
+    let mut last_flush = Instant::now();
+    while !stop.load(Ordering::Relaxed) {
+        match rx.recv_timeout(Duration::from_millis(500)) {
+            Ok(Ok(event)) => {
+                let kind = match change_kind(&event.kind) {
+                    Some(k) => k,
+                    None => continue,
+                };
+                let timestamp = now_secs();
+                for path in &event.paths {
+                    let path = path.to_string_lossy().to_string();
+                    // Writes arrive as bursts of identical modify events
+                    let repeated = buffered.last().is_some_and(|c| {
+                        c.kind == kind && c.path == path && c.timestamp == timestamp
+                    });
+                    if !repeated {
+                        buffered.push(ObservedChange {
+                            kind,
+                            path,
+                            timestamp,
+                        });
+                    }
+                }
+            }
+            Ok(Err(e)) => tracing::error!("Observation watcher error: {}", e),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        if !buffered.is_empty() && last_flush.elapsed() >= FLUSH_INTERVAL {
+            let count = buffered.len();
+            append_changes(&mut buffered);
+            last_flush = Instant::now();
+            let _ = app.emit("observer-changes", count);
+        }
+    }
+    append_changes(&mut buffered);
+}
+
+/// List observed changes (newest first)
+#[tauri::command]
+pub fn get_observation_timeline() -> Vec<ObservedChange> {
+    load_timeline()
+}
+
+/// Delete the observation timeline
+#[tauri::command]
+pub fn clear_observation_timeline() -> Result<(), String> {
+    let _guard = TIMELINE_LOCK.lock();
+    save_timeline(&[])
+}
@@ -1,10 +1,16 @@
 //! Path resolution utilities with registry lookup support
 //! Handles custom CapCut installation paths beyond the default LOCALAPPDATA location
 
-use std::path::PathBuf;
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Component, Path, PathBuf, Prefix};
+use windows_sys::Win32::Storage::FileSystem::GetDriveTypeW;
 use winreg::enums::*;
 use winreg::RegKey;
 
+use super::application;
+use super::{deep_scan, settings};
+
 /// CapCut installation paths
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct CapCutPaths {
@@ -14,6 +20,74 @@ pub struct CapCutPaths {
     pub apps: PathBuf,
     /// Source of the path detection
     pub source: PathDetectionSource,
+    /// Install folder name found (e.g., "CapCut" or a regional variant)
+    pub variant: String,
+    pub layout: InstallLayout,
+    /// Apps path as found, when it goes through a junction or symlink (`apps` is then the real folder)
+    pub linked_from: Option<PathBuf>,
+}
+
+/// How an install is laid out on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallLayout {
+    /// Installer layout: version folders and configure.ini under Apps
+    Standard,
+    /// Extracted build: version folders and configure.ini directly in the root (`apps == root`)
+    Portable,
+}
+
+impl CapCutPaths {
+    /// Whether `dir` (a child of `apps`) is a version folder rather than data like "User Data"
+    pub fn is_version_dir(&self, dir: &Path) -> bool {
+        dir.is_dir() && (self.layout == InstallLayout::Standard || has_main_executable(dir))
+    }
+
+    /// Point `apps` at the real folder when it (or a parent) is a junction or symlink
+    pub fn follow_apps_link(mut self) -> Self {
+        if !self.apps.ancestors().any(is_link) {
+            return self;
+        }
+        let real = match fs::canonicalize(&self.apps) {
+            Ok(p) => strip_verbatim(p),
+            // Dangling link (target drive disconnected); checks see a missing folder
+            Err(_) => return self,
+        };
+        if self.layout == InstallLayout::Portable {
+            self.root = real.clone();
+        }
+        self.linked_from = Some(std::mem::replace(&mut self.apps, real));
+        self
+    }
+}
+
+/// Whether `path` itself is a junction or symbolic link
+pub fn is_link(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink())
+}
+
+/// Drop the "\\?\" prefix `canonicalize` adds to drive and UNC paths
+fn strip_verbatim(path: PathBuf) -> PathBuf {
+    let mut components = path.components();
+    let base = match components.next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::VerbatimDisk(drive) => PathBuf::from(format!("{}:\\", drive as char)),
+            Prefix::VerbatimUNC(server, share) => {
+                let mut unc = std::ffi::OsString::from(r"\\");
+                unc.push(server);
+                unc.push(r"\");
+                unc.push(share);
+                unc.push(r"\");
+                PathBuf::from(unc)
+            }
+            // Volume GUID paths have no shorter form
+            _ => return path,
+        },
+        _ => return path,
+    };
+    components
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .fold(base, |acc, c| acc.join(c))
 }
 
 /// How the path was detected
@@ -21,9 +95,34 @@ pub struct CapCutPaths {
 pub enum PathDetectionSource {
     Registry,
     DefaultLocation,
+    /// Machine-wide install under Program Files
+    ProgramFiles,
+    /// Portable folder on a drive root or next to Version Guard
+    Portable,
+    /// LOCALAPPDATA install of another user profile (named)
+    UserProfile(String),
     Custom(String),
 }
 
+/// Main executables of the supported applications (see `application.rs`)
+pub const MAIN_EXECUTABLES: &[&str] = &["CapCut.exe", "JianyingPro.exe"];
+
+/// Whether a version folder holds the main executable of either build
+pub fn has_main_executable(version_dir: &Path) -> bool {
+    MAIN_EXECUTABLES
+        .iter()
+        .any(|name| version_dir.join(name).is_file())
+}
+
+/// Main executable of a version folder (CapCut.exe when it holds neither)
+pub fn main_executable(version_dir: &Path) -> PathBuf {
+    MAIN_EXECUTABLES
+        .iter()
+        .map(|name| version_dir.join(name))
+        .find(|p| p.is_file())
+        .unwrap_or_else(|| version_dir.join(MAIN_EXECUTABLES[0]))
+}
+
 /// Registry keys to check for CapCut installation
 const REGISTRY_PATHS: &[(&str, &str)] = &[
     // Standard uninstall location
@@ -43,80 +142,437 @@ const REGISTRY_PATHS: &[(&str, &str)] = &[
     ),
 ];
 
-/// Try to find CapCut installation path from Windows Registry
-fn find_from_registry() -> Option<PathBuf> {
+/// Uninstall keys whose entries are searched for CapCut by display name
+const UNINSTALL_KEYS: &[&str] = &[
+    r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+    r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+];
+
+/// Uninstall entries whose display name contains one of these are CapCut or JianYing (剪映专业版)
+const UNINSTALL_NAMES: &[&str] = &["capcut", "jianying", "剪映"];
+
+/// Program Files locations used by machine-wide installers
+const PROGRAM_FILES_VARS: &[&str] = &["ProgramFiles", "ProgramFiles(x86)"];
+
+/// Folder names looked for on drive roots and next to Version Guard for portable copies
+const PORTABLE_FOLDERS: &[&str] = &["CapCut", "CapCut Portable"];
+
+/// Install folder of an uninstall entry: InstallLocation, else the folder of its icon or uninstaller
+fn uninstall_entry_root(entry: &RegKey) -> Option<PathBuf> {
+    if let Ok(location) = entry.get_value::<String, _>("InstallLocation") {
+        if !location.trim().is_empty() {
+            return Some(PathBuf::from(location.trim().trim_matches('"')));
+        }
+    }
+    ["DisplayIcon", "UninstallString"].iter().find_map(|name| {
+        let value: String = entry.get_value(name).ok()?;
+        // "C:\...\uninst.exe" --args or C:\...\CapCut.exe,0
+        let exe = match value.trim().strip_prefix('"') {
+            Some(rest) => rest.split('"').next()?,
+            None => value.trim().split(',').next()?,
+        };
+        let dir = Path::new(exe).parent()?;
+        // The icon is often Apps\<version>\CapCut.exe; the install root is the folder holding Apps
+        Some(
+            dir.ancestors()
+                .find(|a| a.join("Apps").is_dir())
+                .unwrap_or(dir)
+                .to_path_buf(),
+        )
+    })
+}
+
+/// Install folders of uninstall entries named CapCut or JianYing, whatever their key name
+fn uninstall_entry_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    for hive in [HKEY_LOCAL_MACHINE, HKEY_CURRENT_USER] {
+        let hive = RegKey::predef(hive);
+        for uninstall_path in UNINSTALL_KEYS {
+            let uninstall = match hive.open_subkey(uninstall_path) {
+                Ok(k) => k,
+                Err(_) => continue,
+            };
+            for name in uninstall.enum_keys().filter_map(|k| k.ok()) {
+                let entry = match uninstall.open_subkey(&name) {
+                    Ok(k) => k,
+                    Err(_) => continue,
+                };
+                let display_name: String = entry.get_value("DisplayName").unwrap_or_default();
+                let display_name = display_name.to_lowercase();
+                if !UNINSTALL_NAMES.iter().any(|n| display_name.contains(n)) {
+                    continue;
+                }
+                if let Some(root) = uninstall_entry_root(&entry) {
+                    roots.push(root);
+                }
+            }
+        }
+    }
+    roots
+}
+
+/// Every existing CapCut install location listed in the Windows Registry, in lookup order
+pub fn registry_install_roots() -> Vec<PathBuf> {
     let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
     let hkcu = RegKey::predef(HKEY_CURRENT_USER);
 
+    let mut roots = Vec::new();
     for (key_path, value_name) in REGISTRY_PATHS {
-        // Try HKLM first
-        if let Ok(key) = hklm.open_subkey(key_path) {
-            let path_result: Result<String, _> = key.get_value(value_name);
-            if let Ok(path) = path_result {
-                let path_buf = PathBuf::from(&path);
-                if path_buf.exists() {
-                    return Some(path_buf);
+        // HKLM first, then HKCU
+        for hive in [&hklm, &hkcu] {
+            if let Ok(key) = hive.open_subkey(key_path) {
+                let path_result: Result<String, _> = key.get_value(value_name);
+                if let Ok(path) = path_result {
+                    let path_buf = PathBuf::from(&path);
+                    if path_buf.exists() && !roots.contains(&path_buf) {
+                        roots.push(path_buf);
+                    }
                 }
             }
         }
-        // Then try HKCU
-        if let Ok(key) = hkcu.open_subkey(key_path) {
-            let path_result: Result<String, _> = key.get_value(value_name);
-            if let Ok(path) = path_result {
-                let path_buf = PathBuf::from(&path);
-                if path_buf.exists() {
-                    return Some(path_buf);
-                }
-            }
+    }
+    for root in uninstall_entry_roots() {
+        if root.exists() && !roots.contains(&root) {
+            roots.push(root);
         }
     }
+    roots
+}
 
-    None
+/// Machine-wide installs under Program Files (any application's install folder name)
+pub fn program_files_installs() -> Vec<CapCutPaths> {
+    PROGRAM_FILES_VARS
+        .iter()
+        .filter_map(std::env::var_os)
+        .flat_map(|dir| {
+            application::ALL
+                .iter()
+                .flat_map(|app| app.profile().install_folders)
+                .map(move |name| PathBuf::from(&dir).join(name))
+        })
+        .filter(|root| root.is_dir())
+        .filter_map(registry_install)
+        .map(|install| CapCutPaths {
+            source: PathDetectionSource::ProgramFiles,
+            ..install
+        })
+        .collect()
 }
 
-/// Get the default CapCut installation path (LOCALAPPDATA)
-fn get_default_path() -> Option<PathBuf> {
+/// Portable copies on fixed drive roots or next to the Version Guard executable
+pub fn portable_installs() -> Vec<CapCutPaths> {
+    let mut bases = deep_scan::fixed_drives();
+    if let Some(exe_dir) = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(Path::to_path_buf))
+    {
+        bases.extend(exe_dir.parent().map(Path::to_path_buf));
+        bases.push(exe_dir);
+    }
+    bases
+        .iter()
+        .flat_map(|base| PORTABLE_FOLDERS.iter().map(move |name| base.join(name)))
+        .filter(|root| root.is_dir())
+        .filter_map(|root| parse_custom_path(&root.to_string_lossy()))
+        .map(|install| CapCutPaths {
+            source: PathDetectionSource::Portable,
+            ..install
+        })
+        .collect()
+}
+
+/// Get the Version Guard data directory (e.g., ...\AppData\Local\CCVersionGuard)
+pub fn get_app_data_dir() -> Option<PathBuf> {
     std::env::var("LOCALAPPDATA")
         .ok()
-        .map(|p| PathBuf::from(p).join("CapCut"))
+        .map(|p| PathBuf::from(p).join("CCVersionGuard"))
+}
+
+/// Marks a transport path that is hex-encoded UTF-16 (not valid Unicode)
+const WIDE_PATH_PREFIX: &str = "wide:";
+
+/// Encode a path for the frontend without losing non-Unicode characters
+pub fn path_to_transport(path: &Path) -> String {
+    use std::os::windows::ffi::OsStrExt;
+
+    match path.to_str() {
+        Some(s) => s.to_string(),
+        None => {
+            let hex: String = path
+                .as_os_str()
+                .encode_wide()
+                .map(|unit| format!("{:04x}", unit))
+                .collect();
+            format!("{}{}", WIDE_PATH_PREFIX, hex)
+        }
+    }
+}
+
+/// Decode a path received from the frontend (plain or `path_to_transport` form)
+pub fn path_from_transport(value: &str) -> PathBuf {
+    use std::os::windows::ffi::OsStringExt;
+
+    let hex = match value.strip_prefix(WIDE_PATH_PREFIX) {
+        Some(h) => h,
+        None => return PathBuf::from(value),
+    };
+    let units: Option<Vec<u16>> = (0..hex.len())
+        .step_by(4)
+        .map(|i| {
+            hex.get(i..i + 4)
+                .and_then(|u| u16::from_str_radix(u, 16).ok())
+        })
+        .collect();
+    match units {
+        Some(units) => PathBuf::from(std::ffi::OsString::from_wide(&units)),
+        None => PathBuf::from(value),
+    }
+}
+
+/// Environment variables OneDrive sets to its sync roots
+const ONEDRIVE_VARS: &[&str] = &["OneDrive", "OneDriveConsumer", "OneDriveCommercial"];
+
+/// Lowercased path for case-insensitive prefix checks
+fn normalized(path: &Path) -> PathBuf {
+    let text = path.to_string_lossy().to_lowercase();
+    PathBuf::from(text.trim_start_matches(r"\\?\"))
+}
+
+/// OneDrive folder that syncs the given path, if any (follows junctions)
+pub fn cloud_sync_root(path: &Path) -> Option<PathBuf> {
+    let mut candidates = vec![normalized(path)];
+    if let Ok(real) = fs::canonicalize(path) {
+        candidates.push(normalized(&real));
+    }
+
+    ONEDRIVE_VARS
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(PathBuf::from)
+        .filter(|root| !root.as_os_str().is_empty())
+        .find(|root| {
+            let root_norm = normalized(root);
+            candidates.iter().any(|c| c.starts_with(&root_norm))
+        })
+}
+
+/// Kind of volume a path lives on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VolumeKind {
+    Fixed,
+    Removable,
+    Network,
+    /// Drive letter not mounted (unplugged drive, disconnected share)
+    Unavailable,
+    Other,
+}
+
+/// GetDriveTypeW results (WindowsProgramming isn't an enabled feature)
+const DRIVE_NO_ROOT_DIR: u32 = 1;
+const DRIVE_REMOVABLE: u32 = 2;
+const DRIVE_FIXED: u32 = 3;
+const DRIVE_REMOTE: u32 = 4;
+
+/// Classify the volume holding `path` (drive letter or UNC share)
+pub fn volume_kind(path: &Path) -> VolumeKind {
+    let letter = match path.components().next() {
+        Some(Component::Prefix(p)) => match p.kind() {
+            Prefix::Disk(l) | Prefix::VerbatimDisk(l) => l,
+            Prefix::UNC(..) | Prefix::VerbatimUNC(..) => return VolumeKind::Network,
+            _ => return VolumeKind::Other,
+        },
+        _ => return VolumeKind::Other,
+    };
+    let root: Vec<u16> = format!("{}:\\", letter as char)
+        .encode_utf16()
+        .chain(Some(0))
+        .collect();
+    // SAFETY: `root` is a NUL-terminated drive root that outlives the call
+    match unsafe { GetDriveTypeW(root.as_ptr()) } {
+        DRIVE_FIXED => VolumeKind::Fixed,
+        DRIVE_REMOVABLE => VolumeKind::Removable,
+        DRIVE_REMOTE => VolumeKind::Network,
+        DRIVE_NO_ROOT_DIR => VolumeKind::Unavailable,
+        _ => VolumeKind::Other,
+    }
+}
+
+/// Whether the CapCut install is reachable right now
+///
+/// A saved custom folder is checked directly: when its drive is gone, detection would
+/// otherwise fall back to another location.
+pub fn capcut_volume_online() -> bool {
+    if let Some(scoped) = scoped_install() {
+        return scoped.apps.is_dir();
+    }
+    match settings::load_settings().capcut_path {
+        // Follows links, so a junction to a disconnected drive counts as offline
+        Some(custom) => parse_custom_path(&custom).is_some_and(|p| p.apps.is_dir()),
+        None => get_capcut_apps_path().is_some_and(|p| p.is_dir()),
+    }
+}
+
+/// Install folder names to try, from settings or those of the chosen application
+pub fn install_folder_names() -> Vec<String> {
+    let app_settings = settings::load_settings();
+    let configured: Vec<String> = app_settings
+        .install_folder_names
+        .iter()
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .collect();
+    if configured.is_empty() {
+        app_settings
+            .application
+            .profile()
+            .install_folders
+            .iter()
+            .map(|n| n.to_string())
+            .collect()
+    } else {
+        configured
+    }
+}
+
+/// Install folder names of every application plus the configured ones, for listing all installs
+pub fn known_install_folder_names() -> Vec<String> {
+    let mut names = install_folder_names();
+    for name in application::ALL
+        .iter()
+        .flat_map(|app| app.profile().install_folders)
+    {
+        if !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Find the install folder under LOCALAPPDATA
+///
+/// A variant with an Apps folder wins over one that only has its root folder, so a leftover
+/// folder of another distribution doesn't shadow the real install.
+fn get_default_path() -> Option<PathBuf> {
+    let local = PathBuf::from(std::env::var("LOCALAPPDATA").ok()?);
+    let roots: Vec<PathBuf> = install_folder_names()
+        .iter()
+        .map(|name| local.join(name))
+        .collect();
+    roots
+        .iter()
+        .find(|root| root.join("Apps").is_dir())
+        .or_else(|| roots.iter().find(|root| root.exists()))
+        .or(roots.first())
+        .cloned()
+}
+
+/// Name of the install folder (e.g., "CapCut")
+fn variant_name(root: &Path) -> String {
+    root.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+thread_local! {
+    /// Install this thread works on instead of the configured one (see `installs::scope`)
+    static SCOPED_INSTALL: RefCell<Option<CapCutPaths>> = const { RefCell::new(None) };
+}
+
+/// Install the calling thread is scoped to, if any
+pub fn scoped_install() -> Option<CapCutPaths> {
+    SCOPED_INSTALL.with_borrow(Clone::clone)
+}
+
+/// Scope the calling thread to an install (None = configured one); returns the previous scope
+pub fn set_scoped_install(install: Option<CapCutPaths>) -> Option<CapCutPaths> {
+    SCOPED_INSTALL.replace(install)
 }
 
 /// Resolve CapCut installation paths with fallback logic
-/// 1. Check Windows Registry for custom install paths
-/// 2. Fall back to default LOCALAPPDATA location
-/// 3. Return None if not found
+/// 0. Use the install the calling thread is scoped to
+/// 1. Use the user-chosen folder saved in settings
+/// 2. Check Windows Registry for custom install paths (including uninstall entries)
+/// 3. Use the default LOCALAPPDATA location when it has an Apps folder
+/// 4. Check Program Files, then portable folders on drive roots and next to Version Guard
+/// 5. Fall back to the default LOCALAPPDATA folder if it exists
+/// 6. Follow a junction or symlink on the Apps path to the real folder
+/// 7. Return None if not found
 pub fn resolve_capcut_paths() -> Option<CapCutPaths> {
-    // Try registry first
-    if let Some(root) = find_from_registry() {
-        let apps = if root.join("Apps").exists() {
-            root.join("Apps")
-        } else {
-            // Some installations may have Apps directly in the root
-            root.clone()
-        };
+    if let Some(scoped) = scoped_install() {
+        return Some(scoped);
+    }
+    detect_capcut_paths().map(CapCutPaths::follow_apps_link)
+}
 
-        if apps.exists() {
-            return Some(CapCutPaths {
-                root,
-                apps,
-                source: PathDetectionSource::Registry,
-            });
+/// Find the install as configured, before following links
+fn detect_capcut_paths() -> Option<CapCutPaths> {
+    let app_settings = settings::load_settings();
+    // A saved custom path wins while it still looks valid
+    if let Some(custom) = app_settings.capcut_path {
+        if let Some(paths) = parse_custom_path(&custom) {
+            return Some(paths);
         }
     }
+    // Only installs of the application chosen on the Welcome screen are auto-detected
+    let wanted = |paths: &CapCutPaths| application::of_install(paths) == app_settings.application;
 
-    // Fall back to default location
-    if let Some(root) = get_default_path() {
-        let apps = root.join("Apps");
-        if root.exists() || apps.exists() {
-            return Some(CapCutPaths {
-                root,
-                apps,
-                source: PathDetectionSource::DefaultLocation,
-            });
-        }
+    // Try registry
+    if let Some(paths) = registry_install_roots()
+        .into_iter()
+        .filter_map(registry_install)
+        .find(wanted)
+    {
+        return Some(paths);
     }
 
-    None
+    let default = get_default_path().map(|root| CapCutPaths {
+        variant: variant_name(&root),
+        layout: InstallLayout::Standard,
+        apps: root.join("Apps"),
+        root,
+        source: PathDetectionSource::DefaultLocation,
+        linked_from: None,
+    });
+    if let Some(paths) = default.as_ref().filter(|p| p.apps.exists()) {
+        return Some(paths.clone());
+    }
+
+    // Non-standard installs before a leftover default folder without Apps
+    if let Some(paths) = program_files_installs()
+        .into_iter()
+        .chain(portable_installs())
+        .find(wanted)
+    {
+        return Some(paths);
+    }
+
+    default.filter(|p| p.root.exists())
+}
+
+/// Install paths for a registry install location, if it holds an Apps folder or versions
+pub fn registry_install(root: PathBuf) -> Option<CapCutPaths> {
+    let apps = if root.join("Apps").exists() {
+        root.join("Apps")
+    } else {
+        // Some installations may have Apps directly in the root
+        root.clone()
+    };
+    if !apps.exists() {
+        return None;
+    }
+    Some(CapCutPaths {
+        variant: variant_name(&root),
+        layout: if apps == root {
+            InstallLayout::Portable
+        } else {
+            InstallLayout::Standard
+        },
+        root,
+        apps,
+        source: PathDetectionSource::Registry,
+        linked_from: None,
+    })
 }
 
 /// Get CapCut Apps path (convenience function)
@@ -129,35 +585,46 @@ pub fn get_capcut_root_path() -> Option<PathBuf> {
     resolve_capcut_paths().map(|p| p.root)
 }
 
-/// Set a custom CapCut path (for user-specified installations)
+/// Parse a custom CapCut path (for user-specified installations)
 /// Returns the CapCutPaths if valid, None otherwise
-pub fn validate_custom_path(custom_path: &str) -> Option<CapCutPaths> {
+pub fn parse_custom_path(custom_path: &str) -> Option<CapCutPaths> {
     let root = PathBuf::from(custom_path);
     if !root.exists() {
         return None;
     }
 
     // Check if this is a valid CapCut installation
-    let apps = if root.join("Apps").exists() {
-        root.join("Apps")
+    let (root, apps, layout) = if root.join("Apps").exists() {
+        (root.clone(), root.join("Apps"), InstallLayout::Standard)
     } else if root.file_name().map(|n| n == "Apps").unwrap_or(false) {
         // User specified the Apps folder directly
-        root.clone()
+        (root.parent()?.to_path_buf(), root, InstallLayout::Standard)
+    } else if has_version_folders(&root) {
+        (root.clone(), root, InstallLayout::Portable)
     } else {
         return None;
     };
 
     Some(CapCutPaths {
-        root: if apps == root {
-            apps.parent()?.to_path_buf()
-        } else {
-            root
-        },
+        variant: variant_name(&root),
+        root,
         apps,
         source: PathDetectionSource::Custom(custom_path.to_string()),
+        layout,
+        linked_from: None,
     })
 }
 
+/// Whether `dir` directly holds a version folder with CapCut.exe (portable layout)
+fn has_version_folders(dir: &Path) -> bool {
+    fs::read_dir(dir)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .any(|e| has_main_executable(&e.path()))
+}
+
 /// Get path detection info for frontend display
 #[tauri::command]
 pub fn get_path_info() -> Option<CapCutPaths> {
@@ -167,5 +634,95 @@ pub fn get_path_info() -> Option<CapCutPaths> {
 /// Validate a custom CapCut path provided by user
 #[tauri::command]
 pub fn validate_custom_capcut_path(path: String) -> Option<CapCutPaths> {
-    validate_custom_path(&path)
+    parse_custom_path(&path)
+}
+
+/// What was found in a user-supplied CapCut folder
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CustomPathCheck {
+    pub valid: bool,
+    pub paths: Option<CapCutPaths>,
+    /// Version folders found under Apps
+    pub versions: Vec<String>,
+    /// Version folders that contain CapCut.exe
+    pub versions_with_exe: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Validate a user-supplied CapCut folder and remember it for scan/protect
+#[tauri::command]
+pub fn validate_custom_path(path: String) -> CustomPathCheck {
+    let mut check = CustomPathCheck {
+        valid: false,
+        paths: None,
+        versions: Vec::new(),
+        versions_with_exe: Vec::new(),
+        error: None,
+    };
+
+    let paths = match parse_custom_path(&path) {
+        Some(p) => p,
+        None => {
+            check.error = Some(
+                "Folder contains neither a CapCut Apps directory nor CapCut version folders"
+                    .to_string(),
+            );
+            return check;
+        }
+    };
+
+    let version_dirs: Vec<PathBuf> = fs::read_dir(&paths.apps)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| paths.is_version_dir(p))
+        .collect();
+
+    for dir in &version_dirs {
+        let name = dir
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        if has_main_executable(dir) {
+            check.versions_with_exe.push(name.clone());
+        }
+        check.versions.push(name);
+    }
+
+    if check.versions_with_exe.is_empty() {
+        check.error = Some("No CapCut version with CapCut.exe was found".to_string());
+        check.paths = Some(paths);
+        return check;
+    }
+
+    let mut app_settings = settings::load_settings();
+    // Stays listed under Settings → Installations after switching to another install
+    if !app_settings
+        .known_installs
+        .iter()
+        .any(|p| p.eq_ignore_ascii_case(&path))
+    {
+        app_settings.known_installs.push(path.clone());
+    }
+    app_settings.capcut_path = Some(path);
+    if let Err(e) = settings::save_user_settings(&app_settings) {
+        check.error = Some(e);
+        check.paths = Some(paths);
+        return check;
+    }
+
+    check.valid = true;
+    check.paths = Some(paths);
+    check
+}
+
+/// Forget the saved custom CapCut folder and go back to auto-detection
+#[tauri::command]
+pub fn clear_custom_path() -> Result<(), String> {
+    let mut app_settings = settings::load_settings();
+    app_settings.capcut_path = None;
+    settings::save_user_settings(&app_settings)
 }
@@ -0,0 +1,65 @@
+//! Machine-level administrator policy
+//! Reads %ProgramData%\CCVersionGuard\policy.json so schools/studios can lock down protection
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Administrator policy (all restrictions off when no policy file exists)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MachinePolicy {
+    /// Whether a policy file was found and applied
+    #[serde(skip_deserializing)]
+    pub managed: bool,
+    /// Users cannot remove protection
+    pub disallow_unprotect: bool,
+    /// The watchdog must always run and cannot be stopped
+    pub force_watchdog: bool,
+    /// Version Guard must start with Windows
+    pub force_autostart: bool,
+    /// Users cannot change or import app settings
+    pub lock_settings: bool,
+}
+
+/// Error message returned when an action is blocked by policy
+pub const POLICY_BLOCKED: &str = "This action is disabled by your administrator";
+
+/// Get the machine policy file path
+fn get_policy_path() -> Option<PathBuf> {
+    std::env::var("ProgramData")
+        .ok()
+        .map(|p| PathBuf::from(p).join("CCVersionGuard").join("policy.json"))
+}
+
+/// Load the machine policy (defaults if missing or unreadable)
+pub fn load_policy() -> MachinePolicy {
+    let content = match get_policy_path().and_then(|p| fs::read_to_string(p).ok()) {
+        Some(c) => c,
+        None => return MachinePolicy::default(),
+    };
+
+    match serde_json::from_str::<MachinePolicy>(&content) {
+        Ok(mut policy) => {
+            policy.managed = true;
+            policy
+        }
+        Err(e) => {
+            // A broken policy file must not silently unlock everything
+            tracing::warn!("Invalid policy file, applying strictest policy: {}", e);
+            MachinePolicy {
+                managed: true,
+                disallow_unprotect: true,
+                force_watchdog: true,
+                force_autostart: true,
+                lock_settings: true,
+            }
+        }
+    }
+}
+
+/// Get the active machine policy for the frontend
+#[tauri::command]
+pub fn get_machine_policy() -> MachinePolicy {
+    load_policy()
+}
@@ -0,0 +1,28 @@
+//! Low-impact watchdog support
+//! Battery detection and thread priority so continuous protection stays light on laptops
+
+use windows_sys::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+use windows_sys::Win32::System::Threading::{
+    GetCurrentThread, SetThreadPriority, THREAD_PRIORITY_BELOW_NORMAL,
+};
+
+/// Check interval multiplier while on battery in low-impact mode
+pub const BATTERY_INTERVAL_FACTOR: u64 = 4;
+
+/// Quiet period a burst of file events must end with before the guard acts (low-impact mode)
+pub const LOW_IMPACT_SETTLE_MS: u64 = 2000;
+
+/// Whether the machine is running on battery (false on desktops or if unknown)
+pub fn on_battery() -> bool {
+    let mut status = SYSTEM_POWER_STATUS::default();
+    // SAFETY: `status` is a valid, writable SYSTEM_POWER_STATUS
+    let ok = unsafe { GetSystemPowerStatus(&mut status) };
+    // ACLineStatus: 0 = offline, 1 = online, 255 = unknown
+    ok != 0 && status.ACLineStatus == 0
+}
+
+/// Run the calling thread below normal priority
+pub fn lower_thread_priority() {
+    // SAFETY: the pseudo-handle always refers to the calling thread
+    unsafe { SetThreadPriority(GetCurrentThread(), THREAD_PRIORITY_BELOW_NORMAL) };
+}
@@ -0,0 +1,334 @@
+//! Protection preview
+//! Dry run of the protection sequence: lists what each step would delete, create or modify without touching anything
+
+use serde::Serialize;
+use std::path::Path;
+
+use super::protector::ProtectionParams;
+use super::{
+    acl_lock, asset_migration, autoruns, backup, blockers, channel, cleaner, config_backup,
+    domain_block, draft_compat, drafts, exclusions, firewall, hardening, hosts, installs, paths,
+    process, registry_guard, scanner, updater_stub,
+};
+
+/// What a protection step would do to a path, registry value or rule
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    Delete,
+    Create,
+    Modify,
+}
+
+/// One change the protection run would make
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedChange {
+    /// Step name as shown in the activity log and step timings
+    pub step: String,
+    pub action: ChangeAction,
+    pub target: String,
+    pub detail: Option<String>,
+    /// Size of what is deleted
+    pub bytes: Option<u64>,
+}
+
+/// Everything a protection run with the given options would change
+#[derive(Debug, Clone, Serialize)]
+pub struct ProtectionPreview {
+    pub root: String,
+    pub changes: Vec<PlannedChange>,
+    /// Disk space freed by deleting versions and cache, less the assets moved into the kept version
+    pub bytes_freed: u64,
+    /// Disk space the backups of deleted versions take until they are cleared
+    pub backup_bytes: u64,
+    /// Things the real run would stop at or skip
+    pub notes: Vec<String>,
+}
+
+fn change(
+    step: &str,
+    action: ChangeAction,
+    target: String,
+    detail: Option<String>,
+) -> PlannedChange {
+    PlannedChange {
+        step: step.to_string(),
+        action,
+        target,
+        detail,
+        bytes: None,
+    }
+}
+
+/// Created, or replaced when something is already there
+fn create_or_replace(path: &Path) -> ChangeAction {
+    if path.exists() {
+        ChangeAction::Modify
+    } else {
+        ChangeAction::Create
+    }
+}
+
+/// Walk the protection steps of `params` and collect their changes
+fn plan_protection(params: &ProtectionParams) -> Result<ProtectionPreview, String> {
+    let install = paths::resolve_capcut_paths().ok_or("Could not find CapCut installation")?;
+    let mut changes: Vec<PlannedChange> = Vec::new();
+    let mut notes: Vec<String> = Vec::new();
+    let mut bytes_freed: u64 = 0;
+    let mut backup_bytes: u64 = 0;
+
+    if process::is_capcut_running() {
+        notes.push("CapCut is running; it must be closed before the real run".to_string());
+    }
+
+    if let Some(dest) = &params.backup_drafts_to {
+        let projects = drafts::projects_to_archive(&params.draft_projects);
+        if projects.is_empty() {
+            notes.push("No CapCut projects to back up".to_string());
+        } else {
+            let dest = paths::path_from_transport(dest).join(drafts::archive_name());
+            changes.push(change(
+                "Draft backup",
+                ChangeAction::Create,
+                paths::path_to_transport(&dest),
+                Some(format!("Zip of {} project(s)", projects.len())),
+            ));
+        }
+    }
+
+    // Version cleanup, with assets of deleted versions moved into the kept one first
+    let excluded = exclusions::excluded_paths();
+    let kept = params
+        .keep_version
+        .as_deref()
+        .map(paths::path_from_transport);
+    if let Some(kept) = &kept {
+        let name = kept.file_name().unwrap_or_default().to_string_lossy();
+        let compat = draft_compat::check_drafts(&name);
+        if compat.newer > 0 {
+            notes.push(format!(
+                "{} project(s) were last saved by a newer CapCut and may not open in {}",
+                compat.newer, name
+            ));
+        }
+    }
+
+    let backup_dir = backup::get_backup_dir();
+    for version in params
+        .versions_to_delete
+        .iter()
+        .map(|p| paths::path_from_transport(p))
+    {
+        let name = version.file_name().unwrap_or_default().to_string_lossy();
+        if exclusions::touches_excluded(&version, &excluded) {
+            notes.push(format!("{} is skipped (on the never-touch list)", name));
+            continue;
+        }
+        let mut moved_bytes = 0;
+        if let Some(kept) = &kept {
+            let (files, bytes) = asset_migration::pending_assets(&version, kept);
+            if files > 0 {
+                changes.push(change(
+                    "Asset migration",
+                    ChangeAction::Modify,
+                    paths::path_to_transport(kept),
+                    Some(format!(
+                        "{} downloaded asset(s) moved in from {}",
+                        files, name
+                    )),
+                ));
+                moved_bytes = bytes;
+            }
+        }
+        let size = scanner::calculate_dir_size(&version);
+        if let Some(dir) = &backup_dir {
+            changes.push(change(
+                "Version cleanup",
+                ChangeAction::Create,
+                paths::path_to_transport(dir),
+                Some(format!("Backup of {}", name)),
+            ));
+        }
+        changes.push(PlannedChange {
+            bytes: Some(size),
+            ..change(
+                "Version cleanup",
+                ChangeAction::Delete,
+                paths::path_to_transport(&version),
+                None,
+            )
+        });
+        bytes_freed += size.saturating_sub(moved_bytes);
+        backup_bytes += size;
+    }
+
+    if params.clean_cache {
+        let categories = cleaner::resolve_categories(&params.cache_categories);
+        let cache = cleaner::preview_cache_at(&install.root, &categories);
+        for category in cache.categories {
+            changes.push(PlannedChange {
+                bytes: Some(category.bytes),
+                ..change(
+                    "Cache cleaning",
+                    ChangeAction::Delete,
+                    category.paths.join(", "),
+                    Some(format!("{}: {} file(s)", category.name, category.files)),
+                )
+            });
+        }
+        bytes_freed += cache.total_bytes;
+    }
+
+    let profile = channel::guard_profile(channel::detect_channel(&install.root, &install.apps));
+    if params.lock_config || params.create_blockers {
+        if let Some(dir) = config_backup::get_config_backup_dir() {
+            changes.push(change(
+                "Version lock",
+                ChangeAction::Create,
+                paths::path_to_transport(&dir),
+                Some("Backup of configure.ini and ProductInfo.xml".to_string()),
+            ));
+        }
+    }
+    if params.lock_config {
+        let config = install.apps.join("configure.ini");
+        changes.push(change(
+            "Version lock",
+            create_or_replace(&config),
+            paths::path_to_transport(&config),
+            Some(format!(
+                "{} set to 1.0.0.0",
+                profile.version_keys.join(", ")
+            )),
+        ));
+        for value in registry_guard::pending_values() {
+            changes.push(change(
+                "Version lock",
+                ChangeAction::Delete,
+                value,
+                Some("Staged update value (restored on unprotect)".to_string()),
+            ));
+        }
+    }
+    if params.create_blockers {
+        let download_dir = install.root.join("User Data").join("Download");
+        let blockers = std::iter::once(install.apps.join("ProductInfo.xml"))
+            .chain(profile.updater_names.iter().map(|n| download_dir.join(n)))
+            .chain(blockers::extra_blocker_paths(&install.root));
+        for blocker in blockers {
+            changes.push(change(
+                "Version lock",
+                create_or_replace(&blocker),
+                paths::path_to_transport(&blocker),
+                Some("Empty read-only blocker".to_string()),
+            ));
+        }
+    }
+
+    if params.stub_updaters {
+        // Updaters in Download are replaced by blockers first when those are on
+        let download_dir = install.root.join("User Data").join("Download");
+        for updater in updater_stub::updaters_to_stub()
+            .into_iter()
+            .filter(|p| !(params.create_blockers && p.parent() == Some(download_dir.as_path())))
+        {
+            changes.push(change(
+                "Updater stubs",
+                ChangeAction::Modify,
+                paths::path_to_transport(&updater),
+                Some("Renamed to .bak, replaced by a stub (restored on unprotect)".to_string()),
+            ));
+        }
+    }
+
+    if params.block_domains && !domain_block::is_blocked() {
+        changes.push(change(
+            "Update domain blocking",
+            ChangeAction::Modify,
+            paths::path_to_transport(&hosts::hosts_path()),
+            Some(format!(
+                "{} update domain(s) pointed to 0.0.0.0",
+                domain_block::UPDATE_DOMAINS.len()
+            )),
+        ));
+    }
+
+    if params.firewall_rules {
+        for rule in firewall::missing_rules() {
+            changes.push(change(
+                "Firewall rules",
+                ChangeAction::Create,
+                rule.name,
+                Some(format!("Blocks {}", rule.program)),
+            ));
+        }
+    }
+
+    if params.disable_autoruns {
+        // RunOnce entries are left alone, as in `autoruns::disable_all`
+        for entry in autoruns::find_autoruns()
+            .into_iter()
+            .filter(|e| e.enabled && !e.location.ends_with("RunOnce"))
+        {
+            changes.push(change(
+                "Autorun entries",
+                ChangeAction::Modify,
+                format!("{} ({})", entry.name, entry.location),
+                Some("Disabled (re-enabled on unprotect)".to_string()),
+            ));
+        }
+    }
+
+    if let (true, Some(kept)) = (params.harden_binaries, &kept) {
+        for file in hardening::files_to_harden(kept) {
+            changes.push(change(
+                "Binary hardening",
+                ChangeAction::Modify,
+                paths::path_to_transport(&file),
+                Some("Made read-only".to_string()),
+            ));
+        }
+    }
+
+    if let (true, Some(kept)) = (params.lock_acls, &kept) {
+        for (path, _) in acl_lock::paths_to_lock(&install.apps, kept) {
+            changes.push(change(
+                "ACL lock",
+                ChangeAction::Modify,
+                paths::path_to_transport(&path),
+                Some("Write and delete denied (removed on unprotect)".to_string()),
+            ));
+        }
+    }
+
+    Ok(ProtectionPreview {
+        root: paths::path_to_transport(&install.root),
+        changes,
+        bytes_freed,
+        backup_bytes,
+        notes,
+    })
+}
+
+/// Preview a protection run without changing anything; `install` picks an install by key
+#[tauri::command]
+pub async fn preview_protection(
+    params: ProtectionParams,
+    install: Option<String>,
+) -> Result<ProtectionPreview, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let scoped = match install {
+            Some(key) => Some(
+                installs::detect_installs()
+                    .into_iter()
+                    .find(|i| installs::install_key(i) == key)
+                    .ok_or("A selected CapCut installation was not found anymore")?,
+            ),
+            None => None,
+        };
+        let _scope = scoped.as_ref().map(installs::scope);
+        plan_protection(&params)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -1,22 +1,284 @@
 //! Process detection functionality
 //! Migrated from original eframe/egui main.rs
 
+use std::os::windows::process::CommandExt;
+use std::path::Path;
 use std::process::Command;
-use sysinfo::System;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::{Pid, Process, ProcessRefreshKind, System, UpdateKind};
+use tauri::AppHandle;
 
-use super::paths;
+use super::channel::{self, Channel};
+use super::folder_access::{self, FolderAccessBlock};
+use super::{application, paths, reprotect, version};
 
-/// Check if CapCut is currently running
+/// How long terminated processes get to exit (and release their file handles)
+const EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long CapCut gets to close on request before it is force-killed
+const CLOSE_GRACE: Duration = Duration::from_secs(10);
+
+/// Prevents a console window flashing up for taskkill
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+fn is_main_process(p: &Process, names: &[&str]) -> bool {
+    names.iter().any(|name| p.name().eq_ignore_ascii_case(name))
+}
+
+/// Check if CapCut (or JianYing, for a JianYing install) is currently running
 #[tauri::command]
 pub fn is_capcut_running() -> bool {
     let mut sys = System::new();
     sys.refresh_processes();
 
-    sys.processes_by_name("CapCut".as_ref()).next().is_some()
-        || sys
-            .processes_by_name("CapCut.exe".as_ref())
-            .next()
-            .is_some()
+    application::current()
+        .profile()
+        .process_names
+        .iter()
+        .any(|name| sys.processes_by_name(name.as_ref()).next().is_some())
+}
+
+/// CapCut background helpers that can start downloads on their own
+///
+/// Generic names (crashpad_handler, update) are only matched when the executable lives
+/// under the CapCut folder, so other apps' processes are never touched.
+pub const HELPER_PROCESSES: &[&str] = &[
+    "crashpad_handler.exe",
+    "CrashReporter.exe",
+    "VECrashHandler.exe",
+    "CapCutHelper.exe",
+    "ttdaemon.exe",
+    "update.exe",
+    "update_beta.exe",
+];
+
+/// A running CapCut helper process
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HelperProcess {
+    pub pid: u32,
+    pub name: String,
+    pub path: String,
+}
+
+/// Helper processes started from under `capcut_root`
+fn helper_processes<'a>(sys: &'a System, capcut_root: &Path) -> Vec<&'a Process> {
+    let root = capcut_root.to_string_lossy().to_lowercase();
+    sys.processes()
+        .values()
+        .filter(|p| {
+            HELPER_PROCESSES
+                .iter()
+                .any(|name| p.name().eq_ignore_ascii_case(name))
+        })
+        .filter(|p| {
+            p.exe()
+                .is_some_and(|exe| exe.to_string_lossy().to_lowercase().starts_with(&root))
+        })
+        .collect()
+}
+
+fn refreshed_system() -> System {
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(ProcessRefreshKind::new().with_exe(UpdateKind::OnlyIfNotSet));
+    sys
+}
+
+/// List running CapCut helper processes
+pub fn find_helpers() -> Vec<HelperProcess> {
+    let root = match paths::get_capcut_root_path() {
+        Some(r) => r,
+        None => return Vec::new(),
+    };
+    let sys = refreshed_system();
+    helper_processes(&sys, &root)
+        .into_iter()
+        .map(|p| HelperProcess {
+            pid: p.pid().as_u32(),
+            name: p.name().to_string(),
+            path: p
+                .exe()
+                .map(|e| e.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// `roots` and all their descendants, parents before children
+fn process_tree(sys: &System, roots: &[Pid]) -> Vec<Pid> {
+    let mut tree: Vec<Pid> = roots.to_vec();
+    let mut i = 0;
+    while i < tree.len() {
+        let parent = tree[i];
+        let parent_start = sys.process(parent).map_or(0, |p| p.start_time());
+        for (pid, p) in sys.processes() {
+            // A recycled parent PID would otherwise adopt older, unrelated processes
+            if p.parent() == Some(parent) && p.start_time() >= parent_start && !tree.contains(pid) {
+                tree.push(*pid);
+            }
+        }
+        i += 1;
+    }
+    tree
+}
+
+/// Terminate a process tree from `process_tree`; returns the names terminated
+///
+/// Parents go first so they can't respawn children that were already ended.
+fn kill_tree(sys: &System, tree: &[Pid]) -> Vec<String> {
+    tree.iter()
+        .copied()
+        .filter_map(|pid| sys.process(pid))
+        .filter(|p| p.kill())
+        .map(|p| p.name().to_string())
+        .collect()
+}
+
+/// Wait until none of `pids` is running; returns those still alive after `timeout`
+fn wait_for_exit(pids: &[Pid], timeout: Duration) -> Vec<String> {
+    let started = Instant::now();
+    let mut sys = System::new();
+    loop {
+        sys.refresh_processes();
+        let alive: Vec<String> = pids
+            .iter()
+            .filter_map(|pid| sys.process(*pid))
+            .map(|p| p.name().to_string())
+            .collect();
+        if alive.is_empty() || started.elapsed() >= timeout {
+            return alive;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Terminate running CapCut helper processes and their children; returns the names terminated
+pub fn kill_helpers() -> Vec<String> {
+    let root = match paths::get_capcut_root_path() {
+        Some(r) => r,
+        None => return Vec::new(),
+    };
+    let sys = refreshed_system();
+    let helpers: Vec<Pid> = helper_processes(&sys, &root)
+        .iter()
+        .map(|p| p.pid())
+        .collect();
+    kill_tree(&sys, &process_tree(&sys, &helpers))
+}
+
+/// Ask CapCut's windows to close (like clicking X), so it can shut down cleanly
+///
+/// taskkill without /F posts WM_CLOSE; windowless processes ignore it and are killed afterwards.
+fn request_close(sys: &System, roots: &[Pid], names: &[&str]) {
+    for pid in roots {
+        let is_main = sys.process(*pid).is_some_and(|p| is_main_process(p, names));
+        if is_main {
+            let _ = Command::new("taskkill")
+                .args(["/PID", &pid.as_u32().to_string()])
+                .creation_flags(CREATE_NO_WINDOW)
+                .output();
+        }
+    }
+}
+
+/// End CapCut, its child processes and its helpers and wait for them to exit (blocking)
+///
+/// With a `grace` period CapCut is first asked to close and only what is still running
+/// afterwards is killed. Orphaned children keep file handles open, so everything must have
+/// exited before this returns, or deleting versions right after fails.
+fn end_capcut(grace: Duration) -> Result<Vec<String>, String> {
+    let sys = refreshed_system();
+    let root = paths::get_capcut_root_path().map(|r| r.to_string_lossy().to_lowercase());
+    let names = application::current().profile().process_names;
+    // CapCut itself plus anything started from its folder (helpers, crash reporters)
+    let roots: Vec<Pid> = sys
+        .processes()
+        .values()
+        .filter(|p| {
+            is_main_process(p, names)
+                || matches!((p.exe(), &root), (Some(exe), Some(root))
+                    if exe.to_string_lossy().to_lowercase().starts_with(root))
+        })
+        .map(|p| p.pid())
+        .collect();
+    let tree = process_tree(&sys, &roots);
+    let listed_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(u64::MAX);
+    let mut ended: Vec<String> = tree
+        .iter()
+        .filter_map(|pid| sys.process(*pid))
+        .map(|p| p.name().to_string())
+        .collect();
+
+    if !grace.is_zero() {
+        request_close(&sys, &roots, names);
+        if wait_for_exit(&tree, grace).is_empty() {
+            return Ok(ended);
+        }
+    }
+
+    // PIDs freed during the grace period may already belong to new, unrelated processes
+    let sys = refreshed_system();
+    let tree: Vec<Pid> = tree
+        .into_iter()
+        .filter(|pid| {
+            sys.process(*pid)
+                .is_some_and(|p| p.start_time() <= listed_at)
+        })
+        .collect();
+    let killed = kill_tree(&sys, &tree);
+    if grace.is_zero() {
+        ended = killed;
+    }
+
+    let remaining = wait_for_exit(&tree, EXIT_TIMEOUT);
+    if remaining.is_empty() {
+        Ok(ended)
+    } else {
+        Err(format!(
+            "Could not end {} (try running as administrator)",
+            remaining.join(", ")
+        ))
+    }
+}
+
+/// Ask CapCut to close and force-kill whatever is still running after the grace period (blocking)
+pub fn close_capcut() -> Result<Vec<String>, String> {
+    end_capcut(CLOSE_GRACE)
+}
+
+/// Close CapCut gracefully, then force-kill leftovers ("Close CapCut for me" on the pre-check)
+#[tauri::command]
+pub async fn close_capcut_gracefully() -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(close_capcut)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Force-close CapCut, its child processes and its helpers at once (Resource Monitor)
+#[tauri::command]
+pub async fn force_close_capcut() -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(|| end_capcut(Duration::ZERO))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Terminate running CapCut helper processes (pre-check "End Processes")
+#[tauri::command]
+pub fn terminate_helper_processes() -> Result<Vec<String>, String> {
+    let killed = kill_helpers();
+    let remaining = find_helpers();
+    if remaining.is_empty() {
+        Ok(killed)
+    } else {
+        let names: Vec<_> = remaining.iter().map(|h| h.name.as_str()).collect();
+        Err(format!(
+            "Could not end {} (try running as administrator)",
+            names.join(", ")
+        ))
+    }
 }
 
 /// System pre-check results
@@ -25,22 +287,68 @@ pub struct PreCheckResult {
     pub capcut_found: bool,
     pub capcut_running: bool,
     pub apps_path: Option<String>,
+    /// Install folder name found (e.g., "CapCut" or a regional variant)
+    pub variant: Option<String>,
+    pub layout: Option<paths::InstallLayout>,
+    /// Release channel of the install (None if CapCut wasn't found)
+    pub channel: Option<Channel>,
+    /// OneDrive folder syncing the install, if any
+    pub cloud_sync: Option<String>,
+    /// Controlled Folder Access blocking writes to the install, if any
+    pub folder_access: Option<FolderAccessBlock>,
+    /// Removable, network or unavailable drive holding the install (None on a fixed disk)
+    pub volume: Option<paths::VolumeKind>,
+    /// Apps path as found when it is a junction or symlink (`apps_path` is the real folder)
+    pub linked_from: Option<String>,
+    /// CapCut helper/crash-reporter processes currently running
+    pub helpers: Vec<HelperProcess>,
 }
 
 /// Perform system pre-check
 #[tauri::command]
 pub fn perform_precheck() -> PreCheckResult {
-    let apps_path = std::env::var("LOCALAPPDATA")
-        .ok()
-        .map(|p| std::path::PathBuf::from(p).join("CapCut").join("Apps"));
+    let capcut_paths = paths::resolve_capcut_paths();
 
-    let capcut_found = apps_path.as_ref().map(|p| p.exists()).unwrap_or(false);
+    let capcut_found = capcut_paths
+        .as_ref()
+        .map(|p| p.apps.exists())
+        .unwrap_or(false);
     let capcut_running = is_capcut_running();
+    let channel = capcut_paths
+        .as_ref()
+        .filter(|_| capcut_found)
+        .map(|p| channel::detect_channel(&p.root, &p.apps));
+    let cloud_sync = capcut_paths
+        .as_ref()
+        .and_then(|p| paths::cloud_sync_root(&p.root))
+        .map(|p| p.to_string_lossy().to_string());
+    let folder_access = capcut_paths
+        .as_ref()
+        .filter(|_| capcut_found)
+        .and_then(|p| folder_access::check_blocked(&p.apps));
+    let volume = capcut_paths
+        .as_ref()
+        // The Apps folder may be linked to another drive than the root
+        .map(|p| paths::volume_kind(&p.apps))
+        .filter(|k| *k != paths::VolumeKind::Fixed);
 
     PreCheckResult {
         capcut_found,
         capcut_running,
-        apps_path: apps_path.map(|p| p.to_string_lossy().to_string()),
+        apps_path: capcut_paths
+            .as_ref()
+            .map(|p| p.apps.to_string_lossy().to_string()),
+        layout: capcut_paths.as_ref().map(|p| p.layout),
+        linked_from: capcut_paths
+            .as_ref()
+            .and_then(|p| p.linked_from.as_ref())
+            .map(|p| p.to_string_lossy().to_string()),
+        variant: capcut_paths.map(|p| p.variant),
+        channel,
+        cloud_sync,
+        folder_access,
+        volume,
+        helpers: find_helpers(),
     }
 }
 
@@ -74,12 +382,28 @@ pub fn launch_capcut() -> LaunchResult {
         .filter(|e| e.path().is_dir())
         .collect();
 
-    // Sort to get the latest version
+    // Newest version number first
     let mut version_paths: Vec<_> = versions.iter().map(|e| e.path()).collect();
-    version_paths.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+    version_paths.sort_by(|a, b| {
+        version::cmp_names(
+            &b.file_name().unwrap_or_default().to_string_lossy(),
+            &a.file_name().unwrap_or_default().to_string_lossy(),
+        )
+    });
+
+    // The protected version goes first
+    if let Some(kept) = reprotect::kept_version() {
+        if let Some(pos) = version_paths
+            .iter()
+            .position(|p| p.file_name().is_some_and(|n| n.to_string_lossy() == kept))
+        {
+            let protected = version_paths.remove(pos);
+            version_paths.insert(0, protected);
+        }
+    }
 
     for version_path in version_paths {
-        let exe_path = version_path.join("CapCut.exe");
+        let exe_path = paths::main_executable(&version_path);
         if exe_path.exists() {
             match Command::new(&exe_path).spawn() {
                 Ok(_) => {
@@ -103,3 +427,18 @@ pub fn launch_capcut() -> LaunchResult {
         error: Some("CapCut.exe not found in any version".to_string()),
     }
 }
+
+/// Close Version Guard, optionally starting the protected CapCut version first
+#[tauri::command]
+pub fn exit_app(app: AppHandle, launch_capcut_first: bool) -> Result<(), String> {
+    if launch_capcut_first {
+        let launched = launch_capcut();
+        if !launched.success {
+            // Stay open so the user sees why CapCut didn't start
+            return Err(launched.error.unwrap_or_default());
+        }
+    }
+    // Tauri's exit runs the normal shutdown (windows closed, plugins and tray cleaned up)
+    app.exit(0);
+    Ok(())
+}
@@ -2,31 +2,46 @@
 //! Migrated from original eframe/egui main.rs
 
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::process::Command;
-use walkdir::WalkDir;
-
-use super::paths;
-
-/// Unset readonly attribute recursively
-fn unset_readonly_recursive(path: &Path) -> Result<(), String> {
-    for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
-        let p = entry.path();
-        if let Ok(meta) = fs::metadata(p) {
-            let mut perms = meta.permissions();
-            if perms.readonly() {
-                perms.set_readonly(false);
-                fs::set_permissions(p, perms).ok();
+use std::time::Instant;
+
+use super::channel::{self, Channel, GuardProfile};
+use super::cleaner::CacheCategory;
+use super::history::{self, StepTiming};
+use super::log_entry::{self, LogEntry};
+use super::notifier::{self, AlertKind};
+use super::{
+    acl_lock, asset_migration, attributes, autoruns, blockers, config_backup, config_guard,
+    deletion_log, domain_block, drafts, exclusions, firewall, format, hardening, io_priority,
+    logging, paths, registry_guard, reprotect, revert, snapshot, updater_stub,
+};
+
+/// Pin CapCut's update-related registry values so the watchdog can restore them
+fn pin_registry(logs: &mut Vec<LogEntry>) {
+    match registry_guard::pin_registry_values() {
+        Ok(report) => {
+            for value in report.pinned {
+                logs.push(LogEntry::ok(format!("Registry value pinned: {}", value)));
+            }
+            for value in report.cleared {
+                logs.push(LogEntry::ok(format!(
+                    "Staged update cleared from registry: {}",
+                    value
+                )));
             }
         }
+        Err(e) => logs.push(LogEntry::warn(format!(
+            "Could not pin registry values: {}",
+            e
+        ))),
     }
-    Ok(())
 }
 
 /// Create readonly lock file
 fn create_readonly(path: &Path) -> Result<(), String> {
     if path.exists() {
-        unset_readonly_recursive(path).ok();
+        attributes::clear_readonly_recursive(path).ok();
         if path.is_dir() {
             fs::remove_dir_all(path).map_err(|e| e.to_string())?;
         } else {
@@ -42,55 +57,255 @@ fn create_readonly(path: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// Check if a configure.ini line sets the given key
+fn is_key_line(line: &[u8], key: &str) -> bool {
+    line.split(|b| *b == b'=').next().map(<[u8]>::trim_ascii) == Some(key.as_bytes())
+}
+
+/// Read configure.ini as raw lines
+///
+/// CapCut may store paths in the ANSI codepage, so the file is not always valid UTF-8;
+/// working on bytes keeps those lines intact instead of dropping the whole file.
+fn read_config_lines(config_path: &Path) -> Option<Vec<Vec<u8>>> {
+    let content = fs::read(config_path).ok()?;
+    let mut lines: Vec<Vec<u8>> = content
+        .split(|b| *b == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line).to_vec())
+        .collect();
+    // A trailing newline is not an extra line
+    if lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    Some(lines)
+}
+
+/// Write configure.ini lines back unchanged apart from the edits
+fn write_config_lines(config_path: &Path, lines: &[Vec<u8>]) -> Result<(), String> {
+    fs::write(config_path, lines.join(&b'\n')).map_err(|e| e.to_string())
+}
+
 /// Lock configuration file
-fn lock_configuration(apps_path: &Path) -> Result<(), String> {
+fn lock_configuration(apps_path: &Path, profile: &GuardProfile) -> Result<(), String> {
     let config_path = apps_path.join("configure.ini");
-    let content = if config_path.exists() {
-        fs::read_to_string(&config_path).unwrap_or_default()
+    let lines = if config_path.exists() {
+        read_config_lines(&config_path).ok_or("Could not read configure.ini")?
     } else {
-        String::new()
+        Vec::new()
     };
 
-    let mut new_lines: Vec<String> = Vec::new();
-    let mut found = false;
+    let mut new_lines: Vec<Vec<u8>> = Vec::new();
+    let mut found: Vec<&str> = Vec::new();
 
-    for line in content.lines() {
-        if line.trim().starts_with("last_version") {
-            new_lines.push("last_version=1.0.0.0".to_string());
-            found = true;
-        } else {
-            new_lines.push(line.to_string());
+    for line in lines {
+        match profile
+            .version_keys
+            .iter()
+            .find(|key| is_key_line(&line, key))
+        {
+            Some(key) => {
+                new_lines.push(format!("{}=1.0.0.0", key).into_bytes());
+                found.push(key);
+            }
+            None => new_lines.push(line),
         }
     }
 
-    if !found {
-        new_lines.push("last_version=1.0.0.0".to_string());
+    for key in profile.version_keys {
+        if !found.contains(key) {
+            new_lines.push(format!("{}=1.0.0.0", key).into_bytes());
+        }
     }
 
-    fs::write(config_path, new_lines.join("\n")).map_err(|e| e.to_string())?;
-    Ok(())
+    write_config_lines(&config_path, &new_lines)
+}
+
+/// Put the version keys of `original` back into configure.ini, keeping every other line
+///
+/// Keys the original didn't have are dropped, so CapCut writes its own on the next start.
+pub fn restore_version_keys(apps_path: &Path, original: &Path) -> Result<(), String> {
+    let capcut_root = apps_path.parent().ok_or("Invalid Apps folder")?;
+    let profile = channel::guard_profile(channel::detect_channel(capcut_root, apps_path));
+    let original_lines = read_config_lines(original).ok_or("Could not read saved configure.ini")?;
+    let config_path = apps_path.join("configure.ini");
+    let lines = if config_path.exists() {
+        read_config_lines(&config_path).ok_or("Could not read configure.ini")?
+    } else {
+        Vec::new()
+    };
+    let original_line = |key: &str| original_lines.iter().find(|l| is_key_line(l, key));
+
+    let mut new_lines: Vec<Vec<u8>> = Vec::new();
+    let mut found: Vec<&str> = Vec::new();
+    for line in lines {
+        match profile
+            .version_keys
+            .iter()
+            .find(|key| is_key_line(&line, key))
+        {
+            Some(key) => {
+                if let Some(restored) = original_line(key) {
+                    new_lines.push(restored.clone());
+                }
+                found.push(key);
+            }
+            None => new_lines.push(line),
+        }
+    }
+    for key in profile.version_keys {
+        if let Some(restored) = original_line(key).filter(|_| !found.contains(key)) {
+            new_lines.push(restored.clone());
+        }
+    }
+
+    write_config_lines(&config_path, &new_lines)
+}
+
+/// Back up configure.ini and ProductInfo.xml before they are overwritten
+///
+/// Every run gets a timestamped backup; the copy taken before the first lock is also kept for revert.
+fn save_originals(apps_path: &Path, profile: &GuardProfile, logs: &mut Vec<LogEntry>) {
+    match config_backup::create_config_backup(apps_path) {
+        Ok(Some(id)) => logs.push(LogEntry::ok(format!("Configuration backed up ({})", id))),
+        Ok(None) => {}
+        Err(e) => logs.push(LogEntry::warn(format!(
+            "Could not back up configuration: {}",
+            e
+        ))),
+    }
+    if let Err(e) = revert::save_originals(apps_path, is_config_locked(apps_path, profile)) {
+        logs.push(LogEntry::warn(format!(
+            "Could not keep original configuration: {}",
+            e
+        )));
+    }
+}
+
+/// Check if configure.ini pins every version key to 1.0.0.0
+fn is_config_locked(apps_path: &Path, profile: &GuardProfile) -> bool {
+    match read_config_lines(&apps_path.join("configure.ini")) {
+        Some(lines) => profile.version_keys.iter().all(|key| {
+            let pinned = format!("{}=1.0.0.0", key);
+            lines
+                .iter()
+                .any(|line| line.trim_ascii() == pinned.as_bytes())
+        }),
+        None => false,
+    }
+}
+
+/// Re-lock configure.ini if CapCut rewrote it; returns whether anything was restored
+pub fn relock_configuration(apps_path: &Path) -> Result<bool, String> {
+    let capcut_root = apps_path.parent().ok_or("Invalid Apps folder")?;
+    let profile = channel::guard_profile(channel::detect_channel(capcut_root, apps_path));
+    if is_config_locked(apps_path, &profile) {
+        return Ok(false);
+    }
+    lock_configuration(apps_path, &profile)?;
+    Ok(true)
+}
+
+/// Remember the locked configure.ini so the watchdog can restore it
+fn pin_config(apps_path: &Path, logs: &mut Vec<LogEntry>) {
+    if let Err(e) = config_guard::pin_config(apps_path) {
+        logs.push(LogEntry::warn(format!(
+            "Could not pin configure.ini: {}",
+            e
+        )));
+    }
 }
 
 /// Create dummy lock files
-fn create_dummy_files(capcut_path: &Path, apps_path: &Path) -> Result<(), String> {
+fn create_dummy_files(
+    capcut_path: &Path,
+    apps_path: &Path,
+    profile: &GuardProfile,
+    logs: &mut Vec<LogEntry>,
+) -> Result<(), String> {
     let pinfo = apps_path.join("ProductInfo.xml");
     create_readonly(&pinfo)?;
 
     let download_dir = capcut_path.join("User Data").join("Download");
     fs::create_dir_all(&download_dir).map_err(|e| e.to_string())?;
 
-    let update_exe = download_dir.join("update.exe");
-    create_readonly(&update_exe)?;
+    for name in profile.updater_names {
+        create_readonly(&download_dir.join(name))?;
+    }
+
+    // User-added paths only warn: a typo must not fail the whole run
+    for extra in blockers::extra_blocker_paths(capcut_path) {
+        if extra.is_dir() {
+            logs.push(
+                LogEntry::warn("Skipped extra blocker: a folder exists at that path")
+                    .with_path(&extra),
+            );
+            continue;
+        }
+        let created = extra
+            .parent()
+            .map_or(Ok(()), |p| fs::create_dir_all(p).map_err(|e| e.to_string()))
+            .and_then(|_| create_readonly(&extra));
+        match created {
+            Ok(()) => logs.push(LogEntry::ok("Extra blocker in place").with_path(&extra)),
+            Err(e) => logs.push(
+                LogEntry::warn(format!("Could not create extra blocker: {}", e)).with_path(&extra),
+            ),
+        }
+    }
 
     Ok(())
 }
 
+/// Detect the channel and log it when guards need to adapt
+fn detect_profile(capcut_root: &Path, apps_path: &Path, logs: &mut Vec<LogEntry>) -> GuardProfile {
+    let detected = channel::detect_channel(capcut_root, apps_path);
+    if detected == Channel::Beta {
+        logs.push(LogEntry::step(
+            "Beta channel detected - guarding beta updater and config keys",
+        ));
+    }
+    channel::guard_profile(detected)
+}
+
+/// Warn when OneDrive syncs the install (it can restore deleted folders and reset locks)
+fn warn_cloud_sync(capcut_root: &Path, logs: &mut Vec<LogEntry>) {
+    if let Some(sync_root) = paths::cloud_sync_root(capcut_root) {
+        logs.push(LogEntry::warn(format!(
+            "CapCut folder is synced by OneDrive ({}) - sync may restore deleted versions or reset locks",
+            sync_root.display()
+        )));
+    }
+}
+
+/// Remove a version directory, clearing read-only attributes first
+pub fn delete_version_dir(path: &Path) -> Result<(), String> {
+    // A junction to another location: remove the link, never what it points to
+    if paths::is_link(path) {
+        return fs::remove_dir(path).map_err(|e| e.to_string());
+    }
+    let _background = io_priority::enter();
+    // A file left read-only makes the removal below fail with the real error
+    attributes::clear_readonly_recursive(path).ok();
+    fs::remove_dir_all(path).map_err(|e| e.to_string())
+}
+
+/// A version folder that could not be deleted
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FailedDeletion {
+    pub path: String,
+    pub name: String,
+    pub error: String,
+}
+
 /// Protection result
 #[derive(serde::Serialize)]
 pub struct ProtectionResult {
     pub success: bool,
     pub error: Option<String>,
-    pub logs: Vec<String>,
+    pub logs: Vec<LogEntry>,
+    /// Version folders left behind (e.g., a file was locked); offered for retry
+    pub failed_deletions: Vec<FailedDeletion>,
+    /// Duration of each step of a full protection run
+    pub step_timings: Vec<StepTiming>,
 }
 
 /// Delete specified version directories (with automatic backup)
@@ -98,58 +313,87 @@ pub struct ProtectionResult {
 pub fn delete_versions(paths: Vec<String>) -> ProtectionResult {
     use super::backup;
 
-    let mut logs: Vec<String> = Vec::new();
+    let mut logs: Vec<LogEntry> = Vec::new();
+    let mut failed_deletions: Vec<FailedDeletion> = Vec::new();
+    let excluded = exclusions::excluded_paths();
+    let mut skipped = 0;
 
     for path_str in &paths {
-        let path = PathBuf::from(path_str);
+        let path = paths::path_from_transport(path_str);
         let name = path.file_name().unwrap_or_default().to_string_lossy();
 
+        if exclusions::touches_excluded(&path, &excluded) {
+            logs.push(
+                LogEntry::warn(format!("Skipped {} (on the never-touch list)", name))
+                    .with_path(&path),
+            );
+            skipped += 1;
+            continue;
+        }
+
         // Create backup before deletion
-        logs.push(format!("Backing up: {}", name));
+        logs.push(LogEntry::info(format!("Backing up: {}", name)).with_path(&path));
         let backup_result = backup::create_backup(&path, "Version deleted during protection");
 
         if backup_result.success {
             if let Some(backup_id) = &backup_result.backup_id {
-                logs.push(format!("[OK] Backup created: {}", backup_id));
+                logs.push(LogEntry::ok(format!("Backup created: {}", backup_id)));
             }
         } else {
             // Backup failed - warn but continue (user confirmed deletion)
-            logs.push(format!(
-                "[!] Backup failed: {}",
+            logs.push(LogEntry::warn(format!(
+                "Backup failed: {}",
                 backup_result.error.unwrap_or_default()
+            )));
+            logs.push(LogEntry::warn(
+                "Proceeding with deletion (backup unavailable)",
             ));
-            logs.push("[!] Proceeding with deletion (backup unavailable)".to_string());
         }
 
-        logs.push(format!("Deleting: {}", name));
+        logs.push(LogEntry::info(format!("Deleting: {}", name)).with_path(&path));
 
-        if let Err(e) = unset_readonly_recursive(&path) {
-            logs.push(format!("[!] Warning: {}", e));
-        }
-
-        if let Err(e) = fs::remove_dir_all(&path) {
-            return ProtectionResult {
-                success: false,
-                error: Some(format!("Failed to delete {}: {}", name, e)),
-                logs,
-            };
+        // A locked file in one folder shouldn't stop the rest
+        if let Err(e) = deletion_log::remove_logged(&path, "version", delete_version_dir) {
+            logs.push(LogEntry::warn(format!("Failed to delete {}: {}", name, e)).with_path(&path));
+            failed_deletions.push(FailedDeletion {
+                path: path_str.clone(),
+                name: name.to_string(),
+                error: e,
+            });
         }
     }
 
+    let deleted = paths.len() - failed_deletions.len() - skipped;
     if paths.is_empty() {
-        logs.push("[OK] No versions to delete".to_string());
-    } else {
-        logs.push(format!("[OK] Deleted {} version(s)", paths.len()));
-        logs.push("[OK] Backups available for recovery".to_string());
+        logs.push(LogEntry::ok("No versions to delete"));
+    } else if deleted > 0 {
+        logs.push(LogEntry::ok(format!("Deleted {} version(s)", deleted)));
+        logs.push(LogEntry::ok("Backups available for recovery"));
     }
 
     ProtectionResult {
-        success: true,
-        error: None,
+        success: failed_deletions.is_empty(),
+        error: (!failed_deletions.is_empty())
+            .then(|| format!("{} version(s) could not be deleted", failed_deletions.len())),
         logs,
+        failed_deletions,
+        step_timings: Vec::new(),
     }
 }
 
+/// Retry deleting a version folder that failed earlier (its backup was already made)
+#[tauri::command]
+pub fn retry_version_delete(path: String) -> Result<(), String> {
+    if super::process::is_capcut_running() {
+        return Err("CapCut is still running. Please close it.".to_string());
+    }
+    let path = paths::path_from_transport(&path);
+    if !path.exists() {
+        return Ok(());
+    }
+    delete_version_dir(&path)
+}
+
 /// Apply protection (lock config + create locks)
 #[tauri::command]
 pub fn apply_protection() -> ProtectionResult {
@@ -160,40 +404,53 @@ pub fn apply_protection() -> ProtectionResult {
                 success: false,
                 error: Some("Could not find CapCut installation".to_string()),
                 logs: vec![],
+                failed_deletions: Vec::new(),
+                step_timings: Vec::new(),
             }
         }
     };
 
     let apps_path = capcut_paths.apps;
     let capcut_root = capcut_paths.root;
-    let mut logs: Vec<String> = Vec::new();
+    let mut logs: Vec<LogEntry> = Vec::new();
+    let profile = detect_profile(&capcut_root, &apps_path, &mut logs);
+    warn_cloud_sync(&capcut_root, &mut logs);
+    save_originals(&apps_path, &profile, &mut logs);
 
     // Lock configuration
-    logs.push("Modifying config...".to_string());
-    if let Err(e) = lock_configuration(&apps_path) {
+    logs.push(LogEntry::info("Modifying config..."));
+    if let Err(e) = lock_configuration(&apps_path, &profile) {
         return ProtectionResult {
             success: false,
             error: Some(e),
             logs,
+            failed_deletions: Vec::new(),
+            step_timings: Vec::new(),
         };
     }
-    logs.push("[OK] Configuration locked".to_string());
+    logs.push(LogEntry::ok("Configuration locked"));
+    pin_registry(&mut logs);
+    pin_config(&apps_path, &mut logs);
 
     // Create locks
-    logs.push("Creating locks...".to_string());
-    if let Err(e) = create_dummy_files(&capcut_root, &apps_path) {
+    logs.push(LogEntry::info("Creating locks..."));
+    if let Err(e) = create_dummy_files(&capcut_root, &apps_path, &profile, &mut logs) {
         return ProtectionResult {
             success: false,
             error: Some(e),
             logs,
+            failed_deletions: Vec::new(),
+            step_timings: Vec::new(),
         };
     }
-    logs.push("[OK] Version lock active".to_string());
+    logs.push(LogEntry::ok("Version lock active"));
 
     ProtectionResult {
         success: true,
         error: None,
         logs,
+        failed_deletions: Vec::new(),
+        step_timings: Vec::new(),
     }
 }
 
@@ -206,48 +463,99 @@ pub fn apply_protection_with_options(lock_config: bool, create_blockers: bool) -
                 success: false,
                 error: Some("Could not find CapCut installation".to_string()),
                 logs: vec![],
+                failed_deletions: Vec::new(),
+                step_timings: Vec::new(),
             }
         }
     };
 
-    let apps_path = capcut_paths.apps;
-    let capcut_root = capcut_paths.root;
-    let mut logs: Vec<String> = Vec::new();
+    let mut result = apply_protection_at(
+        &capcut_paths.root,
+        &capcut_paths.apps,
+        lock_config,
+        create_blockers,
+    );
+    if result.success && lock_config {
+        pin_registry(&mut result.logs);
+        pin_config(&capcut_paths.apps, &mut result.logs);
+    }
+    result
+}
+
+/// Apply protection to a specific CapCut installation
+pub fn apply_protection_at(
+    capcut_root: &Path,
+    apps_path: &Path,
+    lock_config: bool,
+    create_blockers: bool,
+) -> ProtectionResult {
+    let mut logs: Vec<LogEntry> = Vec::new();
+    let profile = detect_profile(capcut_root, apps_path, &mut logs);
+    warn_cloud_sync(capcut_root, &mut logs);
+    if lock_config || create_blockers {
+        save_originals(apps_path, &profile, &mut logs);
+    }
 
     // Lock configuration if enabled
     if lock_config {
-        logs.push("Modifying config...".to_string());
-        if let Err(e) = lock_configuration(&apps_path) {
+        logs.push(LogEntry::info("Modifying config..."));
+        if let Err(e) = lock_configuration(apps_path, &profile) {
             return ProtectionResult {
                 success: false,
                 error: Some(e),
                 logs,
+                failed_deletions: Vec::new(),
+                step_timings: Vec::new(),
             };
         }
-        logs.push("[OK] Configuration locked".to_string());
+        logs.push(LogEntry::ok("Configuration locked"));
     } else {
-        logs.push("Skipping config lock (disabled)".to_string());
+        logs.push(LogEntry::info("Skipping config lock (disabled)"));
     }
 
     // Create locks if enabled
     if create_blockers {
-        logs.push("Creating locks...".to_string());
-        if let Err(e) = create_dummy_files(&capcut_root, &apps_path) {
+        logs.push(LogEntry::info("Creating locks..."));
+        // A non-empty updater means CapCut already downloaded an update
+        let download_dir = capcut_root.join("User Data").join("Download");
+        let payload = profile
+            .updater_names
+            .iter()
+            .map(|name| download_dir.join(name))
+            .chain(blockers::extra_blocker_paths(capcut_root))
+            .find(|p| fs::metadata(p).is_ok_and(|m| m.is_file() && m.len() > 0));
+
+        if let Err(e) = create_dummy_files(capcut_root, apps_path, &profile, &mut logs) {
             return ProtectionResult {
                 success: false,
                 error: Some(e),
                 logs,
+                failed_deletions: Vec::new(),
+                step_timings: Vec::new(),
             };
         }
-        logs.push("[OK] Version lock active".to_string());
+        logs.push(LogEntry::ok("Version lock active"));
+
+        if let Some(payload) = payload {
+            logs.push(LogEntry::ok("Neutralized downloaded update payload"));
+            let details = format!(
+                "A downloaded CapCut updater was found and replaced with a blocker.\n\nPath: {}",
+                payload.display()
+            );
+            if let Err(e) = notifier::send_alert(AlertKind::UpdateBlocked, &details) {
+                logs.push(LogEntry::warn(format!("Email alert failed: {}", e)));
+            }
+        }
     } else {
-        logs.push("Skipping lock creation (disabled)".to_string());
+        logs.push(LogEntry::info("Skipping lock creation (disabled)"));
     }
 
     ProtectionResult {
         success: true,
         error: None,
         logs,
+        failed_deletions: Vec::new(),
+        step_timings: Vec::new(),
     }
 }
 
@@ -255,69 +563,296 @@ pub fn apply_protection_with_options(lock_config: bool, create_blockers: bool) -
 #[derive(serde::Deserialize)]
 pub struct ProtectionParams {
     pub versions_to_delete: Vec<String>,
+    /// Version being kept; downloaded assets are moved into it before deletion
+    #[serde(default)]
+    pub keep_version: Option<String>,
+    /// Zip the drafts into this folder before anything is deleted; the run stops if that fails
+    #[serde(default)]
+    pub backup_drafts_to: Option<String>,
+    /// Projects to zip; empty means all
+    #[serde(default)]
+    pub draft_projects: Vec<String>,
     pub clean_cache: bool,
+    /// Cache categories to purge; empty means the cleaner's defaults
+    #[serde(default)]
+    pub cache_categories: Vec<CacheCategory>,
     pub lock_config: bool,
     pub create_blockers: bool,
+    /// Make the kept version's executables and manifests read-only
+    #[serde(default)]
+    pub harden_binaries: bool,
+    /// Rename the updater executables to `.bak` and put a do-nothing stub in their place
+    #[serde(default)]
+    pub stub_updaters: bool,
+    /// Deny writes and deletes on the Apps folder, the kept version's binaries and ProductInfo.xml
+    #[serde(default)]
+    pub lock_acls: bool,
+    /// Point CapCut's update-check domains to 0.0.0.0 in the hosts file
+    #[serde(default)]
+    pub block_domains: bool,
+    /// Block the updater executables with outbound Windows Firewall rules
+    #[serde(default)]
+    pub firewall_rules: bool,
+    /// Disable CapCut's scheduled tasks and Run-key entries
+    #[serde(default)]
+    pub disable_autoruns: bool,
 }
 
-#[tauri::command]
-pub fn run_full_protection(params: ProtectionParams) -> ProtectionResult {
+/// Run the protection steps, timing each one; returns the folders that could not be deleted
+fn run_protection_steps(
+    params: &ProtectionParams,
+    logs: &mut Vec<LogEntry>,
+    timings: &mut Vec<StepTiming>,
+) -> Result<Vec<FailedDeletion>, Option<String>> {
     use crate::commands::cleaner;
     use crate::commands::process;
 
-    let mut all_logs: Vec<String> = Vec::new();
-
     // Check if CapCut is running
-    all_logs.push("Checking system state...".to_string());
+    let started = Instant::now();
+    logs.push(LogEntry::info("Checking system state..."));
     if process::is_capcut_running() {
-        return ProtectionResult {
-            success: false,
-            error: Some("CapCut is still running. Please close it.".to_string()),
-            logs: all_logs,
-        };
+        return Err(Some(
+            "CapCut is still running. Please close it.".to_string(),
+        ));
     }
-    all_logs.push("[OK] No running instances".to_string());
+    logs.push(LogEntry::ok("No running instances"));
+    // Deny entries of the last run would block the deletions and rewrites below
+    acl_lock::unlock_all(logs);
+    timings.push(StepTiming::since("System check", started));
+    log_entry::tag_step(logs, 0, "System check");
 
-    // Delete versions
-    let delete_result = delete_versions(params.versions_to_delete);
-    all_logs.extend(delete_result.logs);
-    if !delete_result.success {
-        return ProtectionResult {
-            success: false,
-            error: delete_result.error,
-            logs: all_logs,
+    // Zip the user's projects before anything below can touch them
+    if let Some(dest) = &params.backup_drafts_to {
+        let started = Instant::now();
+        let first = logs.len();
+        logs.push(LogEntry::info("Backing up drafts..."));
+        let backup = if drafts::projects_to_archive(&params.draft_projects).is_empty() {
+            logs.push(LogEntry::info("No CapCut projects to back up"));
+            Ok(())
+        } else {
+            drafts::archive_drafts(&params.draft_projects, &paths::path_from_transport(dest)).map(
+                |info| {
+                    logs.push(LogEntry::ok(format!(
+                        "{} project(s) backed up to {} ({})",
+                        info.projects,
+                        info.path,
+                        format::format_size(info.total_bytes)
+                    )));
+                },
+            )
         };
+        timings.push(StepTiming::since("Draft backup", started));
+        log_entry::tag_step(logs, first, "Draft backup");
+        if let Err(e) = backup {
+            return Err(Some(format!("Draft backup failed: {}", e)));
+        }
+    }
+
+    // Keep effects/fonts/LUTs the user already downloaded into other versions
+    if let Some(kept) = &params.keep_version {
+        let started = Instant::now();
+        let first = logs.len();
+        let kept = paths::path_from_transport(kept);
+        // Never-touch folders are neither emptied nor deleted
+        let excluded = exclusions::excluded_paths();
+        let mut moved = 0;
+        for path in params
+            .versions_to_delete
+            .iter()
+            .map(|p| paths::path_from_transport(p))
+        {
+            if exclusions::touches_excluded(&path, &excluded) {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                logs.push(
+                    LogEntry::warn(format!("Skipped {} (on the never-touch list)", name))
+                        .with_path(&path),
+                );
+                continue;
+            }
+            moved += asset_migration::migrate_assets(&path, &kept, logs);
+        }
+        if moved > 0 {
+            logs.push(LogEntry::ok(format!("{} downloaded asset(s) kept", moved)));
+        }
+        timings.push(StepTiming::since("Asset migration", started));
+        log_entry::tag_step(logs, first, "Asset migration");
     }
 
+    // Delete versions; leftovers are reported for retry instead of aborting the run
+    let started = Instant::now();
+    let first = logs.len();
+    let delete_result = delete_versions(params.versions_to_delete.clone());
+    logs.extend(delete_result.logs);
+    timings.push(StepTiming::since("Version cleanup", started));
+    log_entry::tag_step(logs, first, "Version cleanup");
+
     // Clean cache if enabled
     if params.clean_cache {
-        all_logs.push("Cleaning cache directories...".to_string());
-        let cache_result = cleaner::clean_cache();
-        all_logs.extend(cache_result.logs);
+        let started = Instant::now();
+        let first = logs.len();
+        logs.push(LogEntry::info("Cleaning cache directories..."));
+        let cache_result = match paths::get_capcut_root_path() {
+            Some(root) => cleaner::clean_cache_at(
+                &root,
+                &cleaner::resolve_categories(&params.cache_categories),
+            ),
+            None => cleaner::clean_cache(None),
+        };
+        logs.extend(cache_result.logs);
+        timings.push(StepTiming::since("Cache cleaning", started));
+        log_entry::tag_step(logs, first, "Cache cleaning");
     } else {
-        all_logs.push("Skipping cache cleaning (disabled)".to_string());
+        logs.push(LogEntry::info("Skipping cache cleaning (disabled)"));
     }
 
     // Apply protection (conditionally based on flags)
     if params.lock_config || params.create_blockers {
+        let started = Instant::now();
+        let first = logs.len();
         let protect_result =
             apply_protection_with_options(params.lock_config, params.create_blockers);
-        all_logs.extend(protect_result.logs);
+        logs.extend(protect_result.logs);
+        timings.push(StepTiming::since("Version lock", started));
+        log_entry::tag_step(logs, first, "Version lock");
         if !protect_result.success {
-            return ProtectionResult {
-                success: false,
-                error: protect_result.error,
-                logs: all_logs,
-            };
+            return Err(protect_result.error);
         }
     } else {
-        all_logs.push("Skipping protection (all options disabled)".to_string());
+        logs.push(LogEntry::info("Skipping protection (all options disabled)"));
     }
 
-    ProtectionResult {
-        success: true,
-        error: None,
-        logs: all_logs,
+    // After the blockers, so updaters already replaced by one are left to it
+    if params.stub_updaters {
+        let started = Instant::now();
+        let first = logs.len();
+        logs.push(LogEntry::info("Replacing updaters with stubs..."));
+        if let Err(e) = updater_stub::stub_updaters(logs) {
+            logs.push(LogEntry::warn(e));
+        }
+        timings.push(StepTiming::since("Updater stubs", started));
+        log_entry::tag_step(logs, first, "Updater stubs");
+    }
+
+    // Hosts file entries need administrator rights; without them the run still succeeds
+    if params.block_domains {
+        let started = Instant::now();
+        let first = logs.len();
+        logs.push(LogEntry::info(
+            "Blocking update domains in the hosts file...",
+        ));
+        match domain_block::apply() {
+            Ok(()) => logs.push(LogEntry::ok(format!(
+                "{} update domain(s) blocked",
+                domain_block::UPDATE_DOMAINS.len()
+            ))),
+            Err(e) => logs.push(LogEntry::warn(e)),
+        }
+        timings.push(StepTiming::since("Update domain blocking", started));
+        log_entry::tag_step(logs, first, "Update domain blocking");
+    }
+
+    // Firewall rules need administrator rights too; missing ones are a warning
+    if params.firewall_rules {
+        let started = Instant::now();
+        let first = logs.len();
+        logs.push(LogEntry::info("Adding updater firewall rules..."));
+        if let Err(e) = firewall::block_updaters(logs) {
+            logs.push(LogEntry::warn(e));
+        }
+        timings.push(StepTiming::since("Firewall rules", started));
+        log_entry::tag_step(logs, first, "Firewall rules");
+    }
+
+    // Tasks and Run entries can start the updater at logon; entries that can't be changed are a warning
+    if params.disable_autoruns {
+        let started = Instant::now();
+        let first = logs.len();
+        logs.push(LogEntry::info(
+            "Disabling CapCut scheduled tasks and autorun entries...",
+        ));
+        if let Err(e) = autoruns::disable_all(logs) {
+            logs.push(LogEntry::warn(e));
+        }
+        timings.push(StepTiming::since("Autorun entries", started));
+        log_entry::tag_step(logs, first, "Autorun entries");
+    }
+
+    // Make in-place patching of the kept version harder
+    if let (true, Some(kept)) = (params.harden_binaries, &params.keep_version) {
+        let started = Instant::now();
+        let first = logs.len();
+        logs.push(LogEntry::info("Hardening kept version binaries..."));
+        if let Err(e) = hardening::harden_version(&paths::path_from_transport(kept), logs) {
+            logs.push(LogEntry::warn(e));
+        }
+        timings.push(StepTiming::since("Binary hardening", started));
+        log_entry::tag_step(logs, first, "Binary hardening");
+    }
+
+    // Deny the updater what read-only attributes cannot: clearing them
+    if let (true, Some(kept)) = (params.lock_acls, &params.keep_version) {
+        let started = Instant::now();
+        let first = logs.len();
+        logs.push(LogEntry::info("Locking folder permissions..."));
+        match paths::get_capcut_apps_path() {
+            Some(apps) => {
+                if let Err(e) =
+                    acl_lock::lock_install(&apps, &paths::path_from_transport(kept), logs)
+                {
+                    logs.push(LogEntry::warn(e));
+                }
+            }
+            None => logs.push(LogEntry::warn("Could not find the Apps folder")),
+        }
+        timings.push(StepTiming::since("ACL lock", started));
+        log_entry::tag_step(logs, first, "ACL lock");
+    }
+
+    Ok(delete_result.failed_deletions)
+}
+
+#[tauri::command]
+pub fn run_full_protection(params: ProtectionParams) -> ProtectionResult {
+    let mut all_logs: Vec<LogEntry> = Vec::new();
+    let mut step_timings: Vec<StepTiming> = Vec::new();
+
+    let before = snapshot::capture();
+    let outcome = run_protection_steps(&params, &mut all_logs, &mut step_timings);
+    let changes = snapshot::diff(&before, &snapshot::capture());
+    logging::record("protect", &all_logs);
+    if let Err(Some(e)) = &outcome {
+        tracing::error!(run = "protect", "{}", e);
+    }
+    let kept_version = params.keep_version.as_deref().map(|kept| {
+        paths::path_from_transport(kept)
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    });
+
+    match outcome {
+        Ok(failed_deletions) => {
+            reprotect::save_profile(&params);
+            history::record_run(kept_version, true, None, &step_timings, changes);
+            ProtectionResult {
+                success: true,
+                error: None,
+                logs: all_logs,
+                failed_deletions,
+                step_timings,
+            }
+        }
+        Err(error) => {
+            history::record_run(kept_version, false, error.clone(), &step_timings, changes);
+            ProtectionResult {
+                success: false,
+                error,
+                logs: all_logs,
+                failed_deletions: Vec::new(),
+                step_timings,
+            }
+        }
     }
 }
 
@@ -358,32 +893,18 @@ pub fn check_protection_status() -> ProtectionStatus {
         false
     };
 
-    // Check if update.exe lock exists
-    let update_blocker = capcut_root
-        .join("User Data")
-        .join("Download")
-        .join("update.exe");
-    let update_blocked = if update_blocker.exists() {
-        if let Ok(meta) = fs::metadata(&update_blocker) {
-            meta.len() == 0 && meta.permissions().readonly()
-        } else {
-            false
-        }
-    } else {
-        false
-    };
+    let profile = channel::guard_profile(channel::detect_channel(&capcut_root, &apps_path));
 
-    // Check if configure.ini has last_version=1.0.0.0
-    let config_path = apps_path.join("configure.ini");
-    let config_locked = if config_path.exists() {
-        if let Ok(content) = fs::read_to_string(&config_path) {
-            content.contains("last_version=1.0.0.0")
-        } else {
-            false
-        }
-    } else {
-        false
-    };
+    // Check if every updater lock for this channel exists
+    let download_dir = capcut_root.join("User Data").join("Download");
+    let update_blocked = profile
+        .updater_names
+        .iter()
+        .map(|name| download_dir.join(name))
+        .chain(blockers::extra_blocker_paths(&capcut_root))
+        .all(|p| blockers::is_blocker(&p));
+
+    let config_locked = is_config_locked(&apps_path, &profile);
 
     ProtectionStatus {
         is_protected: blockers_exist || update_blocked || config_locked,
@@ -395,6 +916,16 @@ pub fn check_protection_status() -> ProtectionStatus {
 /// Remove all protection measures
 #[tauri::command]
 pub fn remove_protection() -> ProtectionResult {
+    if super::policy::load_policy().disallow_unprotect {
+        return ProtectionResult {
+            success: false,
+            error: Some(super::policy::POLICY_BLOCKED.to_string()),
+            logs: vec![],
+            failed_deletions: Vec::new(),
+            step_timings: Vec::new(),
+        };
+    }
+
     let capcut_paths = match paths::resolve_capcut_paths() {
         Some(p) => p,
         None => {
@@ -402,68 +933,140 @@ pub fn remove_protection() -> ProtectionResult {
                 success: false,
                 error: Some("Could not find CapCut installation".to_string()),
                 logs: vec![],
+                failed_deletions: Vec::new(),
+                step_timings: Vec::new(),
             }
         }
     };
 
     let apps_path = capcut_paths.apps;
     let capcut_root = capcut_paths.root;
-    let mut logs: Vec<String> = Vec::new();
+    let mut logs: Vec<LogEntry> = Vec::new();
+    let profile = channel::guard_profile(channel::detect_channel(&capcut_root, &apps_path));
+
+    // Release the pin first so the watchdog doesn't re-lock what is reset below
+    if let Err(e) = config_guard::clear_pin() {
+        logs.push(LogEntry::warn(format!(
+            "Could not release configure.ini pin: {}",
+            e
+        )));
+    }
+
+    // Deny entries would block removing the blockers below
+    acl_lock::unlock_all(&mut logs);
 
     // Remove ProductInfo.xml lock
     let product_info = apps_path.join("ProductInfo.xml");
     if product_info.exists() {
-        logs.push("Removing ProductInfo.xml lock...".to_string());
-        if let Err(e) = unset_readonly_recursive(&product_info) {
-            logs.push(format!("[!] Warning: {}", e));
+        logs.push(LogEntry::info("Removing ProductInfo.xml lock..."));
+        if let Err(e) = attributes::clear_readonly_recursive(&product_info) {
+            logs.push(LogEntry::warn(format!("Warning: {}", e)));
         }
         if let Err(e) = fs::remove_file(&product_info) {
-            logs.push(format!("[!] Could not remove ProductInfo.xml: {}", e));
+            logs.push(LogEntry::warn(format!(
+                "Could not remove ProductInfo.xml: {}",
+                e
+            )));
         } else {
-            logs.push("[OK] ProductInfo.xml lock removed".to_string());
+            logs.push(LogEntry::ok("ProductInfo.xml lock removed"));
         }
     }
 
-    // Remove update.exe lock
-    let update_blocker = capcut_root
-        .join("User Data")
-        .join("Download")
-        .join("update.exe");
-    if update_blocker.exists() {
-        logs.push("Removing update.exe lock...".to_string());
-        if let Err(e) = unset_readonly_recursive(&update_blocker) {
-            logs.push(format!("[!] Warning: {}", e));
+    // Remove updater locks
+    let download_dir = capcut_root.join("User Data").join("Download");
+    for name in profile.updater_names {
+        let update_blocker = download_dir.join(name);
+        if !update_blocker.exists() {
+            continue;
+        }
+        logs.push(LogEntry::info(format!("Removing {} lock...", name)));
+        if let Err(e) = attributes::clear_readonly_recursive(&update_blocker) {
+            logs.push(LogEntry::warn(format!("Warning: {}", e)));
         }
         if let Err(e) = fs::remove_file(&update_blocker) {
-            logs.push(format!("[!] Could not remove update.exe: {}", e));
+            logs.push(LogEntry::warn(format!("Could not remove {}: {}", name, e)));
         } else {
-            logs.push("[OK] update.exe lock removed".to_string());
+            logs.push(LogEntry::ok(format!("{} lock removed", name)));
+        }
+    }
+
+    // Remove user-added locks; anything that isn't an empty read-only file was not ours
+    for extra in blockers::extra_blocker_paths(&capcut_root) {
+        if !blockers::is_blocker(&extra) {
+            continue;
+        }
+        if let Err(e) = attributes::clear_readonly_recursive(&extra) {
+            logs.push(LogEntry::warn(format!("Warning: {}", e)));
+        }
+        match fs::remove_file(&extra) {
+            Ok(()) => logs.push(LogEntry::ok("Extra blocker removed").with_path(&extra)),
+            Err(e) => logs.push(
+                LogEntry::warn(format!("Could not remove extra blocker: {}", e)).with_path(&extra),
+            ),
         }
     }
 
-    // Reset configure.ini (remove last_version lock)
+    // Reset configure.ini (remove version key locks)
     let config_path = apps_path.join("configure.ini");
     if config_path.exists() {
-        logs.push("Resetting configure.ini...".to_string());
-        if let Ok(content) = fs::read_to_string(&config_path) {
-            let new_content: String = content
-                .lines()
-                .filter(|line| !line.trim().starts_with("last_version"))
-                .collect::<Vec<_>>()
-                .join("\n");
-            if let Err(e) = fs::write(&config_path, new_content) {
-                logs.push(format!("[!] Could not reset configure.ini: {}", e));
+        logs.push(LogEntry::info("Resetting configure.ini..."));
+        if let Some(lines) = read_config_lines(&config_path) {
+            let new_lines: Vec<Vec<u8>> = lines
+                .into_iter()
+                .filter(|line| {
+                    !profile
+                        .version_keys
+                        .iter()
+                        .any(|key| is_key_line(line, key))
+                })
+                .collect();
+            if let Err(e) = write_config_lines(&config_path, &new_lines) {
+                logs.push(LogEntry::warn(format!(
+                    "Could not reset configure.ini: {}",
+                    e
+                )));
             } else {
-                logs.push("[OK] configure.ini reset".to_string());
+                logs.push(LogEntry::ok("configure.ini reset"));
             }
         }
     }
 
-    logs.push("[OK] Protection removed - CapCut allows updates".to_string());
+    for value in registry_guard::restore_cleared_values() {
+        logs.push(LogEntry::ok(format!("Registry value restored: {}", value)));
+    }
+    if let Err(e) = registry_guard::clear_pins() {
+        logs.push(LogEntry::warn(format!(
+            "Could not release registry pins: {}",
+            e
+        )));
+    }
+
+    hardening::unharden_all(&mut logs);
+    updater_stub::restore_all(&mut logs);
+
+    if let Err(e) = firewall::remove_all(&mut logs) {
+        logs.push(LogEntry::warn(e));
+    }
+
+    autoruns::restore_disabled(&mut logs);
+
+    if domain_block::is_blocked() {
+        match domain_block::remove() {
+            Ok(()) => logs.push(LogEntry::ok("Update domains unblocked")),
+            Err(e) => logs.push(LogEntry::warn(format!(
+                "Could not unblock update domains: {}",
+                e
+            ))),
+        }
+    }
+
+    logs.push(LogEntry::ok("Protection removed - CapCut allows updates"));
 
     ProtectionResult {
         success: true,
         error: None,
         logs,
+        failed_deletions: Vec::new(),
+        step_timings: Vec::new(),
     }
 }
@@ -0,0 +1,273 @@
+//! Registry guard for CapCut update state
+//! Pins update/channel values when protection is applied and restores them if CapCut rewrites them;
+//! values announcing a staged update are cleared instead and written back on revert
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use winreg::enums::*;
+use winreg::{RegKey, RegValue};
+
+use super::paths;
+
+/// HKCU keys where CapCut (and JianYing) keep update state
+const WATCHED_KEYS: &[&str] = &[
+    r"Software\Bytedance\CapCut",
+    r"Software\CapCut",
+    r"Software\Bytedance\JianyingPro",
+];
+
+/// Value names containing any of these are considered update-related
+const WATCHED_NAME_HINTS: &[&str] = &["version", "update", "channel"];
+
+/// Value names containing any of these announce a downloaded update and are cleared
+const PENDING_NAME_HINTS: &[&str] = &["pending", "newversion", "new_version", "downloaded"];
+
+/// A registry value captured at protection time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PinnedValue {
+    key: String,
+    name: String,
+    vtype: String,
+    bytes: Vec<u8>,
+    /// Deleted at protection time (kept absent by the watchdog, written back on revert)
+    #[serde(default)]
+    cleared: bool,
+}
+
+/// Values changed by pinning, as HKCU\key\name paths
+#[derive(Debug, Default)]
+pub struct PinReport {
+    pub pinned: Vec<String>,
+    pub cleared: Vec<String>,
+}
+
+impl PinnedValue {
+    fn display_path(&self) -> String {
+        format!("HKCU\\{}\\{}", self.key, self.name)
+    }
+}
+
+/// Get the pinned values file path
+fn get_pins_path() -> Option<PathBuf> {
+    paths::get_app_data_dir().map(|p| p.join("registry_pins.json"))
+}
+
+fn load_pins() -> Vec<PinnedValue> {
+    get_pins_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_pins(pins: &[PinnedValue]) -> Result<(), String> {
+    let path = get_pins_path().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(pins).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save registry pins: {}", e))
+}
+
+fn type_name(vtype: &RegType) -> Option<&'static str> {
+    match vtype {
+        REG_SZ => Some("sz"),
+        REG_EXPAND_SZ => Some("expand_sz"),
+        REG_MULTI_SZ => Some("multi_sz"),
+        REG_DWORD => Some("dword"),
+        REG_QWORD => Some("qword"),
+        REG_BINARY => Some("binary"),
+        _ => None,
+    }
+}
+
+fn type_from_name(name: &str) -> Option<RegType> {
+    match name {
+        "sz" => Some(REG_SZ),
+        "expand_sz" => Some(REG_EXPAND_SZ),
+        "multi_sz" => Some(REG_MULTI_SZ),
+        "dword" => Some(REG_DWORD),
+        "qword" => Some(REG_QWORD),
+        "binary" => Some(REG_BINARY),
+        _ => None,
+    }
+}
+
+fn is_watched_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    WATCHED_NAME_HINTS.iter().any(|hint| lower.contains(hint))
+}
+
+fn is_pending_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    PENDING_NAME_HINTS.iter().any(|hint| lower.contains(hint))
+}
+
+/// Current update-related values as (HKCU\key\name, value) pairs
+pub fn watched_values() -> Vec<(String, String)> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let mut values = Vec::new();
+    for key_path in WATCHED_KEYS {
+        let key = match hkcu.open_subkey(key_path) {
+            Ok(k) => k,
+            Err(_) => continue,
+        };
+        for (name, value) in key.enum_values().filter_map(|v| v.ok()) {
+            if is_watched_name(&name) {
+                values.push((format!("HKCU\\{}\\{}", key_path, name), value.to_string()));
+            }
+        }
+    }
+    values
+}
+
+/// Staged-update values pinning would clear, as HKCU\key\name paths
+pub fn pending_values() -> Vec<String> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let mut values = Vec::new();
+    for key_path in WATCHED_KEYS {
+        let key = match hkcu.open_subkey(key_path) {
+            Ok(k) => k,
+            Err(_) => continue,
+        };
+        for name in key
+            .enum_values()
+            .filter_map(|v| v.ok())
+            .map(|(name, _)| name)
+        {
+            if is_pending_name(&name) {
+                values.push(format!("HKCU\\{}\\{}", key_path, name));
+            }
+        }
+    }
+    values
+}
+
+/// Snapshot update-related values and clear staged-update ones
+pub fn pin_registry_values() -> Result<PinReport, String> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    // Values cleared by an earlier run are gone now; their originals must survive re-protecting
+    let mut pins: Vec<PinnedValue> = load_pins().into_iter().filter(|p| p.cleared).collect();
+    let mut report = PinReport::default();
+
+    for key_path in WATCHED_KEYS {
+        let key = match hkcu.open_subkey_with_flags(key_path, KEY_READ | KEY_SET_VALUE) {
+            Ok(k) => k,
+            Err(_) => continue,
+        };
+        let values: Vec<(String, RegValue)> = key.enum_values().filter_map(|v| v.ok()).collect();
+        for (name, value) in values {
+            let pending = is_pending_name(&name);
+            if !pending && !is_watched_name(&name) {
+                continue;
+            }
+            let vtype = match type_name(&value.vtype) {
+                Some(t) => t,
+                None => continue,
+            };
+            let pin = PinnedValue {
+                key: key_path.to_string(),
+                name,
+                vtype: vtype.to_string(),
+                bytes: value.bytes,
+                cleared: pending,
+            };
+            if pending {
+                if key.delete_value(&pin.name).is_err() {
+                    continue;
+                }
+                report.cleared.push(pin.display_path());
+                pins.retain(|p| p.key != pin.key || p.name != pin.name);
+            } else {
+                report.pinned.push(pin.display_path());
+            }
+            pins.push(pin);
+        }
+    }
+
+    save_pins(&pins)?;
+    Ok(report)
+}
+
+/// Restore pinned values CapCut changed and clear staged-update values it wrote again;
+/// returns the names that were restored
+pub fn restore_pinned_values() -> Vec<String> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let mut restored: Vec<String> = Vec::new();
+
+    for pin in load_pins() {
+        let vtype = match type_from_name(&pin.vtype) {
+            Some(t) => t,
+            None => continue,
+        };
+        let key = match hkcu.open_subkey_with_flags(&pin.key, KEY_READ | KEY_SET_VALUE) {
+            Ok(k) => k,
+            Err(_) => continue,
+        };
+
+        if pin.cleared {
+            if key.get_raw_value(&pin.name).is_ok() && key.delete_value(&pin.name).is_ok() {
+                restored.push(format!("{}\\{}", pin.key, pin.name));
+            }
+            continue;
+        }
+
+        let unchanged = key
+            .get_raw_value(&pin.name)
+            .map(|current| current.vtype == vtype && current.bytes == pin.bytes)
+            .unwrap_or(false);
+        if unchanged {
+            continue;
+        }
+
+        let value = RegValue {
+            bytes: pin.bytes.clone(),
+            vtype,
+        };
+        if key.set_raw_value(&pin.name, &value).is_ok() {
+            restored.push(format!("{}\\{}", pin.key, pin.name));
+        }
+    }
+
+    restored
+}
+
+/// Number of values still pinned
+pub fn pinned_count() -> usize {
+    load_pins().len()
+}
+
+/// Write cleared staged-update values back (protection removed); returns the paths restored
+pub fn restore_cleared_values() -> Vec<String> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let mut restored: Vec<String> = Vec::new();
+
+    for pin in load_pins().into_iter().filter(|p| p.cleared) {
+        let vtype = match type_from_name(&pin.vtype) {
+            Some(t) => t,
+            None => continue,
+        };
+        let key = match hkcu.create_subkey(&pin.key) {
+            Ok((k, _)) => k,
+            Err(_) => continue,
+        };
+        // A value CapCut wrote since then is newer than ours
+        if key.get_raw_value(&pin.name).is_ok() {
+            continue;
+        }
+        let value = RegValue {
+            bytes: pin.bytes.clone(),
+            vtype,
+        };
+        if key.set_raw_value(&pin.name, &value).is_ok() {
+            restored.push(pin.display_path());
+        }
+    }
+
+    restored
+}
+
+/// Forget pinned values (protection removed)
+pub fn clear_pins() -> Result<(), String> {
+    save_pins(&[])
+}
@@ -0,0 +1,390 @@
+//! Scan result and protection report export
+//! Writes installed versions and cache sizes to CSV or JSON, and protection runs to HTML
+
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use winreg::enums::HKEY_LOCAL_MACHINE;
+use winreg::RegKey;
+
+use super::log_entry::LogEntry;
+use super::scanner::{self, VersionInfo};
+use super::{channel, cleaner, config_diff, format, paths, protector};
+
+/// Windows version registry key
+const WINDOWS_VERSION_KEY: &str = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion";
+
+/// A cache directory and its size
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheEntry {
+    pub name: String,
+    pub path: String,
+    pub size_mb: f64,
+}
+
+/// Snapshot of the machine's CapCut state
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanReport {
+    /// Unix seconds
+    pub generated_at: u64,
+    pub app_version: String,
+    pub versions: Vec<VersionInfo>,
+    pub cache: Vec<CacheEntry>,
+}
+
+/// Collect versions and cache sizes
+pub fn build_report(app_version: String) -> ScanReport {
+    let cache = paths::get_capcut_root_path()
+        .map(|root| cleaner::cache_dir_sizes(&root))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(dir, bytes)| CacheEntry {
+            name: dir
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            path: dir.to_string_lossy().to_string(),
+            size_mb: bytes as f64 / (1024.0 * 1024.0),
+        })
+        .collect();
+
+    ScanReport {
+        generated_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        app_version,
+        versions: scanner::scan_installed_versions(),
+        cache,
+    }
+}
+
+/// Quote a CSV field when it contains separators, quotes or newlines
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Render the report as one CSV table (versions, then cache directories)
+fn to_csv(report: &ScanReport) -> String {
+    let mut out = String::from("kind,name,path,size_mb,build,modified,integrity,tags,note\n");
+
+    for v in &report.versions {
+        let integrity = serde_json::to_value(v.integrity)
+            .ok()
+            .and_then(|i| i.as_str().map(String::from))
+            .unwrap_or_default();
+        let note = v.note.as_ref();
+        let row = [
+            "version".to_string(),
+            csv_field(&v.name),
+            csv_field(&v.path),
+            format!("{:.1}", v.size_mb),
+            csv_field(v.build.as_deref().unwrap_or_default()),
+            v.modified.map(|t| t.to_string()).unwrap_or_default(),
+            integrity,
+            csv_field(&note.map(|n| n.tags.join("; ")).unwrap_or_default()),
+            csv_field(&note.map(|n| n.note.clone()).unwrap_or_default()),
+        ];
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+
+    for c in &report.cache {
+        let row = [
+            "cache".to_string(),
+            csv_field(&c.name),
+            csv_field(&c.path),
+            format!("{:.1}", c.size_mb),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ];
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Export scan results; the format follows the file extension (.csv, otherwise JSON)
+#[tauri::command]
+pub async fn export_scan_results(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    let app_version = app.package_info().version.to_string();
+    let report = tauri::async_runtime::spawn_blocking(move || build_report(app_version))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let is_csv = Path::new(&path)
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("csv"));
+    let content = if is_csv {
+        to_csv(&report)
+    } else {
+        serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("Failed to serialize scan results: {}", e))?
+    };
+
+    fs::write(&path, content).map_err(|e| format!("Failed to write export: {}", e))
+}
+
+/// Escape text for HTML content and attributes
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Format Unix seconds as "YYYY-MM-DD HH:MM UTC"
+fn format_utc(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Civil-from-days (proleptic Gregorian calendar)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        (rem % 3_600) / 60
+    )
+}
+
+/// Windows product name and build (e.g., "Windows 10 Pro (build 19045)")
+pub fn windows_version() -> String {
+    let key = match RegKey::predef(HKEY_LOCAL_MACHINE).open_subkey(WINDOWS_VERSION_KEY) {
+        Ok(k) => k,
+        Err(_) => return "Windows (unknown version)".to_string(),
+    };
+    let name: String = key
+        .get_value("ProductName")
+        .unwrap_or_else(|_| "Windows".to_string());
+    match key.get_value::<String, _>("CurrentBuild") {
+        Ok(build) => format!("{} (build {})", name, build),
+        Err(_) => name,
+    }
+}
+
+/// A two-column table row
+fn info_row(label: &str, value: &str) -> String {
+    format!(
+        "<tr><th>{}</th><td>{}</td></tr>\n",
+        html_escape(label),
+        html_escape(value)
+    )
+}
+
+/// Pass/fail badge
+fn badge(ok: bool, yes: &str, no: &str) -> String {
+    if ok {
+        format!("<span class=\"badge ok\">{}</span>", yes)
+    } else {
+        format!("<span class=\"badge fail\">{}</span>", no)
+    }
+}
+
+/// Render a protection run (machine, versions, actions, guards) as a standalone HTML page
+fn build_protection_html(app_version: &str, logs: &[LogEntry]) -> String {
+    let env = |name: &str| std::env::var(name).unwrap_or_else(|_| "unknown".to_string());
+    let generated_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let mut machine = String::new();
+    machine.push_str(&info_row("Computer", &env("COMPUTERNAME")));
+    machine.push_str(&info_row("User", &env("USERNAME")));
+    machine.push_str(&info_row("Operating system", &windows_version()));
+    machine.push_str(&info_row("Version Guard", &format!("v{}", app_version)));
+    match paths::resolve_capcut_paths() {
+        Some(p) => {
+            let detected = channel::detect_channel(&p.root, &p.apps);
+            machine.push_str(&info_row("CapCut folder", &p.root.to_string_lossy()));
+            machine.push_str(&info_row(
+                "Channel",
+                if detected == channel::Channel::Beta {
+                    "Beta"
+                } else {
+                    "Stable"
+                },
+            ));
+        }
+        None => machine.push_str(&info_row("CapCut folder", "Not found")),
+    }
+
+    let mut versions = String::new();
+    for v in scanner::scan_installed_versions() {
+        let integrity = serde_json::to_value(v.integrity)
+            .ok()
+            .and_then(|i| i.as_str().map(|s| s.replace('_', " ")))
+            .unwrap_or_default();
+        versions.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            html_escape(&v.name),
+            html_escape(v.build.as_deref().unwrap_or("-")),
+            html_escape(&format::format_size_mb(v.size_mb)),
+            html_escape(&integrity)
+        ));
+    }
+    if versions.is_empty() {
+        versions.push_str("<tr><td colspan=\"4\">No versions found</td></tr>\n");
+    }
+
+    let mut actions = String::new();
+    for entry in logs {
+        actions.push_str(&format!(
+            "<li class=\"{}\">{}</li>\n",
+            entry.level.as_str(),
+            html_escape(&entry.message)
+        ));
+    }
+    if actions.is_empty() {
+        actions.push_str("<li class=\"info\">No actions were recorded</li>\n");
+    }
+
+    let status = protector::check_protection_status();
+    let mut guards = String::new();
+    guards.push_str(&format!(
+        "<tr><th>configure.ini version lock</th><td>{}</td></tr>\n",
+        badge(status.config_locked, "Locked", "Not locked")
+    ));
+    guards.push_str(&format!(
+        "<tr><th>Updater blocker files</th><td>{}</td></tr>\n",
+        badge(status.blockers_exist, "In place", "Missing")
+    ));
+    guards.push_str(&format!(
+        "<tr><th>Overall</th><td>{}</td></tr>\n",
+        badge(status.is_protected, "Protected", "Not protected")
+    ));
+
+    let diff = config_diff::current_diff();
+    let mut config_changes = String::new();
+    for change in &diff.changes {
+        config_changes.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            html_escape(&change.key),
+            html_escape(change.locked.as_deref().unwrap_or("-")),
+            html_escape(change.live.as_deref().unwrap_or("-")),
+            match change.status {
+                config_diff::DiffStatus::Added => "Added",
+                config_diff::DiffStatus::Removed => "Removed",
+                config_diff::DiffStatus::Changed => "Changed",
+            }
+        ));
+    }
+    if !diff.available {
+        config_changes.push_str("<tr><td colspan=\"4\">No locked copy to compare with</td></tr>\n");
+    } else if diff.changes.is_empty() {
+        config_changes.push_str(&format!(
+            "<tr><td colspan=\"4\">No changes since protection ({} keys checked)</td></tr>\n",
+            diff.unchanged
+        ));
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>CC Version Guard - Protection Report</title>
+<style>
+  body {{ font-family: "Segoe UI", system-ui, sans-serif; color: #1d1d1f; background: #f5f5f7; margin: 0; padding: 32px; }}
+  main {{ max-width: 880px; margin: 0 auto; background: #fff; border-radius: 12px; padding: 32px 40px; box-shadow: 0 2px 12px rgba(0,0,0,.08); }}
+  h1 {{ font-size: 22px; margin: 0 0 4px; }}
+  h2 {{ font-size: 15px; text-transform: uppercase; letter-spacing: .04em; color: #6e6e73; margin: 28px 0 8px; }}
+  .meta {{ color: #6e6e73; font-size: 13px; }}
+  table {{ width: 100%; border-collapse: collapse; font-size: 13px; }}
+  th, td {{ text-align: left; padding: 6px 8px; border-bottom: 1px solid #e5e5ea; }}
+  th {{ width: 34%; font-weight: 600; }}
+  thead th {{ width: auto; color: #6e6e73; }}
+  ul {{ list-style: none; padding: 0; margin: 0; font-family: Consolas, monospace; font-size: 12px; }}
+  li {{ padding: 3px 8px; border-left: 3px solid #d1d1d6; margin-bottom: 2px; }}
+  li.ok {{ border-color: #34c759; }}
+  li.warn {{ border-color: #ff9500; }}
+  li.step {{ border-color: #007aff; font-weight: 600; }}
+  .badge {{ display: inline-block; padding: 2px 10px; border-radius: 10px; font-weight: 600; font-size: 12px; }}
+  .badge.ok {{ background: #e3f9e8; color: #1b7f37; }}
+  .badge.fail {{ background: #ffe5e3; color: #c62818; }}
+  footer {{ margin-top: 32px; font-size: 11px; color: #8e8e93; }}
+</style>
+</head>
+<body>
+<main>
+<h1>CapCut Protection Report</h1>
+<div class="meta">Generated {generated}</div>
+
+<h2>Machine</h2>
+<table>
+{machine}</table>
+
+<h2>Versions Found</h2>
+<table>
+<thead><tr><th>Version</th><th>Build</th><th>Size</th><th>Integrity</th></tr></thead>
+<tbody>
+{versions}</tbody>
+</table>
+
+<h2>Actions Taken</h2>
+<ul>
+{actions}</ul>
+
+<h2>Guards &amp; Verification</h2>
+<table>
+{guards}</table>
+
+<h2>configure.ini Changes Since Protection</h2>
+<table>
+<thead><tr><th>Key</th><th>Locked</th><th>Live</th><th>Change</th></tr></thead>
+<tbody>
+{config_changes}</tbody>
+</table>
+
+<footer>CC Version Guard v{app_version} &middot; Unofficial tool, not affiliated with CapCut.</footer>
+</main>
+</body>
+</html>
+"#,
+        generated = format_utc(generated_at),
+        machine = machine,
+        versions = versions,
+        actions = actions,
+        guards = guards,
+        config_changes = config_changes,
+        app_version = html_escape(app_version),
+    )
+}
+
+/// Export an HTML report of the last protection run
+#[tauri::command]
+pub async fn export_protection_report(
+    app: tauri::AppHandle,
+    path: String,
+    logs: Vec<LogEntry>,
+) -> Result<(), String> {
+    let app_version = app.package_info().version.to_string();
+    let html =
+        tauri::async_runtime::spawn_blocking(move || build_protection_html(&app_version, &logs))
+            .await
+            .map_err(|e| e.to_string())?;
+
+    fs::write(&path, html).map_err(|e| format!("Failed to write report: {}", e))
+}
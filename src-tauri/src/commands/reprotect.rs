@@ -0,0 +1,240 @@
+//! Automatic re-protection after a reinstall
+//! Remembers the last protection run and re-applies it when the CapCut Apps folder is recreated
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use tauri::{AppHandle, Emitter};
+
+use super::cleaner::CacheCategory;
+use super::notifier::{self, AlertKind};
+use super::protector::{self, ProtectionParams};
+use super::{installs, paths, process};
+
+/// Options of the last successful protection run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ProtectionProfile {
+    /// Folder name of the kept version (e.g., "2.9.0.966")
+    kept_version: String,
+    clean_cache: bool,
+    #[serde(default)]
+    cache_categories: Vec<CacheCategory>,
+    lock_config: bool,
+    create_blockers: bool,
+    #[serde(default)]
+    harden_binaries: bool,
+    #[serde(default)]
+    stub_updaters: bool,
+    #[serde(default)]
+    lock_acls: bool,
+    #[serde(default)]
+    block_domains: bool,
+    #[serde(default)]
+    firewall_rules: bool,
+    #[serde(default)]
+    disable_autoruns: bool,
+}
+
+/// Get the protection profile file path
+fn get_profile_path() -> Option<PathBuf> {
+    installs::get_install_data_dir().map(|p| p.join("protection_profile.json"))
+}
+
+fn load_profile() -> Option<ProtectionProfile> {
+    get_profile_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+}
+
+/// Folder name of the version kept by the last successful protection run
+pub fn kept_version() -> Option<String> {
+    load_profile().map(|p| p.kept_version)
+}
+
+/// Remember a successful protection run so it can be repeated after a reinstall
+pub fn save_profile(params: &ProtectionParams) {
+    let kept_version = match &params.keep_version {
+        Some(kept) => paths::path_from_transport(kept)
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string(),
+        None => return,
+    };
+    let profile = ProtectionProfile {
+        kept_version,
+        clean_cache: params.clean_cache,
+        cache_categories: params.cache_categories.clone(),
+        lock_config: params.lock_config,
+        create_blockers: params.create_blockers,
+        harden_binaries: params.harden_binaries,
+        stub_updaters: params.stub_updaters,
+        lock_acls: params.lock_acls,
+        block_domains: params.block_domains,
+        firewall_rules: params.firewall_rules,
+        disable_autoruns: params.disable_autoruns,
+    };
+
+    let path = match get_profile_path() {
+        Some(p) => p,
+        None => return,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).ok();
+    }
+    if let Ok(content) = serde_json::to_string_pretty(&profile) {
+        if let Err(e) = fs::write(path, content) {
+            tracing::warn!("Could not save protection profile: {}", e);
+        }
+    }
+}
+
+/// Forget the protection run so it is neither repeated nor watched (protection reverted)
+pub fn clear_profile() -> Result<(), String> {
+    match get_profile_path() {
+        Some(p) if p.exists() => fs::remove_file(p).map_err(|e| e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Creation time of the Apps folder (None while it doesn't exist)
+fn apps_created() -> Option<SystemTime> {
+    paths::get_capcut_apps_path()
+        .and_then(|p| fs::metadata(p).ok())
+        .and_then(|m| m.created().ok())
+}
+
+/// Notices the Apps folder being recreated between watchdog checks
+#[derive(Default)]
+pub struct InstallTracker {
+    /// Apps folder creation time at the last check (outer None before the first check)
+    seen: Option<Option<SystemTime>>,
+    /// A reinstall was seen and still needs re-protecting
+    pending: bool,
+}
+
+impl InstallTracker {
+    /// Re-protect once a reinstall has settled; call once per watchdog check
+    pub fn check(&mut self, app: &AppHandle) {
+        let current = apps_created();
+        if let Some(previous) = self.seen {
+            if current.is_some() && current != previous {
+                // Handle it on a later check so the installer can finish copying
+                self.pending = true;
+                self.seen = Some(current);
+                return;
+            }
+        }
+        self.seen = Some(current);
+
+        // The installer usually launches CapCut when it finishes
+        if self.pending && current.is_some() && !process::is_capcut_running() {
+            self.pending = false;
+            reprotect(app, "CapCut was reinstalled");
+        }
+    }
+}
+
+/// Re-run the saved protection after CapCut staged new version folders (e.g., a silent update)
+pub fn reapply_after_update(app: &AppHandle, new_versions: &[String]) {
+    reprotect(
+        app,
+        &format!(
+            "New CapCut version folder(s) appeared ({})",
+            new_versions.join(", ")
+        ),
+    );
+}
+
+/// Re-run the saved protection on request (tray menu)
+pub fn reapply_saved(app: &AppHandle) {
+    reprotect(app, "Re-apply was requested from the tray");
+}
+
+/// Re-run the saved protection, or alert if the kept version is gone; `cause` starts each message
+fn reprotect(app: &AppHandle, cause: &str) {
+    let profile = match load_profile() {
+        Some(p) => p,
+        None => return,
+    };
+    let install = match paths::resolve_capcut_paths() {
+        Some(p) => p,
+        None => return,
+    };
+    let apps_path = &install.apps;
+
+    let kept = apps_path.join(&profile.kept_version);
+    if !kept.is_dir() {
+        let _ = app.emit("watchdog-reinstall-detected", &profile.kept_version);
+        if let Err(e) = notifier::send_alert(
+            AlertKind::ProtectionBroken,
+            &format!(
+                "{} and the protected version {} is no longer installed. Install it again and re-run protection.",
+                cause, profile.kept_version
+            ),
+        ) {
+            tracing::warn!("Re-protect email alert failed: {}", e);
+        }
+        return;
+    }
+
+    let versions_to_delete: Vec<String> = fs::read_dir(apps_path)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        // A portable install's Apps folder is its root, which also holds User Data
+        .filter(|p| install.is_version_dir(p) && *p != kept)
+        .map(|p| paths::path_to_transport(&p))
+        .collect();
+
+    let result = protector::run_full_protection(ProtectionParams {
+        versions_to_delete,
+        keep_version: Some(paths::path_to_transport(&kept)),
+        // Unattended runs don't write draft zips
+        backup_drafts_to: None,
+        draft_projects: Vec::new(),
+        clean_cache: profile.clean_cache,
+        cache_categories: profile.cache_categories,
+        lock_config: profile.lock_config,
+        create_blockers: profile.create_blockers,
+        harden_binaries: profile.harden_binaries,
+        stub_updaters: profile.stub_updaters,
+        lock_acls: profile.lock_acls,
+        block_domains: profile.block_domains,
+        firewall_rules: profile.firewall_rules,
+        disable_autoruns: profile.disable_autoruns,
+    });
+
+    let (kind, details) = if result.success {
+        let _ = app.emit("watchdog-reprotected", &profile.kept_version);
+        (
+            AlertKind::UpdateBlocked,
+            format!(
+                "{}; protection was re-applied to version {}.\n\n{}",
+                cause,
+                profile.kept_version,
+                result
+                    .logs
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ),
+        )
+    } else {
+        (
+            AlertKind::ProtectionBroken,
+            format!(
+                "{} and re-applying protection failed: {}",
+                cause,
+                result.error.unwrap_or_default()
+            ),
+        )
+    };
+    if let Err(e) = notifier::send_alert(kind, &details) {
+        tracing::warn!("Re-protect email alert failed: {}", e);
+    }
+}
@@ -0,0 +1,202 @@
+//! Protection revert
+//! Keeps configure.ini and ProductInfo.xml as they were before protection and puts them back on revert
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::log_entry::LogEntry;
+use super::uninstall::RemovalCheck;
+use super::{
+    acl_lock, attributes, autoruns, blockers, config_guard, domain_block, firewall, hardening,
+    installs, logging, paths, protector, registry_guard, reprotect, updater_stub,
+};
+
+/// Folder under the install data directory holding the pre-protection files
+const ORIGINALS_DIR: &str = "Originals";
+
+/// Outcome of the Remove Protection flow
+#[derive(Debug, Clone, Serialize)]
+pub struct RevertResult {
+    /// Every check passed
+    pub reverted: bool,
+    pub checks: Vec<RemovalCheck>,
+    pub logs: Vec<LogEntry>,
+}
+
+fn get_originals_dir() -> Option<PathBuf> {
+    installs::get_install_data_dir().map(|p| p.join(ORIGINALS_DIR))
+}
+
+/// Copy a file into the originals folder unless an earlier copy is already there
+fn keep_original(source: &Path, dir: &Path, name: &str) -> Result<(), String> {
+    let target = dir.join(name);
+    if target.exists() || !source.is_file() {
+        return Ok(());
+    }
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    fs::copy(source, target).map_err(|e| format!("Failed to copy {}: {}", name, e))?;
+    Ok(())
+}
+
+/// Remember configure.ini and ProductInfo.xml before protection changes them
+///
+/// Files that are already locked are not originals and are skipped.
+pub fn save_originals(apps_path: &Path, config_locked: bool) -> Result<(), String> {
+    let dir = get_originals_dir().ok_or("Could not determine data directory")?;
+    if !config_locked {
+        keep_original(&apps_path.join("configure.ini"), &dir, "configure.ini")?;
+    }
+    let product_info = apps_path.join("ProductInfo.xml");
+    if !blockers::is_blocker(&product_info) {
+        keep_original(&product_info, &dir, "ProductInfo.xml")?;
+    }
+    Ok(())
+}
+
+/// Put the saved originals back after the locks were removed
+fn restore_originals(apps_path: &Path, logs: &mut Vec<LogEntry>) {
+    let dir = match get_originals_dir().filter(|d| d.is_dir()) {
+        Some(d) => d,
+        None => {
+            logs.push(LogEntry::info(
+                "No pre-protection copy of configure.ini kept; version keys were removed instead",
+            ));
+            return;
+        }
+    };
+
+    let config = dir.join("configure.ini");
+    if config.is_file() {
+        match protector::restore_version_keys(apps_path, &config) {
+            Ok(()) => logs.push(LogEntry::ok("Original configure.ini version restored")),
+            Err(e) => logs.push(LogEntry::warn(format!(
+                "Could not restore configure.ini: {}",
+                e
+            ))),
+        }
+    }
+
+    let product_info = apps_path.join("ProductInfo.xml");
+    if dir.join("ProductInfo.xml").is_file() && !product_info.exists() {
+        match fs::copy(dir.join("ProductInfo.xml"), &product_info) {
+            Ok(_) => logs.push(LogEntry::ok("Original ProductInfo.xml restored")),
+            Err(e) => logs.push(
+                LogEntry::warn(format!("Could not restore ProductInfo.xml: {}", e))
+                    .with_path(&product_info),
+            ),
+        }
+    }
+
+    if let Err(e) = fs::remove_dir_all(&dir) {
+        logs.push(
+            LogEntry::warn(format!("Could not delete saved originals: {}", e)).with_path(&dir),
+        );
+    }
+}
+
+/// Re-check each protection measure after the revert
+fn verify(apps_path: &Path) -> Vec<RemovalCheck> {
+    let status = protector::check_protection_status();
+    let hardened = hardening::hardened_count();
+    let acl_locked = acl_lock::locked_count();
+    let stubbed = updater_stub::stubbed_count();
+    let readonly_config =
+        fs::metadata(apps_path.join("configure.ini")).is_ok_and(|m| m.permissions().readonly());
+    vec![
+        RemovalCheck::new(
+            "Update blockers",
+            !status.blockers_exist,
+            "A blocker file is still in place",
+        ),
+        RemovalCheck::new(
+            "configure.ini lock",
+            !status.config_locked && !config_guard::is_pinned() && !readonly_config,
+            "configure.ini is still locked, pinned or read-only",
+        ),
+        RemovalCheck::new(
+            "Hardened files",
+            hardened == 0,
+            format!("{} file(s) still read-only", hardened),
+        ),
+        RemovalCheck::new(
+            "Permission locks",
+            acl_locked == 0,
+            format!("{} path(s) still deny writes", acl_locked),
+        ),
+        RemovalCheck::new(
+            "Updater stubs",
+            stubbed == 0,
+            format!("{} updater(s) not restored from .bak", stubbed),
+        ),
+        RemovalCheck::new(
+            "Update domain block",
+            !domain_block::is_blocked(),
+            "CapCut's update domains are still blocked in the hosts file (needs administrator rights)",
+        ),
+        RemovalCheck::new(
+            "Firewall rules",
+            firewall::present_count() == 0,
+            "An updater firewall rule is still in place (needs administrator rights)",
+        ),
+        RemovalCheck::new(
+            "Autorun entries",
+            autoruns::pending_restore_count() == 0,
+            "A CapCut task or autorun entry disabled by protection is still disabled",
+        ),
+        RemovalCheck::new(
+            "Registry pins",
+            registry_guard::pinned_count() == 0,
+            "CapCut registry values are still pinned",
+        ),
+        RemovalCheck::new(
+            "Automatic re-protection",
+            reprotect::kept_version().is_none(),
+            "The last protection run would still be repeated after a reinstall",
+        ),
+    ]
+}
+
+/// Undo protection of the active install and verify the result (blocking)
+pub fn revert() -> Result<RevertResult, String> {
+    let apps_path = paths::get_capcut_apps_path().ok_or("Could not find CapCut installation")?;
+
+    let removed = protector::remove_protection();
+    if let Some(e) = removed.error {
+        return Err(e);
+    }
+    let mut logs = removed.logs;
+
+    restore_originals(&apps_path, &mut logs);
+
+    // Hardening records can be lost with the data folder; clear what is left in Apps
+    logs.push(LogEntry::info("Unlocking Apps folder..."));
+    match attributes::clear_readonly_recursive(&apps_path) {
+        Ok(()) => logs.push(LogEntry::ok("Apps folder unlocked").with_path(&apps_path)),
+        Err(e) => logs.push(LogEntry::warn(e).with_path(&apps_path)),
+    }
+
+    // Without a profile the watchdog no longer treats the install as protected
+    if let Err(e) = reprotect::clear_profile() {
+        logs.push(LogEntry::warn(format!(
+            "Could not forget the last protection run: {}",
+            e
+        )));
+    }
+
+    let checks = verify(&apps_path);
+    logging::record("revert", &logs);
+    Ok(RevertResult {
+        reverted: checks.iter().all(|c| c.removed),
+        checks,
+        logs,
+    })
+}
+
+/// Undo protection of the active install and restore it to its unprotected state
+#[tauri::command]
+pub async fn revert_protection() -> Result<RevertResult, String> {
+    tauri::async_runtime::spawn_blocking(revert)
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -6,6 +6,10 @@ use std::fs;
 use std::path::Path;
 use walkdir::WalkDir;
 
+use super::integrity::{self, Integrity};
+use super::settings::{self, VersionNote};
+use super::{active_version, favorites, paths, version};
+
 // Re-export path functions from paths module for backwards compatibility
 pub use super::paths::{get_capcut_apps_path, get_capcut_root_path};
 
@@ -99,13 +103,37 @@ const ALL_VERSIONS_DATA: &str = r#"
 1.0.0 (Latest)|1.0.0|https://lf16-capcut.faceulv.com/obj/capcutpc-packages-us/packages/CapCut_1_0_0_44_capcutpc_0.exe
 "#;
 
-
 /// Information about an installed CapCut version
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct VersionInfo {
     pub name: String,
     pub path: String,
     pub size_mb: f64,
+    /// File version of CapCut.exe (e.g., "2.9.0.1457")
+    pub build: Option<String>,
+    /// Folder last-modified time (Unix seconds)
+    pub modified: Option<u64>,
+    pub integrity: Integrity,
+    /// User note and tags, if any
+    pub note: Option<VersionNote>,
+    /// Highest version number in the Apps folder
+    pub newest: bool,
+    /// Lowest version number in the Apps folder (only set with more than one version)
+    pub oldest: bool,
+    /// The version CapCut is configured to launch
+    pub active: bool,
+}
+
+/// One child of a folder inside Apps, for the pre-deletion file tree
+#[derive(Clone, Debug, Serialize)]
+pub struct FolderEntry {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    /// Total size (recursive for folders)
+    pub bytes: u64,
+    /// Holds CapCut project files that belong in the drafts folder, not a version folder
+    pub contains_drafts: bool,
 }
 
 /// Archive version from the curated list
@@ -117,11 +145,22 @@ pub struct ArchiveVersion {
     pub features: Vec<String>,
     pub download_url: String,
     pub risk_level: String,
+    /// Starred by the user
+    #[serde(default)]
+    pub favorite: bool,
+    /// Published SHA-256 of the installer, when known
+    #[serde(default)]
+    pub sha256: Option<String>,
 }
 
-/// Get curated archive versions
+/// Get curated archive versions (starred entries first)
 #[tauri::command]
 pub fn get_archive_versions() -> Vec<ArchiveVersion> {
+    favorites::mark_favorites(curated_archive_versions())
+}
+
+/// Curated archive versions in display order
+pub fn curated_archive_versions() -> Vec<ArchiveVersion> {
     vec![
         ArchiveVersion {
             persona: "Offline Purist".to_string(),
@@ -130,6 +169,8 @@ pub fn get_archive_versions() -> Vec<ArchiveVersion> {
             features: vec!["Clean UI".to_string(), "Offline Only".to_string(), "No Nags".to_string()],
             download_url: "https://lf16-capcut.faceulv.com/obj/capcutpc-packages-us/packages/CapCut_1_5_0_230_capcutpc_0.exe".to_string(),
             risk_level: "Low".to_string(),
+            favorite: false,
+            sha256: None,
         },
         ArchiveVersion {
             persona: "Audio Engineer".to_string(),
@@ -138,6 +179,8 @@ pub fn get_archive_versions() -> Vec<ArchiveVersion> {
             features: vec!["Multi-Track".to_string(), "Audio Mixer".to_string(), "Keyframes".to_string()],
             download_url: "https://lf16-capcut.faceulv.com/obj/capcutpc-packages-us/packages/CapCut_2_5_4_810_capcutpc_0_creatortool.exe".to_string(),
             risk_level: "Low".to_string(),
+            favorite: false,
+            sha256: None,
         },
         ArchiveVersion {
             persona: "Classic Pro".to_string(),
@@ -146,6 +189,8 @@ pub fn get_archive_versions() -> Vec<ArchiveVersion> {
             features: vec!["Max Free Features".to_string(), "Stable".to_string(), "Legacy UI".to_string()],
             download_url: "https://lf16-capcut.faceulv.com/obj/capcutpc-packages-us/packages/CapCut_2_9_0_966_capcutpc_0_creatortool.exe".to_string(),
             risk_level: "Medium".to_string(),
+            favorite: false,
+            sha256: None,
         },
         ArchiveVersion {
             persona: "Modern Stable".to_string(),
@@ -154,6 +199,8 @@ pub fn get_archive_versions() -> Vec<ArchiveVersion> {
             features: vec!["Modern UI".to_string(), "Smooth".to_string(), "Balanced".to_string()],
             download_url: "https://lf16-capcut.faceulv.com/obj/capcutpc-packages-us/packages/CapCut_3_2_0_1106_capcutpc_0_creatortool.exe".to_string(),
             risk_level: "Medium".to_string(),
+            favorite: false,
+            sha256: None,
         },
         ArchiveVersion {
             persona: "Creator".to_string(),
@@ -162,6 +209,8 @@ pub fn get_archive_versions() -> Vec<ArchiveVersion> {
             features: vec!["Auto-Captions".to_string(), "AI Features".to_string(), "Effects".to_string()],
             download_url: "https://lf16-capcut.faceulv.com/obj/capcutpc-packages-us/packages/CapCut_3_9_0_1459_capcutpc_0_creatortool.exe".to_string(),
             risk_level: "High".to_string(),
+            favorite: false,
+            sha256: None,
         },
         ArchiveVersion {
             persona: "Power User".to_string(),
@@ -170,16 +219,23 @@ pub fn get_archive_versions() -> Vec<ArchiveVersion> {
             features: vec!["Track Zoom".to_string(), "Markers".to_string(), "Adv Features".to_string()],
             download_url: "https://lf16-capcut.faceulv.com/obj/capcutpc-packages-us/packages/CapCut_4_0_0_1539_capcutpc_0_creatortool.exe".to_string(),
             risk_level: "Medium".to_string(),
+            favorite: false,
+            sha256: None,
         },
     ]
 }
 
-/// Get ALL archive versions (complete list)
+/// Get ALL archive versions (complete list, starred entries first)
 /// This includes every version up to 5.4.0 Beta 6 (last version where CC Version Guard works)
 #[tauri::command]
 pub fn get_all_archive_versions() -> Vec<ArchiveVersion> {
+    favorites::mark_favorites(all_archive_versions())
+}
+
+/// Every archive version parsed from the bundled list
+pub fn all_archive_versions() -> Vec<ArchiveVersion> {
     // Parse version data from compact format
-    // Format: "Label|BaseVersion|URL"
+    // Format: "Label|BaseVersion|URL" with an optional "|SHA-256" of the installer
     ALL_VERSIONS_DATA
         .lines()
         .filter(|line| !line.trim().is_empty())
@@ -220,14 +276,20 @@ pub fn get_all_archive_versions() -> Vec<ArchiveVersion> {
                 features: vec![],
                 download_url: url.to_string(),
                 risk_level: risk_level.to_string(),
+                favorite: false,
+                sha256: parts
+                    .get(3)
+                    .map(|h| h.trim().to_lowercase())
+                    .filter(|h| !h.is_empty()),
             })
         })
         .collect()
 }
 
 /// Calculate directory size recursively
-fn calculate_dir_size(path: &Path) -> u64 {
+pub fn calculate_dir_size(path: &Path) -> u64 {
     WalkDir::new(path)
+        .follow_root_links(false)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
@@ -236,44 +298,144 @@ fn calculate_dir_size(path: &Path) -> u64 {
         .sum()
 }
 
-/// Scan for installed CapCut versions
+/// Files every CapCut project folder contains
+const DRAFT_MARKERS: &[&str] = &["draft_meta_info.json", "draft_content.json"];
+
+/// Size of a folder and whether any CapCut project lives inside it
+pub fn folder_summary(path: &Path) -> (u64, bool) {
+    let mut bytes = 0;
+    let mut contains_drafts = false;
+    // A linked folder belongs to another location; its size isn't counted here
+    for entry in WalkDir::new(path)
+        .follow_root_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        bytes += entry.metadata().map_or(0, |m| m.len());
+        let name = entry.file_name().to_string_lossy();
+        contains_drafts |= DRAFT_MARKERS.iter().any(|m| name.eq_ignore_ascii_case(m));
+    }
+    (bytes, contains_drafts)
+}
+
+/// Direct children of `dir`, largest first; `dir` must be inside the Apps folder
+fn folder_entries(dir: &Path) -> Result<Vec<FolderEntry>, String> {
+    let apps = get_capcut_apps_path()
+        .and_then(|p| fs::canonicalize(p).ok())
+        .ok_or("CapCut installation not found")?;
+    let real = fs::canonicalize(dir).map_err(|e| e.to_string())?;
+    if !real.starts_with(&apps) {
+        return Err("Only folders inside the CapCut Apps folder can be listed".to_string());
+    }
+
+    let mut entries: Vec<FolderEntry> = fs::read_dir(dir)
+        .map_err(|e| e.to_string())?
+        .filter_map(|e| e.ok())
+        .map(|e| {
+            let p = e.path();
+            let is_dir = p.is_dir();
+            let (bytes, contains_drafts) = if is_dir {
+                folder_summary(&p)
+            } else {
+                let name = e.file_name().to_string_lossy().to_string();
+                (
+                    e.metadata().map_or(0, |m| m.len()),
+                    DRAFT_MARKERS.iter().any(|m| name.eq_ignore_ascii_case(m)),
+                )
+            };
+            FolderEntry {
+                name: e.file_name().to_string_lossy().to_string(),
+                path: paths::path_to_transport(&p),
+                is_dir,
+                bytes,
+                contains_drafts,
+            }
+        })
+        .collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.bytes));
+    Ok(entries)
+}
+
+/// List a version folder (or a folder inside one) with sizes, for review before deletion
 #[tauri::command]
-pub async fn scan_versions() -> Vec<VersionInfo> {
-    let result = tauri::async_runtime::spawn_blocking(move || {
-        let apps_path = match get_capcut_apps_path() {
-            Some(p) if p.exists() => p,
-            _ => return Vec::new(),
-        };
+pub async fn list_folder_entries(path: String) -> Result<Vec<FolderEntry>, String> {
+    let dir = paths::path_from_transport(&path);
+    tauri::async_runtime::spawn_blocking(move || folder_entries(&dir))
+        .await
+        .map_err(|e| e.to_string())?
+}
 
-        let mut versions: Vec<VersionInfo> = fs::read_dir(&apps_path)
-            .ok()
-            .into_iter()
-            .flatten()
-            .filter_map(|e| e.ok())
-            .map(|e| e.path())
-            .filter(|p| p.is_dir())
-            .map(|p| {
-                let name = p
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string();
-                let size_mb = calculate_dir_size(&p) as f64 / (1024.0 * 1024.0);
-                VersionInfo {
-                    name,
-                    path: p.to_string_lossy().to_string(),
-                    size_mb,
-                }
-            })
-            .collect();
+/// Scan installed CapCut versions (blocking)
+pub fn scan_installed_versions() -> Vec<VersionInfo> {
+    let capcut_paths = match paths::resolve_capcut_paths() {
+        Some(p) if p.apps.exists() => p,
+        _ => return Vec::new(),
+    };
+    let apps_path = &capcut_paths.apps;
 
-        // Sort by version name (oldest first) using simple string comparison
-        versions.sort_by(|a, b| a.name.cmp(&b.name));
-        versions
-    })
-    .await;
+    let notes = settings::load_settings().version_notes;
 
-    result.unwrap_or_default()
+    let mut versions: Vec<VersionInfo> = fs::read_dir(apps_path)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| capcut_paths.is_version_dir(p))
+        .map(|p| {
+            let name = p
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            let size_mb = calculate_dir_size(&p) as f64 / (1024.0 * 1024.0);
+            let modified = fs::metadata(&p)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs());
+            let note = notes.get(&name).cloned();
+            VersionInfo {
+                name,
+                path: paths::path_to_transport(&p),
+                size_mb,
+                build: integrity::read_exe_build(&paths::main_executable(&p)),
+                modified,
+                integrity: integrity::check_version(&p),
+                note,
+                newest: false,
+                oldest: false,
+                active: false,
+            }
+        })
+        .collect();
+
+    // Oldest first, by version number
+    versions.sort_by(|a, b| version::cmp_names(&a.name, &b.name));
+    if let Some(newest) = versions.last_mut() {
+        newest.newest = true;
+    }
+    if versions.len() > 1 {
+        versions[0].oldest = true;
+    }
+    let names: Vec<String> = versions.iter().map(|v| v.name.clone()).collect();
+    if let Some(active) = active_version::detect(&capcut_paths, &names) {
+        for v in versions.iter_mut() {
+            v.active = v.name == active.name;
+        }
+    }
+    versions
+}
+
+/// Scan for installed CapCut versions
+#[tauri::command]
+pub async fn scan_versions() -> Vec<VersionInfo> {
+    tauri::async_runtime::spawn_blocking(scan_installed_versions)
+        .await
+        .unwrap_or_default()
 }
 
 /// Get CapCut installation paths
@@ -0,0 +1,245 @@
+//! Scheduled maintenance tasks
+//! Runs cache cleaning, version backups and draft backups on the intervals set in Settings
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+use super::watchdog::{self, WatchdogState};
+use super::{backup, cleaner, drafts, paths, policy, process, reprotect, settings};
+
+/// Seconds between due-task checks
+const TICK_SECS: u64 = 60;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Reason recorded on scheduled version backups; retention only prunes these
+const SCHEDULED_REASON: &str = "Scheduled backup";
+
+/// Last run times of scheduled tasks (Unix seconds)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ScheduleState {
+    last_cache_clean: Option<u64>,
+    last_backup: Option<u64>,
+    last_draft_backup: Option<u64>,
+}
+
+/// Run times for one scheduled task
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskTimes {
+    pub enabled: bool,
+    pub last_run: Option<u64>,
+    pub next_run: Option<u64>,
+}
+
+/// Schedule overview for the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleStatus {
+    pub verify: TaskTimes,
+    pub cache_clean: TaskTimes,
+    pub backup: TaskTimes,
+    pub draft_backup: TaskTimes,
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Get the schedule state file path
+fn get_state_path() -> Option<PathBuf> {
+    paths::get_app_data_dir().map(|p| p.join("schedule_state.json"))
+}
+
+fn load_state() -> ScheduleState {
+    get_state_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &ScheduleState) {
+    let path = match get_state_path() {
+        Some(p) => p,
+        None => return,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).ok();
+    }
+    if let Ok(content) = serde_json::to_string_pretty(state) {
+        fs::write(path, content).ok();
+    }
+}
+
+/// Compute run times for a day-based task
+fn day_task(interval_days: u32, last_run: Option<u64>) -> TaskTimes {
+    let enabled = interval_days > 0;
+    TaskTimes {
+        enabled,
+        last_run,
+        next_run: enabled.then(|| {
+            // Never-run tasks are due right away
+            last_run.map_or(now_secs(), |t| t + u64::from(interval_days) * SECS_PER_DAY)
+        }),
+    }
+}
+
+/// Back up the kept version unless a backup of it already exists, then prune older scheduled backups
+fn run_scheduled_backup(retention: u32) {
+    let (apps_path, kept) = match (paths::get_capcut_apps_path(), reprotect::kept_version()) {
+        (Some(p), Some(k)) => (p, k),
+        _ => return,
+    };
+    let dir = apps_path.join(&kept);
+    let backed_up = backup::list_backups()
+        .iter()
+        .any(|b| b.version_name == kept);
+    if dir.is_dir() && !backed_up {
+        let result = backup::create_backup(&dir, SCHEDULED_REASON);
+        if !result.success {
+            tracing::warn!(
+                "Scheduled backup failed for {}: {}",
+                dir.display(),
+                result.error.unwrap_or_default()
+            );
+        }
+    }
+    prune_scheduled_backups(retention);
+}
+
+/// Delete scheduled backups beyond the retention count (0 keeps all); manual and pre-deletion backups are left alone
+fn prune_scheduled_backups(retention: u32) {
+    if retention == 0 {
+        return;
+    }
+    let scheduled = backup::list_backups()
+        .into_iter()
+        .filter(|b| b.reason == SCHEDULED_REASON)
+        .skip(retention as usize);
+    for old in scheduled {
+        let result = backup::delete_backup(format!("{}_{}", old.version_name, old.created_at));
+        if !result.success {
+            tracing::warn!(
+                "Could not remove old scheduled backup of {}: {}",
+                old.version_name,
+                result.error.unwrap_or_default()
+            );
+        }
+    }
+}
+
+/// Run any scheduled task that is due
+fn run_due_tasks() {
+    let schedules = settings::load_settings().schedules;
+    let mut state = load_state();
+    let now = now_secs();
+    let mut changed = false;
+
+    // Never touch CapCut's files while it is open; retry next tick
+    if process::is_capcut_running() {
+        return;
+    }
+
+    let cache = day_task(schedules.cache_clean_interval_days, state.last_cache_clean);
+    if cache.next_run.is_some_and(|t| t <= now) {
+        if let Some(root) = paths::get_capcut_root_path() {
+            cleaner::clean_cache_at(&root, &cleaner::resolve_categories(&[]));
+        }
+        state.last_cache_clean = Some(now);
+        changed = true;
+    }
+
+    let backup_times = day_task(schedules.backup_interval_days, state.last_backup);
+    if backup_times.next_run.is_some_and(|t| t <= now) {
+        run_scheduled_backup(schedules.backup_retention);
+        state.last_backup = Some(now);
+        changed = true;
+    }
+
+    let draft_times = day_task(
+        schedules.draft_backup_interval_days,
+        state.last_draft_backup,
+    );
+    if draft_times.next_run.is_some_and(|t| t <= now) {
+        let result = drafts::run_configured_backup(&schedules);
+        if !result.success {
+            tracing::warn!(
+                "Scheduled draft backup failed: {}",
+                result.error.unwrap_or_default()
+            );
+        }
+        state.last_draft_backup = Some(now);
+        changed = true;
+    }
+
+    if changed {
+        save_state(&state);
+    }
+}
+
+/// Start the scheduler background thread
+pub fn start_scheduler() {
+    thread::spawn(|| loop {
+        run_due_tasks();
+        thread::sleep(Duration::from_secs(TICK_SECS));
+    });
+}
+
+/// Get last/next run times for every schedule
+#[tauri::command]
+pub fn get_schedule_status(state: State<'_, WatchdogState>) -> ScheduleStatus {
+    let schedules = settings::load_settings().schedules;
+    let run_state = load_state();
+    let watchdog_status = state.status();
+
+    ScheduleStatus {
+        verify: TaskTimes {
+            enabled: watchdog_status.running,
+            last_run: watchdog_status.last_check,
+            next_run: watchdog_status
+                .last_check
+                .filter(|_| watchdog_status.running)
+                .map(|t| t + watchdog::check_interval_secs()),
+        },
+        cache_clean: day_task(
+            schedules.cache_clean_interval_days,
+            run_state.last_cache_clean,
+        ),
+        backup: day_task(schedules.backup_interval_days, run_state.last_backup),
+        draft_backup: day_task(
+            schedules.draft_backup_interval_days,
+            run_state.last_draft_backup,
+        ),
+    }
+}
+
+/// Save schedule intervals; a verification interval starts the watchdog, "off" stops it
+#[tauri::command]
+pub fn save_schedule_settings(
+    app: AppHandle,
+    schedules: settings::ScheduleSettings,
+) -> Result<ScheduleStatus, String> {
+    let machine_policy = policy::load_policy();
+    if machine_policy.lock_settings {
+        return Err(policy::POLICY_BLOCKED.to_string());
+    }
+
+    let mut app_settings = settings::load_settings();
+    app_settings.schedules = schedules;
+    settings::save_settings(&app_settings)?;
+
+    let watchdog_state = app.state::<WatchdogState>();
+    if app_settings.schedules.verify_interval_minutes > 0 {
+        watchdog_state.start(app.clone());
+    } else if !machine_policy.force_watchdog {
+        watchdog_state.stop();
+    }
+
+    Ok(get_schedule_status(watchdog_state))
+}
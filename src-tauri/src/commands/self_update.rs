@@ -0,0 +1,94 @@
+//! Guard tool update check
+//! Lists GitHub releases newer than this build, with their notes, so users see what changed before updating
+
+use serde::{Deserialize, Serialize};
+
+use super::github;
+use super::version::Version;
+
+/// GitHub releases API for this project
+const RELEASES_URL: &str = "https://api.github.com/repos/Zendevve/capcut-version-guard/releases";
+
+/// Release as returned by the GitHub API (only the fields used)
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    name: Option<String>,
+    body: Option<String>,
+    published_at: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+}
+
+/// A release newer than the running build
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolRelease {
+    pub tag: String,
+    pub name: String,
+    /// Release notes (GitHub Markdown)
+    pub notes: String,
+    /// ISO 8601 publish time
+    pub published_at: Option<String>,
+    pub url: String,
+    pub prerelease: bool,
+}
+
+/// Result of the update check
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolUpdateCheck {
+    pub current_version: String,
+    /// Newer releases, newest first (empty when up to date)
+    pub releases: Vec<ToolRelease>,
+}
+
+/// Download the release list as JSON
+fn fetch_releases() -> Result<Vec<GitHubRelease>, String> {
+    github::get_json(RELEASES_URL)
+}
+
+/// Check GitHub for releases newer than this build
+pub fn check() -> Result<ToolUpdateCheck, String> {
+    let current_version = env!("CARGO_PKG_VERSION").to_string();
+    let current = Version::parse(&current_version);
+
+    let mut releases: Vec<(Version, ToolRelease)> = fetch_releases()?
+        .into_iter()
+        .filter(|r| !r.draft)
+        .filter_map(|r| {
+            let version = Version::parse(&r.tag_name)?;
+            (Some(&version) > current.as_ref()).then(|| {
+                (
+                    version,
+                    ToolRelease {
+                        name: r
+                            .name
+                            .filter(|n| !n.trim().is_empty())
+                            .unwrap_or_else(|| r.tag_name.clone()),
+                        tag: r.tag_name,
+                        notes: r.body.unwrap_or_default(),
+                        published_at: r.published_at,
+                        url: r.html_url,
+                        prerelease: r.prerelease,
+                    },
+                )
+            })
+        })
+        .collect();
+    releases.sort_by(|a, b| b.0.cmp(&a.0));
+
+    Ok(ToolUpdateCheck {
+        current_version,
+        releases: releases.into_iter().map(|(_, r)| r).collect(),
+    })
+}
+
+/// Check for newer releases of the guard tool and return their notes
+#[tauri::command]
+pub async fn check_tool_update() -> Result<ToolUpdateCheck, String> {
+    tauri::async_runtime::spawn_blocking(check)
+        .await
+        .map_err(|e| e.to_string())?
+}
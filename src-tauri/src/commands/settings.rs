@@ -0,0 +1,337 @@
+//! Persistent application settings
+//! Stores Version Guard's own preferences and supports export/import for machine moves
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::application::Application;
+use super::{paths, policy};
+
+/// Current settings file format version (bump when the layout changes)
+const SETTINGS_FORMAT_VERSION: u32 = 1;
+
+/// Notification preferences
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// Master switch for all guard notifications
+    pub enabled: bool,
+    /// Notify when an update attempt is blocked
+    pub on_update_blocked: bool,
+    /// Notify when a protection is found broken
+    pub on_protection_broken: bool,
+    /// Notify when a starred archive version changes or disappears
+    pub on_favorite_changed: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            on_update_blocked: true,
+            on_protection_broken: true,
+            on_favorite_changed: true,
+        }
+    }
+}
+
+/// Recurring task schedules (0 = disabled)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScheduleSettings {
+    /// Re-verify protection every N minutes
+    pub verify_interval_minutes: u32,
+    /// Clean CapCut cache every N days
+    pub cache_clean_interval_days: u32,
+    /// Back up the kept version every N days
+    pub backup_interval_days: u32,
+    /// Scheduled version backups to keep (0 = keep all)
+    pub backup_retention: u32,
+    /// Back up CapCut drafts every N days
+    pub draft_backup_interval_days: u32,
+    /// Draft backup folder (empty = Version Guard's data folder)
+    pub draft_backup_destination: String,
+    /// Draft snapshots to keep (0 = keep all)
+    pub draft_backup_retention: u32,
+    /// Sync only changed projects into one mirror instead of full snapshots
+    pub draft_backup_incremental: bool,
+}
+
+/// SMTP settings for admin email alerts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmailSettings {
+    pub enabled: bool,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    /// Use STARTTLS (port 587) instead of implicit TLS (port 465)
+    pub starttls: bool,
+    pub username: String,
+    pub password: String,
+    pub from: String,
+    /// Recipient address (usually the admin)
+    pub to: String,
+}
+
+impl Default for EmailSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            smtp_host: String::new(),
+            smtp_port: 587,
+            starttls: true,
+            username: String::new(),
+            password: String::new(),
+            from: String::new(),
+            to: String::new(),
+        }
+    }
+}
+
+/// User note and tags attached to an installed version
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VersionNote {
+    pub note: String,
+    pub tags: Vec<String>,
+}
+
+/// A starred archive version and the download URL seen when it was starred
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FavoriteArchive {
+    /// Archive label (e.g., "5.3.0 (Latest)" or "Classic Pro")
+    pub label: String,
+    pub url: String,
+}
+
+/// Accent of the built-in theme
+pub const DEFAULT_ACCENT_COLOR: &str = "#007AFF";
+
+/// Version Guard application settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// UI theme ("dark", "light" or "system")
+    pub theme: String,
+    /// UI accent color ("#RRGGBB")
+    pub accent_color: String,
+    /// UI language code (e.g., "en")
+    pub language: String,
+    pub notifications: NotificationSettings,
+    pub schedules: ScheduleSettings,
+    pub email: EmailSettings,
+    /// User-chosen CapCut folder (None = auto-detect); also selects the active install
+    pub capcut_path: Option<String>,
+    /// CapCut folders added by hand, listed with the detected installs
+    pub known_installs: Vec<String>,
+    /// Notes and tags keyed by version folder name
+    pub version_notes: BTreeMap<String, VersionNote>,
+    /// Starred archive versions
+    pub favorite_archives: Vec<FavoriteArchive>,
+    /// Delete update payloads from the download folder as they land
+    pub purge_downloads: bool,
+    /// Warn about fresh CapCut installers in the Downloads folder
+    pub check_downloaded_installers: bool,
+    /// Record every file removed by version deletion and cache cleaning
+    pub log_deleted_files: bool,
+    /// Never-touch paths skipped by version deletion, cache cleaning, slimming and download purge
+    pub excluded_paths: Vec<String>,
+    /// "Launch CapCut when closing" on the Complete screen
+    pub launch_capcut_on_exit: bool,
+    /// Watchdog only records changes in the CapCut folder instead of restoring guards
+    pub watchdog_monitor_only: bool,
+    /// Watchdog debounces file events, checks less often on battery and runs below normal priority
+    pub watchdog_low_impact: bool,
+    /// Watchdog re-runs the saved protection when a new version folder appears in Apps
+    pub auto_reapply_protection: bool,
+    /// Closing or minimizing the window hides it to the tray instead
+    pub minimize_to_tray: bool,
+    /// Install folder names under LOCALAPPDATA to look for (empty = those of `application`)
+    pub install_folder_names: Vec<String>,
+    /// Application chosen on the Welcome screen (CapCut or JianYing Pro)
+    pub application: Application,
+    /// Run version deletion and cache cleaning at background I/O priority
+    pub low_priority_io: bool,
+    /// Extra blocker files, relative to the CapCut folder (e.g., newly found updater staging paths)
+    pub extra_blockers: Vec<String>,
+    /// Personal access token for GitHub API requests (empty = anonymous)
+    pub github_token: String,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            theme: "dark".to_string(),
+            accent_color: DEFAULT_ACCENT_COLOR.to_string(),
+            language: "en".to_string(),
+            notifications: NotificationSettings::default(),
+            schedules: ScheduleSettings::default(),
+            email: EmailSettings::default(),
+            capcut_path: None,
+            known_installs: Vec::new(),
+            version_notes: BTreeMap::new(),
+            favorite_archives: Vec::new(),
+            purge_downloads: false,
+            check_downloaded_installers: false,
+            log_deleted_files: false,
+            excluded_paths: Vec::new(),
+            launch_capcut_on_exit: false,
+            watchdog_monitor_only: false,
+            watchdog_low_impact: false,
+            auto_reapply_protection: false,
+            minimize_to_tray: false,
+            install_folder_names: Vec::new(),
+            application: Application::default(),
+            low_priority_io: false,
+            extra_blockers: Vec::new(),
+            github_token: String::new(),
+        }
+    }
+}
+
+/// Wrapper written by export so imports can be validated
+#[derive(Debug, Serialize, Deserialize)]
+struct SettingsExport {
+    format_version: u32,
+    exported_at: u64,
+    app_version: String,
+    settings: AppSettings,
+}
+
+/// Get the settings file path
+fn get_settings_path() -> Option<PathBuf> {
+    paths::get_app_data_dir().map(|p| p.join("settings.json"))
+}
+
+/// Load settings from disk, falling back to defaults
+pub fn load_settings() -> AppSettings {
+    get_settings_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// Save settings to disk
+pub fn save_settings(settings: &AppSettings) -> Result<(), String> {
+    let path = get_settings_path().ok_or("Could not determine settings directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create settings directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to save settings: {}", e))
+}
+
+/// Save a change the user asked for; rejected when the machine policy locks settings
+pub fn save_user_settings(settings: &AppSettings) -> Result<(), String> {
+    if policy::load_policy().lock_settings {
+        return Err(policy::POLICY_BLOCKED.to_string());
+    }
+    save_settings(settings)
+}
+
+/// Parse an exported settings file (also accepts a bare settings.json)
+fn parse_settings_file(path: &Path) -> Result<AppSettings, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Could not read settings file: {}", e))?;
+
+    if let Ok(export) = serde_json::from_str::<SettingsExport>(&content) {
+        if export.format_version > SETTINGS_FORMAT_VERSION {
+            return Err(format!(
+                "Settings were exported by a newer Version Guard (format {})",
+                export.format_version
+            ));
+        }
+        return Ok(export.settings);
+    }
+
+    serde_json::from_str::<AppSettings>(&content)
+        .map_err(|e| format!("Not a valid settings file: {}", e))
+}
+
+/// Get current application settings
+#[tauri::command]
+pub fn get_app_settings() -> AppSettings {
+    load_settings()
+}
+
+/// Replace application settings
+#[tauri::command]
+pub fn save_app_settings(settings: AppSettings) -> Result<(), String> {
+    save_user_settings(&settings)
+}
+
+/// Attach a note and tags to a version (empty note and tags remove the entry)
+#[tauri::command]
+pub fn set_version_note(
+    version: String,
+    note: String,
+    tags: Vec<String>,
+) -> Result<Option<VersionNote>, String> {
+    let mut settings = load_settings();
+
+    let mut clean_tags: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !clean_tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            clean_tags.push(tag.to_string());
+        }
+    }
+    let entry = VersionNote {
+        note: note.trim().to_string(),
+        tags: clean_tags,
+    };
+
+    let saved = if entry.note.is_empty() && entry.tags.is_empty() {
+        settings.version_notes.remove(&version);
+        None
+    } else {
+        settings.version_notes.insert(version, entry.clone());
+        Some(entry)
+    };
+
+    save_user_settings(&settings)?;
+    Ok(saved)
+}
+
+/// Export application settings to a user-chosen file
+#[tauri::command]
+pub fn export_app_settings(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    let mut settings = load_settings();
+    // Never write the SMTP password or GitHub token into a portable file
+    settings.email.password.clear();
+    settings.github_token.clear();
+
+    let export = SettingsExport {
+        format_version: SETTINGS_FORMAT_VERSION,
+        exported_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        app_version: app.package_info().version.to_string(),
+        settings,
+    };
+
+    let content = serde_json::to_string_pretty(&export)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write export: {}", e))
+}
+
+/// Import application settings from a previously exported file
+#[tauri::command]
+pub fn import_app_settings(path: String) -> Result<AppSettings, String> {
+    let mut settings = parse_settings_file(Path::new(&path))?;
+    // Exports don't carry the SMTP password or GitHub token; keep the local ones
+    let local = load_settings();
+    if settings.email.password.is_empty() {
+        settings.email.password = local.email.password;
+    }
+    if settings.github_token.is_empty() {
+        settings.github_token = local.github_token;
+    }
+    save_user_settings(&settings)?;
+    Ok(settings)
+}
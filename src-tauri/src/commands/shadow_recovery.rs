@@ -0,0 +1,204 @@
+//! Version recovery from Volume Shadow Copies
+//! Last-resort rescue of a version folder deleted before any backup existed
+
+use serde::Serialize;
+use std::ffi::OsString;
+use std::fs;
+use std::os::windows::ffi::{OsStrExt, OsStringExt};
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use windows_sys::Win32::Storage::FileSystem::{
+    GetVolumeNameForVolumeMountPointW, GetVolumePathNameW,
+};
+
+use super::{backup, environment, paths};
+
+/// Prevents a console window flashing up for the query
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Every shadow copy device path starts with this
+const SHADOW_DEVICE_PREFIX: &str = r"\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopy";
+
+/// Listing and reading shadow copies needs administrator rights
+const NOT_ELEVATED: &str = "Run Version Guard as administrator to search shadow copies";
+
+/// A deleted version folder still present in a shadow copy
+#[derive(Debug, Clone, Serialize)]
+pub struct ShadowVersion {
+    pub version: String,
+    /// Folder inside the shadow copy (transport form)
+    pub shadow_path: String,
+    /// When the shadow copy was taken (ISO 8601)
+    pub created: Option<String>,
+}
+
+/// A shadow copy of one volume
+struct ShadowCopy {
+    device: String,
+    volume: String,
+    created: Option<String>,
+}
+
+fn wide(path: &Path) -> Vec<u16> {
+    path.as_os_str().encode_wide().chain(Some(0)).collect()
+}
+
+fn from_wide(buf: &[u16]) -> PathBuf {
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    PathBuf::from(OsString::from_wide(&buf[..len]))
+}
+
+/// Mount point of the volume holding `path` (e.g., "C:\") and its "\\?\Volume{GUID}\" name
+fn volume_of(path: &Path) -> Option<(PathBuf, String)> {
+    let mut mount = [0u16; 261];
+    // SAFETY: both buffers are NUL-terminated or sized as passed
+    let ok =
+        unsafe { GetVolumePathNameW(wide(path).as_ptr(), mount.as_mut_ptr(), mount.len() as u32) };
+    if ok == 0 {
+        return None;
+    }
+    let mut name = [0u16; 64];
+    // SAFETY: `mount` was NUL-terminated by the call above, `name` is sized as passed
+    let ok = unsafe {
+        GetVolumeNameForVolumeMountPointW(mount.as_ptr(), name.as_mut_ptr(), name.len() as u32)
+    };
+    if ok == 0 {
+        return None;
+    }
+    Some((
+        from_wide(&mount),
+        from_wide(&name).to_string_lossy().to_string(),
+    ))
+}
+
+/// Existing shadow copies, newest first
+fn list_shadow_copies() -> Result<Vec<ShadowCopy>, String> {
+    let script = "Get-CimInstance Win32_ShadowCopy | Sort-Object InstallDate -Descending | \
+         ForEach-Object { $_.DeviceObject + '|' + $_.VolumeName + '|' + $_.InstallDate.ToString('o') }";
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("Could not run PowerShell: {}", e))?;
+    if !output.status.success() {
+        return Err(if environment::is_elevated() {
+            "Could not list shadow copies".to_string()
+        } else {
+            NOT_ELEVATED.to_string()
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.trim().splitn(3, '|');
+            let device = parts.next()?.to_string();
+            let volume = parts.next()?.to_string();
+            let created = parts.next().map(str::to_string).filter(|c| !c.is_empty());
+            device
+                .starts_with(SHADOW_DEVICE_PREFIX)
+                .then_some(ShadowCopy {
+                    device,
+                    volume,
+                    created,
+                })
+        })
+        .collect())
+}
+
+/// Version folders in shadow copies of the Apps folder that are no longer installed
+pub fn find_deleted_versions() -> Result<Vec<ShadowVersion>, String> {
+    let apps = paths::get_capcut_apps_path().ok_or("CapCut Apps folder not found")?;
+    let (mount, volume) =
+        volume_of(&apps).ok_or("Could not determine the volume of the Apps folder")?;
+    let relative = apps
+        .strip_prefix(&mount)
+        .map_err(|_| "Could not locate the Apps folder on its volume")?;
+
+    let shadows: Vec<ShadowCopy> = list_shadow_copies()?
+        .into_iter()
+        .filter(|s| s.volume.eq_ignore_ascii_case(&volume))
+        .collect();
+    if shadows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut found: Vec<ShadowVersion> = Vec::new();
+    let mut readable = false;
+    for shadow in &shadows {
+        let shadow_apps = Path::new(&shadow.device).join(relative);
+        let entries = match fs::read_dir(&shadow_apps) {
+            Ok(e) => e,
+            // Shadow copy taken before CapCut was installed
+            Err(_) => continue,
+        };
+        readable = true;
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let version = entry.file_name().to_string_lossy().to_string();
+            // Newest shadow copy wins for each version
+            if !paths::has_main_executable(&path)
+                || apps.join(&version).exists()
+                || found.iter().any(|f| f.version == version)
+            {
+                continue;
+            }
+            found.push(ShadowVersion {
+                version,
+                shadow_path: paths::path_to_transport(&path),
+                created: shadow.created.clone(),
+            });
+        }
+    }
+
+    if !readable && !environment::is_elevated() {
+        return Err(NOT_ELEVATED.to_string());
+    }
+    Ok(found)
+}
+
+/// Copy a version folder out of a shadow copy into Apps; returns the version name
+pub fn restore_version(shadow_path: &Path) -> Result<String, String> {
+    if !shadow_path
+        .to_string_lossy()
+        .starts_with(SHADOW_DEVICE_PREFIX)
+    {
+        return Err("Not a shadow copy path".to_string());
+    }
+    let version = shadow_path
+        .file_name()
+        .ok_or("Invalid version folder")?
+        .to_string_lossy()
+        .to_string();
+    let apps = paths::get_capcut_apps_path().ok_or("CapCut Apps folder not found")?;
+    let target = apps.join(&version);
+    if target.exists() {
+        return Err(format!("Version {} is already installed", version));
+    }
+
+    if let Err(e) = backup::copy_dir_recursive(shadow_path, &target) {
+        // Don't leave a half-copied version behind
+        let _ = fs::remove_dir_all(&target);
+        return Err(format!("Restore failed: {}", e));
+    }
+    Ok(version)
+}
+
+/// Search shadow copies for deleted CapCut versions
+#[tauri::command]
+pub async fn find_shadow_versions() -> Result<Vec<ShadowVersion>, String> {
+    tauri::async_runtime::spawn_blocking(find_deleted_versions)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Restore a deleted version from a shadow copy
+#[tauri::command]
+pub async fn restore_shadow_version(shadow_path: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        restore_version(&paths::path_from_transport(&shadow_path))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
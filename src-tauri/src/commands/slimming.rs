@@ -0,0 +1,217 @@
+//! Optional component cleanup for a kept version
+//! Removes bundled extras (sample templates, extra languages, built-in effects) to save disk space
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::log_entry::LogEntry;
+use super::{backup, exclusions, format, paths, process, protector};
+
+/// A bundled extra that CapCut runs without
+struct ComponentDef {
+    id: &'static str,
+    label: &'static str,
+    description: &'static str,
+    /// Candidate locations relative to the version folder (layouts differ between releases)
+    paths: &'static [&'static str],
+    /// Only files inside the folders are removed, keeping English ones
+    keep_english: bool,
+}
+
+const COMPONENTS: &[ComponentDef] = &[
+    ComponentDef {
+        id: "templates",
+        label: "Sample Templates",
+        description: "Demo templates shipped with the installer",
+        paths: &[
+            "Resources\\templates",
+            "Resources\\template",
+            "Resources\\demo",
+        ],
+        keep_english: false,
+    },
+    ComponentDef {
+        id: "locales",
+        label: "Extra Languages",
+        description: "Interface translations other than English",
+        paths: &["locales", "Resources\\locales", "translations"],
+        keep_english: true,
+    },
+    ComponentDef {
+        id: "effects",
+        label: "Bundled Effects",
+        description: "Built-in effects; CapCut downloads them again when used",
+        paths: &[
+            "Resources\\effect",
+            "Resources\\effects",
+            "Resources\\builtin_effect",
+        ],
+        keep_english: false,
+    },
+];
+
+/// An optional component found in a version folder
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentInfo {
+    pub id: String,
+    pub label: String,
+    pub description: String,
+    /// Bytes freed by removing it
+    pub size_bytes: u64,
+}
+
+/// Slimming result
+#[derive(Debug, Clone, Serialize)]
+pub struct SlimResult {
+    pub success: bool,
+    pub error: Option<String>,
+    pub logs: Vec<LogEntry>,
+    pub freed_bytes: u64,
+}
+
+/// Whether a translation file is English (e.g., en-US.pak, en_GB.qm)
+fn is_english(path: &Path) -> bool {
+    let stem = path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_ascii_lowercase();
+    stem == "en" || stem.starts_with("en-") || stem.starts_with("en_")
+}
+
+/// Files and folders that removing a component deletes
+fn removable_entries(version_dir: &Path, def: &ComponentDef) -> Vec<PathBuf> {
+    let existing = def
+        .paths
+        .iter()
+        .map(|rel| version_dir.join(rel))
+        .filter(|p| p.is_dir());
+
+    if !def.keep_english {
+        return existing.collect();
+    }
+    existing
+        .flat_map(|dir| fs::read_dir(dir).ok().into_iter().flatten())
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| !is_english(p))
+        .collect()
+}
+
+fn entry_size(path: &Path) -> u64 {
+    if path.is_dir() {
+        backup::calc_dir_size(path)
+    } else {
+        fs::metadata(path).map_or(0, |m| m.len())
+    }
+}
+
+fn remove_entry(path: &Path) -> Result<(), String> {
+    if path.is_dir() {
+        return protector::delete_version_dir(path);
+    }
+    if let Ok(meta) = fs::metadata(path) {
+        let mut perms = meta.permissions();
+        if perms.readonly() {
+            #[allow(clippy::permissions_set_readonly_false)]
+            perms.set_readonly(false);
+            fs::set_permissions(path, perms).ok();
+        }
+    }
+    fs::remove_file(path).map_err(|e| e.to_string())
+}
+
+/// List optional components present in a version folder with their sizes
+#[tauri::command]
+pub async fn scan_version_components(path: String) -> Result<Vec<ComponentInfo>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let version_dir = paths::path_from_transport(&path);
+        if !version_dir.is_dir() {
+            return Err(format!("Version folder not found: {}", path));
+        }
+
+        Ok(COMPONENTS
+            .iter()
+            .filter_map(|def| {
+                let size_bytes: u64 = removable_entries(&version_dir, def)
+                    .iter()
+                    .map(|p| entry_size(p))
+                    .sum();
+                (size_bytes > 0).then(|| ComponentInfo {
+                    id: def.id.to_string(),
+                    label: def.label.to_string(),
+                    description: def.description.to_string(),
+                    size_bytes,
+                })
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Remove the chosen optional components from a version folder
+#[tauri::command]
+pub async fn remove_version_components(path: String, components: Vec<String>) -> SlimResult {
+    if process::is_capcut_running() {
+        return SlimResult {
+            success: false,
+            error: Some("Close CapCut before removing its components".to_string()),
+            logs: vec![],
+            freed_bytes: 0,
+        };
+    }
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let version_dir = paths::path_from_transport(&path);
+        let mut logs: Vec<LogEntry> = Vec::new();
+        let mut freed_bytes = 0;
+        let mut failed = 0;
+        let excluded = exclusions::excluded_paths();
+
+        for def in COMPONENTS
+            .iter()
+            .filter(|d| components.iter().any(|c| c == d.id))
+        {
+            logs.push(LogEntry::info(format!("Removing {}...", def.label)));
+            for entry in removable_entries(&version_dir, def) {
+                if exclusions::touches_excluded(&entry, &excluded) {
+                    logs.push(
+                        LogEntry::warn("Skipped (on the never-touch list)").with_path(&entry),
+                    );
+                    continue;
+                }
+                let size = entry_size(&entry);
+                match remove_entry(&entry) {
+                    Ok(()) => freed_bytes += size,
+                    Err(e) => {
+                        logs.push(
+                            LogEntry::warn(format!("Could not remove: {}", e)).with_path(&entry),
+                        );
+                        failed += 1;
+                    }
+                }
+            }
+        }
+        logs.push(LogEntry::ok(format!(
+            "Freed {}",
+            format::format_size(freed_bytes)
+        )));
+
+        SlimResult {
+            success: failed == 0,
+            error: (failed > 0).then(|| format!("{} item(s) could not be removed", failed)),
+            logs,
+            freed_bytes,
+        }
+    })
+    .await;
+
+    result.unwrap_or_else(|e| SlimResult {
+        success: false,
+        error: Some(e.to_string()),
+        logs: vec![],
+        freed_bytes: 0,
+    })
+}
@@ -0,0 +1,98 @@
+//! Before/after state snapshots
+//! Records exactly what a protection run changed (Apps listing, configure.ini, blockers, registry) for the run history
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use super::{blockers, channel, config_diff, paths, registry_guard};
+
+/// Item name to a short description of its state
+pub type StateSnapshot = BTreeMap<String, String>;
+
+/// One item that differs between two snapshots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateChange {
+    pub item: String,
+    /// None when the item did not exist before
+    pub before: Option<String>,
+    /// None when the item is gone after
+    pub after: Option<String>,
+}
+
+/// "folder", or size and attributes of a file
+fn describe_entry(path: &Path) -> Option<String> {
+    let meta = fs::symlink_metadata(path).ok()?;
+    let mut description = match meta.is_dir() {
+        true => "folder".to_string(),
+        false => format!("file, {} bytes", meta.len()),
+    };
+    if meta.permissions().readonly() {
+        description.push_str(", read-only");
+    }
+    Some(description)
+}
+
+/// Capture the state a protection run may change
+pub fn capture() -> StateSnapshot {
+    let mut snapshot = StateSnapshot::new();
+    let capcut_paths = match paths::resolve_capcut_paths() {
+        Some(p) => p,
+        None => return snapshot,
+    };
+    let (apps, root) = (&capcut_paths.apps, &capcut_paths.root);
+
+    // Top level of Apps: version folders, ProductInfo.xml, configure.ini
+    if let Ok(entries) = fs::read_dir(apps) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            if let Some(description) = describe_entry(&entry.path()) {
+                snapshot.insert(
+                    format!("Apps\\{}", entry.file_name().to_string_lossy()),
+                    description,
+                );
+            }
+        }
+    }
+
+    if let Ok(content) = fs::read(apps.join("configure.ini")) {
+        for (key, value) in config_diff::parse_ini(&content) {
+            snapshot.insert(format!("configure.ini: {}", key), value);
+        }
+    }
+
+    let profile = channel::guard_profile(channel::detect_channel(root, apps));
+    let download_dir = root.join("User Data").join("Download");
+    let blocker_paths = profile
+        .updater_names
+        .iter()
+        .map(|name| download_dir.join(name))
+        .chain(blockers::extra_blocker_paths(root));
+    for path in blocker_paths {
+        if let (Some(description), Ok(relative)) = (describe_entry(&path), path.strip_prefix(root))
+        {
+            snapshot.insert(relative.to_string_lossy().to_string(), description);
+        }
+    }
+
+    snapshot.extend(registry_guard::watched_values());
+    snapshot
+}
+
+/// Items added, removed or changed between two snapshots, in item order
+pub fn diff(before: &StateSnapshot, after: &StateSnapshot) -> Vec<StateChange> {
+    let mut items: Vec<&String> = before.keys().chain(after.keys()).collect();
+    items.sort();
+    items.dedup();
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let (old, new) = (before.get(item), after.get(item));
+            (old != new).then(|| StateChange {
+                item: item.clone(),
+                before: old.cloned(),
+                after: new.cloned(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,69 @@
+//! Guard statistics
+//! Running totals of what the guards have done, shown in the Settings stats panel
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use super::paths;
+
+/// Serializes read-modify-write of the stats file across threads
+static STATS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Lifetime guard counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuardStats {
+    /// Update payloads deleted from the download folder
+    pub downloads_purged: u64,
+    /// Total size of deleted payloads
+    pub bytes_purged: u64,
+    /// Unix seconds of the last purge
+    pub last_purge: Option<u64>,
+}
+
+/// Get the stats file path
+fn get_stats_path() -> Option<PathBuf> {
+    paths::get_app_data_dir().map(|p| p.join("stats.json"))
+}
+
+fn load_stats() -> GuardStats {
+    get_stats_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_stats(stats: &GuardStats) -> Result<(), String> {
+    let path = get_stats_path().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(stats).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save stats: {}", e))
+}
+
+/// Count a deleted update payload
+pub fn record_purge(bytes: u64) {
+    let _guard = STATS_LOCK.lock();
+    let mut stats = load_stats();
+    stats.downloads_purged += 1;
+    stats.bytes_purged += bytes;
+    stats.last_purge = Some(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    );
+
+    if let Err(e) = save_stats(&stats) {
+        tracing::warn!("Could not record stats: {}", e);
+    }
+}
+
+/// Get lifetime guard statistics
+#[tauri::command]
+pub fn get_guard_stats() -> GuardStats {
+    load_stats()
+}
@@ -1,7 +1,7 @@
+use crate::commands::paths;
 use crate::commands::scanner::{get_capcut_apps_path, get_capcut_root_path};
 use serde::Serialize;
 use std::fs;
-use std::path::PathBuf;
 
 #[derive(Serialize)]
 pub struct SwitchResult {
@@ -13,7 +13,7 @@ pub struct SwitchResult {
 #[tauri::command]
 pub fn switch_version(target_path: String) -> SwitchResult {
     let mut logs = Vec::new();
-    let target_dir = PathBuf::from(&target_path);
+    let target_dir = paths::path_from_transport(&target_path);
 
     logs.push(format!("Initiating switch to version at: {:?}", target_dir));
 
@@ -40,7 +40,7 @@ pub fn switch_version(target_path: String) -> SwitchResult {
         let product_info_path = root_path.join("Apps").join("ProductInfo.xml");
         logs.push(format!("Updating ProductInfo at: {:?}", product_info_path));
 
-        let target_exe = target_dir.join("CapCut.exe");
+        let target_exe = paths::main_executable(&target_dir);
 
         // Simple XML replacer (robust enough for this specific file)
         let new_content = format!(
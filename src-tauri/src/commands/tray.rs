@@ -0,0 +1,199 @@
+//! System tray
+//! Shield icon colored by protection health, with Re-check, Re-apply and Open actions and minimize-to-tray
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::image::Image;
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Emitter, Manager, Window, WindowEvent};
+
+use super::{process, protector, reprotect, settings};
+
+/// Identifier of the tray icon
+const TRAY_ID: &str = "main";
+
+/// Seconds between health polls
+const POLL_INTERVAL_SECS: u64 = 5;
+
+/// Edge length of the generated shield icon
+const ICON_SIZE: u32 = 32;
+
+/// Protection health shown by the tray icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayHealth {
+    Protected,
+    /// A protection run was saved but the guards are no longer in place
+    Tampered,
+    /// CapCut is open; nothing can be changed until it closes
+    CapCutRunning,
+    /// Never protected (or protection was removed)
+    Unprotected,
+}
+
+impl TrayHealth {
+    fn color(self) -> [u8; 3] {
+        match self {
+            TrayHealth::Protected => [52, 199, 89],
+            TrayHealth::Tampered => [255, 59, 48],
+            TrayHealth::CapCutRunning => [255, 149, 0],
+            TrayHealth::Unprotected => [142, 142, 147],
+        }
+    }
+
+    fn tooltip(self) -> &'static str {
+        match self {
+            TrayHealth::Protected => "CC Version Guard — Protected",
+            TrayHealth::Tampered => "CC Version Guard — Protection broken",
+            TrayHealth::CapCutRunning => "CC Version Guard — CapCut is running",
+            TrayHealth::Unprotected => "CC Version Guard — Not protected",
+        }
+    }
+}
+
+/// Last health shown, so the icon is only redrawn on a change
+#[derive(Default)]
+pub struct TrayState {
+    health: Mutex<Option<TrayHealth>>,
+}
+
+/// Health of the active install
+fn current_health() -> TrayHealth {
+    if process::is_capcut_running() {
+        TrayHealth::CapCutRunning
+    } else if protector::check_protection_status().is_protected {
+        TrayHealth::Protected
+    } else if reprotect::kept_version().is_some() {
+        TrayHealth::Tampered
+    } else {
+        TrayHealth::Unprotected
+    }
+}
+
+/// A shield filled with `color` on a transparent square
+fn shield_icon(color: [u8; 3]) -> Image<'static> {
+    let size = ICON_SIZE as usize;
+    let center = size as f32 / 2.0;
+    let (top, shoulder, tip) = (3.0, 17.0, 30.0);
+    let half_width = 12.5;
+
+    let mut rgba = vec![0u8; size * size * 4];
+    for y in 0..size {
+        let py = y as f32 + 0.5;
+        // Straight sides down to the shoulder, then tapering to the tip
+        let reach = if py < top || py > tip {
+            continue;
+        } else if py <= shoulder {
+            half_width
+        } else {
+            half_width * (tip - py) / (tip - shoulder)
+        };
+        for x in 0..size {
+            if (x as f32 + 0.5 - center).abs() <= reach {
+                let i = (y * size + x) * 4;
+                rgba[i..i + 3].copy_from_slice(&color);
+                rgba[i + 3] = 255;
+            }
+        }
+    }
+    Image::new_owned(rgba, ICON_SIZE, ICON_SIZE)
+}
+
+fn show_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Re-read the health and update the icon; emits `tray-status` when it changed (or when `force`d)
+pub fn refresh(app: &AppHandle, force: bool) -> TrayHealth {
+    let health = current_health();
+    let changed = match app.state::<TrayState>().health.lock() {
+        Ok(mut last) => last.replace(health) != Some(health),
+        Err(_) => true,
+    };
+    if changed {
+        if let Some(tray) = app.tray_by_id(TRAY_ID) {
+            let _ = tray.set_icon(Some(shield_icon(health.color())));
+            let _ = tray.set_tooltip(Some(health.tooltip()));
+        }
+    }
+    if changed || force {
+        let _ = app.emit("tray-status", health);
+    }
+    health
+}
+
+/// Create the tray icon and start polling protection health
+pub fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    app.manage(TrayState::default());
+
+    let open_i = MenuItem::with_id(app, "open", "Open Version Guard", true, None::<&str>)?;
+    let check_i = MenuItem::with_id(app, "recheck", "Re-check", true, None::<&str>)?;
+    let reapply_i = MenuItem::with_id(
+        app,
+        "reapply",
+        "Re-apply Protection",
+        reprotect::kept_version().is_some(),
+        None::<&str>,
+    )?;
+    let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&open_i, &check_i, &reapply_i, &quit_i])?;
+
+    TrayIconBuilder::with_id(TRAY_ID)
+        .icon(shield_icon(TrayHealth::Unprotected.color()))
+        .tooltip("CC Version Guard")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            "open" => show_window(app),
+            "recheck" => {
+                let app = app.clone();
+                thread::spawn(move || refresh(&app, true));
+            }
+            "reapply" => {
+                let app = app.clone();
+                thread::spawn(move || {
+                    reprotect::reapply_saved(&app);
+                    refresh(&app, true);
+                });
+            }
+            "quit" => app.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            // Left click shows the window
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_window(tray.app_handle());
+            }
+        })
+        .build(app)?;
+
+    let handle = app.handle().clone();
+    thread::spawn(move || loop {
+        refresh(&handle, false);
+        let _ = reapply_i.set_enabled(reprotect::kept_version().is_some());
+        thread::sleep(Duration::from_secs(POLL_INTERVAL_SECS));
+    });
+    Ok(())
+}
+
+/// With Minimize to Tray on, closing the main window hides it instead of quitting
+pub fn on_window_event(window: &Window, event: &WindowEvent) {
+    if let WindowEvent::CloseRequested { api, .. } = event {
+        if window.label() == "main" && settings::load_settings().minimize_to_tray {
+            api.prevent_close();
+            let _ = window.hide();
+        }
+    }
+}
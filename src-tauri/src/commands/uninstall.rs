@@ -0,0 +1,245 @@
+//! Guard self-removal
+//! Undoes every persistent change the app made and verifies each one afterwards, so users can walk away cleanly
+
+use serde::Serialize;
+use std::fs;
+use tauri::State;
+
+use super::download_purge::PurgeState;
+use super::log_entry::LogEntry;
+use super::watchdog::WatchdogState;
+use super::{
+    acl_lock, autoruns, autostart, config_guard, domain_block, firewall, hardening, installs,
+    paths, policy, protector, registry_guard, settings, updater_stub,
+};
+
+/// One persistent change and whether it is gone after removal
+#[derive(Debug, Clone, Serialize)]
+pub struct RemovalCheck {
+    pub name: String,
+    /// Verified absent after removal
+    pub removed: bool,
+    /// What is left behind, when not removed
+    pub detail: Option<String>,
+}
+
+impl RemovalCheck {
+    pub fn new(name: &str, removed: bool, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            removed,
+            detail: (!removed).then(|| detail.into()),
+        }
+    }
+}
+
+/// Outcome of the uninstall flow
+#[derive(Debug, Clone, Serialize)]
+pub struct UninstallResult {
+    /// Every check passed
+    pub clean: bool,
+    pub checks: Vec<RemovalCheck>,
+    pub logs: Vec<LogEntry>,
+}
+
+/// Turn off everything that would start background work or system changes on the next launch
+fn disable_persistent_settings() -> Result<(), String> {
+    let mut app_settings = settings::load_settings();
+    app_settings.schedules.verify_interval_minutes = 0;
+    app_settings.schedules.cache_clean_interval_days = 0;
+    app_settings.schedules.backup_interval_days = 0;
+    app_settings.schedules.draft_backup_interval_days = 0;
+    app_settings.purge_downloads = false;
+    settings::save_settings(&app_settings)
+}
+
+/// Run `f` scoped to each detected install (unscoped when CapCut is gone)
+fn for_each_install(mut f: impl FnMut()) {
+    let detected = installs::detect_installs();
+    if detected.is_empty() {
+        f();
+    }
+    for install in &detected {
+        let _scope = installs::scope(install);
+        f();
+    }
+}
+
+/// Re-check each change after removal
+fn verify(
+    watchdog: &WatchdogState,
+    purge: &PurgeState,
+    app_data_removed: Option<bool>,
+) -> Vec<RemovalCheck> {
+    let (mut blockers_exist, mut config_locked, mut hardened, mut acl_locked, mut stubbed) =
+        (false, false, 0, 0, 0);
+    let mut firewall_rules = 0;
+    for_each_install(|| {
+        let status = protector::check_protection_status();
+        blockers_exist |= status.blockers_exist;
+        config_locked |= status.config_locked || config_guard::is_pinned();
+        hardened += hardening::hardened_count();
+        acl_locked += acl_lock::locked_count();
+        stubbed += updater_stub::stubbed_count();
+        firewall_rules += firewall::present_count();
+    });
+    let mut checks = vec![
+        RemovalCheck::new(
+            "Background tasks (watchdog, download purge)",
+            !watchdog.status().running && !purge.status().running,
+            "Still running; restart Version Guard and try again",
+        ),
+        RemovalCheck::new(
+            "Startup entry (HKCU Run key)",
+            !autostart::get_autostart_enabled(),
+            "CCVersionGuard is still listed under HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Run",
+        ),
+        RemovalCheck::new(
+            "Hosts file entries",
+            !domain_block::legacy_redirect_present() && !domain_block::is_blocked(),
+            "A Version Guard block is still in the hosts file (needs administrator rights)",
+        ),
+        RemovalCheck::new(
+            "Update blockers",
+            !blockers_exist,
+            "A blocker file is still in place",
+        ),
+        RemovalCheck::new(
+            "configure.ini lock",
+            !config_locked,
+            "configure.ini is still locked or pinned",
+        ),
+        RemovalCheck::new(
+            "Hardened files",
+            hardened == 0,
+            format!("{} file(s) still read-only", hardened),
+        ),
+        RemovalCheck::new(
+            "Permission locks",
+            acl_locked == 0,
+            format!("{} path(s) still deny writes", acl_locked),
+        ),
+        RemovalCheck::new(
+            "Updater stubs",
+            stubbed == 0,
+            format!("{} updater(s) not restored from .bak", stubbed),
+        ),
+        RemovalCheck::new(
+            "Updater firewall rules",
+            firewall_rules == 0,
+            format!("{} rule(s) still in the firewall", firewall_rules),
+        ),
+        RemovalCheck::new(
+            "CapCut autorun entries",
+            autoruns::pending_restore_count() == 0,
+            "A CapCut task or autorun entry disabled by protection is still disabled",
+        ),
+    ];
+    if let Some(removed) = app_data_removed {
+        checks.push(RemovalCheck::new(
+            "App data folder (settings, backups, logs)",
+            removed,
+            paths::get_app_data_dir()
+                .map(|p| format!("{} could not be fully deleted", p.display()))
+                .unwrap_or_default(),
+        ));
+    }
+    checks
+}
+
+/// Remove every persistent change Version Guard made, optionally with its data folder
+#[tauri::command]
+pub fn uninstall_guard(
+    watchdog: State<'_, WatchdogState>,
+    purge: State<'_, PurgeState>,
+    remove_app_data: bool,
+) -> Result<UninstallResult, String> {
+    let machine_policy = policy::load_policy();
+    // Uninstalling also rewrites settings, so a settings lock blocks it up front
+    if machine_policy.disallow_unprotect
+        || machine_policy.force_watchdog
+        || machine_policy.force_autostart
+        || machine_policy.lock_settings
+    {
+        return Err(policy::POLICY_BLOCKED.to_string());
+    }
+
+    let mut logs: Vec<LogEntry> = Vec::new();
+
+    // Stop background work first so nothing re-applies what is removed below
+    watchdog.stop();
+    purge.stop();
+    if let Err(e) = domain_block::remove_legacy_redirect() {
+        logs.push(LogEntry::warn(e));
+    }
+    if let Err(e) = domain_block::remove() {
+        logs.push(LogEntry::warn(e));
+    }
+    autoruns::restore_disabled(&mut logs);
+    if let Err(e) = disable_persistent_settings() {
+        logs.push(LogEntry::warn(format!(
+            "Could not turn off schedules: {}",
+            e
+        )));
+    }
+    logs.push(LogEntry::ok("Background tasks stopped"));
+
+    match autostart::set_autostart_enabled(false) {
+        Ok(()) => logs.push(LogEntry::ok("Startup entry removed")),
+        Err(e) => logs.push(LogEntry::warn(format!(
+            "Could not remove startup entry: {}",
+            e
+        ))),
+    }
+
+    for_each_install(|| {
+        let unprotect = protector::remove_protection();
+        logs.extend(unprotect.logs);
+        if let Some(e) = unprotect.error {
+            // CapCut already gone: nothing to unlock, but our records still have to go
+            logs.push(LogEntry::warn(e));
+            if let Err(e) = config_guard::clear_pin() {
+                logs.push(LogEntry::warn(format!(
+                    "Could not release configure.ini pin: {}",
+                    e
+                )));
+            }
+            for value in registry_guard::restore_cleared_values() {
+                logs.push(LogEntry::ok(format!("Registry value restored: {}", value)));
+            }
+            if let Err(e) = registry_guard::clear_pins() {
+                logs.push(LogEntry::warn(format!(
+                    "Could not release registry pins: {}",
+                    e
+                )));
+            }
+            hardening::unharden_all(&mut logs);
+            acl_lock::unlock_all(&mut logs);
+            updater_stub::restore_all(&mut logs);
+        }
+    });
+
+    let app_data_removed = remove_app_data.then(|| match paths::get_app_data_dir() {
+        Some(dir) if dir.exists() => match fs::remove_dir_all(&dir) {
+            Ok(()) => {
+                logs.push(LogEntry::ok("App data folder deleted").with_path(&dir));
+                true
+            }
+            Err(e) => {
+                logs.push(
+                    LogEntry::warn(format!("Could not delete app data folder: {}", e))
+                        .with_path(&dir),
+                );
+                false
+            }
+        },
+        _ => true,
+    });
+
+    let checks = verify(&watchdog, &purge, app_data_removed);
+    Ok(UninstallResult {
+        clean: checks.iter().all(|c| c.removed),
+        checks,
+        logs,
+    })
+}
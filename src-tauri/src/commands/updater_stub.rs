@@ -0,0 +1,274 @@
+//! Updater stubs
+//! Renames CapCut's updater executables to `.bak` and writes a do-nothing executable in their place, tracked so unprotect puts the originals back
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::log_entry::LogEntry;
+use super::{attributes, blockers, channel, installs, paths};
+
+/// Updaters some builds ship besides the channel's own
+const EXTRA_UPDATER_NAMES: &[&str] = &["AppUpdater.exe"];
+
+/// Apps subfolders searched for updaters (version folders and their direct subfolders)
+const APPS_SEARCH_DEPTH: usize = 3;
+
+/// Appended to the original's file name
+const BACKUP_SUFFIX: &str = ".bak";
+
+/// Updaters this app replaced, by their original path
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StubbedUpdaters {
+    files: Vec<String>,
+}
+
+/// Get the stubbed updater list path
+fn get_stubbed_path() -> Option<PathBuf> {
+    installs::get_install_data_dir().map(|p| p.join("stubbed_updaters.json"))
+}
+
+fn load_stubbed() -> StubbedUpdaters {
+    get_stubbed_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_stubbed(stubbed: &StubbedUpdaters) -> Result<(), String> {
+    let path = get_stubbed_path().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(stubbed).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save stubbed updater list: {}", e))
+}
+
+/// Minimal x64 GUI executable whose entry point returns 0 straight away
+///
+/// No imports, no resources and no console, so starting it shows nothing and changes nothing.
+fn stub_image() -> Vec<u8> {
+    const FILE_ALIGNMENT: u32 = 0x200;
+    const SECTION_ALIGNMENT: u32 = 0x1000;
+    const CODE: &[u8] = &[0x31, 0xC0, 0xC3]; // xor eax, eax; ret
+
+    let mut image: Vec<u8> = Vec::with_capacity(2 * FILE_ALIGNMENT as usize);
+    let mut put = |bytes: &[u8]| image.extend_from_slice(bytes);
+
+    // DOS header: only the signature and the offset of the PE header matter
+    put(b"MZ");
+    put(&[0; 0x3A]);
+    put(&0x40u32.to_le_bytes());
+
+    // PE signature and file header: x64, one section, executable without relocations
+    put(b"PE\0\0");
+    put(&0x8664u16.to_le_bytes());
+    put(&1u16.to_le_bytes());
+    put(&[0; 12]);
+    put(&240u16.to_le_bytes());
+    put(&0x0023u16.to_le_bytes());
+
+    // PE32+ optional header
+    put(&0x020Bu16.to_le_bytes());
+    put(&[0, 0]);
+    put(&FILE_ALIGNMENT.to_le_bytes()); // size of code
+    put(&[0; 8]);
+    put(&SECTION_ALIGNMENT.to_le_bytes()); // entry point
+    put(&SECTION_ALIGNMENT.to_le_bytes()); // base of code
+    put(&0x1_4000_0000u64.to_le_bytes());
+    put(&SECTION_ALIGNMENT.to_le_bytes());
+    put(&FILE_ALIGNMENT.to_le_bytes());
+    put(&6u16.to_le_bytes()); // OS version 6.0
+    put(&[0; 6]);
+    put(&6u16.to_le_bytes()); // subsystem version 6.0
+    put(&[0; 6]);
+    put(&(2 * SECTION_ALIGNMENT).to_le_bytes()); // size of image
+    put(&FILE_ALIGNMENT.to_le_bytes()); // size of headers
+    put(&[0; 4]);
+    put(&2u16.to_le_bytes()); // Windows GUI subsystem
+    put(&0x8100u16.to_le_bytes()); // NX compatible, terminal server aware
+    for size in [0x10_0000u64, 0x1000, 0x10_0000, 0x1000] {
+        put(&size.to_le_bytes());
+    }
+    put(&[0; 4]);
+    put(&16u32.to_le_bytes());
+    put(&[0; 16 * 8]);
+
+    // .text section holding the code
+    put(b".text\0\0\0");
+    put(&SECTION_ALIGNMENT.to_le_bytes());
+    put(&SECTION_ALIGNMENT.to_le_bytes());
+    put(&FILE_ALIGNMENT.to_le_bytes());
+    put(&FILE_ALIGNMENT.to_le_bytes());
+    put(&[0; 12]);
+    put(&0x6000_0020u32.to_le_bytes()); // code, executable, readable
+
+    image.resize(FILE_ALIGNMENT as usize, 0);
+    image.extend_from_slice(CODE);
+    image.resize(2 * FILE_ALIGNMENT as usize, 0);
+    image
+}
+
+/// Whether `path` holds the stub this app writes
+fn is_stub(path: &Path) -> bool {
+    let stub = stub_image();
+    fs::metadata(path).is_ok_and(|m| m.is_file() && m.len() == stub.len() as u64)
+        && fs::read(path).is_ok_and(|content| content == stub)
+}
+
+/// Whether `path` starts like a Windows executable (blockers and stubs aside)
+fn is_executable(path: &Path) -> bool {
+    let mut magic = [0u8; 2];
+    fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .is_ok()
+        && &magic == b"MZ"
+}
+
+fn backup_path(file: &Path) -> PathBuf {
+    let mut name = file.file_name().unwrap_or_default().to_os_string();
+    name.push(BACKUP_SUFFIX);
+    file.with_file_name(name)
+}
+
+/// Updater executables that would be replaced: under User Data\Download and inside Apps
+pub fn updaters_to_stub() -> Vec<PathBuf> {
+    let install = match paths::resolve_capcut_paths() {
+        Some(p) => p,
+        None => return Vec::new(),
+    };
+    let profile = channel::guard_profile(channel::detect_channel(&install.root, &install.apps));
+    let names: Vec<&str> = profile
+        .updater_names
+        .iter()
+        .chain(EXTRA_UPDATER_NAMES)
+        .copied()
+        .collect();
+    let is_updater = |name: &str| names.iter().any(|n| name.eq_ignore_ascii_case(n));
+
+    let download_dir = install.root.join("User Data").join("Download");
+    let walks = [(download_dir, 1), (install.apps, APPS_SEARCH_DEPTH)];
+    walks
+        .into_iter()
+        .flat_map(|(dir, depth)| WalkDir::new(dir).max_depth(depth).into_iter())
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && is_updater(&e.file_name().to_string_lossy()))
+        .map(|e| e.into_path())
+        .filter(|p| !blockers::is_blocker(p) && !is_stub(p) && is_executable(p))
+        .collect()
+}
+
+/// Rename each updater to `.bak` and put the stub in its place
+pub fn stub_updaters(logs: &mut Vec<LogEntry>) -> Result<(), String> {
+    let mut stubbed = load_stubbed();
+    let stub = stub_image();
+    let mut count = 0;
+
+    for file in updaters_to_stub() {
+        let backup = backup_path(&file);
+        if backup.exists() {
+            logs.push(
+                LogEntry::warn("Updater left alone: a .bak file of the same name exists")
+                    .with_path(&file),
+            );
+            continue;
+        }
+        if let Err(e) = fs::rename(&file, &backup) {
+            logs.push(LogEntry::warn(format!("Could not rename updater: {}", e)).with_path(&file));
+            continue;
+        }
+        if let Err(e) = fs::write(&file, &stub) {
+            logs.push(LogEntry::warn(format!("Could not write stub: {}", e)).with_path(&file));
+            fs::rename(&backup, &file).ok();
+            continue;
+        }
+        let transport = paths::path_to_transport(&file);
+        if !stubbed.files.contains(&transport) {
+            stubbed.files.push(transport);
+        }
+        count += 1;
+    }
+
+    save_stubbed(&stubbed)?;
+    logs.push(LogEntry::ok(format!(
+        "{} updater(s) replaced by a stub",
+        count
+    )));
+    Ok(())
+}
+
+/// Recorded stubs and their `.bak` originals, which cleanup such as the download purge must leave alone
+pub fn stubbed_files() -> Vec<PathBuf> {
+    load_stubbed()
+        .files
+        .iter()
+        .map(|transport| paths::path_from_transport(transport))
+        .flat_map(|file| [backup_path(&file), file])
+        .collect()
+}
+
+/// Number of updaters still recorded as replaced
+pub fn stubbed_count() -> usize {
+    load_stubbed().files.len()
+}
+
+/// Delete the stubs and rename the originals back; updaters that could not be restored stay recorded
+pub fn restore_all(logs: &mut Vec<LogEntry>) {
+    let stubbed = load_stubbed();
+    if stubbed.files.is_empty() {
+        return;
+    }
+
+    let mut remaining = StubbedUpdaters::default();
+    let mut restored = 0;
+    for transport in stubbed.files {
+        let file = paths::path_from_transport(&transport);
+        let backup = backup_path(&file);
+        if !backup.is_file() {
+            // Deleted with its version; otherwise something else removed the original
+            if file.parent().is_some_and(Path::is_dir) {
+                logs.push(
+                    LogEntry::warn("Original updater is missing; nothing to restore")
+                        .with_path(&backup),
+                );
+            }
+            continue;
+        }
+        if file.exists() {
+            if !is_stub(&file) {
+                logs.push(
+                    LogEntry::warn("Updater was replaced since protection; original kept as .bak")
+                        .with_path(&file),
+                );
+                continue;
+            }
+            // Binary hardening may have made the stub read-only
+            if let Err(e) = attributes::clear_readonly_recursive(&file)
+                .and_then(|()| fs::remove_file(&file).map_err(|e| e.to_string()))
+            {
+                logs.push(LogEntry::warn(format!("Could not remove stub: {}", e)).with_path(&file));
+                remaining.files.push(transport);
+                continue;
+            }
+        }
+        match fs::rename(&backup, &file) {
+            Ok(()) => restored += 1,
+            Err(e) => {
+                logs.push(
+                    LogEntry::warn(format!("Could not restore updater: {}", e)).with_path(&file),
+                );
+                remaining.files.push(transport);
+            }
+        }
+    }
+
+    match save_stubbed(&remaining) {
+        Ok(()) => logs.push(LogEntry::ok(format!(
+            "{} original updater(s) restored",
+            restored
+        ))),
+        Err(e) => logs.push(LogEntry::warn(e)),
+    }
+}
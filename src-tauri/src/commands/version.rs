@@ -0,0 +1,129 @@
+//! Version numbers
+//! Numeric parsing and ordering of CapCut version strings, so "3.10.0" ranks above "3.9.0"
+
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Dotted numeric version such as "2.9.0.966" (CapCut folders carry a fourth build part)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    parts: Vec<u64>,
+}
+
+impl Version {
+    /// Parse "3.9.0.1457", "v2.4.0" or "2.4.0-beta"; a pre-release or build suffix is ignored
+    pub fn parse(text: &str) -> Option<Version> {
+        let core = text.trim().trim_start_matches(['v', 'V']);
+        let core = core.split(['-', '+']).next()?;
+        let parts: Vec<u64> = core
+            .split('.')
+            .map(|p| p.parse().ok())
+            .collect::<Option<_>>()?;
+        Some(Version { parts })
+    }
+
+    /// Compare only the parts both versions have
+    ///
+    /// Drafts record "3.2.0" while version folders add a build ("3.2.0.1234"); those count as equal.
+    pub fn cmp_common(&self, other: &Version) -> Ordering {
+        let len = self.parts.len().min(other.parts.len());
+        self.parts[..len].cmp(&other.parts[..len])
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text: Vec<String> = self.parts.iter().map(u64::to_string).collect();
+        f.write_str(&text.join("."))
+    }
+}
+
+/// Order folder names by version number, oldest first
+///
+/// Names that are not a version sort before all versions, by name.
+pub fn cmp_names(a: &str, b: &str) -> Ordering {
+    Version::parse(a)
+        .cmp(&Version::parse(b))
+        .then_with(|| a.cmp(b))
+}
+
+/// Sort folder names by version number, oldest first
+pub fn sort_names(names: &mut [String]) {
+    names.sort_by(|a, b| cmp_names(a, b));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_four_part_versions() {
+        let version = Version::parse("2.9.0.966").unwrap();
+        assert_eq!(version.parts, [2, 9, 0, 966]);
+        assert_eq!(version.to_string(), "2.9.0.966");
+    }
+
+    #[test]
+    fn parses_prefixes_and_suffixes() {
+        assert_eq!(Version::parse("v2.4.0"), Version::parse("2.4.0"));
+        assert_eq!(Version::parse(" 2.4.0-beta "), Version::parse("2.4.0"));
+        assert_eq!(Version::parse("2.4.0+build.7"), Version::parse("2.4.0"));
+    }
+
+    #[test]
+    fn rejects_non_versions() {
+        assert_eq!(Version::parse(""), None);
+        assert_eq!(Version::parse("User Data"), None);
+        assert_eq!(Version::parse("3.9.x"), None);
+        assert_eq!(Version::parse("3..9"), None);
+    }
+
+    #[test]
+    fn orders_parts_numerically() {
+        assert!(Version::parse("3.10.0.100") > Version::parse("3.9.0.2000"));
+        assert!(Version::parse("2.9.0.1457") > Version::parse("2.9.0.966"));
+        assert!(Version::parse("10.0.0.1") > Version::parse("9.9.9.9999"));
+    }
+
+    #[test]
+    fn common_parts_ignore_the_build() {
+        let draft = Version::parse("3.2.0").unwrap();
+        assert_eq!(
+            draft.cmp_common(&Version::parse("3.2.0.1234").unwrap()),
+            Ordering::Equal
+        );
+        assert_eq!(
+            draft.cmp_common(&Version::parse("2.9.0.966").unwrap()),
+            Ordering::Greater
+        );
+        assert_eq!(
+            draft.cmp_common(&Version::parse("3.10.0.1").unwrap()),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn sorts_folder_names_oldest_first() {
+        let mut names: Vec<String> = [
+            "3.10.0.100",
+            "2.9.0.966",
+            "backup",
+            "3.9.0.2000",
+            "2.9.0.1457",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        sort_names(&mut names);
+        assert_eq!(
+            names,
+            [
+                "backup",
+                "2.9.0.966",
+                "2.9.0.1457",
+                "3.9.0.2000",
+                "3.10.0.100"
+            ]
+        );
+    }
+}
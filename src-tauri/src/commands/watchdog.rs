@@ -0,0 +1,325 @@
+//! Protection watchdog
+//! Background task that re-checks the guards, restores pinned registry values and configure.ini,
+//! re-protects after a reinstall or a staged update, and alerts when they break. In monitor-only
+//! mode it just records what changes in the CapCut folder
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+
+use super::notifier::{self, AlertKind};
+use super::reprotect::{self, InstallTracker};
+use super::{
+    apps_guard, config_guard, installs, observer, paths, policy, power, process, protector,
+    registry_guard, settings,
+};
+
+/// Seconds between protection checks when no schedule is configured
+const CHECK_INTERVAL_SECS: u64 = 30;
+
+/// Seconds between checks, from the re-verification schedule (stretched on battery in low-impact mode)
+pub fn check_interval_secs() -> u64 {
+    let app_settings = settings::load_settings();
+    let secs = match app_settings.schedules.verify_interval_minutes {
+        0 => CHECK_INTERVAL_SECS,
+        minutes => u64::from(minutes) * 60,
+    };
+    if app_settings.watchdog_low_impact && power::on_battery() {
+        secs * power::BATTERY_INTERVAL_FACTOR
+    } else {
+        secs
+    }
+}
+
+/// Watchdog status reported to the frontend
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WatchdogStatus {
+    pub running: bool,
+    /// Unix seconds when the watchdog was started
+    pub started_at: Option<u64>,
+    /// Unix seconds of the last completed check
+    pub last_check: Option<u64>,
+    /// Protection state seen by the last check
+    pub protected: Option<bool>,
+    pub checks: u64,
+    /// Observing only: nothing is restored or re-protected
+    pub monitor_only: bool,
+    /// Started in low-impact mode
+    pub low_impact: bool,
+}
+
+/// Managed watchdog state (one background task per app)
+#[derive(Default)]
+pub struct WatchdogState {
+    status: Arc<Mutex<WatchdogStatus>>,
+    stop_flag: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+impl WatchdogState {
+    /// Current status snapshot
+    pub fn status(&self) -> WatchdogStatus {
+        self.status.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Start the background task (no-op if already running)
+    pub fn start(&self, app: AppHandle) -> WatchdogStatus {
+        let mut flag_slot = match self.stop_flag.lock() {
+            Ok(f) => f,
+            Err(_) => return self.status(),
+        };
+        if flag_slot.is_some() {
+            return self.status();
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        *flag_slot = Some(stop.clone());
+        let app_settings = settings::load_settings();
+        let monitor_only = app_settings.watchdog_monitor_only;
+        let low_impact = app_settings.watchdog_low_impact;
+        if let Ok(mut s) = self.status.lock() {
+            *s = WatchdogStatus {
+                running: true,
+                started_at: Some(now_secs()),
+                monitor_only,
+                low_impact,
+                ..Default::default()
+            };
+        }
+
+        let watch_app = app.clone();
+        let watch_stop = stop.clone();
+        thread::spawn(move || {
+            if low_impact {
+                power::lower_thread_priority();
+            }
+            if monitor_only {
+                observer::watch(watch_app, watch_stop);
+            } else {
+                // configure.ini is restored the moment CapCut rewrites it, not on the next check
+                config_guard::watch(watch_app, watch_stop, low_impact);
+            }
+        });
+        if !monitor_only {
+            let guard_app = app.clone();
+            let guard_stop = stop.clone();
+            thread::spawn(move || {
+                if low_impact {
+                    power::lower_thread_priority();
+                }
+                apps_guard::watch(guard_app, guard_stop, low_impact);
+            });
+        }
+
+        let status = self.status.clone();
+        thread::spawn(move || {
+            if low_impact {
+                power::lower_thread_priority();
+            }
+            run_loop(app, status, stop, monitor_only)
+        });
+        self.status()
+    }
+
+    /// Signal the background task to stop
+    pub fn stop(&self) -> WatchdogStatus {
+        if let Some(stop) = self.stop_flag.lock().ok().and_then(|mut f| f.take()) {
+            stop.store(true, Ordering::Relaxed);
+        }
+        if let Ok(mut s) = self.status.lock() {
+            s.running = false;
+        }
+        self.status()
+    }
+}
+
+/// State the watchdog carries between checks for one install
+struct WatchedInstall {
+    was_protected: Option<bool>,
+    tracker: InstallTracker,
+    volume_online: bool,
+}
+
+impl Default for WatchedInstall {
+    fn default() -> Self {
+        Self {
+            was_protected: None,
+            tracker: InstallTracker::default(),
+            volume_online: true,
+        }
+    }
+}
+
+/// Watchdog loop: check protection, alert on a protected -> broken transition
+///
+/// Every install with a protection profile is checked, each in its own install scope.
+fn run_loop(
+    app: AppHandle,
+    status: Arc<Mutex<WatchdogStatus>>,
+    stop: Arc<AtomicBool>,
+    monitor_only: bool,
+) {
+    let mut watched: HashMap<String, WatchedInstall> = HashMap::new();
+
+    while !stop.load(Ordering::Relaxed) {
+        let targets = installs::watched_installs();
+        let multiple = targets.len() > 1;
+        let mut all_protected = true;
+
+        for install in &targets {
+            let _scope = installs::scope(install);
+            let state = watched.entry(installs::install_key(install)).or_default();
+            // Name the install in alerts once there is more than one
+            let location = if multiple {
+                format!(" ({})", install.root.display())
+            } else {
+                String::new()
+            };
+            // A None (drive offline) counts as neither protected nor broken
+            if let Some(protected) = check_install(&app, state, monitor_only, &location) {
+                all_protected &= protected;
+            }
+        }
+
+        // CapCut can bypass the config lock by rewriting its registry state
+        let restored = if monitor_only {
+            Vec::new()
+        } else {
+            registry_guard::restore_pinned_values()
+        };
+        if !restored.is_empty() {
+            let _ = app.emit("watchdog-registry-restored", &restored);
+            if let Err(e) = notifier::send_alert(
+                AlertKind::UpdateBlocked,
+                &format!(
+                    "CapCut rewrote pinned registry values; restored: {}",
+                    restored.join(", ")
+                ),
+            ) {
+                tracing::warn!("Watchdog email alert failed: {}", e);
+            }
+        }
+
+        if let Ok(mut s) = status.lock() {
+            s.last_check = Some(now_secs());
+            s.protected = Some(all_protected);
+            s.checks += 1;
+        }
+
+        if !wait_interval(&stop) {
+            return;
+        }
+    }
+}
+
+/// One check of the install the thread is scoped to; None while its drive is offline
+fn check_install(
+    app: &AppHandle,
+    state: &mut WatchedInstall,
+    monitor_only: bool,
+    location: &str,
+) -> Option<bool> {
+    // An unplugged drive or dropped share would otherwise look like broken protection
+    let online = paths::capcut_volume_online();
+    if online != state.volume_online {
+        state.volume_online = online;
+        let event = if online {
+            "watchdog-volume-online"
+        } else {
+            "watchdog-volume-offline"
+        };
+        let _ = app.emit(event, ());
+    }
+    if !online {
+        return None;
+    }
+
+    if !monitor_only {
+        // Fallback for rewrites the file watcher missed (e.g., while the app was closed)
+        if let Some(config_path) = config_guard::restore_if_unlocked() {
+            config_guard::report_restore(app, &config_path);
+        }
+
+        // A reinstall recreates the Apps folder without any guards
+        state.tracker.check(app);
+    }
+
+    let protected = protector::check_protection_status().is_protected;
+
+    // Helpers can fetch an update in the background even with the updater locked
+    if protected && !monitor_only {
+        let killed = process::kill_helpers();
+        if !killed.is_empty() {
+            let _ = app.emit("watchdog-helpers-terminated", &killed);
+            if let Err(e) = notifier::send_alert(
+                AlertKind::UpdateBlocked,
+                &format!(
+                    "Ended CapCut helper process(es){}: {}",
+                    location,
+                    killed.join(", ")
+                ),
+            ) {
+                tracing::warn!("Watchdog email alert failed: {}", e);
+            }
+        }
+    }
+
+    // A revert forgets the protection run, so an intentional unlock is not reported
+    if state.was_protected == Some(true) && !protected && reprotect::kept_version().is_some() {
+        let _ = app.emit("watchdog-protection-broken", ());
+        if let Err(e) = notifier::send_alert(
+            AlertKind::ProtectionBroken,
+            &format!(
+                "The watchdog found the CapCut version lock removed or modified{}.",
+                location
+            ),
+        ) {
+            tracing::warn!("Watchdog email alert failed: {}", e);
+        }
+    }
+    state.was_protected = Some(protected);
+    Some(protected)
+}
+
+/// Sleep until the next check; false if stopped meanwhile
+fn wait_interval(stop: &AtomicBool) -> bool {
+    // Sleep in short ticks so stop requests take effect quickly
+    for _ in 0..check_interval_secs() {
+        if stop.load(Ordering::Relaxed) {
+            return false;
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+    true
+}
+
+/// Start protection monitoring
+#[tauri::command]
+pub fn start_watchdog(app: AppHandle, state: State<'_, WatchdogState>) -> WatchdogStatus {
+    state.start(app)
+}
+
+/// Stop protection monitoring (blocked when policy forces the watchdog)
+#[tauri::command]
+pub fn stop_watchdog(state: State<'_, WatchdogState>) -> Result<WatchdogStatus, String> {
+    if policy::load_policy().force_watchdog {
+        return Err(policy::POLICY_BLOCKED.to_string());
+    }
+    Ok(state.stop())
+}
+
+/// Get protection monitoring status
+#[tauri::command]
+pub fn watchdog_status(state: State<'_, WatchdogState>) -> WatchdogStatus {
+    state.status()
+}
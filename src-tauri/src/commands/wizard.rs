@@ -0,0 +1,97 @@
+//! Protection wizard state machine
+//! Single source of the Welcome → PreCheck → VersionSelect → Running → Complete flow for every frontend
+
+use serde::{Deserialize, Serialize};
+
+use super::process;
+
+/// Wizard screen (serialized as the frontend view id)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WizardStep {
+    Welcome,
+    Precheck,
+    Versions,
+    Options,
+    Processing,
+    DeleteFailures,
+    Complete,
+    Error,
+}
+
+/// Something that moves the wizard forward
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WizardEvent {
+    /// "Lock Version" on the welcome screen
+    Start,
+    /// Continue button on a precheck/version screen
+    Continue,
+    /// "Apply" on the options screen
+    Run,
+    /// Protection finished cleanly
+    Succeeded,
+    /// Protection finished but some versions could not be deleted
+    PartiallyFailed,
+    /// Protection aborted
+    Failed,
+    /// Leave the delete-failures screen
+    Dismiss,
+    /// Start over after an error
+    Retry,
+}
+
+/// Conditions a transition may depend on
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WizardGuards {
+    pub capcut_running: bool,
+    pub version_selected: bool,
+}
+
+/// Next step for `event`, or why the wizard can't move
+pub fn next_step(
+    from: WizardStep,
+    event: WizardEvent,
+    guards: WizardGuards,
+) -> Result<WizardStep, String> {
+    use WizardEvent::*;
+    use WizardStep::*;
+
+    match (from, event) {
+        (Welcome, Start) => Ok(Precheck),
+        (Precheck, Continue) if guards.capcut_running => {
+            Err("Close CapCut before continuing".to_string())
+        }
+        (Precheck, Continue) => Ok(Versions),
+        (Versions, Continue) if !guards.version_selected => {
+            Err("Select a version to keep".to_string())
+        }
+        (Versions, Continue) => Ok(Options),
+        (Options, Run) if guards.capcut_running => {
+            Err("CapCut is still running. Please close it.".to_string())
+        }
+        (Options, Run) if !guards.version_selected => Err("Select a version to keep".to_string()),
+        (Options, Run) => Ok(Processing),
+        (Processing, Succeeded) => Ok(Complete),
+        (Processing, PartiallyFailed) => Ok(DeleteFailures),
+        (Processing, Failed) => Ok(Error),
+        (DeleteFailures, Dismiss) => Ok(Complete),
+        (Error, Retry) => Ok(Precheck),
+        _ => Err(format!("{:?} is not possible from {:?}", event, from)),
+    }
+}
+
+/// Advance the wizard; the CapCut process guard is checked here so frontends can't skip it
+#[tauri::command]
+pub fn wizard_transition(
+    from: WizardStep,
+    event: WizardEvent,
+    version_selected: bool,
+) -> Result<WizardStep, String> {
+    let guards = WizardGuards {
+        capcut_running: matches!(event, WizardEvent::Continue | WizardEvent::Run)
+            && process::is_capcut_running(),
+        version_selected,
+    };
+    next_step(from, event, guards)
+}
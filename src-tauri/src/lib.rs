@@ -3,119 +3,254 @@
 
 mod commands;
 
-use commands::{autostart, backup, cleaner, paths, process, protector, scanner, switcher};
-use tauri::{
-    menu::{Menu, MenuItem},
-    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Emitter, Manager,
+use commands::{
+    application, apps_watch, archive_install, autoruns, autostart, backup, blockers, cleaner,
+    compare, compression, config_backup, config_diff, deep_scan, deletion_log, diagnostics,
+    domain_block, download_purge, downloader, draft_compat, drafts, environment, events, favorites,
+    firewall, folder_access, golden, health, history, installers, installs, integrity, leftovers,
+    link_reports, logging, monitor, notifier, observer, paths, policy, preview, process, protector,
+    report, revert, scanner, scheduler, self_update, settings, shadow_recovery, slimming, stats,
+    switcher, tray, uninstall, watchdog, wizard,
 };
+use tauri::Manager;
 
-/// Setup the system tray with menu
-fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
-    // Create menu items
-    let show_i = MenuItem::with_id(app, "show", "Show Version Guard", true, None::<&str>)?;
-    let check_i = MenuItem::with_id(
-        app,
-        "check_status",
-        "Check Protection Status",
-        true,
-        None::<&str>,
-    )?;
-    let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-
-    // Build menu
-    let menu = Menu::with_items(app, &[&show_i, &check_i, &quit_i])?;
-
-    // Build tray icon
-    let _tray = TrayIconBuilder::new()
-        .icon(app.default_window_icon().unwrap().clone())
-        .tooltip("CC Version Guard")
-        .menu(&menu)
-        .show_menu_on_left_click(false)
-        .on_menu_event(|app, event| {
-            match event.id.as_ref() {
-                "show" => {
-                    if let Some(window) = app.get_webview_window("main") {
-                        let _ = window.unminimize();
-                        let _ = window.show();
-                        let _ = window.set_focus();
-                    }
-                }
-                "check_status" => {
-                    // Emit event to frontend to show status
-                    if let Some(window) = app.get_webview_window("main") {
-                        let _ = window.emit("tray-check-status", ());
-                    }
-                }
-                "quit" => {
-                    app.exit(0);
-                }
-                _ => {}
-            }
-        })
-        .on_tray_icon_event(|tray, event| {
-            // Left click shows the window
-            if let TrayIconEvent::Click {
-                button: MouseButton::Left,
-                button_state: MouseButtonState::Up,
-                ..
-            } = event
-            {
-                let app = tray.app_handle();
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.unminimize();
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
-            }
-        })
-        .build(app)?;
+/// Start writing the log file (see `commands/logging.rs`); call once before anything else
+pub fn init_logging() {
+    commands::logging::init();
+}
+
+/// Run an unattended fleet deployment (no GUI); returns the process exit code
+pub fn run_fleet(config_path: &str) -> i32 {
+    commands::fleet::run_fleet_from_file(config_path)
+}
+
+/// Print the environment doctor report (no GUI); returns the process exit code
+pub fn run_doctor() -> i32 {
+    commands::doctor::run_doctor_cli()
+}
+
+pub use commands::headless::ProtectOptions;
+
+/// List installed CapCut versions (no GUI); returns the process exit code
+pub fn run_scan(install: Option<&str>, json: bool) -> i32 {
+    commands::headless::run_scan(install, json)
+}
 
-    Ok(())
+/// Keep one CapCut version and protect it (no GUI); returns the process exit code
+pub fn run_protect(install: Option<&str>, options: &ProtectOptions) -> i32 {
+    commands::headless::run_protect(install, options)
+}
+
+/// Print the protection status (no GUI); returns the process exit code
+pub fn run_status(install: Option<&str>, json: bool) -> i32 {
+    commands::headless::run_status(install, json)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_dialog::init())
         .setup(|app| {
             // Initialize system tray
-            setup_tray(app)?;
+            tray::setup_tray(app)?;
+
+            // Protection watchdog (started on demand, by schedule, or forced by policy)
+            app.manage(watchdog::WatchdogState::default());
+
+            // In-app archive installer downloads
+            app.manage(downloader::DownloadState::default());
+
+            // Silent install and protection of a downloaded archive version
+            app.manage(archive_install::ArchiveInstallState::default());
+
+            // Live refresh of the version-select screen
+            app.manage(apps_watch::AppsWatchState::default());
+
+            // Continuous purge of the updater download folder (opt-in)
+            app.manage(download_purge::PurgeState::default());
+            if settings::load_settings().purge_downloads {
+                if let Err(e) = app
+                    .state::<download_purge::PurgeState>()
+                    .start(app.handle().clone())
+                {
+                    tracing::warn!("Could not start download purge: {}", e);
+                }
+            }
+
+            // CapCut process table for the resource monitor
+            app.manage(monitor::MonitorState::default());
+
+            // Scheduled cache cleaning, version and draft backups
+            scheduler::start_scheduler();
+
+            // Starred archive versions may change with a new bundled list
+            std::thread::spawn(favorites::check_favorite_changes);
+
+            // Enforce administrator policy that must hold at every launch
+            let machine_policy = policy::load_policy();
+            if machine_policy.force_autostart && !autostart::get_autostart_enabled() {
+                let _ = autostart::set_autostart_enabled(true);
+            }
+            let verify_scheduled = settings::load_settings().schedules.verify_interval_minutes > 0;
+            if machine_policy.force_watchdog || verify_scheduled {
+                app.state::<watchdog::WatchdogState>()
+                    .start(app.handle().clone());
+            }
             Ok(())
         })
+        .on_window_event(tray::on_window_event)
         .invoke_handler(tauri::generate_handler![
             // Scanner commands
             scanner::get_archive_versions,
             scanner::get_all_archive_versions,
+            downloader::download_archive_version,
+            archive_install::install_archive_version,
             scanner::scan_versions,
+            scanner::list_folder_entries,
+            apps_watch::start_apps_watch,
+            apps_watch::stop_apps_watch,
             scanner::get_capcut_paths,
+            favorites::toggle_favorite_archive,
+            // Link report commands
+            link_reports::report_broken_link,
+            link_reports::list_link_reports,
+            link_reports::clear_link_reports,
+            link_reports::export_link_reports,
+            // Compare commands
+            compare::compare_versions,
+            // Config diff commands
+            config_diff::get_config_diff,
+            // Compression commands
+            compression::compress_version,
+            // Deletion log commands
+            deletion_log::export_deletion_log,
+            deletion_log::clear_deletion_log,
+            // Golden copy commands
+            golden::export_golden_copy,
+            golden::import_golden_copy,
+            // History commands
+            history::list_protection_runs,
+            // Installer commands
+            installers::check_downloaded_installers,
+            installers::quarantine_installer,
+            // Slimming commands
+            slimming::scan_version_components,
+            slimming::remove_version_components,
+            // Report commands
+            report::export_scan_results,
+            report::export_protection_report,
+            logging::open_log_folder,
+            diagnostics::export_diagnostics,
             // Path resolution commands
             paths::get_path_info,
             paths::validate_custom_capcut_path,
+            paths::validate_custom_path,
+            paths::clear_custom_path,
+            application::get_application,
+            application::set_application,
+            // Installation commands
+            installs::list_installs,
+            installs::set_active_install,
+            installs::scan_all_installs,
+            installs::protect_installs,
+            // Deep scan commands
+            deep_scan::deep_scan_installs,
             // Backup commands
             backup::list_backups,
             backup::restore_version_backup,
             backup::delete_backup,
             backup::get_backup_size,
             backup::clear_all_backups,
+            config_backup::list_config_backups,
+            config_backup::restore_config_backup,
+            shadow_recovery::find_shadow_versions,
+            shadow_recovery::restore_shadow_version,
+            // Draft backup commands
+            drafts::backup_drafts_now,
+            drafts::backup_drafts,
+            drafts::list_draft_projects,
+            draft_compat::check_draft_compatibility,
             // Autostart commands
             autostart::get_autostart_enabled,
             autostart::set_autostart_enabled,
+            // Event commands
+            events::list_events,
+            events::unread_event_count,
+            events::mark_events_read,
+            events::clear_events,
+            // Integrity commands
+            integrity::check_build_authenticity,
+            // Notifier commands
+            notifier::send_test_email,
+            // Policy commands
+            policy::get_machine_policy,
+            // Scheduler commands
+            scheduler::get_schedule_status,
+            scheduler::save_schedule_settings,
+            // Settings commands
+            settings::get_app_settings,
+            settings::save_app_settings,
+            settings::export_app_settings,
+            settings::import_app_settings,
+            settings::set_version_note,
+            // Environment commands
+            environment::check_environment,
             // Process commands
             process::is_capcut_running,
             process::perform_precheck,
             process::launch_capcut,
+            process::exit_app,
+            process::terminate_helper_processes,
+            process::force_close_capcut,
+            process::close_capcut_gracefully,
+            folder_access::open_folder_access_settings,
+            // Monitor commands
+            monitor::capcut_resource_usage,
             // Cleaner commands
             cleaner::calculate_cache_size,
             cleaner::clean_cache,
+            cleaner::preview_cache_clean,
             // Protector commands
+            blockers::add_extra_blocker,
             protector::delete_versions,
             protector::apply_protection,
             protector::run_full_protection,
+            preview::preview_protection,
+            protector::retry_version_delete,
             protector::check_protection_status,
+            health::check_protection_health,
             protector::remove_protection,
+            revert::revert_protection,
+            // Wizard commands
+            wizard::wizard_transition,
             // Switcher commands
             switcher::switch_version,
+            // Watchdog commands
+            watchdog::start_watchdog,
+            watchdog::stop_watchdog,
+            watchdog::watchdog_status,
+            observer::get_observation_timeline,
+            observer::clear_observation_timeline,
+            // Download purge commands
+            download_purge::set_download_purge,
+            download_purge::download_purge_status,
+            // Update domain block commands
+            domain_block::block_update_domains,
+            domain_block::update_domains_status,
+            firewall::list_firewall_rules,
+            firewall::remove_firewall_rules,
+            autoruns::scan_autoruns,
+            autoruns::set_autorun_enabled,
+            autoruns::delete_autorun,
+            // Stats commands
+            stats::get_guard_stats,
+            // Self-update commands
+            self_update::check_tool_update,
+            // Uninstall commands
+            uninstall::uninstall_guard,
+            leftovers::scan_leftovers,
+            leftovers::clean_leftovers,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
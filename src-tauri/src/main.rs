@@ -1,6 +1,196 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+use capcut_guard_tauri_lib::ProtectOptions;
+
+/// Lock your CapCut version and prevent auto-updates (opens the window when no command is given)
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// Unattended fleet deployment (same as the `fleet` command)
+    #[arg(long, value_name = "CONFIG")]
+    fleet: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+}
+
+#[derive(Subcommand)]
+enum CliCommand {
+    /// List installed CapCut versions (* marks the protected one)
+    Scan {
+        #[command(flatten)]
+        target: Target,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Keep one version, delete the others and lock updates
+    Protect {
+        #[command(flatten)]
+        target: Target,
+        /// Version folder name or CapCut.exe build to keep (e.g., 2.9.0.966)
+        #[arg(long, value_name = "VERSION")]
+        keep: String,
+        /// Zip all CapCut projects into this folder before deleting anything
+        #[arg(long, value_name = "DIR")]
+        backup_drafts: Option<String>,
+        /// Leave the cache folders alone
+        #[arg(long)]
+        no_clean_cache: bool,
+        /// Don't lock configure.ini
+        #[arg(long)]
+        no_lock_config: bool,
+        /// Don't create updater blocker files
+        #[arg(long)]
+        no_blockers: bool,
+        /// Make the kept version's executables read-only
+        #[arg(long)]
+        harden: bool,
+        /// Replace the updater executables with a do-nothing stub (originals kept as .bak)
+        #[arg(long)]
+        stub_updaters: bool,
+        /// Deny writes and deletes on the Apps folder and the kept version's binaries (undone by unprotect)
+        #[arg(long)]
+        lock_acls: bool,
+        /// Block CapCut's update domains in the hosts file (needs administrator rights)
+        #[arg(long)]
+        block_domains: bool,
+        /// Block the updater executables with Windows Firewall rules (needs administrator rights)
+        #[arg(long)]
+        firewall: bool,
+        /// Disable CapCut's scheduled tasks and Run-key entries
+        #[arg(long)]
+        disable_autoruns: bool,
+    },
+    /// Print the protection status (exit code 0 = protected, 1 = not protected)
+    Status {
+        #[command(flatten)]
+        target: Target,
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the environment report
+    Doctor,
+    /// Run an unattended fleet deployment from a JSON config
+    Fleet {
+        #[arg(value_name = "CONFIG")]
+        config: String,
+    },
+    /// Print a shell completion script to stdout
+    Completions {
+        #[arg(value_enum)]
+        shell: CompletionShell,
+    },
+}
+
+/// Which CapCut install a command works on
+#[derive(Args)]
+struct Target {
+    /// Install key or folder (see Settings → Installations); the active install when omitted
+    #[arg(long, value_name = "KEY|FOLDER")]
+    install: Option<String>,
+}
+
+/// Shells completion scripts are generated for
+#[derive(Clone, Copy, ValueEnum)]
+enum CompletionShell {
+    Powershell,
+    Bash,
+    Zsh,
+}
+
+impl From<CompletionShell> for Shell {
+    fn from(shell: CompletionShell) -> Self {
+        match shell {
+            CompletionShell::Powershell => Shell::PowerShell,
+            CompletionShell::Bash => Shell::Bash,
+            CompletionShell::Zsh => Shell::Zsh,
+        }
+    }
+}
+
+/// Write the completion script for `shell` to stdout
+fn print_completions(shell: CompletionShell) -> i32 {
+    let mut command = Cli::command();
+    let bin_name = command.get_name().to_string();
+    clap_complete::generate(
+        Shell::from(shell),
+        &mut command,
+        bin_name,
+        &mut std::io::stdout(),
+    );
+    0
+}
+
+/// Write to the console the command was started from; release builds are GUI-subsystem and start without one
+fn attach_parent_console() {
+    // SAFETY: no arguments beyond a constant; fails harmlessly when there is no parent console
+    unsafe { AttachConsole(ATTACH_PARENT_PROCESS) };
+}
+
 fn main() {
-    capcut_guard_tauri_lib::run()
+    // Any argument means a command (or --help / --version) whose output must reach the terminal
+    if std::env::args_os().len() > 1 {
+        attach_parent_console();
+    }
+    let cli = Cli::parse();
+    capcut_guard_tauri_lib::init_logging();
+
+    let code = match (cli.command, cli.fleet) {
+        // Headless protection: capcut_guard_tauri.exe protect --keep 2.9.0.966
+        (Some(CliCommand::Scan { target, json }), _) => {
+            capcut_guard_tauri_lib::run_scan(target.install.as_deref(), json)
+        }
+        (
+            Some(CliCommand::Protect {
+                target,
+                keep,
+                backup_drafts,
+                no_clean_cache,
+                no_lock_config,
+                no_blockers,
+                harden,
+                stub_updaters,
+                lock_acls,
+                block_domains,
+                firewall,
+                disable_autoruns,
+            }),
+            _,
+        ) => capcut_guard_tauri_lib::run_protect(
+            target.install.as_deref(),
+            &ProtectOptions {
+                keep,
+                backup_drafts_to: backup_drafts,
+                clean_cache: !no_clean_cache,
+                lock_config: !no_lock_config,
+                create_blockers: !no_blockers,
+                harden_binaries: harden,
+                stub_updaters,
+                lock_acls,
+                block_domains,
+                firewall_rules: firewall,
+                disable_autoruns,
+            },
+        ),
+        (Some(CliCommand::Status { target, json }), _) => {
+            capcut_guard_tauri_lib::run_status(target.install.as_deref(), json)
+        }
+        // Environment report: capcut_guard_tauri.exe doctor
+        (Some(CliCommand::Doctor), _) => capcut_guard_tauri_lib::run_doctor(),
+        // Unattended fleet deployment: capcut_guard_tauri.exe fleet <config.json>
+        (Some(CliCommand::Fleet { config }), _) | (None, Some(config)) => {
+            capcut_guard_tauri_lib::run_fleet(&config)
+        }
+        // Completion script: capcut_guard_tauri.exe completions powershell
+        (Some(CliCommand::Completions { shell }), _) => print_completions(shell),
+        (None, None) => return capcut_guard_tauri_lib::run(),
+    };
+    std::process::exit(code);
 }
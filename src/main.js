@@ -8,6 +8,7 @@
 const { invoke } = window.__TAURI__.core;
 const { getCurrentWindow } = window.__TAURI__.window;
 const { getVersion } = window.__TAURI__.app;
+const { listen } = window.__TAURI__.event;
 
 // ============================================
 // Safe DOM Builder Utilities (XSS Prevention)
@@ -64,6 +65,44 @@ function text(str) {
   return document.createTextNode(str);
 }
 
+// ============================================
+// Number Formatting (user's locale)
+// ============================================
+const SIZE_UNITS = ['B', 'KB', 'MB', 'GB', 'TB'];
+const numberFormats = new Map();
+
+/**
+ * Format a number with the locale's grouping and decimal separators
+ * @param {number} value
+ * @param {number} digits - Fixed number of decimals
+ * @returns {string}
+ */
+function formatNumber(value, digits = 0) {
+  if (!numberFormats.has(digits)) {
+    numberFormats.set(digits, new Intl.NumberFormat(undefined, { minimumFractionDigits: digits, maximumFractionDigits: digits }));
+  }
+  return numberFormats.get(digits).format(value);
+}
+
+/**
+ * Format a byte count in the largest fitting unit (sign dropped; callers add it)
+ * @param {number} bytes
+ * @returns {string}
+ */
+function formatBytes(bytes) {
+  let value = Math.abs(bytes);
+  let unit = 0;
+  while (value >= 1024 && unit < SIZE_UNITS.length - 1) {
+    value /= 1024;
+    unit++;
+  }
+  return `${formatNumber(value, unit === 0 ? 0 : 1)} ${SIZE_UNITS[unit]}`;
+}
+
+function formatMegabytes(mb) {
+  return formatBytes(mb * 1024 * 1024);
+}
+
 // ============================================
 // Modal Confirmation Dialog
 // ============================================
@@ -136,7 +175,12 @@ document.getElementById('modal-overlay')?.addEventListener('click', (e) => {
 // Window Controls
 // ============================================
 document.getElementById('btn-close')?.addEventListener('click', () => getCurrentWindow().close());
-document.getElementById('btn-minimize')?.addEventListener('click', () => getCurrentWindow().minimize());
+document.getElementById('btn-minimize')?.addEventListener('click', async () => {
+  // Closing is hidden to the tray by the backend; minimizing is decided here
+  const settings = await invoke('get_app_settings').catch(() => null);
+  if (settings?.minimize_to_tray) getCurrentWindow().hide();
+  else getCurrentWindow().minimize();
+});
 document.getElementById('btn-maximize')?.addEventListener('click', () => getCurrentWindow().toggleMaximize());
 
 // ============================================
@@ -146,11 +190,33 @@ const state = {
   history: ['welcome'],
   versions: [],
   selectedVersion: null,
+  // Protect every detected install in one run, each keeping its own pick
+  allInstalls: false,
+  installVersions: [],
+  installSelections: {},
   switchTarget: null,
   cacheEnabled: true,
   lockEnabled: true,
   blockerEnabled: true,
+  hardenEnabled: false,
+  stubEnabled: false,
+  aclEnabled: false,
+  domainsEnabled: false,
+  // Hosts file already blocks the update domains (unchecking removes the block on apply)
+  domainsBlocked: false,
+  firewallEnabled: false,
+  autorunsEnabled: false,
+  previewEnabled: false,
   cacheSizeMb: 0,
+  cacheCategories: null, // null until picked in the cache preview; the backend then uses its defaults
+  draftBackupEnabled: false,
+  draftBackupDir: null,
+  draftProjects: null, // null until picked in the project list; the backend then zips every project
+  policy: {},
+  lastRunLogs: [],
+  failedDeletions: [],
+  slimComponents: [],
+  leftovers: [], // last Deep Clean scan
 };
 
 // ============================================
@@ -162,10 +228,37 @@ function navigateTo(viewId) {
 
   // Trigger data loading based on view
   if (viewId === 'precheck') runPreCheck();
-  if (viewId === 'versions') loadVersions();
+  if (viewId === 'versions') {
+    loadVersions();
+    invoke('start_apps_watch').catch(e => console.warn('Could not watch the Apps folder:', e));
+  }
   if (viewId === 'legacy') loadArchiveVersions();
-  if (viewId === 'options') loadCacheSize();
+  if (viewId === 'options') {
+    loadCacheSize();
+    loadDomainBlockStatus();
+    renderDeleteTree();
+    loadDraftCompatibility();
+  }
+  if (viewId === 'preview') loadPreview();
   if (viewId === 'switch') loadSwitchVersions();
+  if (viewId === 'settings') loadSettings();
+  if (viewId === 'email') loadEmailSettings();
+  if (viewId === 'schedules') loadSchedules();
+  if (viewId === 'compare') loadCompare();
+  if (viewId === 'firewall') loadFirewallRules();
+  if (viewId === 'autoruns') loadAutoruns();
+  if (viewId === 'deep-clean') loadLeftovers();
+  if (viewId === 'monitor') loadMonitor();
+  if (viewId === 'events') loadEvents();
+  if (viewId === 'delete-failures') loadDeleteFailures();
+  if (viewId === 'slim') loadSlim();
+  if (viewId === 'history') loadHistory();
+  if (viewId === 'timeline') loadTimeline();
+  if (viewId === 'config-diff') loadConfigDiff();
+  if (viewId === 'health') loadHealth();
+  if (viewId === 'release-notes') loadReleaseNotes();
+  if (viewId === 'deep-scan') loadDeepScan();
+  if (viewId === 'complete') loadCompleteOptions();
 }
 
 function showView(viewId) {
@@ -181,6 +274,37 @@ function goBack() {
   }
 }
 
+/**
+ * Ask the backend wizard for the step after `event`; resolves null (after explaining why) if blocked
+ */
+async function wizardNext(event) {
+  try {
+    return await invoke('wizard_transition', {
+      from: state.history[state.history.length - 1],
+      event,
+      versionSelected: state.selectedVersion !== null
+    });
+  } catch (e) {
+    await modal.show({
+      title: 'Cannot Continue',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      iconName: 'warning-circle'
+    });
+    return null;
+  }
+}
+
+/**
+ * Move the protection wizard forward; resolves whether it moved
+ */
+async function wizardAdvance(event) {
+  const step = await wizardNext(event);
+  if (step) navigateTo(step);
+  return step !== null;
+}
+
 // Expose navigation functions for inline handlers
 window.goBack = goBack;
 window.navigateTo = navigateTo;
@@ -188,36 +312,188 @@ window.navigateTo = navigateTo;
 // ============================================
 // Welcome View Handlers
 // ============================================
-document.getElementById('btn-start')?.addEventListener('click', () => navigateTo('precheck'));
+document.getElementById('btn-start')?.addEventListener('click', () => wizardAdvance('start'));
 document.getElementById('btn-switch')?.addEventListener('click', () => navigateTo('switch'));
 document.getElementById('btn-legacy')?.addEventListener('click', () => navigateTo('legacy'));
 document.getElementById('btn-remove-protection')?.addEventListener('click', removeProtection);
 
+document.getElementById('btn-unsupported-continue')?.addEventListener('click', () => {
+  state.history = ['welcome'];
+  showView('welcome');
+});
+
+// Explain an unsupported OS or missing LOCALAPPDATA up front
+(async function checkEnvironmentOnLoad() {
+  try {
+    const env = await invoke('check_environment');
+    env.notes.forEach(note => console.info(note));
+    if (env.supported) return;
+
+    document.getElementById('unsupported-os').textContent = env.os;
+    document.getElementById('unsupported-problems').replaceChildren(
+      ...[...env.problems, ...env.notes].map(p => el('li', { className: 'mt-2' }, p)));
+    state.history = ['unsupported'];
+    showView('unsupported');
+  } catch (e) {
+    console.warn('Could not check environment:', e);
+  }
+})();
+
+// ============================================
+// Accent Color
+// ============================================
+const DEFAULT_ACCENT = '#007AFF';
+const ACCENT_PRESETS = [
+  { name: 'Blue', color: '#007AFF' },
+  { name: 'Indigo', color: '#5856D6' },
+  { name: 'Purple', color: '#BF5AF2' },
+  { name: 'Pink', color: '#FF375F' },
+  { name: 'Orange', color: '#FF9F0A' },
+  { name: 'Green', color: '#30D158' },
+  { name: 'Teal', color: '#40C8E0' },
+  { name: 'Graphite', color: '#8E8E93' }
+];
+
+// Scale each channel toward black (factor < 1) for hover/pressed shades
+function shadeHex(rgb, factor) {
+  return '#' + rgb.map(c => Math.round(c * factor).toString(16).padStart(2, '0')).join('');
+}
+
+function applyAccent(color) {
+  const hex = /^#[0-9a-f]{6}$/i.test(color || '') ? color : DEFAULT_ACCENT;
+  const rgb = [1, 3, 5].map(i => parseInt(hex.slice(i, i + 2), 16));
+  const root = document.documentElement.style;
+  root.setProperty('--accent-blue', hex);
+  root.setProperty('--accent-blue-hover', shadeHex(rgb, 0.92));
+  root.setProperty('--accent-blue-active', shadeHex(rgb, 0.77));
+  root.setProperty('--accent-rgb', rgb.join(', '));
+
+  const picker = document.getElementById('setting-accent-color');
+  if (picker) picker.value = hex.toLowerCase();
+  document.querySelectorAll('.accent-swatch').forEach(swatch => {
+    swatch.classList.toggle('selected', swatch.dataset.color.toLowerCase() === hex.toLowerCase());
+  });
+}
+
+async function saveAccent(color) {
+  try {
+    const settings = await invoke('get_app_settings');
+    const previous = settings.accent_color;
+    settings.accent_color = color;
+    applyAccent(color);
+    try {
+      await invoke('save_app_settings', { settings });
+    } catch (e) {
+      applyAccent(previous);
+      throw e;
+    }
+  } catch (e) {
+    console.error('Failed to save accent color:', e);
+  }
+}
+
+document.getElementById('accent-presets')?.replaceChildren(...ACCENT_PRESETS.map(preset =>
+  el('button', {
+    className: 'accent-swatch',
+    title: preset.name,
+    'aria-label': `${preset.name} accent`,
+    dataset: { color: preset.color },
+    style: { background: preset.color },
+    onClick: () => saveAccent(preset.color)
+  })));
+document.getElementById('setting-accent-color')?.addEventListener('change', (e) => saveAccent(e.target.value.toUpperCase()));
+
+// Apply the saved accent before anything else renders
+(async function applyAccentOnLoad() {
+  try {
+    const settings = await invoke('get_app_settings');
+    applyAccent(settings.accent_color);
+  } catch (e) {
+    console.warn('Could not load accent color:', e);
+    applyAccent(DEFAULT_ACCENT);
+  }
+})();
+
 // Load protection status on start
 (async function checkProtectionOnLoad() {
+  try {
+    state.policy = await invoke('get_machine_policy');
+  } catch (e) {
+    console.warn('Could not load machine policy:', e);
+  }
+
   try {
     const status = await invoke('check_protection_status');
     updateStatusCard(status.is_protected);
   } catch (e) {
     console.warn('Could not check protection status:', e);
   }
+
+  refreshEventsBadge();
+  loadApplication();
 })();
 
+function showApplication(status) {
+  document.getElementById('welcome-application').value = status.application;
+  document.getElementById('welcome-application-path').textContent = status.found
+    ? status.root
+    : `${status.display_name} not found`;
+}
+
+async function loadApplication() {
+  try {
+    showApplication(await invoke('get_application'));
+  } catch (e) {
+    console.warn('Could not load application:', e);
+  }
+}
+
+document.getElementById('welcome-application')?.addEventListener('change', async (e) => {
+  try {
+    const status = await invoke('set_application', { application: e.target.value });
+    showApplication(status);
+    const protection = await invoke('check_protection_status');
+    updateStatusCard(protection.is_protected);
+    if (status.found) return;
+    const browse = await modal.show({
+      title: `${status.display_name} Not Found`,
+      message: `No ${status.display_name} installation was detected. If it is installed in a custom location, choose its folder.`,
+      confirmText: 'Browse',
+      cancelText: 'Close',
+      iconName: 'magnifying-glass'
+    });
+    if (browse) chooseCapCutFolder();
+  } catch (err) {
+    await modal.show({
+      title: 'Could Not Switch Application',
+      message: String(err),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+    loadApplication();
+  }
+});
+
 function updateStatusCard(isProtected) {
   const wrapper = document.getElementById('status-icon-wrapper');
   const icon = document.getElementById('status-icon');
   const title = document.getElementById('status-title');
   const subtitle = document.getElementById('status-subtitle');
   const removeBtn = document.getElementById('btn-remove-protection');
+  const diffBtn = document.getElementById('btn-config-diff');
 
   if (!wrapper || !icon || !title) return;
+  if (diffBtn) diffBtn.style.display = isProtected ? 'flex' : 'none';
 
   if (isProtected) {
     wrapper.className = 'status-icon-wrapper protected';
     icon.className = 'ph ph-shield-check';
     title.innerText = 'Version Locked';
     subtitle.innerText = 'Updates are disabled';
-    if (removeBtn) removeBtn.style.display = 'inline-flex';
+    // Administrator policy may forbid unprotecting
+    if (removeBtn) removeBtn.style.display = state.policy.disallow_unprotect ? 'none' : 'inline-flex';
   } else {
     wrapper.className = 'status-icon-wrapper unprotected';
     icon.className = 'ph ph-shield-warning';
@@ -228,54 +504,44 @@ function updateStatusCard(isProtected) {
 }
 
 async function removeProtection() {
-  // Show confirmation dialog first
   const confirmed = await modal.show({
-    title: 'Unlock Version?',
-    message: 'Updates will be enabled again. You can re-lock anytime.',
-    confirmText: 'Unlock',
+    title: 'Remove Protection?',
+    message: 'Blockers are deleted, configure.ini and ProductInfo.xml are restored and the Apps folder is unlocked. CapCut will be able to update again; you can re-lock anytime.',
+    confirmText: 'Remove',
     cancelText: 'Keep Protected',
     danger: true,
     iconName: 'shield-slash'
   });
-
   if (!confirmed) return;
 
-  const btn = document.getElementById('btn-remove-protection');
-
-  // Store original content for reset
-  const originalContent = Array.from(btn.childNodes).map(n => n.cloneNode(true));
-
-  btn.disabled = true;
-  btn.replaceChildren(icon('circle-notch', { className: 'ph ph-circle-notch spin' }), ' Removing...');
+  navigateTo('revert');
+  const list = document.getElementById('revert-checks');
+  const header = document.getElementById('revert-header');
+  const summary = document.getElementById('revert-summary');
+  header.textContent = 'RESTORING';
+  summary.textContent = '';
+  list.replaceChildren(createSkeletonRows(5));
 
   try {
-    const result = await invoke('remove_protection');
-
-    if (result.success) {
-      btn.replaceChildren(icon('check'), ' Removed!');
-      btn.style.background = 'var(--accent-green)';
-
-      // Update status card
-      updateStatusCard(false);
-
-      await sleep(1500);
-      btn.style.display = 'none';
-
-      // Reset button state for next time
-      btn.disabled = false;
-      btn.replaceChildren(...originalContent);
-      btn.style.background = '';
-    } else {
-      throw new Error(result.error);
-    }
+    const result = await invoke('revert_protection');
+    header.textContent = 'VERIFIED';
+    list.replaceChildren(...result.checks.map(check =>
+      el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4); gap: var(--space-3);' },
+        icon(check.removed ? 'check-circle' : 'x-circle', {
+          style: { color: check.removed ? 'var(--accent-green)' : 'var(--accent-red)', fontSize: '18px' }
+        }),
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, check.name),
+          el('span', { className: 'row-subtitle' }, check.removed ? 'Removed' : check.detail)))));
+    summary.textContent = result.reverted
+      ? 'CapCut is back to its unprotected state and can update again.'
+      : 'Some protection could not be removed. Close CapCut, run Version Guard as administrator and try again.';
+    summary.style.color = result.reverted ? '' : 'var(--accent-red)';
+    updateStatusCard(false);
   } catch (e) {
-    btn.replaceChildren(icon('x'), ' Failed');
-    btn.style.background = 'var(--accent-red)';
-    console.error(e);
-    await sleep(2000);
-    btn.replaceChildren(icon('lock-open-open'), ' Unlock Version');
-    btn.style.background = '';
-    btn.disabled = false;
+    header.textContent = 'FAILED';
+    list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+      el('span', { className: 'row-subtitle', style: { color: 'var(--accent-red)' } }, String(e))));
   }
 }
 
@@ -283,13 +549,30 @@ async function removeProtection() {
 // Precheck View Handlers
 // ============================================
 document.getElementById('precheck-back')?.addEventListener('click', goBack);
-document.getElementById('btn-continue-precheck')?.addEventListener('click', () => navigateTo('versions'));
+document.getElementById('btn-continue-precheck')?.addEventListener('click', () => wizardAdvance('continue'));
+
+document.getElementById('btn-precheck-browse')?.addEventListener('click', chooseCapCutFolder);
+document.getElementById('btn-precheck-deep-scan')?.addEventListener('click', () => navigateTo('deep-scan'));
+
+// Re-check this often while waiting for the user to close CapCut
+const PRECHECK_POLL_MS = 2000;
+let precheckTimer = null;
 
 async function runPreCheck() {
+  clearInterval(precheckTimer);
+  precheckTimer = null;
+
   const installIcon = document.getElementById('check-install');
   const installText = document.getElementById('check-install-text');
   const processIcon = document.getElementById('check-process');
   const processText = document.getElementById('check-process-text');
+  const channelRow = document.getElementById('check-channel-row');
+  const channelIcon = document.getElementById('check-channel');
+  const channelText = document.getElementById('check-channel-text');
+  const channelNote = document.getElementById('precheck-channel-note');
+  const cloudRow = document.getElementById('check-cloud-row');
+  const cloudNote = document.getElementById('precheck-cloud-note');
+  const cfaRow = document.getElementById('check-cfa-row');
   const nextBtn = document.getElementById('btn-continue-precheck');
 
   // Peak-End Rule: Elements
@@ -305,6 +588,16 @@ async function runPreCheck() {
   installText.textContent = 'Checking installation...';
   setStatusIcon(processIcon, 'pending');
   processText.textContent = 'Checking processes...';
+  channelRow.style.display = 'none';
+  channelNote.style.display = 'none';
+  cloudRow.style.display = 'none';
+  cloudNote.style.display = 'none';
+  cfaRow.style.display = 'none';
+  document.getElementById('check-volume-row').style.display = 'none';
+  document.getElementById('check-link-row').style.display = 'none';
+  document.getElementById('check-helpers-row').style.display = 'none';
+  document.getElementById('check-deep-scan-row').style.display = 'none';
+  document.getElementById('check-installers-row').style.display = 'none';
   nextBtn.disabled = true;
 
   await sleep(600); // Doherty Threshold: Perceptible delay
@@ -314,21 +607,79 @@ async function runPreCheck() {
 
     if (result.capcut_found) {
       setStatusIcon(installIcon, 'success');
-      installText.textContent = 'CapCut installation found';
+      const details = [
+        result.variant && result.variant !== 'CapCut' ? result.variant : null,
+        result.layout === 'portable' ? 'portable' : null
+      ].filter(Boolean);
+      installText.textContent = details.length
+        ? `CapCut installation found (${details.join(', ')})`
+        : 'CapCut installation found';
     } else {
       setStatusIcon(installIcon, 'error');
       installText.textContent = 'CapCut not found';
+      document.getElementById('check-deep-scan-row').style.display = 'flex';
+    }
+
+    // Beta builds use a different updater; guards adapt automatically
+    if (result.channel) {
+      const isBeta = result.channel === 'beta';
+      channelRow.style.display = 'flex';
+      setStatusIcon(channelIcon, isBeta ? 'warning' : 'success');
+      channelText.textContent = isBeta ? 'Beta channel (guards adjusted)' : 'Stable channel';
+      channelNote.style.display = isBeta ? 'block' : 'none';
+    }
+
+    // OneDrive can resurrect deleted folders and fight read-only locks
+    if (result.cloud_sync) {
+      cloudRow.style.display = 'flex';
+      setStatusIcon(document.getElementById('check-cloud'), 'warning');
+      document.getElementById('check-cloud-text').textContent =
+        `${result.cloud_sync} — consider choosing a CapCut folder outside OneDrive`;
+      cloudNote.style.display = 'block';
+    }
+
+    // Guards can't be checked or restored while the drive is disconnected
+    if (result.volume) {
+      const labels = {
+        removable: 'CapCut is on a removable drive',
+        network: 'CapCut is on a network location',
+        unavailable: 'CapCut drive is not connected',
+        other: 'CapCut is on an unusual drive type'
+      };
+      document.getElementById('check-volume-row').style.display = 'flex';
+      document.getElementById('check-volume-text').textContent = labels[result.volume];
     }
 
+    // Apps is a junction/symlink; guards act on the real folder
+    if (result.linked_from) {
+      document.getElementById('check-link-row').style.display = 'flex';
+      document.getElementById('check-link-text').textContent = `Apps folder is relocated to ${result.apps_path}`;
+      document.getElementById('check-link-from').textContent = `Linked from ${result.linked_from}`;
+    }
+
+    showHelperProcesses(result.helpers);
+
+    await showDownloadedInstallers();
+
+    // Ransomware protection silently blocks deletes and config writes
+    if (result.folder_access) {
+      cfaRow.style.display = 'flex';
+      setStatusIcon(document.getElementById('check-cfa'), 'error');
+      document.getElementById('check-cfa-text').textContent =
+        `Windows Security → Virus & threat protection → Ransomware protection → Allow an app through Controlled folder access → Add an allowed app → ${result.folder_access.app_path}`;
+    }
+
+    document.getElementById('btn-force-close').style.display = result.capcut_running ? '' : 'none';
     if (result.capcut_running) {
       setStatusIcon(processIcon, 'warning');
-      processText.textContent = 'CapCut is running — close it first';
+      processText.textContent = 'CapCut is running — close it first (checking automatically)';
+      precheckTimer = setInterval(pollCapCutClosed, PRECHECK_POLL_MS);
     } else {
       setStatusIcon(processIcon, 'success');
       processText.textContent = 'CapCut is not running';
     }
 
-    if (result.capcut_found && !result.capcut_running) {
+    if (result.capcut_found && !result.capcut_running && !result.folder_access) {
       // Peak-End Rule: Delightful success state
       await sleep(500);
       heroCheck.style.display = 'none';
@@ -346,6 +697,187 @@ async function runPreCheck() {
   }
 }
 
+/**
+ * Re-run the pre-check as soon as CapCut has been closed
+ */
+async function pollCapCutClosed() {
+  // Stop polling once the user leaves the view
+  if (!document.getElementById('view-precheck')?.classList.contains('active')) {
+    clearInterval(precheckTimer);
+    precheckTimer = null;
+    return;
+  }
+
+  try {
+    if (!(await invoke('is_capcut_running'))) runPreCheck();
+  } catch (e) { }
+}
+
+/**
+ * Warn about fresh CapCut installers in Downloads (when enabled in Settings)
+ */
+async function showDownloadedInstallers() {
+  const row = document.getElementById('check-installers-row');
+  const list = document.getElementById('check-installers-list');
+  try {
+    const settings = await invoke('get_app_settings');
+    if (!settings.check_downloaded_installers) return;
+    const installers = await invoke('check_downloaded_installers');
+    if (installers.length === 0) {
+      row.style.display = 'none';
+      return;
+    }
+
+    row.style.display = 'flex';
+    list.replaceChildren(...installers.map(installer => {
+      const btn = el('button', { className: 'btn-plain' }, icon('lock-key'), ' Quarantine');
+      btn.addEventListener('click', async () => {
+        btn.disabled = true;
+        try {
+          await invoke('quarantine_installer', { path: installer.path });
+          showDownloadedInstallers();
+        } catch (e) {
+          btn.disabled = false;
+          await modal.show({
+            title: 'Quarantine Failed',
+            message: String(e),
+            confirmText: 'OK',
+            cancelText: 'Close',
+            danger: true,
+            iconName: 'x-circle'
+          });
+        }
+      });
+      return el('div', { style: { display: 'flex', alignItems: 'center', justifyContent: 'space-between', gap: '8px' } },
+        el('span', { className: 'row-subtitle' }, `${installer.name} · ${formatBytes(installer.size_bytes)}`),
+        btn
+      );
+    }));
+  } catch (e) {
+    console.warn('Could not check Downloads for installers:', e);
+  }
+}
+
+// Crash reporters and helpers can download updates while CapCut is closed
+function showHelperProcesses(helpers) {
+  const row = document.getElementById('check-helpers-row');
+  if (helpers.length === 0) {
+    row.style.display = 'none';
+    return;
+  }
+  row.style.display = 'flex';
+  const names = [...new Set(helpers.map(h => h.name))];
+  document.getElementById('check-helpers-text').textContent =
+    `${names.join(', ')} — these can download updates in the background`;
+}
+
+document.getElementById('btn-end-helpers')?.addEventListener('click', async () => {
+  const btn = document.getElementById('btn-end-helpers');
+  btn.disabled = true;
+  try {
+    await invoke('terminate_helper_processes');
+    showHelperProcesses([]);
+  } catch (e) {
+    await modal.show({
+      title: 'Could Not End Processes',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  } finally {
+    btn.disabled = false;
+  }
+});
+
+/**
+ * Force-close CapCut with its child and helper processes; returns whether it succeeded
+ */
+async function forceCloseCapCut() {
+  const confirmed = await modal.show({
+    title: 'Force Close CapCut?',
+    message: 'CapCut and every process it started are ended immediately. Unsaved project changes are lost.',
+    confirmText: 'Force Close',
+    cancelText: 'Cancel',
+    danger: true,
+    iconName: 'prohibit'
+  });
+  if (!confirmed) return false;
+
+  try {
+    await invoke('force_close_capcut');
+    return true;
+  } catch (e) {
+    await modal.show({
+      title: 'Could Not End Processes',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+    return false;
+  }
+}
+
+/**
+ * Close CapCut like its close button would, force-killing what is still running after the grace period
+ */
+async function closeCapCutForMe(btn) {
+  const confirmed = await modal.show({
+    title: 'Close CapCut?',
+    message: 'CapCut is asked to close. Whatever is still running after 10 seconds, including a "save changes?" prompt, is ended and unsaved changes are lost.',
+    confirmText: 'Close CapCut',
+    cancelText: 'Cancel',
+    danger: true,
+    iconName: 'x-square'
+  });
+  if (!confirmed) return false;
+
+  const original = Array.from(btn.childNodes).map(n => n.cloneNode(true));
+  btn.disabled = true;
+  btn.replaceChildren(icon('circle-notch', { className: 'ph ph-circle-notch spin' }), ' Closing...');
+  try {
+    await invoke('close_capcut_gracefully');
+    return true;
+  } catch (e) {
+    await modal.show({
+      title: 'Could Not End Processes',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+    return false;
+  } finally {
+    btn.replaceChildren(...original);
+    btn.disabled = false;
+  }
+}
+
+document.getElementById('btn-force-close')?.addEventListener('click', async (e) => {
+  if (await closeCapCutForMe(e.currentTarget)) runPreCheck();
+});
+
+document.getElementById('btn-cfa-open')?.addEventListener('click', () => {
+  invoke('open_folder_access_settings').catch(e => console.warn('Could not open Windows Security:', e));
+});
+
+// Re-run the check once the user says Version Guard is on the allow list
+document.getElementById('btn-cfa-retry')?.addEventListener('click', async () => {
+  const confirmed = await modal.show({
+    title: 'Check Again?',
+    message: 'Make sure CC Version Guard was added to "Allow an app through Controlled folder access", then check again.',
+    confirmText: 'Check Again',
+    cancelText: 'Cancel',
+    danger: false,
+    iconName: 'shield-check'
+  });
+  if (confirmed) runPreCheck();
+});
+
 function setStatusIcon(icon, status) {
   icon.className = 'status-icon ph';
   const icons = {
@@ -360,19 +892,57 @@ function setStatusIcon(icon, status) {
 // ============================================
 // Versions View Handlers
 // ============================================
-document.getElementById('versions-back')?.addEventListener('click', goBack);
-document.getElementById('btn-continue-version')?.addEventListener('click', () => navigateTo('options'));
+document.getElementById('versions-back')?.addEventListener('click', () => {
+  invoke('stop_apps_watch');
+  goBack();
+});
+document.getElementById('btn-continue-version')?.addEventListener('click', () => {
+  invoke('stop_apps_watch');
+  wizardAdvance('continue');
+});
+document.getElementById('version-install-select')?.addEventListener('change', async (e) => {
+  state.allInstalls = e.target.value === 'all';
+  if (!state.allInstalls) {
+    try {
+      await invoke('set_active_install', { key: e.target.value });
+      invoke('start_apps_watch').catch(err => console.warn('Could not watch the Apps folder:', err));
+    } catch (err) {
+      console.warn('Could not switch installation:', err);
+    }
+  }
+  loadVersions();
+});
+
+// A version folder appeared or disappeared while the list was open
+listen('apps-changed', () => {
+  if (!document.getElementById('view-versions')?.classList.contains('active')) {
+    invoke('stop_apps_watch');
+    return;
+  }
+  loadVersions({ refresh: true });
+});
 
-async function loadVersions() {
+/**
+ * Scan and list installed versions; a live refresh keeps the selected version if it still exists
+ */
+async function loadVersions({ refresh = false } = {}) {
   const container = document.getElementById('version-list');
   const continueBtn = document.getElementById('btn-continue-version');
+  const previousPath = refresh ? state.selectedVersion?.path : null;
 
   // Reset state
   state.selectedVersion = null;
   continueBtn.disabled = true;
+  updateReclaimEstimate();
 
-  // Show skeleton loader
-  container.replaceChildren(createSkeletonRows(3));
+  // Show skeleton loader (a live refresh keeps the old list until the new one is ready)
+  if (!refresh) container.replaceChildren(createSkeletonRows(3));
+
+  await loadInstallPicker();
+  if (state.allInstalls) {
+    await loadAllInstallVersions();
+    return;
+  }
 
   try {
     const vers = await invoke('scan_versions');
@@ -388,6 +958,16 @@ async function loadVersions() {
         goBack();
         setTimeout(() => navigateTo('legacy'), 100);
       });
+      const locateBtn = el('button', { className: 'btn-plain' },
+        icon('folder-simple-plus'),
+        ' Choose CapCut Folder'
+      );
+      locateBtn.addEventListener('click', chooseCapCutFolder);
+      const deepScanBtn = el('button', { className: 'btn-plain' },
+        icon('hard-drives'),
+        ' Deep Scan All Drives'
+      );
+      deepScanBtn.addEventListener('click', () => navigateTo('deep-scan'));
 
       container.replaceChildren(
         el('div', {
@@ -397,7 +977,9 @@ async function loadVersions() {
           icon('folder-open', { style: { fontSize: '32px', color: 'var(--label-tertiary)', marginBottom: '8px' } }),
           el('span', { className: 'row-title' }, 'No installations found'),
           el('span', { className: 'row-subtitle', style: { marginBottom: '12px' } }, 'Download a legacy version to get started'),
-          downloadBtn
+          downloadBtn,
+          locateBtn,
+          deepScanBtn
         )
       );
       return;
@@ -414,9 +996,12 @@ async function loadVersions() {
           icon('hard-drives')
         ),
         el('div', { className: 'row-content' },
-          el('span', { className: 'row-title' }, `CapCut v${v.name}`),
-          el('span', { className: 'row-subtitle' }, `${v.size_mb.toFixed(0)} MB`)
+          el('span', { className: 'row-title' }, `CapCut v${v.name}${v.active ? ' (currently active)' : ''}`),
+          el('span', { className: 'row-subtitle' }, versionDetails(v)),
+          integrityBadge(v.integrity),
+          el('div', { className: 'version-note' }, ...versionNoteContent(v))
         ),
+        noteEditButton(v),
         icon('check', {
           className: 'ph ph-check row-accessory',
           style: { opacity: '0', color: 'var(--accent-blue)', fontSize: '18px' }
@@ -429,6 +1014,12 @@ async function loadVersions() {
     });
     container.replaceChildren(fragment);
 
+    // Keep the pick across a refresh; otherwise start from the version CapCut launches
+    const kept = vers.findIndex(v => v.path === previousPath);
+    const active = vers.findIndex(v => v.active);
+    if (kept !== -1) selectVersion(kept);
+    else if (active !== -1) selectVersion(active);
+
   } catch (e) {
     container.replaceChildren(
       el('div', { className: 'list-row' },
@@ -438,34 +1029,418 @@ async function loadVersions() {
   }
 }
 
-window.selectVersion = function (idx) {
-  state.selectedVersion = state.versions[idx];
-  document.getElementById('btn-continue-version').disabled = false;
+function installName(install) {
+  const name = install.variant || 'CapCut';
+  return install.source?.UserProfile ? `${name} (${install.source.UserProfile})` : name;
+}
 
-  document.querySelectorAll('#version-list .list-row').forEach((el, i) => {
-    const check = el.querySelector('.row-accessory');
-    if (i === idx) {
-      el.classList.add('selected');
-      if (check) check.style.opacity = '1';
-    } else {
-      el.classList.remove('selected');
-      if (check) check.style.opacity = '0';
-    }
-  });
-};
+/**
+ * Offer the installation choice when more than one CapCut install is detected
+ */
+async function loadInstallPicker() {
+  const section = document.getElementById('version-install-section');
+  const select = document.getElementById('version-install-select');
+  let installs = [];
+  try {
+    installs = await invoke('list_installs');
+  } catch (e) {
+    console.warn('Could not list installations:', e);
+  }
+  section.style.display = installs.length > 1 ? 'block' : 'none';
+  if (installs.length <= 1) {
+    state.allInstalls = false;
+    return;
+  }
+  select.replaceChildren(
+    ...installs.map(install => el('option', { value: install.key }, `${installName(install)} — ${install.root}`)),
+    el('option', { value: 'all' }, 'All installations')
+  );
+  select.value = state.allInstalls ? 'all' : (installs.find(i => i.active) || installs[0]).key;
+}
 
-// ============================================
-// Options View Handlers
+/**
+ * Scan every install and list its versions; picks survive a rescan while the folder exists
+ */
+async function loadAllInstallVersions() {
+  try {
+    state.installVersions = await invoke('scan_all_installs');
+  } catch (e) {
+    document.getElementById('version-list').replaceChildren(
+      el('div', { className: 'list-row' },
+        el('span', { className: 'row-title', style: { color: 'var(--accent-red)' } }, `Error: ${e}`)
+      )
+    );
+    return;
+  }
+  for (const [key, path] of Object.entries(state.installSelections)) {
+    const install = state.installVersions.find(i => i.key === key);
+    if (!install?.versions.some(v => v.path === path)) delete state.installSelections[key];
+  }
+  renderAllInstallVersions();
+}
+
+function renderAllInstallVersions() {
+  const fragment = document.createDocumentFragment();
+  state.installVersions.forEach(install => {
+    fragment.append(el('span', {
+      className: 'list-header',
+      style: { display: 'block', padding: 'var(--space-3) var(--space-4) 0', wordBreak: 'break-all' }
+    }, `${installName(install)} — ${install.root}`));
+    if (install.versions.length === 0) {
+      fragment.append(el('div', { className: 'list-row' },
+        el('span', { className: 'row-subtitle' }, 'No versions found')));
+      return;
+    }
+    install.versions.forEach(v => {
+      const selected = state.installSelections[install.key] === v.path;
+      const row = el('div', { className: selected ? 'list-row selectable selected' : 'list-row selectable', tabindex: '0' },
+        el('div', { className: 'row-icon bg-accent-indigo' }, icon('hard-drives')),
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, `v${v.name}${v.active ? ' (currently active)' : ''}`),
+          el('span', { className: 'row-subtitle' }, versionDetails(v))
+        ),
+        icon('check', {
+          className: 'ph ph-check row-accessory',
+          style: { opacity: selected ? '1' : '0', color: 'var(--accent-blue)', fontSize: '18px' }
+        })
+      );
+      const pick = () => pickInstallVersion(install, v);
+      row.addEventListener('click', pick);
+      row.addEventListener('keydown', (e) => handleKey(e, pick));
+      fragment.append(row);
+    });
+  });
+  document.getElementById('version-list').replaceChildren(fragment);
+
+  const targets = protectionTargets();
+  const toDelete = plannedDeletions();
+  state.selectedVersion = targets[0]?.keep || null;
+  document.getElementById('btn-continue-version').disabled = targets.length === 0;
+  const summary = document.getElementById('version-reclaim');
+  summary.textContent = `${targets.length} of ${state.installVersions.length} installation(s) selected · deleting ${toDelete.length} other version(s) frees about ${formatMegabytes(toDelete.reduce((sum, v) => sum + v.size_mb, 0))}`;
+  summary.style.display = 'block';
+  document.getElementById('version-reclaim-warning').style.display = 'none';
+}
+
+function pickInstallVersion(install, v) {
+  // Picking the same version again leaves the install out of the run
+  if (state.installSelections[install.key] === v.path) {
+    delete state.installSelections[install.key];
+  } else {
+    state.installSelections[install.key] = v.path;
+  }
+  renderAllInstallVersions();
+}
+
+/**
+ * Installs the next protection run covers as { key, keep, remove }; key is null for the active install
+ */
+function protectionTargets() {
+  if (!state.allInstalls) {
+    if (!state.selectedVersion) return [];
+    return [{
+      key: null,
+      keep: state.selectedVersion,
+      remove: state.versions.filter(v => v.path !== state.selectedVersion.path)
+    }];
+  }
+  return state.installVersions
+    .filter(install => state.installSelections[install.key])
+    .map(install => ({
+      key: install.key,
+      keep: install.versions.find(v => v.path === state.installSelections[install.key]),
+      remove: install.versions.filter(v => v.path !== state.installSelections[install.key])
+    }));
+}
+
+function plannedDeletions() {
+  return protectionTargets().flatMap(target => target.remove);
+}
+
+// Installed CapCut versions are several hundred MB; smaller folders are usually staged or partial updates
+const SUSPICIOUS_VERSION_MB = 100;
+
+/**
+ * Show how much space deleting the unselected versions frees, and flag tiny folders
+ */
+function updateReclaimEstimate() {
+  const summary = document.getElementById('version-reclaim');
+  const warning = document.getElementById('version-reclaim-warning');
+  if (!summary || !warning) return;
+
+  if (!state.selectedVersion) {
+    summary.style.display = 'none';
+    warning.style.display = 'none';
+    return;
+  }
+
+  const toDelete = state.versions.filter(v => v.path !== state.selectedVersion.path);
+  const totalMb = toDelete.reduce((sum, v) => sum + v.size_mb, 0);
+  summary.textContent = toDelete.length === 0
+    ? 'No other versions to delete'
+    : `Deleting ${toDelete.length} other version${toDelete.length !== 1 ? 's' : ''} frees about ${formatMegabytes(totalMb)}`;
+  summary.style.display = 'block';
+
+  const small = toDelete.filter(v => v.size_mb < SUSPICIOUS_VERSION_MB);
+  if (small.length > 0) {
+    warning.textContent = `${small.map(v => `v${v.name}`).join(', ')} ${small.length !== 1 ? 'are' : 'is'} under ${SUSPICIOUS_VERSION_MB} MB and may be a staged or incomplete update rather than a full install.`;
+    warning.style.display = 'block';
+  } else {
+    warning.style.display = 'none';
+  }
+}
+
+/**
+ * Let the user point at a CapCut install outside the detected locations
+ */
+async function chooseCapCutFolder() {
+  const { open } = window.__TAURI__.dialog;
+  const path = await open({ directory: true, multiple: false });
+  if (!path) return;
+
+  if (await useCapCutFolder(path)) reloadForNewFolder();
+}
+
+/**
+ * Validate and remember a CapCut folder; resolves whether it was saved
+ */
+async function useCapCutFolder(path) {
+  const check = await invoke('validate_custom_path', { path });
+  if (!check.valid) {
+    await modal.show({
+      title: 'Not a CapCut Folder',
+      message: check.error || 'The selected folder is not a CapCut installation.',
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'folder-notch-open'
+    });
+    return false;
+  }
+
+  await modal.show({
+    title: 'CapCut Folder Saved',
+    message: `Found ${check.versions_with_exe.length} version(s) in ${check.paths.root}.`,
+    confirmText: 'OK',
+    cancelText: 'Close',
+    danger: false,
+    iconName: 'check-circle'
+  });
+  return true;
+}
+
+/**
+ * Re-run whatever the current screen shows for the previous CapCut folder
+ */
+function reloadForNewFolder() {
+  const current = state.history[state.history.length - 1];
+  if (current === 'welcome') loadApplication();
+  if (current === 'precheck') runPreCheck();
+  if (current === 'versions') {
+    loadVersions();
+    invoke('start_apps_watch').catch(e => console.warn('Could not watch the Apps folder:', e));
+  }
+}
+
+// ============================================
+// Drag & Drop: register a dropped CapCut folder
+// ============================================
+const dropOverlay = document.getElementById('drop-overlay');
+
+/**
+ * Screens where switching the CapCut folder would pull the rug out from under a running task
+ */
+function dropBlocked() {
+  return ['processing', 'delete-failures'].includes(state.history[state.history.length - 1]);
+}
+
+window.__TAURI__.webview.getCurrentWebview().onDragDropEvent(async (event) => {
+  const { type, paths } = event.payload;
+  if (type === 'enter' || type === 'over') {
+    if (!dropBlocked()) dropOverlay.style.display = 'flex';
+    return;
+  }
+  dropOverlay.style.display = 'none';
+  if (type !== 'drop' || !paths?.length || dropBlocked()) return;
+
+  if (await useCapCutFolder(paths[0])) reloadForNewFolder();
+});
+
+// ============================================
+// Deep Scan View Handlers
+// ============================================
+document.getElementById('deep-scan-back')?.addEventListener('click', goBack);
+
+async function loadDeepScan() {
+  const list = document.getElementById('deep-scan-list');
+  list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+    el('i', { className: 'ph ph-circle-notch spin' }),
+    el('span', { className: 'row-subtitle' }, 'Searching all local drives — this can take a few minutes')));
+
+  try {
+    const hits = await invoke('deep_scan_installs');
+    if (hits.length === 0) {
+      list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+        el('span', { className: 'row-subtitle' }, 'No CapCut installation was found on any local drive')));
+      return;
+    }
+
+    list.replaceChildren(...hits.map(hit => {
+      const row = el('div', { className: 'list-row selectable', tabIndex: 0, role: 'button' },
+        icon('folder-simple', { style: { color: 'var(--accent-blue)', fontSize: '20px' } }),
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, hit.root),
+          el('span', { className: 'row-subtitle' }, hit.versions.map(v => `v${v}`).join(', '))),
+        icon('caret-right', { style: { color: 'var(--label-tertiary)', fontSize: '16px' } }));
+      const pick = async () => {
+        if (!(await useCapCutFolder(hit.root))) return;
+        goBack();
+        reloadForNewFolder();
+      };
+      row.addEventListener('click', pick);
+      row.addEventListener('keydown', (e) => handleKey(e, pick));
+      return row;
+    }));
+  } catch (e) {
+    list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+      el('span', { className: 'row-subtitle', style: { color: 'var(--accent-red)' } }, `Error: ${e}`)));
+  }
+}
+
+/**
+ * Subtitle for a version card: newest/oldest mark, size, exe build and folder date
+ */
+function versionDetails(v) {
+  const parts = [formatMegabytes(v.size_mb)];
+  if (v.newest) parts.unshift('Newest');
+  else if (v.oldest) parts.unshift('Oldest');
+  if (v.build) parts.push(`Build ${v.build}`);
+  if (v.modified) parts.push(new Date(v.modified * 1000).toLocaleDateString());
+  return parts.join(' · ');
+}
+
+/**
+ * Integrity indicator for a version card
+ */
+function integrityBadge(integrity) {
+  const badges = {
+    complete: { iconName: 'shield-check', text: 'Complete', color: 'var(--accent-green)' },
+    missing_binaries: { iconName: 'warning', text: 'Missing binaries', color: 'var(--accent-orange)' },
+    hash_mismatch: { iconName: 'warning-octagon', text: 'Modified since first scan', color: 'var(--accent-red)' }
+  };
+  const badge = badges[integrity] || badges.complete;
+  return el('span', {
+    className: 'row-subtitle',
+    style: { color: badge.color, display: 'flex', alignItems: 'center', gap: '4px' }
+  },
+    icon(badge.iconName),
+    badge.text
+  );
+}
+
+/**
+ * Tag chips and note text for a version card
+ */
+function versionNoteContent(v) {
+  if (!v.note) return [];
+  return [
+    ...v.note.tags.map(tag => el('span', { className: 'version-tag' }, tag)),
+    v.note.note ? el('span', { className: 'row-subtitle version-note-text' }, v.note.note) : null
+  ];
+}
+
+/**
+ * Pencil button that opens the inline note/tags editor on a version card
+ */
+function noteEditButton(v) {
+  const btn = el('button', { className: 'btn-plain note-edit-button', title: 'Edit note and tags', 'aria-label': 'Edit note and tags' },
+    icon('pencil-simple')
+  );
+  btn.addEventListener('click', (e) => {
+    e.stopPropagation();
+    openNoteEditor(btn.closest('.list-row'), v);
+  });
+  btn.addEventListener('keydown', (e) => e.stopPropagation());
+  return btn;
+}
+
+function openNoteEditor(row, v) {
+  const noteBox = row.querySelector('.version-note');
+  if (!noteBox || noteBox.querySelector('.note-editor')) return;
+
+  const noteInput = el('input', { className: 'text-field', type: 'text', placeholder: 'Note (e.g., has my caption workflow)' });
+  noteInput.value = v.note ? v.note.note : '';
+  const tagsInput = el('input', { className: 'text-field', type: 'text', placeholder: 'Tags, comma separated' });
+  tagsInput.value = v.note ? v.note.tags.join(', ') : '';
+  const saveBtn = el('button', { className: 'btn-plain' }, icon('check'), ' Save');
+  const cancelBtn = el('button', { className: 'btn-plain' }, 'Cancel');
+
+  const editor = el('div', { className: 'note-editor' }, noteInput, tagsInput,
+    el('div', { style: { display: 'flex', gap: '8px' } }, saveBtn, cancelBtn)
+  );
+  // Typing and clicking in the editor must not select the row
+  editor.addEventListener('click', (e) => e.stopPropagation());
+  editor.addEventListener('keydown', (e) => e.stopPropagation());
+
+  const close = () => noteBox.replaceChildren(...versionNoteContent(v));
+  cancelBtn.addEventListener('click', close);
+  saveBtn.addEventListener('click', async () => {
+    try {
+      v.note = await invoke('set_version_note', {
+        version: v.name,
+        note: noteInput.value,
+        tags: tagsInput.value.split(',')
+      });
+      close();
+    } catch (e) {
+      console.error('Failed to save version note:', e);
+    }
+  });
+
+  noteBox.replaceChildren(editor);
+  noteInput.focus();
+}
+
+window.selectVersion = function (idx) {
+  state.selectedVersion = state.versions[idx];
+  document.getElementById('btn-continue-version').disabled = false;
+  updateReclaimEstimate();
+
+  document.querySelectorAll('#version-list .list-row').forEach((el, i) => {
+    const check = el.querySelector('.row-accessory');
+    if (i === idx) {
+      el.classList.add('selected');
+      if (check) check.style.opacity = '1';
+    } else {
+      el.classList.remove('selected');
+      if (check) check.style.opacity = '0';
+    }
+  });
+};
+
+// ============================================
+// Options View Handlers
 // ============================================
 document.getElementById('options-back')?.addEventListener('click', goBack);
-document.getElementById('btn-apply')?.addEventListener('click', async () => {
-  const versionsToDelete = state.versions.filter(v => v.path !== state.selectedVersion.path);
+document.getElementById('btn-apply')?.addEventListener('click', () => {
+  if (state.previewEnabled) navigateTo('preview');
+  else lockVersion();
+});
+
+async function lockVersion() {
+  const targets = protectionTargets();
+  const versionsToDelete = plannedDeletions();
+
+  // Guards on a repacked build cause confusing breakage; warn first
+  for (const target of targets) {
+    if (!(await confirmBuildAuthenticity(target.keep))) return;
+  }
 
   // If deleting other versions, show confirmation
   if (versionsToDelete.length > 0) {
     const confirmed = await modal.show({
       title: 'Lock Version?',
-      message: `This will permanently delete ${versionsToDelete.length} other version${versionsToDelete.length !== 1 ? 's' : ''} and lock your selected version.`,
+      message: `This will permanently delete ${versionsToDelete.length} other version${versionsToDelete.length !== 1 ? 's' : ''} (about ${formatMegabytes(versionsToDelete.reduce((sum, v) => sum + v.size_mb, 0))}) and lock your selected version${targets.length > 1 ? `s in ${targets.length} installations` : ''}.`,
       confirmText: 'Lock Version',
       cancelText: 'Go Back',
       danger: false,
@@ -475,11 +1450,121 @@ document.getElementById('btn-apply')?.addEventListener('click', async () => {
     if (!confirmed) return;
   }
 
+  if (!(await wizardAdvance('run'))) return;
   runProtectionSequence();
+}
+
+// ============================================
+// Preview View Handlers
+// ============================================
+document.getElementById('preview-back')?.addEventListener('click', goBack);
+document.getElementById('btn-preview-apply')?.addEventListener('click', () => {
+  goBack();
+  lockVersion();
 });
 
+/**
+ * Dry-run the protection sequence for every target and list what it would change
+ */
+async function loadPreview() {
+  const summary = document.getElementById('preview-summary');
+  const list = document.getElementById('preview-list');
+  const notes = document.getElementById('preview-notes');
+  summary.replaceChildren();
+  notes.textContent = '';
+  notes.style.color = '';
+  list.replaceChildren(createSkeletonRows(4));
+
+  try {
+    const targets = protectionTargets();
+    const previews = [];
+    for (const target of targets) {
+      previews.push(await invoke('preview_protection', {
+        params: {
+          versions_to_delete: target.remove.map(v => v.path),
+          keep_version: target.keep.path,
+          ...protectionOptions()
+        },
+        install: target.key
+      }));
+    }
+
+    const changes = previews.flatMap(p => p.changes);
+    const count = (action) => changes.filter(c => c.action === action).length;
+    const summaryRow = (label, value) =>
+      el('div', { className: 'list-row', style: 'padding: var(--space-2) var(--space-4); justify-content: space-between;' },
+        el('span', { className: 'row-subtitle' }, label),
+        el('span', { className: 'row-title' }, value));
+    summary.replaceChildren(
+      summaryRow('Disk space freed', formatBytes(previews.reduce((sum, p) => sum + p.bytes_freed, 0))),
+      summaryRow('Kept in backups', formatBytes(previews.reduce((sum, p) => sum + p.backup_bytes, 0))),
+      summaryRow('Deleted / created / modified', `${count('delete')} / ${count('create')} / ${count('modify')}`));
+
+    const actionIcons = { delete: 'trash', create: 'plus-circle', modify: 'pencil-simple' };
+    const actionColors = { delete: 'var(--accent-red)', create: 'var(--accent-green)', modify: 'var(--accent-orange)' };
+    const changeRow = (c) =>
+      el('div', { className: 'list-row', style: 'padding: var(--space-2) var(--space-4); gap: var(--space-3);' },
+        icon(actionIcons[c.action], { style: { color: actionColors[c.action], fontSize: '18px' } }),
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title', style: { wordBreak: 'break-all' } }, c.target),
+          el('span', { className: 'row-subtitle' },
+            [c.step, c.detail, c.bytes != null ? formatBytes(c.bytes) : null].filter(Boolean).join(' · '))));
+    const rows = previews.flatMap(p => [
+      ...(previews.length > 1 ? [el('div', { className: 'list-header', style: 'padding: var(--space-2) var(--space-4) 0;' }, p.root)] : []),
+      ...p.changes.map(changeRow)
+    ]);
+    list.replaceChildren(...(rows.length > 0 ? rows : [
+      el('div', { className: 'list-row', style: { justifyContent: 'center', padding: '24px' } },
+        el('span', { className: 'row-subtitle' }, 'Nothing would change with these options.'))
+    ]));
+    notes.textContent = previews.flatMap(p => p.notes).join(' ');
+  } catch (e) {
+    list.replaceChildren();
+    notes.textContent = String(e);
+    notes.style.color = 'var(--accent-red)';
+  }
+}
+
+/**
+ * Warn if the selected build looks modified; resolves true to continue
+ */
+async function confirmBuildAuthenticity(version) {
+  const applyBtn = document.getElementById('btn-apply');
+  applyBtn.disabled = true;
+  let report;
+  try {
+    report = await invoke('check_build_authenticity', { path: version.path });
+  } catch (e) {
+    console.warn('Could not check build authenticity:', e);
+    return true;
+  } finally {
+    applyBtn.disabled = false;
+  }
+  if (!report.modified) return true;
+
+  const reasons = {
+    unsigned: 'CapCut.exe is not digitally signed',
+    invalid: 'CapCut.exe has a broken digital signature',
+    unexpected_signer: `CapCut.exe is signed by someone else (${report.signer || 'unknown'})`
+  };
+  const findings = [];
+  if (reasons[report.signature]) findings.push(reasons[report.signature]);
+  if (report.suspicious_files.length > 0) {
+    findings.push(`Loader files found: ${report.suspicious_files.join(', ')}`);
+  }
+
+  return modal.show({
+    title: 'Modified Build Detected',
+    message: `CapCut v${version.name} looks repacked or modded. ${findings.join('. ')}. Protection may not work as expected with modified builds.`,
+    confirmText: 'Continue Anyway',
+    cancelText: 'Go Back',
+    danger: true,
+    iconName: 'warning-octagon'
+  });
+}
+
 // Toggle handlers with keyboard support (Accessibility)
-function setupToggle(id, stateKey) {
+function setupToggle(id, stateKey, onChange) {
   const toggle = document.getElementById(id);
   if (!toggle) return;
 
@@ -487,6 +1572,7 @@ function setupToggle(id, stateKey) {
     state[stateKey] = !state[stateKey];
     this.classList.toggle('on', state[stateKey]);
     this.setAttribute('aria-checked', state[stateKey]);
+    onChange?.(state[stateKey]);
   };
 
   toggle.addEventListener('click', handler);
@@ -501,78 +1587,416 @@ function setupToggle(id, stateKey) {
 setupToggle('toggle-cache', 'cacheEnabled');
 setupToggle('toggle-lock', 'lockEnabled');
 setupToggle('toggle-blocker', 'blockerEnabled');
+setupToggle('toggle-harden', 'hardenEnabled');
+setupToggle('toggle-stub', 'stubEnabled');
+setupToggle('toggle-acl', 'aclEnabled');
+setupToggle('toggle-domains', 'domainsEnabled');
+setupToggle('toggle-firewall', 'firewallEnabled');
+setupToggle('toggle-autoruns', 'autorunsEnabled');
+setupToggle('toggle-drafts', 'draftBackupEnabled', async (enabled) => {
+  if (!enabled || state.draftBackupDir || (await chooseDraftBackupDir())) return;
+  // No folder picked; nothing to back up to
+  const toggle = document.getElementById('toggle-drafts');
+  state.draftBackupEnabled = false;
+  toggle.classList.remove('on');
+  toggle.setAttribute('aria-checked', 'false');
+});
+setupToggle('toggle-preview', 'previewEnabled', (enabled) => {
+  document.getElementById('btn-apply').replaceChildren(
+    icon(enabled ? 'eye' : 'lock'), enabled ? ' Preview Changes' : ' Lock Version');
+});
+
+/**
+ * Preset the update domain toggle from the hosts file
+ */
+async function loadDomainBlockStatus() {
+  const toggle = document.getElementById('toggle-domains');
+  try {
+    const status = await invoke('update_domains_status');
+    state.domainsBlocked = status.blocked;
+    state.domainsEnabled = status.blocked;
+    toggle.classList.toggle('on', status.blocked);
+    toggle.setAttribute('aria-checked', String(status.blocked));
+    toggle.title = status.conflict
+      ? `${status.domains.join(', ')} · an old update check redirect also lists these hosts; applying the block replaces it`
+      : status.domains.join(', ');
+  } catch (e) {
+    console.warn('Could not read update domain block:', e);
+  }
+}
 
 async function loadCacheSize() {
   const sizeText = document.getElementById('cache-size');
   try {
-    const size = await invoke('calculate_cache_size');
+    const size = await invoke('calculate_cache_size', { categories: state.cacheCategories });
     state.cacheSizeMb = size;
-    sizeText.textContent = `${size.toFixed(1)} MB can be freed`;
+    sizeText.textContent = `${formatMegabytes(size)} can be freed`;
   } catch {
     sizeText.textContent = 'Size unavailable';
   }
 }
 
-// ============================================
-// Protection Sequence
-// ============================================
-async function runProtectionSequence() {
-  navigateTo('processing');
+document.getElementById('btn-cache-preview')?.addEventListener('click', previewCacheClean);
+document.getElementById('btn-draft-projects')?.addEventListener('click', showDraftProjects);
 
-  const progressBar = document.getElementById('progress-bar');
-  const statusText = document.getElementById('status-text');
-  const logContainer = document.getElementById('activity-log');
-  logContainer.replaceChildren();
-
-  const setProgress = (msg, pct) => {
-    statusText.textContent = msg;
-    progressBar.style.width = `${pct}%`;
-  };
+/**
+ * Ask for the folder draft zips are written to
+ */
+async function chooseDraftBackupDir() {
+  const { open } = window.__TAURI__.dialog;
+  const folder = await open({ directory: true, multiple: false });
+  if (!folder) return false;
+  state.draftBackupDir = folder;
+  document.getElementById('draft-backup-dest').textContent = `Zip to ${folder}`;
+  return true;
+}
 
-  const addLog = (msg, type = 'info') => {
-    const iconNames = { ok: 'check', warn: 'warning', info: 'dot' };
-    const classes = { ok: 'success', warn: 'warning', info: '' };
-    const entry = el('div', { className: `log-entry ${classes[type]}` },
-      icon(iconNames[type]),
-      el('span', {}, msg)
-    );
-    logContainer.append(entry);
-    logContainer.scrollTop = logContainer.scrollHeight;
-  };
+/**
+ * List the CapCut projects so the draft backup can be limited to some of them
+ */
+async function showDraftProjects() {
+  const panel = document.getElementById('draft-projects');
+  if (panel.style.display !== 'none') {
+    panel.style.display = 'none';
+    return;
+  }
 
+  panel.replaceChildren(createSkeletonRows(2));
+  panel.style.display = 'block';
   try {
-    setProgress('Preparing...', 10);
-    addLog('Starting version lock sequence');
-    await sleep(300);
-
-    const versionsToDelete = state.versions
-      .filter(v => v.path !== state.selectedVersion.path)
-      .map(v => v.path);
+    const projects = await invoke('list_draft_projects');
+    const folder = el('span', { className: 'row-subtitle' },
+      state.draftBackupDir ? `Zip to ${state.draftBackupDir}` : 'No backup folder chosen');
+    const destination = el('div', { className: 'list-row', style: { justifyContent: 'space-between' } },
+      folder,
+      el('button', {
+        className: 'btn-plain',
+        onclick: async () => {
+          if (await chooseDraftBackupDir()) folder.textContent = `Zip to ${state.draftBackupDir}`;
+        }
+      }, 'Change'));
+    if (projects.length === 0) {
+      panel.replaceChildren(destination, el('div', { className: 'list-row' },
+        el('span', { className: 'row-subtitle' }, 'No CapCut projects found')));
+      return;
+    }
+    panel.replaceChildren(destination, ...projects.map(p => {
+      const checkbox = el('input', { type: 'checkbox', 'data-project': p.name });
+      checkbox.checked = state.draftProjects ? state.draftProjects.includes(p.name) : true;
+      checkbox.addEventListener('change', () => {
+        const boxes = [...panel.querySelectorAll('input[data-project]')];
+        const checked = boxes.filter(b => b.checked).map(b => b.dataset.project);
+        state.draftProjects = checked.length === boxes.length ? null : checked;
+      });
+      return el('div', { className: 'list-row', style: { justifyContent: 'space-between' } },
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, p.name),
+          el('span', { className: 'row-subtitle' }, `${formatBytes(p.bytes)} · edited ${new Date(p.modified * 1000).toLocaleDateString()}`)),
+        el('label', { className: 'switch' }, checkbox, el('span', { className: 'slider round' })));
+    }));
+  } catch (e) {
+    panel.replaceChildren(el('div', { className: 'list-row' },
+      el('span', { className: 'row-subtitle', style: { color: 'var(--accent-red)' } }, `Error: ${e}`)));
+  }
+}
 
-    setProgress('Cleaning versions...', 30);
-    addLog(`Found ${versionsToDelete.length} version(s) to remove`);
-    await sleep(200);
+/**
+ * List the versions that will be deleted; each expands into a lazily loaded file tree
+ */
+function renderDeleteTree() {
+  const section = document.getElementById('delete-tree-section');
+  const tree = document.getElementById('delete-tree');
+  const toDelete = plannedDeletions();
+  section.style.display = toDelete.length > 0 ? 'block' : 'none';
+  tree.replaceChildren(...toDelete.map(v => treeNode({
+    name: `v${v.name}`,
+    path: v.path,
+    is_dir: true,
+    bytes: v.size_mb * 1024 * 1024,
+    contains_drafts: false
+  }, 0)));
+}
 
-    setProgress('Locking version...', 50);
+/**
+ * Warn about projects the kept version may refuse to open
+ */
+async function loadDraftCompatibility() {
+  const section = document.getElementById('draft-compat-section');
+  const list = document.getElementById('draft-compat-list');
+  section.style.display = 'none';
+  try {
+    const rows = [];
+    for (const target of protectionTargets()) {
+      const report = await invoke('check_draft_compatibility', { keepVersion: target.keep.path, install: target.key });
+      rows.push(...report.projects.filter(p => p.status === 'newer').map(p =>
+        el('div', { className: 'list-row', style: { gap: '8px' } },
+          icon('warning', { style: { color: 'var(--accent-orange)' } }),
+          el('div', { className: 'row-content' },
+            el('span', { className: 'row-title' }, p.name),
+            el('span', { className: 'row-subtitle' }, `Last saved with CapCut ${p.saved_with}; may not open in v${report.kept_version}`)))));
+    }
+    list.replaceChildren(...rows);
+    section.style.display = rows.length > 0 ? 'block' : 'none';
+  } catch (e) {
+    console.warn('Could not check draft compatibility:', e);
+  }
+}
 
-    const result = await invoke('run_full_protection', {
-      params: {
-        versions_to_delete: versionsToDelete,
-        clean_cache: state.cacheEnabled,
-        lock_config: state.lockEnabled,
-        create_blockers: state.blockerEnabled
+/**
+ * One file tree row; folders load their children on first expand
+ */
+function treeNode(entry, depth) {
+  const children = el('div', { style: { display: 'none' } });
+  const chevron = entry.is_dir
+    ? el('i', { className: 'ph ph-caret-right', style: { color: 'var(--label-tertiary)', fontSize: '12px', transition: 'transform 0.15s' } })
+    : el('span', { style: { width: '12px' } });
+  const row = el('div', {
+    className: entry.is_dir ? 'list-row selectable' : 'list-row',
+    style: { padding: `6px var(--space-4) 6px calc(var(--space-4) + ${depth * 16}px)`, gap: '8px' }
+  },
+    chevron,
+    icon(entry.is_dir ? 'folder' : 'file', { style: { color: 'var(--label-secondary)' } }),
+    el('div', { className: 'row-content' },
+      el('span', { className: 'row-title', style: { fontSize: '13px', wordBreak: 'break-all' } }, entry.name),
+      entry.contains_drafts
+        ? el('span', { className: 'row-subtitle', style: { color: 'var(--accent-orange)' } }, 'Contains CapCut projects')
+        : null),
+    el('span', { className: 'row-subtitle' }, formatBytes(entry.bytes)));
+
+  if (entry.is_dir) {
+    row.tabIndex = 0;
+    let loaded = false;
+    const toggle = async () => {
+      const open = children.style.display === 'none';
+      children.style.display = open ? 'block' : 'none';
+      chevron.style.transform = open ? 'rotate(90deg)' : '';
+      if (!open || loaded) return;
+      loaded = true;
+      children.replaceChildren(createSkeletonRows(1));
+      try {
+        const entries = await invoke('list_folder_entries', { path: entry.path });
+        children.replaceChildren(...(entries.length > 0
+          ? entries.map(e => treeNode(e, depth + 1))
+          : [el('div', { className: 'list-row', style: { padding: `6px var(--space-4) 6px calc(var(--space-4) + ${(depth + 1) * 16}px)` } },
+            el('span', { className: 'row-subtitle' }, 'Empty folder'))]));
+      } catch (e) {
+        loaded = false;
+        children.replaceChildren(el('div', { className: 'list-row' },
+          el('span', { className: 'row-subtitle', style: { color: 'var(--accent-red)' } }, `Error: ${e}`)));
       }
-    });
+    };
+    row.addEventListener('click', toggle);
+    row.addEventListener('keydown', (e) => handleKey(e, toggle));
+  }
+  return el('div', {}, row, children);
+}
 
-    if (result.logs) {
-      result.logs.forEach(log => {
-        const type = log.startsWith('[OK]') ? 'ok' : log.startsWith('[!]') ? 'warn' : 'info';
-        addLog(log.replace(/^\[OK\] |\[!\] |>> /g, ''), type);
-      });
-    }
+/**
+ * Show what cache cleaning would remove without deleting anything
+ */
+async function previewCacheClean() {
+  const panel = document.getElementById('cache-preview');
+  if (panel.style.display !== 'none') {
+    panel.style.display = 'none';
+    return;
+  }
 
-    if (!result.success) {
-      throw new Error(result.error || 'Protection failed');
+  panel.replaceChildren(createSkeletonRows(1));
+  panel.style.display = 'block';
+  try {
+    const preview = await invoke('preview_cache_clean');
+    if (preview.categories.length === 0) {
+      panel.replaceChildren(el('div', { className: 'list-row' },
+        el('span', { className: 'row-subtitle' }, 'No cache folders found — nothing would be removed')));
+      return;
+    }
+    panel.replaceChildren(...preview.categories.map(c => {
+      const checkbox = el('input', { type: 'checkbox', 'data-category': c.category });
+      checkbox.checked = state.cacheCategories ? state.cacheCategories.includes(c.category) : c.default;
+      checkbox.addEventListener('change', () => updateCacheSelection(preview));
+      return el('div', { className: 'list-row', style: { justifyContent: 'space-between' } },
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, c.name),
+          el('span', { className: 'row-subtitle' }, `${formatNumber(c.files)} file(s) · ${formatBytes(c.bytes)} · ${c.paths.join(', ')}`)),
+        el('label', { className: 'switch' }, checkbox, el('span', { className: 'slider round' })));
+    }));
+  } catch (e) {
+    panel.replaceChildren(el('div', { className: 'list-row' },
+      el('span', { className: 'row-subtitle', style: { color: 'var(--accent-red)' } }, `Error: ${e}`)));
+  }
+}
+
+/**
+ * Remember the ticked cache categories and show how much they free
+ */
+function updateCacheSelection(preview) {
+  state.cacheCategories = [...document.querySelectorAll('#cache-preview input[data-category]:checked')]
+    .map(input => input.dataset.category);
+  const bytes = preview.categories
+    .filter(c => state.cacheCategories.includes(c.category))
+    .reduce((sum, c) => sum + c.bytes, 0);
+  state.cacheSizeMb = bytes / (1024 * 1024);
+  document.getElementById('cache-size').textContent = state.cacheCategories.length > 0
+    ? `${formatBytes(bytes)} can be freed`
+    : 'No categories selected';
+}
+
+// ============================================
+// Protection Sequence
+// ============================================
+/**
+ * Activity log panel on the Running screen
+ * Auto-scrolls to the newest entry unless paused
+ */
+const activityLog = {
+  container: document.getElementById('activity-log'),
+  panel: document.getElementById('activity-panel'),
+  count: 0,
+  paused: false,
+
+  clear() {
+    activityLog.container.replaceChildren();
+    activityLog.count = 0;
+    document.getElementById('log-count').textContent = '0';
+  },
+
+  add(msg, type = 'info') {
+    const iconNames = { ok: 'check', warn: 'warning', error: 'x', step: 'caret-right', info: 'dot' };
+    const classes = { ok: 'success', warn: 'warning', error: 'error', step: 'step', info: '' };
+    const entry = el('div', { className: `log-entry ${classes[type]}` },
+      icon(iconNames[type]),
+      el('span', {}, msg)
+    );
+    activityLog.container.append(entry);
+    activityLog.count++;
+    document.getElementById('log-count').textContent = String(activityLog.count);
+    if (!activityLog.paused) {
+      activityLog.container.scrollTop = activityLog.container.scrollHeight;
+    }
+  },
+
+  toggleCollapsed() {
+    const collapsed = activityLog.panel.classList.toggle('collapsed');
+    document.getElementById('log-toggle').setAttribute('aria-expanded', String(!collapsed));
+  },
+
+  togglePaused() {
+    activityLog.paused = !activityLog.paused;
+    const btn = document.getElementById('log-autoscroll');
+    btn.setAttribute('aria-pressed', String(activityLog.paused));
+    btn.title = activityLog.paused ? 'Resume auto-scroll' : 'Pause auto-scroll';
+    btn.replaceChildren(icon(activityLog.paused ? 'play' : 'pause'));
+    if (!activityLog.paused) {
+      activityLog.container.scrollTop = activityLog.container.scrollHeight;
+    }
+  }
+};
+
+document.getElementById('log-toggle')?.addEventListener('click', activityLog.toggleCollapsed);
+document.getElementById('log-autoscroll')?.addEventListener('click', activityLog.togglePaused);
+
+/**
+ * Protection options chosen on the options screen
+ */
+function protectionOptions() {
+  return {
+    // Nothing ticked means nothing to back up or clean, not everything
+    backup_drafts_to: state.draftBackupEnabled && state.draftProjects?.length !== 0 ? state.draftBackupDir : null,
+    draft_projects: state.draftProjects ?? [],
+    clean_cache: state.cacheEnabled && state.cacheCategories?.length !== 0,
+    cache_categories: state.cacheCategories ?? [],
+    lock_config: state.lockEnabled,
+    create_blockers: state.blockerEnabled,
+    harden_binaries: state.hardenEnabled,
+    stub_updaters: state.stubEnabled,
+    lock_acls: state.aclEnabled,
+    block_domains: state.domainsEnabled,
+    firewall_rules: state.firewallEnabled,
+    disable_autoruns: state.autorunsEnabled
+  };
+}
+
+/**
+ * Protect each selected install in turn and merge the outcomes into one run result
+ */
+async function protectInstalls(targets) {
+  const results = await invoke('protect_installs', {
+    runs: targets.map(target => ({
+      key: target.key,
+      params: {
+        versions_to_delete: target.remove.map(v => v.path),
+        keep_version: target.keep.path,
+        ...protectionOptions()
+      }
+    }))
+  });
+  const failed = results.filter(r => !r.result.success);
+  return {
+    success: failed.length === 0,
+    error: failed.map(r => `${r.root}: ${r.result.error || 'protection failed'}`).join('; ') || null,
+    logs: results.flatMap(r => [
+      { level: 'step', message: `Installation ${r.variant} (${r.root})` },
+      ...(r.result.logs || [])
+    ]),
+    failed_deletions: results.flatMap(r => r.result.failed_deletions || []),
+    step_timings: results.flatMap(r => r.result.step_timings || [])
+  };
+}
+
+async function runProtectionSequence() {
+  const progressBar = document.getElementById('progress-bar');
+  const statusText = document.getElementById('status-text');
+  activityLog.clear();
+  const addLog = activityLog.add;
+
+  const setProgress = (msg, pct) => {
+    statusText.textContent = msg;
+    progressBar.style.width = `${pct}%`;
+  };
+
+  try {
+    setProgress('Preparing...', 10);
+    addLog('Starting version lock sequence');
+    await sleep(300);
+
+    const targets = protectionTargets();
+    const versionsToDelete = targets.flatMap(target => target.remove.map(v => v.path));
+
+    setProgress('Cleaning versions...', 30);
+    addLog(`Found ${versionsToDelete.length} version(s) to remove`);
+    await sleep(200);
+
+    setProgress('Locking version...', 50);
+
+    const result = state.allInstalls
+      ? await protectInstalls(targets)
+      : await invoke('run_full_protection', {
+        params: {
+          versions_to_delete: versionsToDelete,
+          keep_version: state.selectedVersion.path,
+          ...protectionOptions()
+        }
+      });
+
+    if (state.domainsBlocked && !state.domainsEnabled && result.success) {
+      try {
+        await invoke('block_update_domains', { enabled: false });
+        state.domainsBlocked = false;
+        addLog('Update domains unblocked', 'ok');
+      } catch (e) {
+        addLog(`Could not unblock update domains: ${e}`, 'warn');
+      }
+    }
+
+    if (result.logs) {
+      result.logs.forEach(log => addLog(log.message, log.level));
+    }
+
+    state.lastRunLogs = result.logs || [];
+    renderCompleteTimings(result.step_timings || []);
+
+    if (!result.success) {
+      throw new Error(result.error || 'Protection failed');
     }
 
     setProgress('Finalizing...', 90);
@@ -582,18 +2006,198 @@ async function runProtectionSequence() {
     addLog('Version locked successfully', 'ok');
     await sleep(400);
 
-    navigateTo('complete');
+    if (result.failed_deletions && result.failed_deletions.length > 0) {
+      state.failedDeletions = result.failed_deletions;
+      await wizardAdvance('partially_failed');
+    } else {
+      await wizardAdvance('succeeded');
+    }
 
   } catch (e) {
     console.error(e);
+    addLog(String(e), 'error');
     document.getElementById('error-message').textContent = String(e);
-    navigateTo('error');
+    await wizardAdvance('failed');
+  }
+}
+
+// ============================================
+// Delete Failures View Handlers
+// ============================================
+document.getElementById('btn-failures-continue')?.addEventListener('click', () => wizardAdvance('dismiss'));
+
+function loadDeleteFailures() {
+  const container = document.getElementById('delete-failure-list');
+  if (!container) return;
+
+  if (state.failedDeletions.length === 0) {
+    container.replaceChildren(
+      el('div', { className: 'list-row', style: { justifyContent: 'center', padding: '24px' } },
+        el('span', { className: 'row-subtitle' }, 'All leftover folders are resolved')
+      )
+    );
+    return;
   }
+
+  container.replaceChildren(...state.failedDeletions.map(failure => {
+    const subtitle = el('span', { className: 'row-subtitle' }, failure.error);
+    const retryBtn = el('button', { className: 'btn-plain' }, icon('arrow-clockwise'), ' Retry');
+    const skipBtn = el('button', { className: 'btn-plain' }, 'Skip');
+
+    const resolve = () => {
+      state.failedDeletions = state.failedDeletions.filter(f => f.path !== failure.path);
+      loadDeleteFailures();
+    };
+    retryBtn.addEventListener('click', async () => {
+      retryBtn.disabled = true;
+      try {
+        await invoke('retry_version_delete', { path: failure.path });
+        resolve();
+      } catch (e) {
+        subtitle.textContent = String(e);
+        retryBtn.disabled = false;
+      }
+    });
+    skipBtn.addEventListener('click', resolve);
+
+    return el('div', { className: 'list-row' },
+      el('div', { className: 'row-icon bg-accent-orange' }, icon('warning')),
+      el('div', { className: 'row-content' },
+        el('span', { className: 'row-title' }, `CapCut v${failure.name}`),
+        subtitle
+      ),
+      retryBtn,
+      skipBtn
+    );
+  }));
 }
 
 // ============================================
 // Complete View Handlers
 // ============================================
+/**
+ * Human-readable step duration ("2m 14s", "3.2s", "420ms")
+ */
+function formatDuration(ms) {
+  if (ms < 1000) return `${ms}ms`;
+  if (ms < 60000) return `${formatNumber(ms / 1000, 1)}s`;
+  const secs = Math.round(ms / 1000);
+  return `${Math.floor(secs / 60)}m ${secs % 60}s`;
+}
+
+function timingRows(steps) {
+  return steps.map(t =>
+    el('div', { className: 'list-row', style: 'padding: var(--space-2) var(--space-4); justify-content: space-between;' },
+      el('span', { className: 'row-subtitle' }, t.step),
+      el('span', { className: 'row-subtitle' }, formatDuration(t.millis))
+    )
+  );
+}
+
+// Collapsible before/after diff of what a run changed
+function changeList(changes) {
+  const rows = changes.map(c =>
+    el('div', { className: 'list-row', style: 'padding: var(--space-2) var(--space-4); flex-direction: column; align-items: flex-start;' },
+      el('span', { className: 'row-subtitle', style: { color: 'var(--label-primary)', wordBreak: 'break-all' } }, c.item),
+      el('span', { className: 'row-subtitle', style: { wordBreak: 'break-all' } },
+        `${c.before ?? '(none)'} → ${c.after ?? '(removed)'}`)));
+  return el('details', { style: 'padding: 0 var(--space-4) var(--space-2);' },
+    el('summary', { className: 'row-subtitle', style: 'cursor: pointer; padding: var(--space-2) 0;' },
+      `${changes.length} change${changes.length === 1 ? '' : 's'}`),
+    ...rows);
+}
+
+function renderCompleteTimings(steps) {
+  const section = document.getElementById('complete-timings-section');
+  if (!section) return;
+  section.style.display = steps.length > 0 ? 'block' : 'none';
+  document.getElementById('complete-timings').replaceChildren(...timingRows(steps));
+}
+
+document.getElementById('btn-export-protection-report')?.addEventListener('click', exportProtectionReport);
+
+// Complete and Error screens: the log file holds every run with timestamps
+document.querySelectorAll('.btn-open-logs').forEach(btn => btn.addEventListener('click', async () => {
+  try {
+    await invoke('open_log_folder');
+  } catch (e) {
+    await modal.show({
+      title: 'Could Not Open Logs',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'folder-notch-open'
+    });
+  }
+}));
+
+async function exportProtectionReport() {
+  const { save } = window.__TAURI__.dialog;
+  const path = await save({
+    defaultPath: 'capcut-protection-report.html',
+    filters: [{ name: 'HTML', extensions: ['html'] }]
+  });
+  if (!path) return;
+
+  try {
+    await invoke('export_protection_report', { path, logs: state.lastRunLogs });
+    await modal.show({
+      title: 'Report Exported',
+      message: `The protection report was saved to ${path}.`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: false,
+      iconName: 'check-circle'
+    });
+  } catch (e) {
+    await modal.show({
+      title: 'Export Failed',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  }
+}
+
+async function loadCompleteOptions() {
+  try {
+    const settings = await invoke('get_app_settings');
+    if (launchOnExitToggle) launchOnExitToggle.checked = settings.launch_capcut_on_exit;
+  } catch (e) {
+    console.warn('Could not load launch-on-exit preference:', e);
+  }
+}
+
+// Remembered across runs; saving is best-effort (settings may be locked by policy)
+const launchOnExitToggle = document.getElementById('complete-launch-capcut');
+launchOnExitToggle?.addEventListener('change', async (e) => {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.launch_capcut_on_exit = e.target.checked;
+    await invoke('save_app_settings', { settings });
+  } catch (err) {
+    console.warn('Could not save launch-on-exit preference:', err);
+  }
+});
+
+document.getElementById('btn-close-app')?.addEventListener('click', async () => {
+  try {
+    await invoke('exit_app', { launchCapcutFirst: !!launchOnExitToggle?.checked });
+  } catch (e) {
+    await modal.show({
+      title: 'Could Not Launch CapCut',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  }
+});
+
 document.getElementById('btn-done')?.addEventListener('click', () => {
   state.history = ['welcome'];
   showView('welcome');
@@ -603,16 +2207,19 @@ document.getElementById('btn-done')?.addEventListener('click', () => {
       const status = await invoke('check_protection_status');
       updateStatusCard(status.is_protected);
     } catch (e) { }
+    refreshEventsBadge();
   })();
 });
 
 // ============================================
 // Error View Handlers
 // ============================================
-document.getElementById('btn-retry')?.addEventListener('click', () => {
+document.getElementById('btn-retry')?.addEventListener('click', async () => {
   // Start fresh from precheck (where the protection flow begins)
+  const step = await wizardNext('retry');
+  if (!step) return;
   state.history = ['welcome'];
-  navigateTo('precheck');
+  navigateTo(step);
 });
 document.getElementById('btn-back-error')?.addEventListener('click', () => {
   // Go back to options so user can try again with different settings
@@ -628,6 +2235,7 @@ document.getElementById('legacy-back')?.addEventListener('click', goBack);
 async function loadArchiveVersions() {
   const container = document.getElementById('legacy-list');
   container.replaceChildren(createSkeletonRows(4));
+  refreshLinkReportButton();
 
   try {
     const archives = await invoke('get_archive_versions');
@@ -643,9 +2251,7 @@ async function loadArchiveVersions() {
       },
         icon('download-simple', { style: { fontSize: '18px' } })
       );
-      downloadBtn.addEventListener('click', () => {
-        window.__TAURI__.opener.openUrl(v.download_url);
-      });
+      downloadBtn.addEventListener('click', () => downloadArchive(v, downloadBtn));
 
       fragment.append(
         el('div', { className: 'list-row' },
@@ -656,6 +2262,8 @@ async function loadArchiveVersions() {
             el('span', { className: 'row-title' }, `v${v.version} · ${v.persona}`),
             el('span', { className: 'row-subtitle' }, v.description)
           ),
+          favoriteButton(v, loadArchiveVersions),
+          reportLinkButton(v),
           downloadBtn
         )
       );
@@ -671,6 +2279,285 @@ async function loadArchiveVersions() {
   }
 }
 
+// Download buttons showing progress, by URL
+const downloadButtons = new Map();
+
+listen('archive-download-progress', (event) => {
+  const { url, downloaded, total } = event.payload;
+  const btn = downloadButtons.get(url);
+  if (!btn) return;
+  const label = total ? `${Math.floor(downloaded * 100 / total)}%` : formatBytes(downloaded);
+  btn.replaceChildren(icon('circle-notch', { className: 'ph ph-circle-notch spin' }), ` ${label}`);
+});
+
+/**
+ * Download an archive installer in-app (resumes a partial download), falling back to the browser
+ */
+async function downloadArchive(v, btn) {
+  const original = Array.from(btn.childNodes).map(n => n.cloneNode(true));
+  btn.disabled = true;
+  btn.replaceChildren(icon('circle-notch', { className: 'ph ph-circle-notch spin' }));
+  downloadButtons.set(v.download_url, btn);
+
+  try {
+    const installer = await invoke('download_archive_version', { url: v.download_url });
+    const checks = [
+      installer.hash_verified ? 'SHA-256 matches the published hash' : 'no published hash to compare',
+      installer.signature === 'valid' ? 'Bytedance signature valid' : 'signature could not be confirmed'
+    ];
+    const install = await modal.show({
+      title: 'Download Complete',
+      message: `${v.persona || `v${v.version}`} was saved to ${installer.path} (${formatBytes(installer.size)}; ${checks.join(', ')}). Install it now and lock it?`,
+      confirmText: 'Install & Protect',
+      cancelText: 'Later',
+      danger: false,
+      iconName: installer.signature === 'valid' ? 'check-circle' : 'warning-circle'
+    });
+    if (install) installArchive(v, installer);
+  } catch (e) {
+    const browser = await modal.show({
+      title: 'Download Failed',
+      message: `${e} A partial download is continued next time.`,
+      confirmText: 'Open in Browser',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+    if (browser) window.__TAURI__.opener.openUrl(v.download_url);
+  } finally {
+    downloadButtons.delete(v.download_url);
+    btn.replaceChildren(...original);
+    btn.disabled = false;
+  }
+}
+
+// Pipeline steps of an archive install, in order, as emitted by the backend
+const ARCHIVE_INSTALL_STAGES = [
+  ['unprotecting', 'Remove current protection'],
+  ['installing', 'Run installer'],
+  ['closing_capcut', 'Close CapCut'],
+  ['scanning', 'Find installed version'],
+  ['protecting', 'Lock installed version']
+];
+
+// Stages reached by the running install (optional ones are skipped when not needed)
+let archiveInstallSeen = [];
+
+listen('archive-install-progress', (event) => {
+  archiveInstallSeen.push(event.payload);
+  renderArchiveInstallStages(null);
+});
+
+/**
+ * Stage rows of the archive install screen; `outcome` is null while running, else 'done' or 'failed'
+ */
+function renderArchiveInstallStages(outcome) {
+  const list = document.getElementById('archive-install-stages');
+  if (!list) return;
+  const currentId = archiveInstallSeen[archiveInstallSeen.length - 1];
+  const current = ARCHIVE_INSTALL_STAGES.findIndex(([id]) => id === currentId);
+  list.replaceChildren(...ARCHIVE_INSTALL_STAGES.map(([id, label], i) => {
+    const reached = archiveInstallSeen.includes(id);
+    let status = 'Waiting';
+    let iconName = 'circle';
+    let color = 'var(--label-tertiary)';
+    if (i === current && outcome === null) {
+      status = 'In progress...';
+      iconName = 'circle-notch';
+    } else if (i === current && outcome === 'failed') {
+      status = 'Failed';
+      iconName = 'x-circle';
+      color = 'var(--accent-red)';
+    } else if (reached) {
+      status = 'Done';
+      iconName = 'check-circle';
+      color = 'var(--accent-green)';
+    } else if (i < current) {
+      status = 'Skipped';
+    }
+    const style = { color, fontSize: '18px' };
+    return el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4); gap: var(--space-3);' },
+      iconName === 'circle-notch'
+        ? icon(iconName, { className: 'ph ph-circle-notch spin', style })
+        : icon(iconName, { style }),
+      el('div', { className: 'row-content' },
+        el('span', { className: 'row-title' }, label),
+        el('span', { className: 'row-subtitle' }, status)));
+  }));
+}
+
+/**
+ * Install a downloaded archive version silently, then keep it and lock it ("downgrade and lock")
+ */
+async function installArchive(v, installer) {
+  const confirmed = await modal.show({
+    title: 'Install & Protect?',
+    message: `CapCut ${v.persona || `v${v.version}`} is installed without prompts. Current protection is removed first; afterwards every other version is deleted and the new one is locked with your protection options.`,
+    confirmText: 'Install',
+    cancelText: 'Cancel',
+    danger: true,
+    iconName: 'download-simple'
+  });
+  if (!confirmed) return;
+
+  navigateTo('archive-install');
+  const header = document.getElementById('archive-install-header');
+  const summary = document.getElementById('archive-install-summary');
+  const back = document.getElementById('archive-install-back');
+  header.textContent = 'INSTALLING';
+  summary.textContent = '';
+  summary.style.color = '';
+  back.disabled = true;
+  archiveInstallSeen = [];
+  renderArchiveInstallStages(null);
+
+  try {
+    const result = await invoke('install_archive_version', {
+      path: installer.path,
+      options: protectionOptions()
+    });
+    const protection = result.protection;
+    state.lastRunLogs = [...result.logs, ...(protection.logs || [])];
+    renderArchiveInstallStages(protection.success ? 'done' : 'failed');
+    if (!protection.success) {
+      header.textContent = 'NOT PROTECTED';
+      summary.textContent = `Version ${result.installed_version} was installed but could not be locked: ${protection.error || 'protection failed'}`;
+      summary.style.color = 'var(--accent-red)';
+    } else {
+      header.textContent = 'PROTECTED';
+      const leftovers = protection.failed_deletions || [];
+      summary.textContent = leftovers.length > 0
+        ? `Version ${result.installed_version} is installed and locked. ${leftovers.length} old version folder(s) could not be deleted: ${leftovers.map(f => f.name).join(', ')}.`
+        : `Version ${result.installed_version} is installed and locked; other versions were removed.`;
+    }
+    updateStatusCard(protection.success);
+  } catch (e) {
+    renderArchiveInstallStages('failed');
+    header.textContent = 'FAILED';
+    summary.textContent = String(e);
+    summary.style.color = 'var(--accent-red)';
+  } finally {
+    back.disabled = false;
+  }
+}
+
+/**
+ * Star toggle for an archive entry; starred entries are listed first
+ */
+function favoriteButton(v, onChange) {
+  const btn = el('button', {
+    className: 'btn-plain favorite-button' + (v.favorite ? ' starred' : ''),
+    style: { padding: '8px' },
+    title: v.favorite ? 'Remove from favorites' : 'Add to favorites',
+    'aria-label': v.favorite ? 'Remove from favorites' : 'Add to favorites',
+    'aria-pressed': String(!!v.favorite)
+  },
+    icon('star', { style: { fontSize: '18px' } })
+  );
+  btn.addEventListener('click', async () => {
+    try {
+      await invoke('toggle_favorite_archive', { label: v.persona, url: v.download_url });
+      await onChange();
+    } catch (e) {
+      console.error('Failed to update favorite:', e);
+    }
+  });
+  return btn;
+}
+
+/**
+ * "Report broken" action: re-checks the link and records the result locally (no telemetry)
+ */
+function reportLinkButton(v) {
+  const btn = el('button', {
+    className: 'btn-plain',
+    style: { padding: '8px', color: 'var(--label-tertiary)' },
+    title: 'Report broken link',
+    'aria-label': 'Report broken link'
+  },
+    icon('flag', { style: { fontSize: '18px' } })
+  );
+  btn.addEventListener('click', async () => {
+    const confirmed = await modal.show({
+      title: 'Report Broken Link?',
+      message: `Version Guard will re-check the download for ${v.persona} and note the result on this computer only. Nothing is sent anywhere.`,
+      confirmText: 'Report',
+      cancelText: 'Cancel',
+      danger: false,
+      iconName: 'flag'
+    });
+    if (!confirmed) return;
+
+    btn.disabled = true;
+    try {
+      const report = await invoke('report_broken_link', { label: v.persona, url: v.download_url });
+      const result = report.http_status ? `HTTP ${report.http_status}` : report.error;
+      await modal.show({
+        title: 'Link Reported',
+        message: `Recorded ${v.persona} (${result}). Export the report from the Legacy screen to include it in a GitHub issue.`,
+        confirmText: 'OK',
+        cancelText: 'Close',
+        danger: false,
+        iconName: 'check-circle'
+      });
+      refreshLinkReportButton();
+    } catch (e) {
+      console.error('Failed to report link:', e);
+    } finally {
+      btn.disabled = false;
+    }
+  });
+  return btn;
+}
+
+document.getElementById('btn-export-link-report')?.addEventListener('click', exportLinkReport);
+
+async function refreshLinkReportButton() {
+  const btn = document.getElementById('btn-export-link-report');
+  if (!btn) return;
+  try {
+    const reports = await invoke('list_link_reports');
+    btn.style.display = reports.length > 0 ? '' : 'none';
+    btn.replaceChildren(icon('flag'), ` Export Broken Link Report (${reports.length})`);
+  } catch (e) {
+    console.warn('Could not load link reports:', e);
+  }
+}
+
+async function exportLinkReport() {
+  const { save } = window.__TAURI__.dialog;
+  const path = await save({
+    defaultPath: 'broken-links.md',
+    filters: [{ name: 'Markdown', extensions: ['md'] }]
+  });
+  if (!path) return;
+
+  try {
+    await invoke('export_link_reports', { path });
+    const clear = await modal.show({
+      title: 'Report Exported',
+      message: `Saved to ${path}. Paste it into a GitHub issue so the list can be fixed. Clear the local reports now?`,
+      confirmText: 'Clear Reports',
+      cancelText: 'Keep',
+      danger: false,
+      iconName: 'check-circle'
+    });
+    if (clear) {
+      await invoke('clear_link_reports');
+      refreshLinkReportButton();
+    }
+  } catch (e) {
+    await modal.show({
+      title: 'Export Failed',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  }
+}
+
 // ============================================
 // All Versions Modal
 // ============================================
@@ -740,9 +2627,7 @@ function renderAllVersionsList(versions) {
     },
       icon('download-simple', { style: { fontSize: '18px' } })
     );
-    downloadBtn.addEventListener('click', () => {
-      window.__TAURI__.opener.openUrl(v.download_url);
-    });
+    downloadBtn.addEventListener('click', () => downloadArchive(v, downloadBtn));
 
     // Use persona field (contains full label like "5.4.0 (Beta3)")
     // If no persona, fall back to version
@@ -757,6 +2642,11 @@ function renderAllVersionsList(versions) {
           el('span', { className: 'row-title' }, versionLabel),
           el('span', { className: 'row-subtitle' }, v.description || 'Legacy Version')
         ),
+        favoriteButton(v, async () => {
+          await loadAllVersions();
+          handleVersionFilter(document.getElementById('version-search').value);
+        }),
+        reportLinkButton(v),
         downloadBtn
       )
     );
@@ -795,6 +2685,9 @@ function handleVersionFilter(searchQuery = '') {
 document.getElementById('btn-switch')?.addEventListener('click', () => navigateTo('switch'));
 document.getElementById('switch-back')?.addEventListener('click', goBack);
 document.getElementById('btn-switch-apply')?.addEventListener('click', applySwitch);
+document.getElementById('btn-switch-compress')?.addEventListener('click', compressSwitchTarget);
+document.getElementById('btn-switch-slim')?.addEventListener('click', () => navigateTo('slim'));
+document.getElementById('btn-switch-golden')?.addEventListener('click', exportGoldenCopy);
 
 async function loadSwitchVersions() {
   const container = document.getElementById('switch-list');
@@ -804,6 +2697,9 @@ async function loadSwitchVersions() {
     const vers = await invoke('scan_versions');
     state.versions = vers;
     state.switchTarget = null;
+    document.getElementById('btn-switch-compress').disabled = true;
+    document.getElementById('btn-switch-slim').disabled = true;
+    document.getElementById('btn-switch-golden').disabled = true;
 
     if (vers.length === 0) {
       // Empty state with actionable guidance
@@ -841,8 +2737,9 @@ async function loadSwitchVersions() {
           icon('hard-drives')
         ),
         el('div', { className: 'row-content' },
-          el('span', { className: 'row-title' }, `CapCut v${v.name}`),
-          el('span', { className: 'row-subtitle' }, `${v.size_mb.toFixed(0)} MB`)
+          el('span', { className: 'row-title' }, `CapCut v${v.name}${v.active ? ' (currently active)' : ''}`),
+          el('span', { className: 'row-subtitle' }, formatMegabytes(v.size_mb)),
+          el('div', { className: 'version-note' }, ...versionNoteContent(v))
         ),
         icon('check', {
           className: 'ph ph-check row-accessory',
@@ -868,6 +2765,9 @@ async function loadSwitchVersions() {
 window.selectSwitchVersion = function (idx) {
   state.switchTarget = state.versions[idx];
   document.getElementById('btn-switch-apply').disabled = false;
+  document.getElementById('btn-switch-compress').disabled = false;
+  document.getElementById('btn-switch-slim').disabled = false;
+  document.getElementById('btn-switch-golden').disabled = false;
 
   document.querySelectorAll('#switch-list .list-row').forEach((el, i) => {
     const check = el.querySelector('.row-accessory');
@@ -927,16 +2827,203 @@ async function applySwitch() {
   }
 }
 
-// ============================================
-// Utilities
-// ============================================
-function sleep(ms) { return new Promise(r => setTimeout(r, ms)); }
+/**
+ * NTFS-compress the selected (kept) version folder and report space saved
+ */
+async function compressSwitchTarget() {
+  if (!state.switchTarget) return;
 
-// P3: Keyboard Helper
-window.handleKey = function (e, action) {
-  if (e.key === 'Enter' || e.key === ' ') {
-    e.preventDefault();
-    action();
+  const confirmed = await modal.show({
+    title: 'Compress Version?',
+    message: `Apply NTFS compression to CapCut v${state.switchTarget.name}. It stays usable but may start slightly slower.`,
+    confirmText: 'Compress',
+    cancelText: 'Cancel',
+    danger: false,
+    iconName: 'file-zip'
+  });
+  if (!confirmed) return;
+
+  const btn = document.getElementById('btn-switch-compress');
+  btn.disabled = true;
+  btn.replaceChildren(icon('circle-notch', { className: 'ph ph-circle-notch spin' }), ' Compressing...');
+
+  try {
+    const result = await invoke('compress_version', { path: state.switchTarget.path });
+    if (!result.success) throw new Error(result.error);
+
+    await modal.show({
+      title: 'Version Compressed',
+      message: `${formatMegabytes(result.before_mb)} → ${formatMegabytes(result.after_mb)} on disk (${formatMegabytes(result.before_mb - result.after_mb)} saved).`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: false,
+      iconName: 'check-circle'
+    });
+  } catch (e) {
+    await modal.show({
+      title: 'Compression Failed',
+      message: String(e.message || e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  } finally {
+    btn.replaceChildren(icon('file-zip'), ' Compress Selected Version');
+    btn.disabled = !state.switchTarget;
+  }
+}
+
+/**
+ * Save the selected version as a checksummed archive for other workstations
+ */
+async function exportGoldenCopy() {
+  if (!state.switchTarget) return;
+  const { save } = window.__TAURI__.dialog;
+  const dest = await save({
+    defaultPath: `capcut-${state.switchTarget.name}-golden.zip`,
+    filters: [{ name: 'Golden Copy', extensions: ['zip'] }]
+  });
+  if (!dest) return;
+
+  const btn = document.getElementById('btn-switch-golden');
+  btn.disabled = true;
+  btn.replaceChildren(el('i', { className: 'ph ph-circle-notch spin' }), ' Exporting...');
+
+  try {
+    const info = await invoke('export_golden_copy', { path: state.switchTarget.path, dest });
+    await modal.show({
+      title: 'Golden Copy Exported',
+      message: `CapCut v${info.version} (${formatNumber(info.files)} files, ${formatBytes(info.total_bytes)}) was saved to ${dest}.`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: false,
+      iconName: 'check-circle'
+    });
+  } catch (e) {
+    await modal.show({
+      title: 'Export Failed',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  } finally {
+    btn.replaceChildren(icon('seal-check'), ' Export Golden Copy');
+    btn.disabled = !state.switchTarget;
+  }
+}
+
+// ============================================
+// Slim Version View Handlers
+// ============================================
+document.getElementById('slim-back')?.addEventListener('click', goBack);
+document.getElementById('btn-slim-apply')?.addEventListener('click', applySlim);
+
+/**
+ * List optional components of the selected version with a size preview each
+ */
+async function loadSlim() {
+  const container = document.getElementById('slim-components');
+  const applyBtn = document.getElementById('btn-slim-apply');
+  applyBtn.disabled = true;
+  if (!state.switchTarget) return;
+
+  document.getElementById('slim-subtitle').textContent =
+    `Choose optional components to remove from CapCut v${state.switchTarget.name}.`;
+  container.replaceChildren(createSkeletonRows(3));
+
+  try {
+    const components = await invoke('scan_version_components', { path: state.switchTarget.path });
+    if (components.length === 0) {
+      container.replaceChildren(
+        el('div', { className: 'list-row', style: { justifyContent: 'center', padding: '24px' } },
+          el('span', { className: 'row-subtitle' }, 'No optional components found — this version is already slim')
+        )
+      );
+      return;
+    }
+
+    container.replaceChildren(...components.map(c => {
+      const checkbox = el('input', { type: 'checkbox', 'data-component': c.id });
+      checkbox.addEventListener('change', updateSlimTotal);
+      return el('div', { className: 'list-row' },
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, c.label),
+          el('span', { className: 'row-subtitle' }, `${formatBytes(c.size_bytes)} · ${c.description}`)
+        ),
+        el('label', { className: 'switch' }, checkbox, el('span', { className: 'slider round' }))
+      );
+    }));
+    state.slimComponents = components;
+    updateSlimTotal();
+  } catch (e) {
+    container.replaceChildren(
+      el('div', { className: 'list-row' },
+        el('span', { className: 'row-title', style: { color: 'var(--accent-red)' } }, `Error: ${e}`)
+      )
+    );
+  }
+}
+
+function selectedSlimComponents() {
+  return [...document.querySelectorAll('#slim-components input[data-component]:checked')]
+    .map(input => input.dataset.component);
+}
+
+function updateSlimTotal() {
+  const selected = selectedSlimComponents();
+  const total = state.slimComponents
+    .filter(c => selected.includes(c.id))
+    .reduce((sum, c) => sum + c.size_bytes, 0);
+  const btn = document.getElementById('btn-slim-apply');
+  btn.disabled = selected.length === 0;
+  btn.replaceChildren(icon('broom'), selected.length === 0 ? ' Remove Selected' : ` Remove Selected (${formatBytes(total)})`);
+}
+
+async function applySlim() {
+  const components = selectedSlimComponents();
+  if (!state.switchTarget || components.length === 0) return;
+
+  const confirmed = await modal.show({
+    title: 'Remove Components?',
+    message: `The selected components will be permanently removed from CapCut v${state.switchTarget.name}. Reinstall the version to get them back.`,
+    confirmText: 'Remove',
+    cancelText: 'Cancel',
+    danger: true,
+    iconName: 'broom'
+  });
+  if (!confirmed) return;
+
+  const btn = document.getElementById('btn-slim-apply');
+  btn.disabled = true;
+  btn.replaceChildren(icon('circle-notch', { className: 'ph ph-circle-notch spin' }), ' Removing...');
+
+  const result = await invoke('remove_version_components', { path: state.switchTarget.path, components });
+  await modal.show({
+    title: result.success ? 'Version Slimmed' : 'Some Items Remain',
+    message: result.success
+      ? `${formatBytes(result.freed_bytes)} freed.`
+      : `${formatBytes(result.freed_bytes)} freed. ${result.error}`,
+    confirmText: 'OK',
+    cancelText: 'Close',
+    danger: !result.success,
+    iconName: result.success ? 'check-circle' : 'warning'
+  });
+  loadSlim();
+}
+
+// ============================================
+// Utilities
+// ============================================
+function sleep(ms) { return new Promise(r => setTimeout(r, ms)); }
+
+// P3: Keyboard Helper
+window.handleKey = function (e, action) {
+  if (e.key === 'Enter' || e.key === ' ') {
+    e.preventDefault();
+    action();
   }
 }
 
@@ -985,12 +3072,14 @@ function createSkeletonRows(count = 3) {
 document.getElementById('btn-backups')?.addEventListener('click', () => navigateTo('backups'));
 document.getElementById('backups-back')?.addEventListener('click', goBack);
 document.getElementById('btn-clear-backups')?.addEventListener('click', clearAllBackups);
+document.getElementById('btn-search-shadows')?.addEventListener('click', searchShadowCopies);
 
 async function loadBackups() {
   const container = document.getElementById('backup-list');
   const sizeInfo = document.getElementById('backup-size-info');
 
   container.replaceChildren(createSkeletonFragment(2));
+  loadConfigBackups();
 
   try {
     const [backups, size] = await Promise.all([
@@ -999,8 +3088,7 @@ async function loadBackups() {
     ]);
 
     // Update size info
-    const sizeMB = (size / (1024 * 1024)).toFixed(1);
-    sizeInfo.textContent = `Total: ${sizeMB} MB`;
+    sizeInfo.textContent = `Total: ${formatBytes(size)}`;
 
     if (backups.length === 0) {
       container.replaceChildren(
@@ -1015,7 +3103,6 @@ async function loadBackups() {
     backups.forEach((backup, idx) => {
       const date = new Date(backup.created_at * 1000);
       const dateStr = date.toLocaleDateString() + ' ' + date.toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
-      const sizeMB = (backup.size_bytes / (1024 * 1024)).toFixed(1);
       const backupId = `${backup.version_name}_${backup.created_at}`;
 
       const row = el('div', { className: 'list-row', style: { gap: 'var(--space-3)' } },
@@ -1024,7 +3111,7 @@ async function loadBackups() {
         ),
         el('div', { className: 'row-content' },
           el('span', { className: 'row-title' }, backup.version_name),
-          el('span', { className: 'row-subtitle' }, `${dateStr} • ${sizeMB} MB`)
+          el('span', { className: 'row-subtitle' }, `${dateStr} • ${formatBytes(backup.size_bytes)}`)
         ),
         el('div', { style: { display: 'flex', gap: 'var(--space-2)' } },
           el('button', {
@@ -1095,6 +3182,147 @@ async function restoreBackup(backupId, versionName) {
   }
 }
 
+// configure.ini and ProductInfo.xml as they were before each protection run
+async function loadConfigBackups() {
+  const container = document.getElementById('config-backup-list');
+  container.replaceChildren(createSkeletonFragment(1));
+  try {
+    const backups = await invoke('list_config_backups');
+    if (backups.length === 0) {
+      container.replaceChildren(
+        el('div', { className: 'list-row', style: { justifyContent: 'center', color: 'var(--label-tertiary)' } },
+          el('span', {}, 'Taken automatically before each protection run')));
+      return;
+    }
+    container.replaceChildren(...backups.map(backup => {
+      const date = new Date(backup.created_at * 1000);
+      const dateStr = date.toLocaleDateString() + ' ' + date.toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+      return el('div', { className: 'list-row', style: { gap: 'var(--space-3)' } },
+        el('div', { className: 'row-icon', style: { background: 'var(--fill-secondary)' } }, icon('file-text')),
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, dateStr),
+          el('span', { className: 'row-subtitle' }, backup.files.join(', '))),
+        el('button', {
+          className: 'btn-secondary',
+          style: { padding: '6px 10px', height: 'auto', minWidth: 'auto' },
+          title: 'Restore these files',
+          onclick: () => restoreConfigBackup(backup.id, dateStr)
+        }, icon('arrow-counter-clockwise')));
+    }));
+  } catch (e) {
+    container.replaceChildren(
+      el('div', { className: 'list-row', style: { color: 'var(--accent-red)' } },
+        el('span', {}, `Error: ${e}`)));
+  }
+}
+
+async function restoreConfigBackup(backupId, dateStr) {
+  const confirmed = await modal.show({
+    title: 'Restore Configuration?',
+    message: `configure.ini and ProductInfo.xml are put back as they were on ${dateStr}. The configuration lock is released, so CapCut may update until you protect again.`,
+    confirmText: 'Restore',
+    cancelText: 'Cancel',
+    danger: true,
+    iconName: 'arrow-counter-clockwise'
+  });
+  if (!confirmed) return;
+
+  try {
+    const result = await invoke('restore_config_backup', { backupId });
+    if (!result.success) throw new Error(result.error);
+    const status = await invoke('check_protection_status');
+    updateStatusCard(status.is_protected);
+    await modal.show({
+      title: 'Restored!',
+      message: 'The configuration files have been restored.',
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: false,
+      iconName: 'check-circle'
+    });
+  } catch (e) {
+    await modal.show({
+      title: 'Restore Failed',
+      message: e.toString(),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  }
+}
+
+// Last resort: version folders still present in Volume Shadow Copies
+async function searchShadowCopies() {
+  const list = document.getElementById('shadow-list');
+  const searchRow = list.firstElementChild;
+  const btn = document.getElementById('btn-search-shadows');
+  const message = (text, color) => el('div', { className: 'list-row', style: { justifyContent: 'center', color: color || 'var(--label-tertiary)' } },
+    el('span', {}, text));
+
+  btn.disabled = true;
+  list.replaceChildren(searchRow, createSkeletonFragment(1));
+  try {
+    const versions = await invoke('find_shadow_versions');
+    if (versions.length === 0) {
+      list.replaceChildren(searchRow, message('No deleted versions found in shadow copies'));
+      return;
+    }
+    list.replaceChildren(searchRow, ...versions.map(found => {
+      const taken = found.created ? `Shadow copy of ${new Date(found.created).toLocaleString()}` : 'Shadow copy';
+      return el('div', { className: 'list-row', style: { gap: 'var(--space-3)' } },
+        el('div', { className: 'row-icon', style: { background: 'var(--fill-secondary)' } }, icon('lifebuoy')),
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, found.version),
+          el('span', { className: 'row-subtitle' }, taken)),
+        el('button', {
+          className: 'btn-secondary',
+          style: { padding: '6px 10px', height: 'auto', minWidth: 'auto' },
+          title: 'Restore into Apps',
+          onclick: () => restoreShadowVersion(found)
+        }, icon('arrow-counter-clockwise')));
+    }));
+  } catch (e) {
+    list.replaceChildren(searchRow, message(String(e), 'var(--accent-red)'));
+  } finally {
+    btn.disabled = false;
+  }
+}
+
+async function restoreShadowVersion(found) {
+  const confirmed = await modal.show({
+    title: 'Restore From Shadow Copy?',
+    message: `${found.version} will be copied back into the Apps folder. Files changed after the shadow copy was taken are not included.`,
+    confirmText: 'Restore',
+    cancelText: 'Cancel',
+    danger: false,
+    iconName: 'lifebuoy'
+  });
+  if (!confirmed) return;
+
+  try {
+    const version = await invoke('restore_shadow_version', { shadowPath: found.shadow_path });
+    await modal.show({
+      title: 'Restored!',
+      message: `${version} is installed again.`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: false,
+      iconName: 'check-circle'
+    });
+    searchShadowCopies();
+  } catch (e) {
+    await modal.show({
+      title: 'Restore Failed',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  }
+}
+
 async function deleteBackup(backupId, versionName) {
   const confirmed = await modal.show({
     title: 'Delete Backup?',
@@ -1161,6 +3389,25 @@ window.showView = function (view) {
 document.getElementById('btn-settings')?.addEventListener('click', () => navigateTo('settings'));
 document.getElementById('settings-back')?.addEventListener('click', goBack);
 
+const minimizeToTrayToggle = document.getElementById('setting-minimize-to-tray');
+minimizeToTrayToggle?.addEventListener('change', async (e) => {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.minimize_to_tray = e.target.checked;
+    await invoke('save_app_settings', { settings });
+  } catch (err) {
+    e.target.checked = !e.target.checked;
+    await modal.show({
+      title: 'Error',
+      message: `Failed to save setting: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+});
+
 const autostartToggle = document.getElementById('setting-autostart');
 autostartToggle?.addEventListener('change', async (e) => {
   try {
@@ -1179,21 +3426,1864 @@ autostartToggle?.addEventListener('change', async (e) => {
   }
 });
 
-async function loadSettings() {
-  // Load app version
+const watchdogToggle = document.getElementById('setting-watchdog');
+watchdogToggle?.addEventListener('change', async (e) => {
   try {
-    const version = await getVersion();
-    const settingsVersionEl = document.getElementById('settings-version');
-    if (settingsVersionEl) settingsVersionEl.textContent = `v${version}`;
-  } catch (e) {
-    console.warn('Could not load version for settings:', e);
+    const status = await invoke(e.target.checked ? 'start_watchdog' : 'stop_watchdog');
+    updateWatchdogSubtitle(status);
+  } catch (err) {
+    e.target.checked = !e.target.checked;
+    await modal.show({
+      title: 'Error',
+      message: `Failed to update monitoring: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
   }
+});
 
-  // Load auto-start state
+const downloadPurgeToggle = document.getElementById('setting-download-purge');
+downloadPurgeToggle?.addEventListener('change', async (e) => {
   try {
-    const isEnabled = await invoke('get_autostart_enabled');
-    if (autostartToggle) autostartToggle.checked = isEnabled;
-  } catch (e) {
-    console.warn('Could not load autostart status:', e);
+    const status = await invoke('set_download_purge', { enabled: e.target.checked });
+    updateDownloadPurgeSubtitle(status);
+  } catch (err) {
+    e.target.checked = !e.target.checked;
+    await modal.show({
+      title: 'Error',
+      message: `Failed to update download purge: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+});
+
+const installerCheckToggle = document.getElementById('setting-installer-check');
+installerCheckToggle?.addEventListener('change', async (e) => {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.check_downloaded_installers = e.target.checked;
+    await invoke('save_app_settings', { settings });
+  } catch (err) {
+    e.target.checked = !e.target.checked;
+    await modal.show({
+      title: 'Error',
+      message: `Failed to save installer check setting: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+});
+
+// Never-touch list (paths skipped by every destructive operation)
+document.getElementById('btn-add-exclusion')?.addEventListener('click', addExclusion);
+document.getElementById('btn-add-exclusion')?.addEventListener('keydown', (e) => handleKey(e, addExclusion));
+
+function renderExclusions(paths) {
+  const list = document.getElementById('exclusion-list');
+  if (!list) return;
+  list.replaceChildren(...paths.map(path => {
+    const removeBtn = el('button', { className: 'btn-plain' }, 'Remove');
+    removeBtn.disabled = !!state.policy.lock_settings;
+    removeBtn.addEventListener('click', () => updateExclusions(current => current.filter(p => p !== path)));
+    return el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4); justify-content: space-between;' },
+      el('div', { className: 'row-content' },
+        el('span', { className: 'row-title', style: { wordBreak: 'break-all' } }, path)),
+      removeBtn);
+  }));
+}
+
+async function addExclusion() {
+  const { open } = window.__TAURI__.dialog;
+  const path = await open({ directory: true, multiple: false });
+  if (!path) return;
+  await updateExclusions(current => current.includes(path) ? current : [...current, path]);
+}
+
+/**
+ * Apply `change` to the saved never-touch list and re-render it
+ */
+async function updateExclusions(change) {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.excluded_paths = change(settings.excluded_paths);
+    await invoke('save_app_settings', { settings });
+    renderExclusions(settings.excluded_paths);
+  } catch (err) {
+    await modal.show({
+      title: 'Error',
+      message: `Failed to save the never-touch list: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+}
+
+function renderExtraBlockers(paths) {
+  const list = document.getElementById('blocker-list');
+  if (!list) return;
+  list.replaceChildren(...paths.map(path => {
+    const removeBtn = el('button', { className: 'btn-plain' }, 'Remove');
+    removeBtn.disabled = !!state.policy.lock_settings;
+    removeBtn.addEventListener('click', () => removeExtraBlocker(path));
+    return el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4); justify-content: space-between;' },
+      el('div', { className: 'row-content' },
+        el('span', { className: 'row-title', style: { wordBreak: 'break-all' } }, path)),
+      removeBtn);
+  }));
+}
+
+/**
+ * List detected CapCut installs with their protection state; a click makes one active
+ */
+async function renderInstalls() {
+  const list = document.getElementById('install-list');
+  if (!list) return;
+  list.replaceChildren(createSkeletonRows(2));
+  try {
+    const installs = await invoke('list_installs');
+    if (installs.length === 0) {
+      list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+        el('span', { className: 'row-subtitle' }, 'No CapCut installation found')));
+      return;
+    }
+    list.replaceChildren(...installs.map(install => {
+      const protection = install.protected
+        ? `Protected · v${install.kept_version}`
+        : install.kept_version ? `Protection broken · v${install.kept_version}` : 'Not protected';
+      const row = el('div', {
+        className: install.active ? 'list-row' : 'list-row selectable',
+        style: 'padding: var(--space-3) var(--space-4); justify-content: space-between; gap: var(--space-3);'
+      },
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, install.variant || 'CapCut'),
+          el('span', { className: 'row-subtitle', style: { wordBreak: 'break-all' } }, install.root),
+          el('span', {
+            className: 'row-subtitle',
+            style: { color: install.protected ? 'var(--accent-green)' : install.kept_version ? 'var(--accent-red)' : '' }
+          }, `${protection} · ${install.versions.length} version(s)`)),
+        install.active
+          ? el('span', { className: 'row-subtitle', style: { color: 'var(--accent-blue)' } }, icon('check-circle'), ' Active')
+          : el('span', { className: 'row-subtitle' }, 'Switch'));
+      if (!install.active) {
+        row.tabIndex = 0;
+        row.addEventListener('click', () => switchInstall(install));
+        row.addEventListener('keydown', (e) => handleKey(e, () => switchInstall(install)));
+      }
+      return row;
+    }));
+  } catch (e) {
+    list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+      el('span', { className: 'row-subtitle', style: { color: 'var(--accent-red)' } }, `Could not list installations: ${e}`)));
+  }
+}
+
+async function switchInstall(install) {
+  try {
+    await invoke('set_active_install', { key: install.key });
+    reloadForNewFolder();
+    renderInstalls();
+  } catch (err) {
+    await modal.show({
+      title: 'Could Not Switch',
+      message: String(err),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+}
+
+async function addInstall() {
+  const { open } = window.__TAURI__.dialog;
+  const path = await open({ directory: true, multiple: false });
+  if (!path) return;
+  if (await useCapCutFolder(path)) {
+    reloadForNewFolder();
+    renderInstalls();
+  }
+}
+
+document.getElementById('btn-add-install')?.addEventListener('click', addInstall);
+document.getElementById('btn-add-install')?.addEventListener('keydown', (e) => handleKey(e, addInstall));
+
+async function addExtraBlocker() {
+  const field = document.getElementById('setting-new-blocker');
+  if (!field.value.trim()) return;
+  try {
+    renderExtraBlockers(await invoke('add_extra_blocker', { path: field.value }));
+    field.value = '';
+  } catch (err) {
+    await modal.show({
+      title: 'Invalid Blocker Path',
+      message: String(err),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+}
+
+// Takes effect on the next protection run; an existing lock file stays until Unprotect
+async function removeExtraBlocker(path) {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.extra_blockers = settings.extra_blockers.filter(p => p !== path);
+    await invoke('save_app_settings', { settings });
+    renderExtraBlockers(settings.extra_blockers);
+  } catch (err) {
+    await modal.show({
+      title: 'Error',
+      message: `Failed to save the blocker list: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+}
+
+document.getElementById('btn-add-blocker')?.addEventListener('click', addExtraBlocker);
+document.getElementById('setting-new-blocker')?.addEventListener('keydown', (e) => {
+  if (e.key === 'Enter') addExtraBlocker();
+});
+
+const installFoldersField = document.getElementById('setting-install-folders');
+installFoldersField?.addEventListener('change', async (e) => {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.install_folder_names = e.target.value.split(',').map(n => n.trim()).filter(Boolean);
+    await invoke('save_app_settings', { settings });
+    e.target.value = settings.install_folder_names.join(', ');
+  } catch (err) {
+    await modal.show({
+      title: 'Error',
+      message: `Failed to save install folder names: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+});
+
+const githubTokenField = document.getElementById('setting-github-token');
+githubTokenField?.addEventListener('change', async (e) => {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.github_token = e.target.value.trim();
+    await invoke('save_app_settings', { settings });
+    e.target.value = settings.github_token;
+  } catch (err) {
+    await modal.show({
+      title: 'Error',
+      message: `Failed to save GitHub token: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+});
+
+const deletionLogToggle = document.getElementById('setting-deletion-log');
+deletionLogToggle?.addEventListener('change', async (e) => {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.log_deleted_files = e.target.checked;
+    await invoke('save_app_settings', { settings });
+  } catch (err) {
+    e.target.checked = !e.target.checked;
+    await modal.show({
+      title: 'Error',
+      message: `Failed to save deletion log setting: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+});
+
+const lowPriorityIoToggle = document.getElementById('setting-low-priority-io');
+lowPriorityIoToggle?.addEventListener('change', async (e) => {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.low_priority_io = e.target.checked;
+    await invoke('save_app_settings', { settings });
+  } catch (err) {
+    e.target.checked = !e.target.checked;
+    await modal.show({
+      title: 'Error',
+      message: `Failed to save low priority setting: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+});
+
+async function exportDeletionLog() {
+  const { save } = window.__TAURI__.dialog;
+  const path = await save({
+    defaultPath: 'capcut-deletion-log.csv',
+    filters: [{ name: 'CSV', extensions: ['csv'] }]
+  });
+  if (!path) return;
+
+  try {
+    await invoke('export_deletion_log', { path });
+    await modal.show({
+      title: 'Log Exported',
+      message: `The deletion log was saved to ${path}.`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: false,
+      iconName: 'check-circle'
+    });
+  } catch (e) {
+    await modal.show({
+      title: 'Export Failed',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  }
+}
+
+document.getElementById('btn-import-golden')?.addEventListener('click', importGoldenCopy);
+document.getElementById('btn-import-golden')?.addEventListener('keydown', (e) => handleKey(e, importGoldenCopy));
+
+/**
+ * Verify and install a golden copy archive into CapCut\Apps
+ */
+async function importGoldenCopy() {
+  const { open } = window.__TAURI__.dialog;
+  const path = await open({ multiple: false, filters: [{ name: 'Golden Copy', extensions: ['zip'] }] });
+  if (!path) return;
+
+  const confirmed = await modal.show({
+    title: 'Import Golden Copy?',
+    message: 'Every file is checked against the archive\'s checksums before the version is installed and pinned in configure.ini. CapCut must be closed.',
+    confirmText: 'Import',
+    cancelText: 'Cancel',
+    danger: false,
+    iconName: 'seal-check'
+  });
+  if (!confirmed) return;
+
+  const result = await invoke('import_golden_copy', { path });
+  await modal.show({
+    title: result.success ? 'Golden Copy Installed' : 'Import Failed',
+    message: result.success
+      ? `CapCut v${result.info.version} was verified (${result.info.files} files) and installed with its version pinned.`
+      : result.error,
+    confirmText: 'OK',
+    cancelText: 'Close',
+    danger: !result.success,
+    iconName: result.success ? 'check-circle' : 'x-circle'
+  });
+}
+
+document.getElementById('btn-export-deletion-log')?.addEventListener('click', exportDeletionLog);
+document.getElementById('btn-export-deletion-log')?.addEventListener('keydown', (e) => handleKey(e, exportDeletionLog));
+document.getElementById('btn-clear-deletion-log')?.addEventListener('click', async (e) => {
+  e.stopPropagation();
+  const confirmed = await modal.show({
+    title: 'Clear Deletion Log?',
+    message: 'The recorded list of deleted files will be removed. Export it first if you need to keep it.',
+    confirmText: 'Clear',
+    cancelText: 'Cancel',
+    danger: true,
+    iconName: 'trash'
+  });
+  if (!confirmed) return;
+  try {
+    await invoke('clear_deletion_log');
+  } catch (err) {
+    await modal.show({
+      title: 'Error',
+      message: String(err),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+});
+document.getElementById('btn-clear-deletion-log')?.addEventListener('keydown', (e) => e.stopPropagation());
+
+const favoriteAlertsToggle = document.getElementById('setting-favorite-alerts');
+favoriteAlertsToggle?.addEventListener('change', async (e) => {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.notifications.on_favorite_changed = e.target.checked;
+    await invoke('save_app_settings', { settings });
+  } catch (err) {
+    e.target.checked = !e.target.checked;
+    await modal.show({
+      title: 'Error',
+      message: `Failed to save notification setting: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+});
+
+const monitorOnlyToggle = document.getElementById('setting-watchdog-monitor-only');
+monitorOnlyToggle?.addEventListener('change', async (e) => {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.watchdog_monitor_only = e.target.checked;
+    await invoke('save_app_settings', { settings });
+
+    // The mode is read when monitoring starts
+    let status = await invoke('watchdog_status');
+    if (status.running) {
+      await invoke('stop_watchdog');
+      status = await invoke('start_watchdog');
+    }
+    updateWatchdogSubtitle(status);
+  } catch (err) {
+    e.target.checked = !e.target.checked;
+    await modal.show({
+      title: 'Error',
+      message: `Failed to change monitoring mode: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+});
+
+// Read by the Apps folder guard on its next reload, so no watchdog restart is needed
+const autoReapplyToggle = document.getElementById('setting-auto-reapply');
+autoReapplyToggle?.addEventListener('change', async (e) => {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.auto_reapply_protection = e.target.checked;
+    await invoke('save_app_settings', { settings });
+  } catch (err) {
+    e.target.checked = !e.target.checked;
+    await modal.show({
+      title: 'Error',
+      message: `Failed to save setting: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+});
+
+const lowImpactToggle = document.getElementById('setting-watchdog-low-impact');
+lowImpactToggle?.addEventListener('change', async (e) => {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.watchdog_low_impact = e.target.checked;
+    await invoke('save_app_settings', { settings });
+
+    // Thread priority and debouncing are set when monitoring starts
+    let status = await invoke('watchdog_status');
+    if (status.running) {
+      await invoke('stop_watchdog');
+      status = await invoke('start_watchdog');
+    }
+    updateWatchdogSubtitle(status);
+  } catch (err) {
+    e.target.checked = !e.target.checked;
+    await modal.show({
+      title: 'Error',
+      message: `Failed to change monitoring mode: ${err}`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'warning-circle'
+    });
+  }
+});
+
+function updateWatchdogSubtitle(status) {
+  const subtitle = document.getElementById('watchdog-subtitle');
+  if (!subtitle) return;
+  if (status.running && status.monitor_only) {
+    subtitle.textContent = 'Observing · changes are recorded, nothing is restored';
+  } else if (status.running && status.last_check) {
+    const time = new Date(status.last_check * 1000).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+    subtitle.textContent = `Watching${status.low_impact ? ' (low impact)' : ''} · last check ${time}`;
+  } else {
+    subtitle.textContent = 'Alert if the version lock breaks';
+  }
+}
+
+function updateDownloadPurgeSubtitle(status) {
+  const subtitle = document.getElementById('download-purge-subtitle');
+  if (!subtitle) return;
+  if (status.running) {
+    subtitle.textContent = status.purged > 0
+      ? `Watching · ${status.purged} purged this session`
+      : 'Watching the CapCut download folder';
+  } else {
+    subtitle.textContent = 'Delete installers CapCut downloads as soon as they land';
+  }
+}
+
+async function loadGuardStats() {
+  try {
+    const stats = await invoke('get_guard_stats');
+    const count = document.getElementById('stats-purged-count');
+    const subtitle = document.getElementById('stats-purged-subtitle');
+    if (count) count.textContent = stats.downloads_purged;
+    if (subtitle) {
+      subtitle.textContent = stats.last_purge
+        ? `${formatBytes(stats.bytes_purged)} deleted · last ${new Date(stats.last_purge * 1000).toLocaleString()}`
+        : 'Nothing purged yet';
+    }
+  } catch (e) {
+    console.warn('Could not load stats:', e);
+  }
+}
+
+// An update payload was deleted from the download folder
+listen('download-purged', async () => {
+  refreshEventsBadge();
+  try {
+    updateDownloadPurgeSubtitle(await invoke('download_purge_status'));
+  } catch (e) {
+    console.warn('Could not load download purge status:', e);
+  }
+  loadGuardStats();
+});
+
+// Tray health changed, or Re-check / Re-apply was used from the tray menu
+listen('tray-status', async () => {
+  try {
+    const status = await invoke('check_protection_status');
+    updateStatusCard(status.is_protected);
+  } catch (e) {
+    console.warn('Could not check protection status:', e);
+  }
+  refreshEventsBadge();
+});
+
+// Watchdog found the lock removed while the app was running
+listen('watchdog-protection-broken', () => {
+  updateStatusCard(false);
+  refreshEventsBadge();
+});
+
+// CapCut was reinstalled or staged a new version, and the watchdog re-applied the saved protection
+listen('watchdog-reprotected', () => {
+  updateStatusCard(true);
+  refreshEventsBadge();
+});
+
+// CapCut was reinstalled without the protected version
+listen('watchdog-reinstall-detected', () => {
+  updateStatusCard(false);
+  refreshEventsBadge();
+});
+
+// The drive holding CapCut went away; the watchdog pauses instead of alerting
+listen('watchdog-volume-offline', () => {
+  const subtitle = document.getElementById('status-subtitle');
+  if (subtitle) subtitle.innerText = 'CapCut drive is offline — checks paused';
+});
+
+listen('watchdog-volume-online', async () => {
+  try {
+    const status = await invoke('check_protection_status');
+    updateStatusCard(status.is_protected);
+  } catch (e) {
+    console.warn('Could not check protection status:', e);
+  }
+});
+
+// The watchdog ended CapCut helper processes
+listen('watchdog-helpers-terminated', () => {
+  refreshEventsBadge();
+});
+
+// CapCut rewrote configure.ini and the watchdog put the locked keys back
+listen('watchdog-config-restored', () => {
+  refreshEventsBadge();
+});
+
+async function loadSettings() {
+  renderInstalls();
+
+  // Load app version
+  try {
+    const version = await getVersion();
+    const settingsVersionEl = document.getElementById('settings-version');
+    if (settingsVersionEl) settingsVersionEl.textContent = `v${version}`;
+  } catch (e) {
+    console.warn('Could not load version for settings:', e);
+  }
+
+  // Load auto-start state
+  try {
+    const isEnabled = await invoke('get_autostart_enabled');
+    if (autostartToggle) autostartToggle.checked = isEnabled;
+  } catch (e) {
+    console.warn('Could not load autostart status:', e);
+  }
+
+  // Load watchdog state
+  try {
+    const status = await invoke('watchdog_status');
+    if (watchdogToggle) watchdogToggle.checked = status.running;
+    updateWatchdogSubtitle(status);
+  } catch (e) {
+    console.warn('Could not load watchdog status:', e);
+  }
+
+  // Load download purge state
+  try {
+    const status = await invoke('download_purge_status');
+    if (downloadPurgeToggle) downloadPurgeToggle.checked = status.running;
+    updateDownloadPurgeSubtitle(status);
+  } catch (e) {
+    console.warn('Could not load download purge status:', e);
+  }
+
+  loadGuardStats();
+
+  // Load favorite change notification preference
+  try {
+    const settings = await invoke('get_app_settings');
+    if (favoriteAlertsToggle) favoriteAlertsToggle.checked = settings.notifications.on_favorite_changed;
+    if (installerCheckToggle) installerCheckToggle.checked = settings.check_downloaded_installers;
+    if (deletionLogToggle) deletionLogToggle.checked = settings.log_deleted_files;
+    if (lowPriorityIoToggle) lowPriorityIoToggle.checked = settings.low_priority_io;
+    if (monitorOnlyToggle) monitorOnlyToggle.checked = settings.watchdog_monitor_only;
+    if (lowImpactToggle) lowImpactToggle.checked = settings.watchdog_low_impact;
+    if (autoReapplyToggle) autoReapplyToggle.checked = settings.auto_reapply_protection;
+    if (minimizeToTrayToggle) minimizeToTrayToggle.checked = settings.minimize_to_tray;
+    if (installFoldersField) installFoldersField.value = settings.install_folder_names.join(', ');
+    if (githubTokenField) githubTokenField.value = settings.github_token;
+    renderExclusions(settings.excluded_paths);
+    renderExtraBlockers(settings.extra_blockers);
+  } catch (e) {
+    console.warn('Could not load notification settings:', e);
+  }
+
+  applyPolicyToSettings();
+}
+
+/**
+ * Lock settings controls that are managed by an administrator policy file
+ */
+function applyPolicyToSettings() {
+  const policy = state.policy;
+  const notice = document.getElementById('settings-policy-notice');
+  const importRow = document.getElementById('btn-import-settings');
+
+  if (notice) notice.style.display = policy.managed ? 'block' : 'none';
+  if (autostartToggle) autostartToggle.disabled = !!policy.force_autostart;
+  if (watchdogToggle) watchdogToggle.disabled = !!policy.force_watchdog;
+  if (downloadPurgeToggle) downloadPurgeToggle.disabled = !!policy.lock_settings;
+  if (favoriteAlertsToggle) favoriteAlertsToggle.disabled = !!policy.lock_settings;
+  if (installerCheckToggle) installerCheckToggle.disabled = !!policy.lock_settings;
+  if (deletionLogToggle) deletionLogToggle.disabled = !!policy.lock_settings;
+  if (autoReapplyToggle) autoReapplyToggle.disabled = !!policy.lock_settings;
+  if (minimizeToTrayToggle) minimizeToTrayToggle.disabled = !!policy.lock_settings;
+  if (githubTokenField) githubTokenField.disabled = !!policy.lock_settings;
+  const addExclusionRow = document.getElementById('btn-add-exclusion');
+  if (addExclusionRow) {
+    addExclusionRow.style.pointerEvents = policy.lock_settings ? 'none' : '';
+    addExclusionRow.style.opacity = policy.lock_settings ? '0.5' : '';
+  }
+  const blockerField = document.getElementById('setting-new-blocker');
+  if (blockerField) blockerField.disabled = !!policy.lock_settings;
+  const addBlockerBtn = document.getElementById('btn-add-blocker');
+  if (addBlockerBtn) addBlockerBtn.disabled = !!policy.lock_settings;
+  if (importRow) {
+    importRow.style.pointerEvents = policy.lock_settings ? 'none' : '';
+    importRow.style.opacity = policy.lock_settings ? '0.5' : '';
+  }
+}
+
+// Settings export/import (file pickers via dialog plugin)
+document.getElementById('btn-export-settings')?.addEventListener('click', exportSettings);
+document.getElementById('btn-import-settings')?.addEventListener('click', importSettings);
+document.getElementById('btn-export-settings')?.addEventListener('keydown', (e) => handleKey(e, exportSettings));
+document.getElementById('btn-import-settings')?.addEventListener('keydown', (e) => handleKey(e, importSettings));
+document.getElementById('btn-export-diagnostics')?.addEventListener('click', exportDiagnostics);
+document.getElementById('btn-export-diagnostics')?.addEventListener('keydown', (e) => handleKey(e, exportDiagnostics));
+
+async function exportDiagnostics() {
+  const { save } = window.__TAURI__.dialog;
+  const path = await save({
+    defaultPath: 'cc-version-guard-diagnostics.zip',
+    filters: [{ name: 'Zip archive', extensions: ['zip'] }]
+  });
+  if (!path) return;
+
+  try {
+    const info = await invoke('export_diagnostics', { path });
+    await modal.show({
+      title: 'Diagnostics Exported',
+      message: `${info.files.length} file(s) were saved to ${path}. Your user and computer names are masked; attach the zip to your bug report.`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: false,
+      iconName: 'check-circle'
+    });
+  } catch (e) {
+    await modal.show({
+      title: 'Export Failed',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  }
+}
+
+async function exportSettings() {
+  const { save } = window.__TAURI__.dialog;
+  const path = await save({
+    defaultPath: 'cc-version-guard-settings.json',
+    filters: [{ name: 'Settings', extensions: ['json'] }]
+  });
+  if (!path) return;
+
+  try {
+    await invoke('export_app_settings', { path });
+    await modal.show({
+      title: 'Settings Exported',
+      message: `Your preferences were saved to ${path}.`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: false,
+      iconName: 'check-circle'
+    });
+  } catch (e) {
+    await modal.show({
+      title: 'Export Failed',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  }
+}
+
+// Scan result export (versions + cache sizes) from the version and options screens
+document.querySelectorAll('.btn-export-scan').forEach(btn => {
+  btn.addEventListener('click', exportScanResults);
+  btn.addEventListener('keydown', (e) => handleKey(e, exportScanResults));
+});
+
+async function exportScanResults() {
+  const { save } = window.__TAURI__.dialog;
+  const path = await save({
+    defaultPath: 'capcut-scan.csv',
+    filters: [
+      { name: 'CSV', extensions: ['csv'] },
+      { name: 'JSON', extensions: ['json'] }
+    ]
+  });
+  if (!path) return;
+
+  try {
+    await invoke('export_scan_results', { path });
+    await modal.show({
+      title: 'Scan Results Exported',
+      message: `Versions, sizes, dates and integrity status were saved to ${path}.`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: false,
+      iconName: 'check-circle'
+    });
+  } catch (e) {
+    await modal.show({
+      title: 'Export Failed',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  }
+}
+
+async function importSettings() {
+  const { open } = window.__TAURI__.dialog;
+  const path = await open({
+    multiple: false,
+    directory: false,
+    filters: [{ name: 'Settings', extensions: ['json'] }]
+  });
+  if (!path) return;
+
+  const confirmed = await modal.show({
+    title: 'Import Settings?',
+    message: 'Your current preferences will be replaced with the imported ones.',
+    confirmText: 'Import',
+    cancelText: 'Cancel',
+    danger: false,
+    iconName: 'download-simple'
+  });
+  if (!confirmed) return;
+
+  try {
+    await invoke('import_app_settings', { path });
+    loadSettings();
+  } catch (e) {
+    await modal.show({
+      title: 'Import Failed',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  }
+}
+
+// ============================================
+// Email Alerts View Handlers
+// ============================================
+document.getElementById('btn-email-alerts')?.addEventListener('click', () => navigateTo('email'));
+document.getElementById('btn-email-alerts')?.addEventListener('keydown', (e) => handleKey(e, () => navigateTo('email')));
+document.getElementById('email-back')?.addEventListener('click', goBack);
+document.getElementById('btn-save-email')?.addEventListener('click', saveEmailSettings);
+document.getElementById('btn-test-email')?.addEventListener('click', sendTestEmail);
+
+const emailFields = {
+  enabled: document.getElementById('email-enabled'),
+  smtp_host: document.getElementById('email-host'),
+  smtp_port: document.getElementById('email-port'),
+  starttls: document.getElementById('email-starttls'),
+  username: document.getElementById('email-username'),
+  password: document.getElementById('email-password'),
+  from: document.getElementById('email-from'),
+  to: document.getElementById('email-to')
+};
+
+async function loadEmailSettings() {
+  try {
+    const settings = await invoke('get_app_settings');
+    const email = settings.email;
+    emailFields.enabled.checked = email.enabled;
+    emailFields.smtp_host.value = email.smtp_host;
+    emailFields.smtp_port.value = email.smtp_port;
+    emailFields.starttls.checked = email.starttls;
+    emailFields.username.value = email.username;
+    emailFields.password.value = email.password;
+    emailFields.from.value = email.from;
+    emailFields.to.value = email.to;
+  } catch (e) {
+    console.warn('Could not load email settings:', e);
+  }
+
+  const locked = !!state.policy.lock_settings;
+  Object.values(emailFields).forEach(field => { field.disabled = locked; });
+  document.getElementById('btn-save-email').disabled = locked;
+}
+
+/**
+ * Persist the email form into the app settings (other settings untouched)
+ */
+async function saveEmailSettings() {
+  try {
+    const settings = await invoke('get_app_settings');
+    settings.email = {
+      enabled: emailFields.enabled.checked,
+      smtp_host: emailFields.smtp_host.value.trim(),
+      smtp_port: parseInt(emailFields.smtp_port.value, 10) || 587,
+      starttls: emailFields.starttls.checked,
+      username: emailFields.username.value.trim(),
+      password: emailFields.password.value,
+      from: emailFields.from.value.trim(),
+      to: emailFields.to.value.trim()
+    };
+    await invoke('save_app_settings', { settings });
+    return true;
+  } catch (e) {
+    await modal.show({
+      title: 'Save Failed',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+    return false;
+  }
+}
+
+async function sendTestEmail() {
+  if (!state.policy.lock_settings && !(await saveEmailSettings())) return;
+
+  const btn = document.getElementById('btn-test-email');
+  btn.disabled = true;
+  try {
+    await invoke('send_test_email');
+    await modal.show({
+      title: 'Test Email Sent',
+      message: `Check the inbox of ${emailFields.to.value}.`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: false,
+      iconName: 'check-circle'
+    });
+  } catch (e) {
+    await modal.show({
+      title: 'Test Email Failed',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+  } finally {
+    btn.disabled = false;
+  }
+}
+
+// ============================================
+// Schedules View Handlers
+// ============================================
+document.getElementById('btn-schedules')?.addEventListener('click', () => navigateTo('schedules'));
+document.getElementById('btn-schedules')?.addEventListener('keydown', (e) => handleKey(e, () => navigateTo('schedules')));
+document.getElementById('schedules-back')?.addEventListener('click', goBack);
+document.getElementById('btn-save-schedules')?.addEventListener('click', saveSchedules);
+
+const scheduleFields = {
+  verify_interval_minutes: document.getElementById('schedule-verify'),
+  cache_clean_interval_days: document.getElementById('schedule-cache'),
+  backup_interval_days: document.getElementById('schedule-backup'),
+  backup_retention: document.getElementById('schedule-backup-retention'),
+  draft_backup_interval_days: document.getElementById('schedule-drafts'),
+  draft_backup_retention: document.getElementById('schedule-draft-retention')
+};
+const draftDestinationField = document.getElementById('schedule-draft-destination');
+const draftIncrementalToggle = document.getElementById('schedule-draft-incremental');
+
+// Retention only applies to full snapshots
+draftIncrementalToggle?.addEventListener('change', () => {
+  scheduleFields.draft_backup_retention.disabled = draftIncrementalToggle.checked || !!state.policy.lock_settings;
+});
+
+/**
+ * Describe a task's next run (e.g., "Next run 10/17/2026 09:00")
+ */
+function describeNextRun(task) {
+  if (!task.enabled) return 'Off';
+  if (!task.next_run) return 'Waiting for first run';
+  const date = new Date(task.next_run * 1000);
+  if (task.next_run * 1000 <= Date.now()) return 'Due now';
+  return `Next run ${date.toLocaleDateString()} ${date.toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' })}`;
+}
+
+function renderScheduleStatus(status) {
+  document.getElementById('schedule-verify-next').textContent = describeNextRun(status.verify);
+  document.getElementById('schedule-cache-next').textContent = describeNextRun(status.cache_clean);
+  document.getElementById('schedule-backup-next').textContent = describeNextRun(status.backup);
+  document.getElementById('schedule-drafts-next').textContent = describeNextRun(status.draft_backup);
+}
+
+async function loadSchedules() {
+  try {
+    const settings = await invoke('get_app_settings');
+    for (const [key, field] of Object.entries(scheduleFields)) {
+      field.value = String(settings.schedules[key]);
+      // Keep custom intervals (e.g., from an imported file) selectable
+      if (field.value !== String(settings.schedules[key])) {
+        field.append(el('option', { value: String(settings.schedules[key]) }, `Custom (${settings.schedules[key]})`));
+        field.value = String(settings.schedules[key]);
+      }
+    }
+    draftDestinationField.value = settings.schedules.draft_backup_destination;
+    draftIncrementalToggle.checked = settings.schedules.draft_backup_incremental;
+    renderScheduleStatus(await invoke('get_schedule_status'));
+  } catch (e) {
+    console.warn('Could not load schedules:', e);
+  }
+
+  const locked = !!state.policy.lock_settings;
+  Object.values(scheduleFields).forEach(field => { field.disabled = locked; });
+  draftDestinationField.disabled = locked;
+  draftIncrementalToggle.disabled = locked;
+  scheduleFields.draft_backup_retention.disabled = locked || draftIncrementalToggle.checked;
+  document.getElementById('btn-browse-draft-destination').disabled = locked;
+  document.getElementById('btn-save-schedules').disabled = locked;
+}
+
+document.getElementById('btn-browse-draft-destination')?.addEventListener('click', async () => {
+  const { open } = window.__TAURI__.dialog;
+  const folder = await open({ directory: true, multiple: false });
+  if (folder) draftDestinationField.value = folder;
+});
+document.getElementById('btn-backup-drafts-now')?.addEventListener('click', backupDraftsNow);
+
+async function backupDraftsNow() {
+  // Use the destination and retention shown on screen
+  if (!state.policy.lock_settings && !(await saveSchedules())) return;
+
+  const btn = document.getElementById('btn-backup-drafts-now');
+  btn.disabled = true;
+  try {
+    const result = await invoke('backup_drafts_now');
+    await modal.show({
+      title: result.success ? 'Drafts Backed Up' : 'Draft Backup Failed',
+      message: !result.success ? result.error
+        : draftIncrementalToggle.checked
+          ? `${result.copied} changed project(s) synced to ${result.snapshot}; ${result.unchanged} unchanged.`
+          : `Your drafts were copied to ${result.snapshot}.`,
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: !result.success,
+      iconName: result.success ? 'check-circle' : 'x-circle'
+    });
+  } catch (e) {
+    console.error('Draft backup failed:', e);
+  } finally {
+    btn.disabled = false;
+  }
+}
+
+async function saveSchedules() {
+  const schedules = {};
+  for (const [key, field] of Object.entries(scheduleFields)) {
+    schedules[key] = parseInt(field.value, 10) || 0;
+  }
+  schedules.draft_backup_destination = draftDestinationField.value.trim();
+  schedules.draft_backup_incremental = draftIncrementalToggle.checked;
+
+  try {
+    renderScheduleStatus(await invoke('save_schedule_settings', { schedules }));
+    return true;
+  } catch (e) {
+    await modal.show({
+      title: 'Save Failed',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: true,
+      iconName: 'x-circle'
+    });
+    return false;
+  }
+}
+
+// ============================================
+// Resource Monitor View
+// ============================================
+const MONITOR_INTERVAL_MS = 2000;
+let monitorTimer = null;
+
+document.getElementById('btn-resource-monitor')?.addEventListener('click', () => navigateTo('monitor'));
+document.getElementById('btn-resource-monitor')?.addEventListener('keydown', (e) => handleKey(e, () => navigateTo('monitor')));
+document.getElementById('monitor-back')?.addEventListener('click', goBack);
+
+document.getElementById('btn-protection-history')?.addEventListener('click', () => navigateTo('history'));
+document.getElementById('btn-protection-history')?.addEventListener('keydown', (e) => handleKey(e, () => navigateTo('history')));
+document.getElementById('history-back')?.addEventListener('click', goBack);
+document.getElementById('btn-observation-timeline')?.addEventListener('click', () => navigateTo('timeline'));
+document.getElementById('btn-observation-timeline')?.addEventListener('keydown', (e) => handleKey(e, () => navigateTo('timeline')));
+document.getElementById('timeline-back')?.addEventListener('click', goBack);
+document.getElementById('btn-config-diff')?.addEventListener('click', () => navigateTo('config-diff'));
+document.getElementById('btn-health')?.addEventListener('click', () => navigateTo('health'));
+document.getElementById('health-back')?.addEventListener('click', goBack);
+document.getElementById('btn-health-recheck')?.addEventListener('click', loadHealth);
+
+/**
+ * Protection health: each guard listed as intact or broken
+ */
+async function loadHealth() {
+  const list = document.getElementById('health-checks');
+  const summary = document.getElementById('health-summary');
+  summary.textContent = '';
+  summary.style.color = '';
+  list.replaceChildren(createSkeletonRows(4));
+
+  try {
+    const health = await invoke('check_protection_health');
+    const broken = health.checks.filter(check => !check.intact).length;
+    list.replaceChildren(...health.checks.map(check =>
+      el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4); gap: var(--space-3);' },
+        icon(check.intact ? 'check-circle' : 'x-circle', {
+          style: { color: check.intact ? 'var(--accent-green)' : 'var(--accent-red)', fontSize: '18px' }
+        }),
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, check.name),
+          el('span', { className: 'row-subtitle' }, check.intact ? 'Intact' : check.detail)))));
+    const kept = health.kept_version ? ` for CapCut v${health.kept_version}` : '';
+    summary.textContent = health.intact
+      ? `All protections are in place${kept}.`
+      : `${broken} of ${health.checks.length} protections are broken${kept}. Lock the version again to repair them.`;
+    if (!health.intact) summary.style.color = 'var(--accent-red)';
+  } catch (e) {
+    list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+      el('span', { className: 'row-subtitle', style: { color: 'var(--accent-red)' } }, String(e))));
+  }
+}
+document.getElementById('btn-check-tool-update')?.addEventListener('click', () => navigateTo('release-notes'));
+document.getElementById('btn-check-tool-update')?.addEventListener('keydown', (e) => handleKey(e, () => navigateTo('release-notes')));
+document.getElementById('release-notes-back')?.addEventListener('click', goBack);
+document.getElementById('btn-uninstall-guard')?.addEventListener('click', uninstallGuard);
+document.getElementById('btn-uninstall-guard')?.addEventListener('keydown', (e) => handleKey(e, uninstallGuard));
+document.getElementById('uninstall-back')?.addEventListener('click', goBack);
+document.getElementById('revert-back')?.addEventListener('click', goBack);
+document.getElementById('archive-install-back')?.addEventListener('click', goBack);
+document.getElementById('config-diff-back')?.addEventListener('click', goBack);
+
+async function loadConfigDiff() {
+  const summary = document.getElementById('config-diff-summary');
+  const list = document.getElementById('config-diff-list');
+  summary.style.color = '';
+  list.replaceChildren(createSkeletonRows(3));
+  const labels = {
+    changed: { text: 'Changed', color: 'var(--accent-orange)' },
+    added: { text: 'Added', color: 'var(--accent-blue)' },
+    removed: { text: 'Removed', color: 'var(--accent-red)' }
+  };
+  try {
+    const diff = await invoke('get_config_diff');
+    if (!diff.available) {
+      summary.textContent = 'No locked copy of configure.ini yet. Lock a version to start tracking changes.';
+      list.replaceChildren();
+      return;
+    }
+    if (diff.changes.length === 0) {
+      summary.textContent = `configure.ini matches the locked copy (${diff.unchanged} keys)`;
+      summary.style.color = 'var(--accent-green)';
+      list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+        el('span', { className: 'row-subtitle' }, 'CapCut has not changed any key since protection')));
+      return;
+    }
+
+    summary.textContent = `${diff.changes.length} key(s) changed · ${diff.unchanged} unchanged`;
+    list.replaceChildren(...diff.changes.map(change => {
+      const label = labels[change.status];
+      return el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4); justify-content: space-between;' },
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title', style: { wordBreak: 'break-all' } }, change.key),
+          el('span', { className: 'row-subtitle', style: { wordBreak: 'break-all', fontFamily: 'Consolas, monospace' } },
+            `${change.locked ?? '—'} → ${change.live ?? '—'}`)),
+        el('span', { className: 'row-subtitle', style: { color: label.color, fontWeight: '600' } }, label.text));
+    }));
+  } catch (e) {
+    summary.textContent = '';
+    list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+      el('span', { className: 'row-subtitle', style: { color: 'var(--accent-red)' } }, `Error: ${e}`)));
+  }
+}
+document.getElementById('btn-clear-timeline')?.addEventListener('click', async () => {
+  try {
+    await invoke('clear_observation_timeline');
+    loadTimeline();
+  } catch (e) {
+    console.error('Failed to clear timeline:', e);
+  }
+});
+
+async function loadTimeline() {
+  const list = document.getElementById('timeline-list');
+  const kinds = {
+    create: { iconName: 'plus-circle', color: 'var(--accent-green)' },
+    modify: { iconName: 'pencil-simple', color: 'var(--accent-blue)' },
+    rename: { iconName: 'arrows-left-right', color: 'var(--accent-yellow)' },
+    delete: { iconName: 'minus-circle', color: 'var(--accent-red)' }
+  };
+  try {
+    const changes = await invoke('get_observation_timeline');
+    if (changes.length === 0) {
+      list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+        el('span', { className: 'row-subtitle' }, 'No changes recorded. Turn on Monitor Only and Protection Monitoring to observe CapCut.')));
+      return;
+    }
+
+    list.replaceChildren(...changes.map(change => {
+      const kind = kinds[change.kind] || { iconName: 'info', color: 'var(--label-secondary)' };
+      return el('div', { className: 'list-row', style: 'padding: var(--space-2) var(--space-4);' },
+        icon(kind.iconName, { style: { color: kind.color, fontSize: '16px' } }),
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title', style: { wordBreak: 'break-all', fontSize: '13px' } }, change.path),
+          el('span', { className: 'row-subtitle' },
+            `${change.kind} · ${new Date(change.timestamp * 1000).toLocaleString()}`)));
+    }));
+  } catch (e) {
+    list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+      el('span', { className: 'row-subtitle', style: { color: 'var(--accent-red)' } }, `Error: ${e}`)));
+  }
+}
+
+// New changes were recorded while the timeline is open
+listen('observer-changes', () => {
+  if (document.getElementById('view-timeline')?.classList.contains('active')) loadTimeline();
+});
+
+// Inline `code` and **bold**; everything else stays plain text
+function markdownInline(text) {
+  const nodes = [];
+  for (const part of text.split(/(`[^`]+`|\*\*[^*]+\*\*)/)) {
+    if (!part) continue;
+    if (part.startsWith('`')) nodes.push(el('code', {}, part.slice(1, -1)));
+    else if (part.startsWith('**')) nodes.push(el('strong', {}, part.slice(2, -2)));
+    else nodes.push(part);
+  }
+  return nodes;
+}
+
+// Headings, bullet lists and paragraphs of GitHub release notes, built as DOM (no HTML injection)
+function renderMarkdown(markdown) {
+  const root = el('div', { className: 'release-notes' });
+  let list = null;
+  let paragraph = [];
+  const flushParagraph = () => {
+    if (paragraph.length) root.append(el('p', {}, ...markdownInline(paragraph.join(' '))));
+    paragraph = [];
+  };
+
+  for (const raw of markdown.split(/\r?\n/)) {
+    const line = raw.trim();
+    const heading = line.match(/^#{1,6}\s+(.*)$/);
+    const bullet = line.match(/^[-*+]\s+(.*)$/);
+    if (bullet) {
+      flushParagraph();
+      if (!list) root.append(list = el('ul'));
+      list.append(el('li', {}, ...markdownInline(bullet[1])));
+      continue;
+    }
+    list = null;
+    if (heading) {
+      flushParagraph();
+      root.append(el('h4', {}, ...markdownInline(heading[1])));
+    } else if (line) {
+      paragraph.push(line);
+    } else {
+      flushParagraph();
+    }
+  }
+  flushParagraph();
+  return root;
+}
+
+async function loadReleaseNotes() {
+  const list = document.getElementById('release-notes-list');
+  const header = document.getElementById('release-notes-header');
+  const message = (text, color) => el('div', { className: 'glass-panel', style: 'padding: var(--space-3) var(--space-4);' },
+    el('span', { className: 'row-subtitle', style: color ? { color } : {} }, text));
+
+  header.textContent = 'NEWER RELEASES';
+  list.replaceChildren(el('div', { className: 'glass-panel', style: 'padding: 0;' }, createSkeletonRows(2)));
+  try {
+    const result = await invoke('check_tool_update');
+    if (result.releases.length === 0) {
+      list.replaceChildren(message(`You're up to date (v${result.current_version})`));
+      return;
+    }
+
+    header.textContent = `NEWER RELEASES · YOU HAVE v${result.current_version}`;
+    list.replaceChildren(...result.releases.map(release => {
+      const date = release.published_at ? new Date(release.published_at).toLocaleDateString() : '';
+      const openBtn = el('button', { className: 'btn-plain' }, icon('arrow-square-out'), ' Download');
+      openBtn.addEventListener('click', () => window.__TAURI__.opener.openUrl(release.url));
+      return el('div', { className: 'glass-panel', style: 'padding: var(--space-3) var(--space-4); margin-bottom: var(--space-3);' },
+        el('div', { style: { display: 'flex', alignItems: 'center', justifyContent: 'space-between', gap: '8px' } },
+          el('div', { className: 'row-content' },
+            el('span', { className: 'row-title' }, release.name),
+            el('span', { className: 'row-subtitle' },
+              [release.tag, date, release.prerelease ? 'pre-release' : ''].filter(Boolean).join(' · '))),
+          openBtn),
+        release.notes.trim()
+          ? renderMarkdown(release.notes)
+          : el('p', { className: 'row-subtitle' }, 'No release notes'));
+    }));
+  } catch (e) {
+    list.replaceChildren(message(`Could not check for updates: ${e}`, 'var(--accent-red)'));
+  }
+}
+
+async function uninstallGuard() {
+  if (state.policy.disallow_unprotect || state.policy.force_watchdog || state.policy.force_autostart) {
+    await modal.show({
+      title: 'Managed by Administrator',
+      message: 'Your administrator\'s policy keeps Version Guard installed on this PC.',
+      confirmText: 'OK',
+      cancelText: 'Close',
+      iconName: 'buildings'
+    });
+    return;
+  }
+
+  const confirmed = await modal.show({
+    title: 'Uninstall Version Guard?',
+    message: 'Protection is removed, the watchdog and schedules stop, and the startup and hosts file entries are deleted. CapCut will be able to update again.',
+    confirmText: 'Uninstall',
+    cancelText: 'Cancel',
+    danger: true,
+    iconName: 'sign-out'
+  });
+  if (!confirmed) return;
+
+  const removeAppData = await modal.show({
+    title: 'Delete App Data Too?',
+    message: 'Settings, version backups, logs and history in Version Guard\'s data folder can be deleted as well. This cannot be undone.',
+    confirmText: 'Delete Data',
+    cancelText: 'Keep Data',
+    danger: true,
+    iconName: 'trash'
+  });
+
+  navigateTo('uninstall');
+  const list = document.getElementById('uninstall-checks');
+  const header = document.getElementById('uninstall-header');
+  const summary = document.getElementById('uninstall-summary');
+  header.textContent = 'REMOVING';
+  summary.textContent = '';
+  list.replaceChildren(createSkeletonRows(4));
+
+  try {
+    const result = await invoke('uninstall_guard', { removeAppData });
+    header.textContent = 'VERIFIED';
+    list.replaceChildren(...result.checks.map(check =>
+      el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4); gap: var(--space-3);' },
+        icon(check.removed ? 'check-circle' : 'x-circle', {
+          style: { color: check.removed ? 'var(--accent-green)' : 'var(--accent-red)', fontSize: '18px' }
+        }),
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, check.name),
+          el('span', { className: 'row-subtitle' }, check.removed ? 'Removed' : check.detail)))));
+    summary.textContent = result.clean
+      ? 'Everything Version Guard changed is gone. You can now delete the app.'
+      : 'Some changes could not be removed. Run Version Guard as administrator and uninstall again.';
+    summary.style.color = result.clean ? '' : 'var(--accent-red)';
+    updateStatusCard(false);
+  } catch (e) {
+    header.textContent = 'FAILED';
+    list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+      el('span', { className: 'row-subtitle', style: { color: 'var(--accent-red)' } }, String(e))));
+  }
+}
+
+async function loadHistory() {
+  const list = document.getElementById('history-list');
+  try {
+    const runs = await invoke('list_protection_runs');
+    if (runs.length === 0) {
+      list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+        el('span', { className: 'row-subtitle' }, 'No protection runs recorded yet')));
+      return;
+    }
+
+    list.replaceChildren(...runs.map(run => {
+      const total = run.steps.reduce((sum, t) => sum + t.millis, 0);
+      const title = run.kept_version ? `CapCut v${run.kept_version}` : 'Protection run';
+      return el('div', { style: 'border-bottom: 1px solid var(--border-separator);' },
+        el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4); justify-content: space-between;' },
+          el('div', { className: 'row-content' },
+            el('span', { className: 'row-title' }, title),
+            el('span', { className: 'row-subtitle', style: run.success ? {} : { color: 'var(--accent-red)' } },
+              `${new Date(run.timestamp * 1000).toLocaleString()} · ${run.success ? 'Succeeded' : (run.error || 'Failed')}`)),
+          el('span', { className: 'row-subtitle' }, formatDuration(total))),
+        ...timingRows(run.steps),
+        ...(run.changes.length > 0 ? [changeList(run.changes)] : [])
+      );
+    }));
+  } catch (e) {
+    list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+      el('span', { className: 'row-subtitle', style: { color: 'var(--accent-red)' } }, `Error: ${e}`)));
+  }
+}
+
+function loadMonitor() {
+  clearInterval(monitorTimer);
+  refreshMonitor();
+  monitorTimer = setInterval(refreshMonitor, MONITOR_INTERVAL_MS);
+}
+
+document.getElementById('btn-monitor-end-all')?.addEventListener('click', async () => {
+  const btn = document.getElementById('btn-monitor-end-all');
+  btn.disabled = true;
+  if (await forceCloseCapCut()) refreshMonitor();
+  btn.disabled = false;
+});
+
+async function refreshMonitor() {
+  // Stop polling once the user leaves the view
+  if (!document.getElementById('view-monitor')?.classList.contains('active')) {
+    clearInterval(monitorTimer);
+    monitorTimer = null;
+    return;
+  }
+
+  const summary = document.getElementById('monitor-summary');
+  const list = document.getElementById('monitor-processes');
+  try {
+    const usage = await invoke('capcut_resource_usage');
+    document.getElementById('btn-monitor-end-all').style.display = usage.processes.length ? '' : 'none';
+    if (usage.processes.length === 0) {
+      summary.textContent = 'No CapCut processes running';
+      summary.style.color = 'var(--accent-green)';
+      list.replaceChildren(el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4);' },
+        el('span', { className: 'row-subtitle' }, 'CapCut and its updater/helper processes are not running')));
+      return;
+    }
+
+    summary.textContent = `${usage.processes.length} process(es) · ${formatNumber(usage.total_cpu_percent, 1)}% CPU · ${formatMegabytes(usage.total_memory_mb)}`;
+    summary.style.color = '';
+    list.replaceChildren(...usage.processes.map(p =>
+      el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4); justify-content: space-between;' },
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, p.name),
+          el('span', { className: 'row-subtitle' }, `PID ${p.pid}`)),
+        el('span', { className: 'row-subtitle' }, `${formatNumber(p.cpu_percent, 1)}% · ${formatMegabytes(p.memory_mb)}`))
+    ));
+  } catch (e) {
+    summary.textContent = `Error: ${e}`;
+    summary.style.color = 'var(--accent-red)';
+  }
+}
+
+// ============================================
+// Firewall Rules View
+// ============================================
+document.getElementById('btn-firewall-rules')?.addEventListener('click', () => navigateTo('firewall'));
+document.getElementById('btn-firewall-rules')?.addEventListener('keydown', (e) => handleKey(e, () => navigateTo('firewall')));
+document.getElementById('firewall-back')?.addEventListener('click', goBack);
+document.getElementById('btn-remove-firewall-rules')?.addEventListener('click', removeFirewallRules);
+
+async function loadFirewallRules() {
+  const list = document.getElementById('firewall-rule-list');
+  const summary = document.getElementById('firewall-summary');
+  const removeBtn = document.getElementById('btn-remove-firewall-rules');
+  summary.style.color = '';
+  list.replaceChildren(createSkeletonRows(2));
+  try {
+    const rules = await invoke('list_firewall_rules');
+    removeBtn.disabled = rules.length === 0 || !!state.policy.disallow_unprotect;
+    if (rules.length === 0) {
+      list.replaceChildren(el('div', { className: 'list-row', style: { justifyContent: 'center', padding: '24px' } },
+        el('span', { className: 'row-subtitle' }, 'No firewall rules created. Turn on "Firewall Updater" in the protection options.')));
+      summary.textContent = '';
+      return;
+    }
+    list.replaceChildren(...rules.map(rule =>
+      el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4); gap: var(--space-3);' },
+        icon(rule.present ? 'prohibit' : 'question', {
+          style: { color: rule.present ? 'var(--accent-red)' : 'var(--label-tertiary)', fontSize: '18px' }
+        }),
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, rule.name),
+          el('span', { className: 'row-subtitle' }, rule.present ? rule.program : `${rule.program} (removed outside Version Guard)`)))));
+    summary.textContent = `${rules.filter(r => r.present).length} of ${rules.length} rule(s) active in Windows Firewall.`;
+  } catch (e) {
+    list.replaceChildren();
+    summary.textContent = String(e);
+    summary.style.color = 'var(--accent-red)';
+  }
+}
+
+async function removeFirewallRules() {
+  const confirmed = await modal.show({
+    title: 'Remove Firewall Rules?',
+    message: 'The CapCut updater will be able to reach the network again.',
+    confirmText: 'Remove',
+    cancelText: 'Cancel',
+    danger: true,
+    iconName: 'wall'
+  });
+  if (!confirmed) return;
+
+  try {
+    await invoke('remove_firewall_rules');
+  } catch (e) {
+    await modal.show({
+      title: 'Could Not Remove Rules',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: false,
+      iconName: 'warning-circle'
+    });
+  }
+  loadFirewallRules();
+}
+
+// ============================================
+// Scheduled Tasks & Autoruns
+// ============================================
+document.getElementById('btn-autoruns')?.addEventListener('click', () => navigateTo('autoruns'));
+document.getElementById('btn-autoruns')?.addEventListener('keydown', (e) => handleKey(e, () => navigateTo('autoruns')));
+document.getElementById('autoruns-back')?.addEventListener('click', goBack);
+document.getElementById('btn-rescan-autoruns')?.addEventListener('click', loadAutoruns);
+
+async function loadAutoruns() {
+  const list = document.getElementById('autorun-list');
+  const summary = document.getElementById('autoruns-summary');
+  summary.style.color = '';
+  list.replaceChildren(createSkeletonRows(2));
+  try {
+    const entries = await invoke('scan_autoruns');
+    if (entries.length === 0) {
+      list.replaceChildren(el('div', { className: 'list-row', style: { justifyContent: 'center', padding: '24px' } },
+        el('span', { className: 'row-subtitle' }, 'No CapCut scheduled tasks or autorun entries found.')));
+      summary.textContent = '';
+      return;
+    }
+    list.replaceChildren(...entries.map(autorunRow));
+    summary.textContent = `${entries.filter(e => e.enabled).length} of ${entries.length} entry(s) enabled.`;
+  } catch (e) {
+    list.replaceChildren();
+    summary.textContent = String(e);
+    summary.style.color = 'var(--accent-red)';
+  }
+}
+
+function autorunRow(entry) {
+  const isTask = entry.kind === 'scheduled_task';
+  // RunOnce entries can't be disabled, only deleted
+  const canToggle = isTask || !entry.location.endsWith('RunOnce');
+  const toggleBtn = el('button', { className: 'btn-plain' },
+    icon(entry.enabled ? 'pause' : 'play'), entry.enabled ? ' Disable' : ' Enable');
+  toggleBtn.disabled = !canToggle || (!entry.enabled && !!state.policy.disallow_unprotect);
+  toggleBtn.addEventListener('click', () => changeAutorun(entry, () =>
+    invoke('set_autorun_enabled', { id: entry.id, enabled: !entry.enabled })));
+  const deleteBtn = el('button', { className: 'btn-plain' }, icon('trash'), ' Delete');
+  deleteBtn.addEventListener('click', async () => {
+    const confirmed = await modal.show({
+      title: 'Delete Entry?',
+      message: `"${entry.name}" will be removed for good. CapCut may recreate it the next time it runs.`,
+      confirmText: 'Delete',
+      cancelText: 'Cancel',
+      danger: true,
+      iconName: 'trash'
+    });
+    if (confirmed) changeAutorun(entry, () => invoke('delete_autorun', { id: entry.id }));
+  });
+
+  return el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4); gap: var(--space-3);' },
+    icon(isTask ? 'calendar-check' : 'key', {
+      style: { color: entry.enabled ? 'var(--accent-orange)' : 'var(--label-tertiary)', fontSize: '18px' }
+    }),
+    el('div', { className: 'row-content' },
+      el('span', { className: 'row-title' }, entry.enabled ? entry.name : `${entry.name} (disabled)`),
+      el('span', { className: 'row-subtitle' }, `${isTask ? 'Task' : 'Registry'}: ${entry.location}`),
+      el('span', { className: 'row-subtitle' }, entry.command)),
+    el('div', { style: { display: 'flex', gap: '8px' } }, toggleBtn, deleteBtn));
+}
+
+async function changeAutorun(entry, action) {
+  try {
+    await action();
+  } catch (e) {
+    await modal.show({
+      title: 'Could Not Change Entry',
+      message: String(e),
+      confirmText: 'OK',
+      cancelText: 'Close',
+      danger: false,
+      iconName: 'warning-circle'
+    });
+  }
+  loadAutoruns();
+}
+
+// ============================================
+// Deep Clean (uninstall leftovers)
+// ============================================
+const LEFTOVER_KINDS = {
+  app_data: { label: 'App data', icon: 'folder' },
+  temp: { label: 'Temp', icon: 'file-dashed' },
+  registry: { label: 'Registry', icon: 'key' },
+  shortcut: { label: 'Start Menu', icon: 'link' },
+  prefetch: { label: 'Prefetch', icon: 'lightning' }
+};
+
+document.getElementById('btn-deep-clean')?.addEventListener('click', () => navigateTo('deep-clean'));
+document.getElementById('btn-deep-clean')?.addEventListener('keydown', (e) => handleKey(e, () => navigateTo('deep-clean')));
+document.getElementById('deep-clean-back')?.addEventListener('click', goBack);
+document.getElementById('btn-rescan-leftovers')?.addEventListener('click', loadLeftovers);
+document.getElementById('btn-clean-leftovers')?.addEventListener('click', cleanLeftovers);
+document.getElementById('leftover-list')?.addEventListener('change', updateLeftoverSelection);
+
+async function loadLeftovers() {
+  const list = document.getElementById('leftover-list');
+  const summary = document.getElementById('deep-clean-summary');
+  summary.style.color = '';
+  list.replaceChildren(createSkeletonRows(3));
+  document.getElementById('btn-clean-leftovers').disabled = true;
+  try {
+    const items = await invoke('scan_leftovers');
+    if (items.length === 0) {
+      list.replaceChildren(el('div', { className: 'list-row', style: { justifyContent: 'center', padding: '24px' } },
+        el('span', { className: 'row-subtitle' }, 'No leftovers found. Apps that are still installed are never listed.')));
+      summary.textContent = '';
+      return;
+    }
+    state.leftovers = items;
+    list.replaceChildren(...items.map(leftoverRow));
+    updateLeftoverSelection();
+  } catch (e) {
+    list.replaceChildren();
+    summary.textContent = String(e);
+    summary.style.color = 'var(--accent-red)';
+  }
+}
+
+function leftoverRow(item) {
+  const kind = LEFTOVER_KINDS[item.kind];
+  const checkbox = el('input', { type: 'checkbox', 'data-leftover': item.id });
+  // Project folders are only removed when ticked by hand
+  checkbox.checked = !item.contains_drafts;
+  const details = [`${item.application} · ${kind.label}`];
+  if (item.kind !== 'registry') details.push(formatBytes(item.bytes));
+  if (item.contains_drafts) details.push('contains projects');
+  return el('div', { className: 'list-row', style: 'padding: var(--space-3) var(--space-4); gap: var(--space-3);' },
+    icon(kind.icon, {
+      style: { color: item.contains_drafts ? 'var(--accent-orange)' : 'var(--label-tertiary)', fontSize: '18px' }
+    }),
+    el('div', { className: 'row-content' },
+      el('span', { className: 'row-title', style: { wordBreak: 'break-all' } }, item.id),
+      el('span', { className: 'row-subtitle' }, details.join(' · '))),
+    el('label', { className: 'switch' }, checkbox, el('span', { className: 'slider round' })));
+}
+
+function selectedLeftovers() {
+  return [...document.querySelectorAll('#leftover-list input[data-leftover]')]
+    .filter(b => b.checked)
+    .map(b => b.dataset.leftover);
+}
+
+function updateLeftoverSelection() {
+  const items = state.leftovers;
+  const selected = selectedLeftovers();
+  const bytes = items.filter(i => selected.includes(i.id)).reduce((sum, i) => sum + i.bytes, 0);
+  document.getElementById('deep-clean-summary').textContent =
+    `${selected.length} of ${items.length} item(s) selected (${formatBytes(bytes)}).`;
+  document.getElementById('btn-clean-leftovers').disabled = selected.length === 0;
+}
+
+async function cleanLeftovers() {
+  const ids = selectedLeftovers();
+  const confirmed = await modal.show({
+    title: 'Remove Leftovers?',
+    message: `${ids.length} item(s) will be deleted for good. Projects inside a selected folder are deleted with it.`,
+    confirmText: 'Remove',
+    cancelText: 'Cancel',
+    danger: true,
+    iconName: 'broom'
+  });
+  if (!confirmed) return;
+
+  const summary = document.getElementById('deep-clean-summary');
+  document.getElementById('btn-clean-leftovers').disabled = true;
+  try {
+    const logs = await invoke('clean_leftovers', { ids });
+    await loadLeftovers();
+    const warnings = logs.filter(l => l.level === 'warn');
+    summary.textContent = [logs[logs.length - 1].message, ...warnings.map(w => w.message)].join(' ');
+    summary.style.color = warnings.length ? 'var(--accent-orange)' : '';
+  } catch (e) {
+    summary.textContent = String(e);
+    summary.style.color = 'var(--accent-red)';
+    document.getElementById('btn-clean-leftovers').disabled = false;
+  }
+}
+
+// ============================================
+// Compare Versions View
+// ============================================
+document.getElementById('btn-compare')?.addEventListener('click', () => navigateTo('compare'));
+document.getElementById('btn-compare')?.addEventListener('keydown', (e) => handleKey(e, () => navigateTo('compare')));
+document.getElementById('compare-back')?.addEventListener('click', goBack);
+document.getElementById('btn-run-compare')?.addEventListener('click', runCompare);
+
+/** Rows shown per change group; the rest are summarized */
+const COMPARE_ROW_LIMIT = 50;
+
+function compareMessage(text, color = 'var(--label-secondary)') {
+  return el('div', { className: 'list-row', style: { justifyContent: 'center', padding: '24px' } },
+    el('span', { className: 'row-subtitle', style: { color } }, text)
+  );
+}
+
+async function loadCompare() {
+  const left = document.getElementById('compare-left');
+  const right = document.getElementById('compare-right');
+  const results = document.getElementById('compare-results');
+  results.replaceChildren(compareMessage('Pick two versions to compare'));
+
+  try {
+    const versions = await invoke('scan_versions');
+    const options = () => versions.map(v => el('option', { value: v.path }, v.name));
+    left.replaceChildren(...options());
+    right.replaceChildren(...options());
+    // Default to the two newest installs
+    if (versions.length >= 2) {
+      left.value = versions[versions.length - 2].path;
+      right.value = versions[versions.length - 1].path;
+    }
+    document.getElementById('btn-run-compare').disabled = versions.length < 2;
+    if (versions.length < 2) {
+      results.replaceChildren(compareMessage('At least two installed versions are needed'));
+    }
+  } catch (e) {
+    results.replaceChildren(compareMessage(`Error: ${e}`, 'var(--accent-red)'));
+  }
+}
+
+function compareGroup(title, iconName, color, changes, sizeOf) {
+  const rows = changes.slice(0, COMPARE_ROW_LIMIT).map(change =>
+    el('div', { className: 'list-row' },
+      el('div', { className: 'row-icon', style: { background: 'var(--fill-secondary)', color } }, icon(iconName)),
+      el('div', { className: 'row-content' },
+        el('span', { className: 'row-title', style: { wordBreak: 'break-all' } }, change.path),
+        el('span', { className: 'row-subtitle' }, sizeOf(change))
+      )
+    )
+  );
+  if (changes.length > COMPARE_ROW_LIMIT) {
+    rows.push(compareMessage(`…and ${changes.length - COMPARE_ROW_LIMIT} more`));
+  }
+  return [el('span', { className: 'list-header' }, `${title} (${changes.length})`), ...rows];
+}
+
+async function runCompare() {
+  const left = document.getElementById('compare-left').value;
+  const right = document.getElementById('compare-right').value;
+  const results = document.getElementById('compare-results');
+  const btn = document.getElementById('btn-run-compare');
+
+  if (!left || !right || left === right) {
+    results.replaceChildren(compareMessage('Pick two different versions'));
+    return;
+  }
+
+  btn.disabled = true;
+  results.replaceChildren(createSkeletonRows(3));
+  try {
+    const diff = await invoke('compare_versions', { left, right });
+    const sign = diff.size_delta >= 0 ? '+' : '-';
+    results.replaceChildren(
+      el('div', { className: 'list-row' },
+        el('div', { className: 'row-content' },
+          el('span', { className: 'row-title' }, `Size change: ${sign}${formatBytes(diff.size_delta)}`),
+          el('span', { className: 'row-subtitle' },
+            `${diff.added.length} added, ${diff.removed.length} removed, ${diff.changed.length} changed, ${diff.unchanged} unchanged`)
+        )
+      ),
+      ...compareGroup('Added', 'plus-circle', 'var(--accent-green)', diff.added, c => formatBytes(c.right_size)),
+      ...compareGroup('Removed', 'minus-circle', 'var(--accent-red)', diff.removed, c => formatBytes(c.left_size)),
+      ...compareGroup('Changed', 'pencil-simple', 'var(--accent-orange)', diff.changed,
+        c => `${formatBytes(c.left_size)} → ${formatBytes(c.right_size)}`)
+    );
+  } catch (e) {
+    results.replaceChildren(compareMessage(`Error: ${e}`, 'var(--accent-red)'));
+  } finally {
+    btn.disabled = false;
+  }
+}
+
+// ============================================
+// Notification Center
+// ============================================
+document.getElementById('btn-events')?.addEventListener('click', () => navigateTo('events'));
+document.getElementById('events-back')?.addEventListener('click', () => {
+  goBack();
+  refreshEventsBadge();
+});
+document.getElementById('btn-clear-events')?.addEventListener('click', async () => {
+  try {
+    await invoke('clear_events');
+    loadEvents();
+  } catch (e) {
+    console.error('Failed to clear events:', e);
+  }
+});
+
+async function refreshEventsBadge() {
+  const badge = document.getElementById('events-badge');
+  if (!badge) return;
+  try {
+    const count = await invoke('unread_event_count');
+    badge.textContent = count > 99 ? '99+' : String(count);
+    badge.style.display = count > 0 ? 'block' : 'none';
+  } catch (e) {
+    console.warn('Could not load unread events:', e);
+  }
+}
+
+async function loadEvents() {
+  const container = document.getElementById('events-list');
+  container.replaceChildren(createSkeletonRows(3));
+
+  const kinds = {
+    update_blocked: { iconName: 'hand-palm', title: 'Update blocked', color: 'var(--accent-green)' },
+    protection_broken: { iconName: 'shield-warning', title: 'Protection broken', color: 'var(--accent-red)' },
+    favorite_changed: { iconName: 'star', title: 'Favorite version changed', color: 'var(--accent-yellow)' }
+  };
+
+  try {
+    const events = await invoke('list_events');
+
+    if (events.length === 0) {
+      container.replaceChildren(
+        el('div', {
+          className: 'list-row',
+          style: { flexDirection: 'column', textAlign: 'center', padding: '24px' }
+        },
+          icon('bell-slash', { style: { fontSize: '32px', color: 'var(--label-tertiary)', marginBottom: '8px' } }),
+          el('span', { className: 'row-title' }, 'No notifications'),
+          el('span', { className: 'row-subtitle' }, 'Guard events will appear here')
+        )
+      );
+      return;
+    }
+
+    const fragment = document.createDocumentFragment();
+    events.forEach(event => {
+      const kind = kinds[event.kind] || { iconName: 'info', title: event.kind, color: 'var(--label-secondary)' };
+      const date = new Date(event.timestamp * 1000);
+      const dateStr = date.toLocaleDateString() + ' ' + date.toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+
+      fragment.append(
+        el('div', { className: 'list-row', style: { alignItems: 'flex-start' } },
+          el('div', { className: 'row-icon', style: { background: 'var(--fill-secondary)', color: kind.color } },
+            icon(kind.iconName)
+          ),
+          el('div', { className: 'row-content' },
+            el('span', { className: 'row-title' }, kind.title),
+            el('span', { className: 'row-subtitle', style: { whiteSpace: 'pre-line' } }, event.message),
+            el('span', { className: 'row-subtitle', style: { color: 'var(--label-tertiary)' } }, dateStr)
+          ),
+          event.read ? null : el('span', { className: 'event-unread-dot', title: 'Unread' })
+        )
+      );
+    });
+    container.replaceChildren(fragment);
+
+    // Opening the center counts as reviewing everything shown
+    await invoke('mark_events_read');
+  } catch (e) {
+    container.replaceChildren(
+      el('div', { className: 'list-row' },
+        el('span', { className: 'row-title', style: { color: 'var(--accent-red)' } }, `Error: ${e}`)
+      )
+    );
   }
 }